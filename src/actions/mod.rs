@@ -2,14 +2,39 @@
 
 #![allow(dead_code)]
 
+pub mod progress;
+pub mod pty;
+pub mod shortcuts;
+pub mod test_summary;
+
 use anyhow::Result;
-use portable_pty::{CommandBuilder, NativePtySystem, PtySize, PtySystem};
-use std::collections::HashMap;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, VecDeque};
 use std::io::{BufRead, BufReader as StdBufReader};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tokio::sync::mpsc;
 
+use ratatui::style::Color;
+
+use crate::config::CustomAction;
 use crate::detection::{DiscoveredScript, ScriptCategory, ScriptSource};
+use crate::ui::theme::parse_hex_color;
+
+/// fzf-style subsequence matcher for the palette filter
+static FUZZY_MATCHER: Lazy<SkimMatcherV2> = Lazy::new(|| SkimMatcherV2::default().ignore_case());
+
+/// Char positions in `text` matched by `query`, for highlighting; empty if it doesn't match
+pub fn fuzzy_indices(text: &str, query: &str) -> Vec<usize> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    FUZZY_MATCHER
+        .fuzzy_indices(text, query)
+        .map(|(_, indices)| indices)
+        .unwrap_or_default()
+}
 
 /// An executable action
 #[derive(Debug, Clone)]
@@ -22,12 +47,21 @@ pub struct Action {
     pub source: ActionSource,
     pub keybinding: Option<String>,
     pub requires_confirm: bool,
+    /// Text the user must type before a confirmed action runs
+    pub confirm_phrase: Option<String>,
     pub env_required: Vec<String>,
     pub working_dir: Option<PathBuf>,
+    pub icon: Option<String>,
+    pub color: Option<Color>,
+    /// Shell program this action runs under, overriding the executor's
+    pub shell: Option<String>,
 }
 
 impl Action {
+    /// The id follows the script name; see [`ActionRegistry::from_project`]
+    /// for scripts whose names collide
     pub fn from_script(script: DiscoveredScript) -> Self {
+        let requires_confirm = is_destructive(&script.command);
         Self {
             id: format!("script:{}", script.name),
             name: script.name,
@@ -36,12 +70,59 @@ impl Action {
             category: ActionCategory::from(script.category),
             source: ActionSource::from(script.source),
             keybinding: None,
-            requires_confirm: false,
+            requires_confirm,
+            confirm_phrase: None,
             env_required: script.env_required,
+            working_dir: script.working_dir,
+            icon: None,
+            color: None,
+            shell: None,
+        }
+    }
+
+    pub fn from_custom(custom: &CustomAction) -> Self {
+        Self {
+            id: format!("custom:{}", custom.name),
+            name: custom.name.clone(),
+            command: custom.command.clone(),
+            description: custom.description.clone(),
+            category: custom
+                .category
+                .as_deref()
+                .and_then(ActionCategory::from_name)
+                .unwrap_or(ActionCategory::Custom),
+            source: ActionSource::OrbitConfig,
+            keybinding: custom.keybinding.clone(),
+            requires_confirm: custom.confirm
+                || custom.confirm_phrase
+                || is_destructive(&custom.command),
+            confirm_phrase: custom.confirm_phrase.then(|| custom.name.clone()),
+            env_required: vec![],
             working_dir: None,
+            icon: custom.icon.clone(),
+            color: custom.color.as_deref().and_then(parse_hex_color),
+            shell: custom.shell.clone(),
+        }
+    }
+
+    /// The name to list this action under: labelled with its source, e.g.
+    /// `test (Makefile)`, when another of `actions` shares the name
+    pub fn display_name(&self, actions: &[Action]) -> String {
+        let shared = actions
+            .iter()
+            .any(|other| other.name == self.name && other.id != self.id);
+        if shared {
+            format!("{} ({})", self.name, self.source)
+        } else {
+            self.name.clone()
         }
     }
 
+    /// Whether running this action can destroy data
+    pub fn is_destructive(&self) -> bool {
+        is_destructive(&self.command)
+    }
+
     /// Check if action matches a search query
     pub fn matches(&self, query: &str) -> bool {
         let query_lower = query.to_lowercase();
@@ -53,6 +134,43 @@ impl Action {
                 .map(|d| d.to_lowercase().contains(&query_lower))
                 .unwrap_or(false)
     }
+
+    /// Best fuzzy score of `query` against the name, command or description,
+    /// or `None` if it matches none of them
+    pub fn fuzzy_score(&self, query: &str) -> Option<i64> {
+        [
+            Some(self.name.as_str()),
+            Some(self.command.as_str()),
+            self.description.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .filter_map(|text| FUZZY_MATCHER.fuzzy_match(text, query))
+        .max()
+    }
+}
+
+/// Words in an action's name or command that make it ask before running
+pub const DEFAULT_CONFIRM_PATTERNS: &[&str] = &["deploy", "rm", "down", "prune"];
+
+/// Whether any of `patterns` appears as a whole word, ignoring case, in `text`
+/// (so `rm` matches `rm -rf dist` but not `npm run format`)
+pub fn matches_confirm_pattern(text: &str, patterns: &[String]) -> bool {
+    text.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .any(|word| patterns.iter().any(|p| word.eq_ignore_ascii_case(p)))
+}
+
+/// Commands that delete persistent data, such as Docker volumes
+pub fn is_destructive(command: &str) -> bool {
+    let words: Vec<&str> = command.split_whitespace().collect();
+    let has = |w: &str| words.contains(&w);
+
+    let compose_down = has("down") && (has("compose") || has("docker-compose"));
+    let removes_volumes = has("-v") || has("--volumes");
+
+    (compose_down && removes_volumes)
+        || (has("volume") && (has("rm") || has("prune")))
+        || (has("system") && has("prune") && has("--volumes"))
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -68,6 +186,22 @@ pub enum ActionCategory {
     Custom,
 }
 
+impl ActionCategory {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "dev" => Some(Self::Dev),
+            "build" => Some(Self::Build),
+            "test" => Some(Self::Test),
+            "lint" => Some(Self::Lint),
+            "deploy" => Some(Self::Deploy),
+            "database" | "db" => Some(Self::Database),
+            "docker" => Some(Self::Docker),
+            "custom" => Some(Self::Custom),
+            _ => None,
+        }
+    }
+}
+
 impl From<ScriptCategory> for ActionCategory {
     fn from(cat: ScriptCategory) -> Self {
         match cat {
@@ -103,8 +237,10 @@ impl std::fmt::Display for ActionCategory {
 pub enum ActionSource {
     PackageJson,
     Makefile,
+    Rakefile,
     CargoToml,
     PyProjectToml,
+    ComposerJson,
     DockerCompose,
     OrbitConfig,
     Detected,
@@ -116,8 +252,10 @@ impl From<ScriptSource> for ActionSource {
         match source {
             ScriptSource::PackageJson => Self::PackageJson,
             ScriptSource::Makefile => Self::Makefile,
+            ScriptSource::Rakefile => Self::Rakefile,
             ScriptSource::CargoToml => Self::CargoToml,
             ScriptSource::PyProjectToml => Self::PyProjectToml,
+            ScriptSource::ComposerJson => Self::ComposerJson,
             ScriptSource::DockerCompose => Self::DockerCompose,
             ScriptSource::OrbitConfig => Self::OrbitConfig,
             ScriptSource::Detected => Self::Detected,
@@ -130,8 +268,10 @@ impl std::fmt::Display for ActionSource {
         match self {
             Self::PackageJson => write!(f, "package.json"),
             Self::Makefile => write!(f, "Makefile"),
+            Self::Rakefile => write!(f, "Rakefile"),
             Self::CargoToml => write!(f, "Cargo.toml"),
             Self::PyProjectToml => write!(f, "pyproject.toml"),
+            Self::ComposerJson => write!(f, "composer.json"),
             Self::DockerCompose => write!(f, "docker-compose"),
             Self::OrbitConfig => write!(f, ".orbit.toml"),
             Self::Detected => write!(f, "detected"),
@@ -157,10 +297,16 @@ pub enum OutputLine {
     Exit(i32),
 }
 
+/// Default number of lines `execute_collect` keeps
+pub const DEFAULT_COLLECT_LIMIT: usize = 10_000;
+
 /// Action executor with streaming output
+#[derive(Clone)]
 pub struct ActionExecutor {
     working_dir: PathBuf,
     env_vars: HashMap<String, String>,
+    collect_limit: usize,
+    shell: Option<String>,
 }
 
 impl ActionExecutor {
@@ -168,9 +314,36 @@ impl ActionExecutor {
         Self {
             working_dir,
             env_vars: HashMap::new(),
+            collect_limit: DEFAULT_COLLECT_LIMIT,
+            shell: None,
         }
     }
 
+    /// Run commands under `shell` (e.g. "zsh") instead of `$SHELL`
+    pub fn with_shell(mut self, shell: Option<String>) -> Self {
+        self.shell = shell.filter(|s| !s.trim().is_empty());
+        self
+    }
+
+    /// The shell program for `action`: its own, then ours, then `$SHELL`, then `sh`
+    pub fn shell_for(&self, action: &Action) -> String {
+        action.shell.clone().unwrap_or_else(|| self.default_shell())
+    }
+
+    /// Our shell, then `$SHELL`, then `sh`
+    pub fn default_shell(&self) -> String {
+        self.shell
+            .clone()
+            .or_else(|| std::env::var("SHELL").ok().filter(|s| !s.is_empty()))
+            .unwrap_or_else(|| "sh".to_string())
+    }
+
+    /// Cap how many trailing lines `execute_collect` retains
+    pub fn with_collect_limit(mut self, lines: usize) -> Self {
+        self.collect_limit = lines.max(1);
+        self
+    }
+
     pub fn with_env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
         self.env_vars.insert(key.into(), value.into());
         self
@@ -182,6 +355,11 @@ impl ActionExecutor {
     }
 
     /// Execute an action with streaming output using a PTY for real-time output
+    ///
+    /// Lines are sent with `blocking_send` from the PTY reader thread, so a bounded
+    /// `output_tx` applies backpressure: when the receiver falls behind, the reader
+    /// stops draining the PTY and the child blocks on write instead of buffering
+    /// unboundedly in memory.
     pub async fn execute(
         &self,
         action: &Action,
@@ -196,12 +374,13 @@ impl ActionExecutor {
             .clone();
 
         let command = action.command.clone();
+        let shell = self.shell_for(action);
         let action_id = action.id.clone();
         let env_vars = self.env_vars.clone();
 
         // Run PTY in a blocking task since portable-pty is not async
         let result = tokio::task::spawn_blocking(move || {
-            execute_with_pty(&command, &working_dir, &env_vars, output_tx)
+            execute_with_pty(&shell, &command, &working_dir, &env_vars, output_tx)
         })
         .await??;
 
@@ -215,10 +394,10 @@ impl ActionExecutor {
         })
     }
 
-    /// Execute an action and collect all output
+    /// Execute an action and collect its output, keeping only the last `collect_limit` lines
     pub async fn execute_collect(&self, action: &Action) -> Result<(ActionResult, Vec<String>)> {
         let (tx, mut rx) = mpsc::channel(1000);
-        let mut output = Vec::new();
+        let mut output = VecDeque::with_capacity(self.collect_limit.min(1024));
 
         let executor = self.execute(action, tx);
 
@@ -227,7 +406,10 @@ impl ActionExecutor {
             while let Some(line) = rx.recv().await {
                 match line {
                     OutputLine::Stdout(s) | OutputLine::Stderr(s) => {
-                        output.push(s);
+                        if output.len() == self.collect_limit {
+                            output.pop_front();
+                        }
+                        output.push_back(s);
                     }
                     OutputLine::Exit(_) => {}
                 }
@@ -236,50 +418,37 @@ impl ActionExecutor {
 
         let (result, _) = tokio::join!(executor, collect);
 
-        Ok((result?, output))
+        Ok((result?, output.into()))
     }
 }
 
 /// Execute a command with PTY for real-time unbuffered output
+///
+/// `shell` runs as a login shell (`-lc`) so profiles that set up nvm, pyenv and
+/// the like are loaded.
 fn execute_with_pty(
+    shell: &str,
     command: &str,
-    working_dir: &PathBuf,
+    working_dir: &Path,
     env_vars: &HashMap<String, String>,
     output_tx: mpsc::Sender<OutputLine>,
 ) -> Result<(Option<i32>,)> {
-    let pty_system = NativePtySystem::default();
-
-    let pair = pty_system.openpty(PtySize {
-        rows: 24,
-        cols: 80,
-        pixel_width: 0,
-        pixel_height: 0,
-    })?;
-
-    let mut cmd = CommandBuilder::new("sh");
-    cmd.arg("-c");
-    cmd.arg(command);
-    cmd.cwd(working_dir);
-
-    // Add environment variables
-    for (key, value) in env_vars {
-        cmd.env(key, value);
-    }
-
     // Force colors and unbuffered output
-    cmd.env("TERM", "xterm-256color");
-    cmd.env("CARGO_TERM_COLOR", "always");
-    cmd.env("FORCE_COLOR", "1");
-    cmd.env("CLICOLOR_FORCE", "1");
-    cmd.env("PYTHONUNBUFFERED", "1");
-
-    let mut child = pair.slave.spawn_command(cmd)?;
+    let mut env_vars = env_vars.clone();
+    for (key, value) in [
+        ("CARGO_TERM_COLOR", "always"),
+        ("FORCE_COLOR", "1"),
+        ("CLICOLOR_FORCE", "1"),
+        ("PYTHONUNBUFFERED", "1"),
+    ] {
+        env_vars.insert(key.to_string(), value.to_string());
+    }
 
-    // Drop the slave to close it properly - we only need the master
-    drop(pair.slave);
+    let pty::PtyProcess { master, mut child } =
+        pty::spawn(shell, &["-lc", command], working_dir, &env_vars, 24, 80)?;
 
     // Read from the master (which receives all output from the PTY)
-    let reader = pair.master.try_clone_reader()?;
+    let reader = master.try_clone_reader()?;
 
     // Spawn a thread to read output so we don't block on wait()
     let output_tx_clone = output_tx.clone();
@@ -289,7 +458,7 @@ fn execute_with_pty(
             match line {
                 Ok(content) => {
                     // Strip ANSI escape codes for cleaner output display
-                    let clean_content = strip_ansi_codes(&content);
+                    let clean_content = pty::strip_ansi_codes(&content);
                     // Send even if empty to preserve blank lines in output
                     if output_tx_clone
                         .blocking_send(OutputLine::Stdout(if clean_content.is_empty() {
@@ -312,7 +481,7 @@ fn execute_with_pty(
     let exit_code = status.exit_code() as i32;
 
     // Drop the master to signal EOF to the reader thread
-    drop(pair.master);
+    drop(master);
 
     // Wait for the reader thread to finish
     let _ = read_thread.join();
@@ -322,34 +491,6 @@ fn execute_with_pty(
     Ok((Some(exit_code),))
 }
 
-/// Strip ANSI escape codes from a string
-fn strip_ansi_codes(s: &str) -> String {
-    let mut result = String::with_capacity(s.len());
-    let mut chars = s.chars().peekable();
-
-    while let Some(c) = chars.next() {
-        if c == '\x1b' {
-            // Skip escape sequence
-            if chars.peek() == Some(&'[') {
-                chars.next(); // consume '['
-                              // Skip until we hit a letter (end of escape sequence)
-                while let Some(&next) = chars.peek() {
-                    chars.next();
-                    if next.is_ascii_alphabetic() {
-                        break;
-                    }
-                }
-            }
-        } else if c == '\r' {
-            // Skip carriage returns
-        } else {
-            result.push(c);
-        }
-    }
-
-    result
-}
-
 /// Built-in system actions
 pub fn system_actions() -> Vec<Action> {
     vec![
@@ -362,8 +503,12 @@ pub fn system_actions() -> Vec<Action> {
             source: ActionSource::System,
             keybinding: Some("r".to_string()),
             requires_confirm: false,
+            confirm_phrase: None,
             env_required: vec![],
             working_dir: None,
+            icon: None,
+            color: None,
+            shell: None,
         },
         Action {
             id: "system:toggle_docker".to_string(),
@@ -374,8 +519,12 @@ pub fn system_actions() -> Vec<Action> {
             source: ActionSource::System,
             keybinding: Some("d".to_string()),
             requires_confirm: false,
+            confirm_phrase: None,
             env_required: vec![],
             working_dir: None,
+            icon: None,
+            color: None,
+            shell: None,
         },
         Action {
             id: "system:toggle_ports".to_string(),
@@ -386,8 +535,12 @@ pub fn system_actions() -> Vec<Action> {
             source: ActionSource::System,
             keybinding: Some("p".to_string()),
             requires_confirm: false,
+            confirm_phrase: None,
             env_required: vec![],
             working_dir: None,
+            icon: None,
+            color: None,
+            shell: None,
         },
         Action {
             id: "system:focus_mode".to_string(),
@@ -398,8 +551,12 @@ pub fn system_actions() -> Vec<Action> {
             source: ActionSource::System,
             keybinding: Some("f".to_string()),
             requires_confirm: false,
+            confirm_phrase: None,
             env_required: vec![],
             working_dir: None,
+            icon: None,
+            color: None,
+            shell: None,
         },
         Action {
             id: "system:help".to_string(),
@@ -410,8 +567,92 @@ pub fn system_actions() -> Vec<Action> {
             source: ActionSource::System,
             keybinding: Some("?".to_string()),
             requires_confirm: false,
+            confirm_phrase: None,
             env_required: vec![],
             working_dir: None,
+            icon: None,
+            color: None,
+            shell: None,
+        },
+        Action {
+            id: "system:view_logs".to_string(),
+            name: "View Orbit Logs".to_string(),
+            command: String::new(),
+            description: Some("Tail today's Orbit log".to_string()),
+            category: ActionCategory::System,
+            source: ActionSource::System,
+            keybinding: None,
+            requires_confirm: false,
+            confirm_phrase: None,
+            env_required: vec![],
+            working_dir: None,
+            icon: None,
+            color: None,
+            shell: None,
+        },
+        Action {
+            id: "system:open_logs".to_string(),
+            name: "Open Orbit Logs".to_string(),
+            command: String::new(),
+            description: Some("Reveal the log directory".to_string()),
+            category: ActionCategory::System,
+            source: ActionSource::System,
+            keybinding: None,
+            requires_confirm: false,
+            confirm_phrase: None,
+            env_required: vec![],
+            working_dir: None,
+            icon: None,
+            color: None,
+            shell: None,
+        },
+        Action {
+            id: "system:copy_summary".to_string(),
+            name: "Copy Project Summary".to_string(),
+            command: String::new(),
+            description: Some("Copy detected scripts, ports and env vars as Markdown".to_string()),
+            category: ActionCategory::System,
+            source: ActionSource::System,
+            keybinding: None,
+            requires_confirm: false,
+            confirm_phrase: None,
+            env_required: vec![],
+            working_dir: None,
+            icon: None,
+            color: None,
+            shell: None,
+        },
+        Action {
+            id: "system:snapshot".to_string(),
+            name: "Save Dashboard Snapshot".to_string(),
+            command: String::new(),
+            description: Some("Write the dashboard to a text file for sharing".to_string()),
+            category: ActionCategory::System,
+            source: ActionSource::System,
+            keybinding: Some("S".to_string()),
+            requires_confirm: false,
+            confirm_phrase: None,
+            env_required: vec![],
+            working_dir: None,
+            icon: None,
+            color: None,
+            shell: None,
+        },
+        Action {
+            id: "system:next_theme".to_string(),
+            name: "Next Theme".to_string(),
+            command: String::new(),
+            description: Some("Switch to the next color theme and save it".to_string()),
+            category: ActionCategory::System,
+            source: ActionSource::System,
+            keybinding: Some("T".to_string()),
+            requires_confirm: false,
+            confirm_phrase: None,
+            env_required: vec![],
+            working_dir: None,
+            icon: None,
+            color: None,
+            shell: None,
         },
         Action {
             id: "system:quit".to_string(),
@@ -422,12 +663,37 @@ pub fn system_actions() -> Vec<Action> {
             source: ActionSource::System,
             keybinding: Some("q".to_string()),
             requires_confirm: false,
+            confirm_phrase: None,
             env_required: vec![],
             working_dir: None,
+            icon: None,
+            color: None,
+            shell: None,
         },
     ]
 }
 
+/// Actions as JSON for `orbit actions --json`; system actions are keyed by their key
+pub fn actions_json(actions: &[DiscoveredScript], include_system: bool) -> serde_json::Value {
+    let mut json = serde_json::json!({ "actions": actions });
+    if include_system {
+        let system: Vec<serde_json::Value> = system_actions()
+            .into_iter()
+            .filter(|action| action.keybinding.is_some())
+            .map(|action| {
+                serde_json::json!({
+                    "id": action.id,
+                    "name": action.name,
+                    "key": action.keybinding,
+                    "description": action.description,
+                })
+            })
+            .collect();
+        json["system"] = system.into();
+    }
+    json
+}
+
 /// Print actions for CLI output
 pub fn print_actions(actions: &[DiscoveredScript], include_system: bool) {
     println!("{:<20} {:<12} {:<15} COMMAND", "NAME", "CATEGORY", "SOURCE");
@@ -436,10 +702,10 @@ pub fn print_actions(actions: &[DiscoveredScript], include_system: bool) {
     for script in actions {
         println!(
             "{:<20} {:<12} {:<15} {}",
-            truncate(&script.name, 18),
+            crate::ui::util::truncate(&script.name, 18),
             script.category.to_string(),
             script.source.to_string(),
-            truncate(&script.command, 30)
+            crate::ui::util::truncate(&script.command, 30)
         );
     }
 
@@ -458,38 +724,314 @@ pub fn print_actions(actions: &[DiscoveredScript], include_system: bool) {
     }
 }
 
-fn truncate(s: &str, max_len: usize) -> String {
-    let char_count = s.chars().count();
-    if char_count <= max_len {
+/// Task-runner formats detected scripts can be exported to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptExportFormat {
+    /// A `Makefile` with one phony target per script
+    Make,
+    /// A `justfile` with one recipe per script
+    Just,
+    /// A shell file with one function per script, for sourcing from an rc file
+    Sh,
+}
+
+/// Render `scripts` as targets, recipes or functions; package scripts `cd` into
+/// their directory relative to `root` first
+pub fn export_scripts(
+    scripts: &[DiscoveredScript],
+    format: ScriptExportFormat,
+    root: &Path,
+) -> String {
+    let separator = match format {
+        ScriptExportFormat::Make | ScriptExportFormat::Just => '-',
+        ScriptExportFormat::Sh => '_',
+    };
+    let names = unique_identifiers(scripts.iter().map(|s| s.name.as_str()), separator);
+
+    let mut out = String::new();
+    match format {
+        ScriptExportFormat::Make => {
+            out.push_str("# Generated by `orbit actions --export make`\n\n");
+            out.push_str(&format!(".PHONY: {}\n", names.join(" ")));
+        }
+        ScriptExportFormat::Just => {
+            out.push_str("# Generated by `orbit actions --export justfile`\n")
+        }
+        ScriptExportFormat::Sh => out.push_str("# Generated by `orbit actions --export sh`\n"),
+    }
+
+    for (script, name) in scripts.iter().zip(&names) {
+        let command = match &script.working_dir {
+            Some(dir) => {
+                let dir = dir.strip_prefix(root).unwrap_or(dir);
+                format!(
+                    "cd {} && {}",
+                    shell_quote(&dir.to_string_lossy()),
+                    script.command
+                )
+            }
+            None => script.command.clone(),
+        };
+
+        out.push('\n');
+        if let Some(description) = &script.description {
+            out.push_str(&format!("# {}\n", description));
+        }
+        match format {
+            // `$` would otherwise start a make variable
+            ScriptExportFormat::Make => out.push_str(&format!(
+                "{}:\n\t{}\n",
+                name,
+                command.replace('$', "$$").replace('\n', "\n\t")
+            )),
+            // `{{` would otherwise start a just interpolation
+            ScriptExportFormat::Just => out.push_str(&format!(
+                "{}:\n    {}\n",
+                name,
+                command.replace("{{", "{{{{").replace('\n', "\n    ")
+            )),
+            ScriptExportFormat::Sh => {
+                out.push_str(&format!("{}() {{\n    {}\n}}\n", name, command))
+            }
+        }
+    }
+    out
+}
+
+/// Turn action names into identifiers (`[A-Za-z][A-Za-z0-9_-]*`, or without `-`
+/// when `separator` is `_`), suffixing an index when two names collapse to the same one
+///
+/// Names starting with a digit or `_` get an `x` prefix, since just treats
+/// `_`-prefixed recipes as private.
+fn unique_identifiers<'a>(names: impl Iterator<Item = &'a str>, separator: char) -> Vec<String> {
+    let mut taken: Vec<String> = Vec::new();
+    for name in names {
+        let mut ident = String::new();
+        for c in name.chars() {
+            let c = if c.is_ascii_alphanumeric() || c == '_' {
+                c
+            } else {
+                separator
+            };
+            if c == separator && (ident.is_empty() || ident.ends_with(separator)) {
+                continue;
+            }
+            ident.push(c);
+        }
+        let mut ident = ident.trim_end_matches(separator).to_string();
+        if ident.is_empty() {
+            ident = "action".to_string();
+        } else if ident.starts_with(|c: char| c.is_ascii_digit() || c == '_') {
+            ident.insert(0, 'x');
+        }
+
+        let mut candidate = ident.clone();
+        let mut index = 2;
+        while taken.contains(&candidate) {
+            candidate = format!("{}{}{}", ident, separator, index);
+            index += 1;
+        }
+        taken.push(candidate);
+    }
+    taken
+}
+
+/// Single-quote `s` for a POSIX shell unless it's made of safe characters only
+fn shell_quote(s: &str) -> String {
+    if !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./".contains(c))
+    {
         s.to_string()
-    } else if max_len > 3 {
-        format!("{}...", s.chars().take(max_len - 3).collect::<String>())
     } else {
-        s.chars().take(max_len).collect()
+        format!("'{}'", s.replace('\'', "'\\''"))
     }
 }
 
 /// Registry of all available actions
+#[derive(Clone)]
 pub struct ActionRegistry {
     actions: Vec<Action>,
+    /// Macro name to the ids of the actions it replays
+    macros: indexmap::IndexMap<String, Vec<String>>,
 }
 
 impl ActionRegistry {
     pub fn new() -> Self {
         Self {
             actions: system_actions(),
+            macros: indexmap::IndexMap::new(),
         }
     }
 
     pub fn from_project(project: &crate::detection::ProjectContext) -> Self {
         let mut actions = system_actions();
 
-        // Add project scripts as actions
+        // Add project scripts as actions. The first script with a name keeps the
+        // plain `script:<name>` id that macros, aliases and favorites refer to;
+        // later ones with the same name are qualified by source
         for script in &project.scripts {
-            actions.push(Action::from_script(script.clone()));
+            let mut action = Action::from_script(script.clone());
+            if actions.iter().any(|a| a.id == action.id) {
+                let qualified = format!("script:{}:{}", action.source, action.name);
+                action.id = (1..)
+                    .map(|n| match n {
+                        1 => qualified.clone(),
+                        n => format!("{}:{}", qualified, n),
+                    })
+                    .find(|id| !actions.iter().any(|a| a.id == *id))
+                    .expect("unbounded range");
+            }
+            actions.push(action);
+        }
+
+        Self {
+            actions,
+            macros: indexmap::IndexMap::new(),
+        }
+    }
+
+    /// Add custom actions from `.orbit.toml`, replacing detected ones with the same name
+    pub fn with_custom_actions(mut self, custom: &[CustomAction]) -> Self {
+        for custom in custom {
+            self.actions
+                .retain(|a| a.name != custom.name || a.source == ActionSource::System);
+            self.actions.push(Action::from_custom(custom));
+        }
+        self
+    }
+
+    /// Ask before running actions whose name or command contains one of `patterns`
+    pub fn with_confirm_patterns(mut self, patterns: &[String]) -> Self {
+        for action in &mut self.actions {
+            if action.source != ActionSource::System
+                && (matches_confirm_pattern(&action.name, patterns)
+                    || matches_confirm_pattern(&action.command, patterns))
+            {
+                action.requires_confirm = true;
+            }
+        }
+        self
+    }
+
+    /// Move `favorites`, by action name or script name, to the top in the order listed
+    pub fn with_favorites(mut self, favorites: &[String]) -> Self {
+        let rank = |action: &Action| {
+            let script = action.id.strip_prefix("script:");
+            favorites
+                .iter()
+                .position(|f| *f == action.name || Some(f.as_str()) == script)
+                .unwrap_or(favorites.len())
+        };
+        self.actions.sort_by_key(rank);
+        self
+    }
+
+    /// The non-system action whose `keybinding` matches `key`
+    pub fn action_for_key(&self, key: &crossterm::event::KeyEvent) -> Option<Action> {
+        self.actions
+            .iter()
+            .filter(|a| a.source != ActionSource::System)
+            .find(|a| {
+                a.keybinding
+                    .as_deref()
+                    .and_then(|kb| crate::core::events::KeyBinding::parse(kb).ok())
+                    .is_some_and(|kb| kb.matches(key))
+            })
+            .cloned()
+    }
+
+    /// Show discovered scripts under friendlier names; ids, and so execution and
+    /// history, keep the original script name
+    pub fn with_aliases(mut self, aliases: &HashMap<String, String>) -> Self {
+        for action in &mut self.actions {
+            let alias = action
+                .id
+                .strip_prefix("script:")
+                .and_then(|script| aliases.get(script));
+            if let Some(alias) = alias {
+                action.name = alias.clone();
+            }
+        }
+        self
+    }
+
+    /// Add the macros from `.orbit.toml` as `macro:<name>` actions
+    pub fn with_macros(mut self, macros: &indexmap::IndexMap<String, Vec<String>>) -> Self {
+        for (name, steps) in macros {
+            self.add_macro(name.clone(), steps.clone());
+        }
+        self
+    }
+
+    /// Register a macro, replacing one with the same name
+    pub fn add_macro(&mut self, name: String, steps: Vec<String>) {
+        let id = format!("macro:{}", name);
+        let resolved: Vec<Action> = steps.iter().filter_map(|step| self.get(step)).collect();
+        let action = Action {
+            id: id.clone(),
+            name: format!("Macro: {}", name),
+            command: resolved
+                .iter()
+                .map(|a| a.name.as_str())
+                .collect::<Vec<_>>()
+                .join(" → "),
+            description: Some(format!("Run {} actions in order", steps.len())),
+            category: ActionCategory::Custom,
+            source: ActionSource::OrbitConfig,
+            keybinding: None,
+            requires_confirm: resolved.iter().any(|a| a.requires_confirm),
+            confirm_phrase: resolved.iter().find_map(|a| a.confirm_phrase.clone()),
+            env_required: vec![],
+            working_dir: None,
+            icon: None,
+            color: None,
+            shell: None,
+        };
+
+        self.actions.retain(|a| a.id != id);
+        self.actions.push(action);
+        self.macros.insert(name, steps);
+    }
+
+    pub fn macro_names(&self) -> Vec<String> {
+        self.macros.keys().cloned().collect()
+    }
+
+    /// First unused `macro-N` name
+    pub fn next_macro_name(&self) -> String {
+        (1..)
+            .map(|n| format!("macro-{}", n))
+            .find(|name| !self.macros.contains_key(name))
+            .expect("unbounded range")
+    }
+
+    /// The actions a macro replays, in order
+    pub fn macro_steps(&self, name: &str) -> Result<Vec<Action>> {
+        let steps = self
+            .macros
+            .get(name)
+            .ok_or_else(|| anyhow::anyhow!("No macro named '{}'", name))?;
+        steps
+            .iter()
+            .map(|id| {
+                self.get(id).ok_or_else(|| {
+                    anyhow::anyhow!("Macro '{}' refers to unknown action '{}'", name, id)
+                })
+            })
+            .collect()
+    }
+
+    /// Replace the synthesized `open:` shortcuts, returning whether they changed
+    pub fn set_open_actions(&mut self, open: Vec<Action>) -> bool {
+        let current = self.actions.iter().filter(|a| a.id.starts_with("open:"));
+        if current.map(|a| &a.id).eq(open.iter().map(|a| &a.id)) {
+            return false;
         }
 
-        Self { actions }
+        self.actions.retain(|a| !a.id.starts_with("open:"));
+        self.actions.extend(open);
+        true
     }
 
     pub fn all_actions(&self) -> Vec<Action> {
@@ -500,6 +1042,28 @@ impl ActionRegistry {
         self.actions.iter().find(|a| a.id == id).cloned()
     }
 
+    /// The action registered for a detected script, looked up by id since aliases
+    /// rename actions; among same-named scripts, the one with the same source and command
+    pub fn for_script(&self, script: &DiscoveredScript) -> Option<Action> {
+        let source = ActionSource::from(script.source.clone());
+        let plain = format!("script:{}", script.name);
+        let qualified = format!("script:{}:{}", source, script.name);
+        let is_script = |a: &&Action| {
+            a.id == plain
+                || a.id == qualified
+                || a.id
+                    .strip_prefix(qualified.as_str())
+                    .and_then(|n| n.strip_prefix(':'))
+                    .is_some_and(|n| n.parse::<u32>().is_ok())
+        };
+        self.actions
+            .iter()
+            .filter(is_script)
+            .find(|a| a.command == script.command && a.source == source)
+            .or_else(|| self.actions.iter().find(is_script))
+            .cloned()
+    }
+
     pub fn filter(&self, query: &str) -> Vec<&Action> {
         if query.is_empty() {
             self.actions.iter().collect()
@@ -520,6 +1084,36 @@ mod tests {
     use super::*;
     use tokio::sync::mpsc;
 
+    fn script(name: &str, command: &str) -> DiscoveredScript {
+        DiscoveredScript {
+            name: name.to_string(),
+            command: command.to_string(),
+            source: ScriptSource::PackageJson,
+            category: ScriptCategory::Utility,
+            description: None,
+            ports: vec![],
+            env_required: vec![],
+            working_dir: None,
+        }
+    }
+
+    /// Registry for a project whose only scripts are `scripts`
+    fn registry_with(scripts: Vec<DiscoveredScript>) -> ActionRegistry {
+        ActionRegistry::from_project(&crate::detection::ProjectContext {
+            root: "/tmp/app".into(),
+            name: "app".to_string(),
+            types: vec![],
+            scripts,
+            env_vars: Default::default(),
+            ports: vec![],
+            docker_services: vec![],
+            git_info: None,
+            advisories: vec![],
+            warnings: vec![],
+            member_manifests: vec![],
+        })
+    }
+
     #[test]
     fn test_action_matches() {
         let action = Action {
@@ -531,8 +1125,12 @@ mod tests {
             source: ActionSource::PackageJson,
             keybinding: None,
             requires_confirm: false,
+            confirm_phrase: None,
             env_required: vec![],
             working_dir: None,
+            icon: None,
+            color: None,
+            shell: None,
         };
 
         assert!(action.matches("test"));
@@ -553,8 +1151,12 @@ mod tests {
             source: ActionSource::Detected,
             keybinding: None,
             requires_confirm: false,
+            confirm_phrase: None,
             env_required: vec![],
             working_dir: None,
+            icon: None,
+            color: None,
+            shell: None,
         };
 
         let (tx, mut rx) = mpsc::channel(16);
@@ -585,4 +1187,203 @@ mod tests {
             lines
         );
     }
+
+    #[tokio::test]
+    async fn execute_collect_keeps_only_trailing_lines() {
+        let executor = ActionExecutor::new(std::env::current_dir().unwrap()).with_collect_limit(5);
+        let action = Action {
+            id: "test-seq".to_string(),
+            name: "Seq".to_string(),
+            // Linger briefly so the PTY reader drains before the slave side closes
+            command: "i=1; while [ $i -le 200 ]; do echo $i; i=$((i+1)); done; sleep 0.3"
+                .to_string(),
+            description: None,
+            category: ActionCategory::Custom,
+            source: ActionSource::Detected,
+            keybinding: None,
+            requires_confirm: false,
+            confirm_phrase: None,
+            env_required: vec![],
+            working_dir: None,
+            icon: None,
+            color: None,
+            shell: None,
+        };
+
+        let (result, output) = executor.execute_collect(&action).await.unwrap();
+        assert!(result.success);
+        assert_eq!(output, vec!["196", "197", "198", "199", "200"]);
+    }
+
+    #[tokio::test]
+    async fn configured_shell_runs_the_command() {
+        let mut action = Action::from_script(DiscoveredScript {
+            name: "test".to_string(),
+            command: "pytest".to_string(),
+            source: ScriptSource::Detected,
+            category: ScriptCategory::Test,
+            description: None,
+            ports: vec![],
+            env_required: vec![],
+            working_dir: None,
+        });
+        let executor = ActionExecutor::new(std::env::current_dir().unwrap())
+            .with_shell(Some("bash".to_string()));
+
+        assert_eq!(executor.shell_for(&action), "bash");
+        action.shell = Some("sh".to_string());
+        assert_eq!(executor.shell_for(&action), "sh");
+
+        if std::process::Command::new("bash")
+            .arg("-c")
+            .arg("true")
+            .status()
+            .is_err()
+        {
+            return;
+        }
+        let executor = ActionExecutor::new(std::env::current_dir().unwrap())
+            .with_shell(Some("bash".to_string()));
+        action.shell = None;
+        action.command =
+            "shopt -s expand_aliases\nalias hi='echo hello'\nhi; sleep 0.3".to_string();
+        let (result, output) = executor.execute_collect(&action).await.unwrap();
+        assert!(result.success);
+        assert!(output.iter().any(|l| l.contains("hello")), "{:?}", output);
+    }
+
+    #[test]
+    fn aliased_script_keeps_its_original_id() {
+        let script = DiscoveredScript {
+            category: ScriptCategory::Database,
+            ..script("db:mig:gen", "npm run db:mig:gen")
+        };
+        let registry = registry_with(vec![script.clone()]);
+        let aliases = HashMap::from([("db:mig:gen".to_string(), "Generate migration".to_string())]);
+
+        let registry = registry.with_aliases(&aliases);
+        let action = registry.get("script:db:mig:gen").expect("id is unchanged");
+        assert_eq!(action.name, "Generate migration");
+        assert_eq!(action.command, "npm run db:mig:gen");
+        assert_eq!(registry.filter("migration").len(), 1);
+
+        let found = registry.for_script(&script).expect("found by id");
+        assert_eq!(found.name, "Generate migration");
+    }
+
+    #[test]
+    fn volume_destroying_compose_down_requires_confirm() {
+        let action = Action::from_script(DiscoveredScript {
+            name: "compose down --volumes".to_string(),
+            command: "docker compose down --volumes".to_string(),
+            source: ScriptSource::DockerCompose,
+            category: ScriptCategory::Docker,
+            description: None,
+            ports: vec![],
+            env_required: vec![],
+            working_dir: None,
+        });
+        assert!(action.requires_confirm);
+        assert!(action.is_destructive());
+
+        assert!(is_destructive("docker-compose down -v"));
+        assert!(!is_destructive("docker compose down"));
+    }
+
+    #[test]
+    fn favorites_sort_first_and_custom_keys_resolve() {
+        let registry = registry_with(vec![
+            script("build", "npm run build"),
+            script("dev", "npm run dev"),
+            script("lint", "npm run lint"),
+        ])
+        .with_custom_actions(&[CustomAction {
+            name: "deploy".to_string(),
+            command: "./deploy.sh".to_string(),
+            category: Some("deploy".to_string()),
+            description: Some("Ship it".to_string()),
+            keybinding: Some("ctrl+d".to_string()),
+            confirm: true,
+            confirm_phrase: false,
+            icon: None,
+            color: None,
+            shell: None,
+        }])
+        .with_favorites(&["deploy".to_string(), "lint".to_string()]);
+
+        let names: Vec<String> = registry
+            .all_actions()
+            .into_iter()
+            .filter(|a| a.source != ActionSource::System)
+            .map(|a| a.name)
+            .collect();
+        assert_eq!(names, ["deploy", "lint", "build", "dev"]);
+
+        let deploy = registry.get("custom:deploy").unwrap();
+        assert_eq!(deploy.source, ActionSource::OrbitConfig);
+        assert_eq!(deploy.category, ActionCategory::Deploy);
+        assert!(deploy.requires_confirm);
+
+        let ctrl_d = crossterm::event::KeyEvent::new(
+            crossterm::event::KeyCode::Char('d'),
+            crossterm::event::KeyModifiers::CONTROL,
+        );
+        assert_eq!(
+            registry.action_for_key(&ctrl_d).map(|a| a.id),
+            Some("custom:deploy".to_string())
+        );
+    }
+
+    #[test]
+    fn confirm_patterns_match_whole_words() {
+        let registry = registry_with(vec![
+            script("deploy:prod", "vercel --prod"),
+            script("clean", "rm -rf dist"),
+            script("format", "npm run format"),
+            script("lint", "eslint --fix ."),
+        ]);
+        let patterns: Vec<String> = DEFAULT_CONFIRM_PATTERNS
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+
+        let registry = registry.with_confirm_patterns(&patterns);
+        let confirms = |id: &str| registry.get(id).unwrap().requires_confirm;
+        assert!(confirms("script:deploy:prod"));
+        assert!(confirms("script:clean"));
+        assert!(!confirms("script:format"));
+        assert!(!confirms("script:lint"));
+    }
+
+    #[test]
+    fn exports_scripts_with_unique_identifiers() {
+        let scripts = vec![
+            script("test:unit", "npm run test:unit"),
+            script("test unit", "pytest -k 'not slow'"),
+            DiscoveredScript {
+                working_dir: Some(PathBuf::from("/repo/apps/web")),
+                ..script("web:dev", "PORT=$PORT pnpm run dev")
+            },
+            script("2fa", "echo {{code}}"),
+            script("_release", "git tag v1\ngit push --tags"),
+        ];
+        let root = Path::new("/repo");
+
+        let just = export_scripts(&scripts, ScriptExportFormat::Just, root);
+        assert!(just.contains("\ntest-unit:\n    npm run test:unit\n"));
+        assert!(just.contains("\ntest-unit-2:\n    pytest -k 'not slow'\n"));
+        assert!(just.contains("\nweb-dev:\n    cd apps/web && PORT=$PORT pnpm run dev\n"));
+        assert!(just.contains("\nx2fa:\n    echo {{{{code}}\n"));
+        assert!(just.contains("\nx_release:\n    git tag v1\n    git push --tags\n"));
+
+        let make = export_scripts(&scripts, ScriptExportFormat::Make, root);
+        assert!(make.contains(".PHONY: test-unit test-unit-2 web-dev x2fa x_release\n"));
+        assert!(make.contains("\nx_release:\n\tgit tag v1\n\tgit push --tags\n"));
+        assert!(make.contains("\nweb-dev:\n\tcd apps/web && PORT=$$PORT pnpm run dev\n"));
+
+        let sh = export_scripts(&scripts, ScriptExportFormat::Sh, root);
+        assert!(sh.contains("\ntest_unit() {\n    npm run test:unit\n}\n"));
+        assert!(sh.contains("\ntest_unit_2() {\n"));
+        assert!(sh.contains("\nweb_dev() {\n"));
+    }
 }
@@ -0,0 +1,142 @@
+//! Progress scraped from the output of well-known long-running commands
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// `Building [=====>    ] 12/34: serde, tokio` (cargo's TTY progress bar) or a
+/// `Compiling serde v1.0.0 (12/34)` line
+static CARGO_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:Building \[[=> ]*\]\s*|Compiling (\S+).*\()(\d+)/(\d+)")
+        .expect("Invalid cargo progress regex")
+});
+
+/// `Step 3/10 : RUN make` (legacy builder)
+static DOCKER_STEP_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^Step (\d+)/(\d+) : (.*)$").expect("Invalid docker step regex"));
+
+/// `#7 [builder 3/5] RUN make` (BuildKit)
+static BUILDKIT_STEP_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^#\d+ \[(?:[\w.-]+ )?(\d+)/(\d+)\] (.*)$").expect("Invalid buildkit regex")
+});
+
+/// How far along a running command is
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Progress {
+    pub current: u32,
+    pub total: u32,
+    /// What it's working on, e.g. the crate being compiled
+    pub label: String,
+}
+
+impl Progress {
+    pub fn ratio(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            (self.current as f64 / self.total as f64).min(1.0)
+        }
+    }
+}
+
+/// Commands whose progress output we understand
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressExtractor {
+    Cargo,
+    DockerBuild,
+}
+
+impl ProgressExtractor {
+    /// Guess the extractor from the command line
+    pub fn for_command(command: &str) -> Option<Self> {
+        let words: Vec<&str> = command.split_whitespace().collect();
+        let has = |w: &str| words.contains(&w);
+
+        if has("cargo") && (has("build") || has("b") || has("install")) {
+            Some(Self::Cargo)
+        } else if has("docker") && has("build") {
+            Some(Self::DockerBuild)
+        } else {
+            None
+        }
+    }
+
+    /// Progress reported on a single output line, if any
+    ///
+    /// Terminal progress bars redraw with `\r`, so only the last segment counts.
+    pub fn parse_line(self, line: &str) -> Option<Progress> {
+        let line = line.rsplit('\r').find(|s| !s.trim().is_empty())?.trim();
+        let (current, total, label) = match self {
+            Self::Cargo => {
+                let caps = CARGO_REGEX.captures(line)?;
+                let label = match caps.get(1) {
+                    Some(name) => name.as_str().to_string(),
+                    None => line
+                        .split_once(": ")
+                        .map_or_else(String::new, |(_, crates)| crates.to_string()),
+                };
+                (caps[2].parse().ok()?, caps[3].parse().ok()?, label)
+            }
+            Self::DockerBuild => {
+                let caps = DOCKER_STEP_REGEX
+                    .captures(line)
+                    .or_else(|| BUILDKIT_STEP_REGEX.captures(line))?;
+                (
+                    caps[1].parse().ok()?,
+                    caps[2].parse().ok()?,
+                    caps[3].to_string(),
+                )
+            }
+        };
+        Some(Progress {
+            current,
+            total,
+            label,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn progress(current: u32, total: u32, label: &str) -> Option<Progress> {
+        Some(Progress {
+            current,
+            total,
+            label: label.to_string(),
+        })
+    }
+
+    #[test]
+    fn parses_cargo_compiling_progress() {
+        let cargo = ProgressExtractor::for_command("cargo build --release").unwrap();
+        assert_eq!(
+            cargo.parse_line("   Compiling serde v1.0.203 (12/34)"),
+            progress(12, 34, "serde")
+        );
+        assert_eq!(
+            cargo.parse_line("    Building [=======>     ] 20/34: tokio, hyper\r"),
+            progress(20, 34, "tokio, hyper")
+        );
+        // A path in parentheses isn't a step count
+        assert_eq!(
+            cargo.parse_line("   Compiling orbit v0.1.0 (/home/me/orbit)"),
+            None
+        );
+        assert_eq!(cargo.parse_line("warning: unused variable: `x`"), None);
+    }
+
+    #[test]
+    fn parses_docker_build_steps() {
+        let docker = ProgressExtractor::for_command("docker build -t api .").unwrap();
+        assert_eq!(
+            docker.parse_line("Step 3/10 : RUN npm ci"),
+            progress(3, 10, "RUN npm ci")
+        );
+        assert_eq!(
+            docker.parse_line("#7 [builder 2/5] COPY . ."),
+            progress(2, 5, "COPY . .")
+        );
+        assert_eq!(ProgressExtractor::for_command("npm run dev"), None);
+    }
+}
@@ -0,0 +1,79 @@
+//! Pseudo-terminal plumbing shared by action execution and the embedded shell
+
+use anyhow::Result;
+use portable_pty::{Child, CommandBuilder, MasterPty, NativePtySystem, PtySize, PtySystem};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// A child process attached to its own pseudo-terminal
+pub struct PtyProcess {
+    pub master: Box<dyn MasterPty + Send>,
+    pub child: Box<dyn Child + Send + Sync>,
+}
+
+/// The `PtySize` for a `rows` x `cols` terminal
+pub fn size(rows: u16, cols: u16) -> PtySize {
+    PtySize {
+        rows: rows.max(1),
+        cols: cols.max(1),
+        pixel_width: 0,
+        pixel_height: 0,
+    }
+}
+
+/// Start `program args` in `working_dir` on a new `rows` x `cols` pseudo-terminal
+pub fn spawn(
+    program: &str,
+    args: &[&str],
+    working_dir: &Path,
+    env_vars: &HashMap<String, String>,
+    rows: u16,
+    cols: u16,
+) -> Result<PtyProcess> {
+    let pair = NativePtySystem::default().openpty(size(rows, cols))?;
+
+    let mut cmd = CommandBuilder::new(program);
+    cmd.args(args);
+    cmd.cwd(working_dir);
+    for (key, value) in env_vars {
+        cmd.env(key, value);
+    }
+    cmd.env("TERM", "xterm-256color");
+
+    let child = pair.slave.spawn_command(cmd)?;
+    // Only the master side is needed; dropping the slave lets reads end at exit
+    drop(pair.slave);
+
+    Ok(PtyProcess {
+        master: pair.master,
+        child,
+    })
+}
+
+/// Strip ANSI escape codes from a string
+pub fn strip_ansi_codes(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Skip escape sequence
+            if chars.peek() == Some(&'[') {
+                chars.next(); // consume '['
+                              // Skip until we hit a letter (end of escape sequence)
+                while let Some(&next) = chars.peek() {
+                    chars.next();
+                    if next.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            }
+        } else if c == '\r' {
+            // Skip carriage returns
+        } else {
+            result.push(c);
+        }
+    }
+
+    result
+}
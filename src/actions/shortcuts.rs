@@ -0,0 +1,290 @@
+//! "Open" shortcuts synthesized from what is detected and running
+//!
+//! Each [`OpenRule`] pairs a trigger (a framework, a listening port, a running
+//! container) with a URL or app to open. Add entries to [`OPEN_RULES`] to extend it.
+
+use crate::detection::{ProjectContext, ProjectKind};
+use crate::integrations::docker::{ContainerInfo, ContainerStatus};
+use crate::integrations::ports::{ActivePort, PortState};
+
+use super::{Action, ActionCategory, ActionSource};
+
+/// What has to be true for a rule to offer its shortcut
+#[derive(Debug, Clone, Copy)]
+pub enum Trigger {
+    /// The project uses a web framework and one of its expected ports is listening
+    WebApp,
+    /// Something is listening on this port
+    Port(u16),
+    /// A running container whose image contains this name publishes this container port
+    Container { image: &'static str, port: u16 },
+    /// The project has a Dockerfile or compose file
+    Docker,
+}
+
+/// Where a shortcut leads
+#[derive(Debug, Clone, Copy)]
+pub enum Target {
+    /// URL template; `{port}` is replaced with the matched port
+    Url(&'static str),
+    /// macOS application name, opened with `open -a`
+    App(&'static str),
+}
+
+/// A data-driven rule producing one "open" action per match
+#[derive(Debug, Clone, Copy)]
+pub struct OpenRule {
+    pub id: &'static str,
+    /// Action name; `{port}` is replaced with the matched port
+    pub name: &'static str,
+    pub trigger: Trigger,
+    pub target: Target,
+}
+
+pub const OPEN_RULES: &[OpenRule] = &[
+    OpenRule {
+        id: "app",
+        name: "Open app (localhost:{port})",
+        trigger: Trigger::WebApp,
+        target: Target::Url("http://localhost:{port}"),
+    },
+    OpenRule {
+        id: "postgres",
+        name: "Open Postgres (localhost:{port})",
+        trigger: Trigger::Port(5432),
+        target: Target::Url("postgresql://localhost:{port}"),
+    },
+    OpenRule {
+        id: "pgadmin",
+        name: "Open pgAdmin (localhost:{port})",
+        trigger: Trigger::Container {
+            image: "pgadmin",
+            port: 80,
+        },
+        target: Target::Url("http://localhost:{port}"),
+    },
+    OpenRule {
+        id: "adminer",
+        name: "Open Adminer (localhost:{port})",
+        trigger: Trigger::Container {
+            image: "adminer",
+            port: 8080,
+        },
+        target: Target::Url("http://localhost:{port}"),
+    },
+    OpenRule {
+        id: "mailhog",
+        name: "Open Mailhog (localhost:{port})",
+        trigger: Trigger::Container {
+            image: "mailhog",
+            port: 8025,
+        },
+        target: Target::Url("http://localhost:{port}"),
+    },
+    OpenRule {
+        id: "rabbitmq",
+        name: "Open RabbitMQ management (localhost:{port})",
+        trigger: Trigger::Container {
+            image: "rabbitmq",
+            port: 15672,
+        },
+        target: Target::Url("http://localhost:{port}"),
+    },
+    OpenRule {
+        id: "docker",
+        name: "Open Docker Desktop",
+        trigger: Trigger::Docker,
+        target: Target::App("Docker"),
+    },
+];
+
+/// Shortcut actions for every rule in [`OPEN_RULES`] that matches right now
+pub fn open_actions(
+    project: &ProjectContext,
+    ports: &[ActivePort],
+    containers: &[ContainerInfo],
+) -> Vec<Action> {
+    let mut actions: Vec<Action> = Vec::new();
+
+    for rule in OPEN_RULES {
+        for port in matched_ports(rule.trigger, project, ports, containers) {
+            let Some(command) = open_command(rule.target, port) else {
+                continue;
+            };
+            let id = match port {
+                Some(port) => format!("open:{}:{}", rule.id, port),
+                None => format!("open:{}", rule.id),
+            };
+            if actions.iter().any(|a| a.id == id) {
+                continue;
+            }
+
+            let name = fill_port(rule.name, port);
+            actions.push(Action {
+                id,
+                description: Some(format!("Run `{}`", command)),
+                name,
+                command,
+                category: ActionCategory::Custom,
+                source: ActionSource::Detected,
+                keybinding: None,
+                requires_confirm: false,
+                confirm_phrase: None,
+                env_required: vec![],
+                working_dir: None,
+                icon: None,
+                color: None,
+                shell: None,
+            });
+        }
+    }
+
+    actions
+}
+
+/// Ports a trigger matched; `Some(None)` means a match that isn't tied to a port
+fn matched_ports(
+    trigger: Trigger,
+    project: &ProjectContext,
+    ports: &[ActivePort],
+    containers: &[ContainerInfo],
+) -> Vec<Option<u16>> {
+    let listening = |port: u16| {
+        ports
+            .iter()
+            .any(|p| p.port == port && p.state == PortState::Listening)
+    };
+
+    match trigger {
+        Trigger::WebApp => {
+            if !has_web_framework(project) {
+                return vec![];
+            }
+            project
+                .ports
+                .iter()
+                .map(|p| p.port)
+                .filter(|&port| listening(port))
+                .map(Some)
+                .collect()
+        }
+        Trigger::Port(port) => {
+            if listening(port) {
+                vec![Some(port)]
+            } else {
+                vec![]
+            }
+        }
+        Trigger::Container { image, port } => containers
+            .iter()
+            .filter(|c| c.status == ContainerStatus::Running && c.image.contains(image))
+            .flat_map(|c| &c.ports)
+            .filter(|m| m.private_port == port)
+            .filter_map(|m| m.public_port)
+            .map(Some)
+            .collect(),
+        Trigger::Docker => {
+            let docker = project
+                .types
+                .iter()
+                .any(|t| matches!(t.kind, ProjectKind::Docker { .. }));
+            if docker {
+                vec![None]
+            } else {
+                vec![]
+            }
+        }
+    }
+}
+
+fn has_web_framework(project: &ProjectContext) -> bool {
+    project.types.iter().any(|t| match &t.kind {
+        ProjectKind::Node { framework, .. } => framework.is_some(),
+        ProjectKind::Python { framework, .. } => framework.is_some(),
+        _ => false,
+    })
+}
+
+/// Shell command that opens `target`, or `None` when this platform can't open it
+fn open_command(target: Target, port: Option<u16>) -> Option<String> {
+    match target {
+        Target::Url(url) => {
+            let opener = if cfg!(target_os = "macos") {
+                "open"
+            } else {
+                "xdg-open"
+            };
+            Some(format!("{} {}", opener, fill_port(url, port)))
+        }
+        Target::App(app) if cfg!(target_os = "macos") => Some(format!("open -a {}", app)),
+        Target::App(_) => None,
+    }
+}
+
+fn fill_port(template: &str, port: Option<u16>) -> String {
+    match port {
+        Some(port) => template.replace("{port}", &port.to_string()),
+        None => template.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::{
+        EnvVarAnalysis, ExpectedPort, NodeFramework, PackageManager, ProjectType,
+    };
+
+    fn nextjs_project() -> ProjectContext {
+        ProjectContext {
+            root: "/tmp/web".into(),
+            name: "web".to_string(),
+            types: vec![ProjectType {
+                kind: ProjectKind::Node {
+                    package_manager: PackageManager::Npm,
+                    framework: Some(NodeFramework::NextJs),
+                },
+                confidence: 1.0,
+                primary: true,
+            }],
+            scripts: vec![],
+            env_vars: EnvVarAnalysis::default(),
+            ports: vec![ExpectedPort {
+                port: 3000,
+                source: "package.json".to_string(),
+                service_name: "dev".to_string(),
+            }],
+            docker_services: vec![],
+            git_info: None,
+            advisories: vec![],
+            warnings: vec![],
+            member_manifests: vec![],
+        }
+    }
+
+    fn listening(port: u16) -> ActivePort {
+        ActivePort {
+            port,
+            pid: 42,
+            process_name: "node".to_string(),
+            state: PortState::Listening,
+            remote: None,
+        }
+    }
+
+    #[test]
+    fn nextjs_with_listening_port_offers_open_app() {
+        let project = nextjs_project();
+
+        let actions = open_actions(&project, &[listening(3000)], &[]);
+        let app = actions
+            .iter()
+            .find(|a| a.name == "Open app (localhost:3000)")
+            .expect("open app action");
+        assert_eq!(app.id, "open:app:3000");
+        assert!(app.command.ends_with("http://localhost:3000"));
+
+        // Nothing to open while the dev server is down
+        assert!(open_actions(&project, &[], &[]).is_empty());
+    }
+}
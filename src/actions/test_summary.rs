@@ -0,0 +1,232 @@
+//! Pass/fail counts scraped from well-known test runner output
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::{Action, ActionCategory};
+
+static COUNT_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(\d+) (passed|failed|ignored|skipped|todo|errors?)\b")
+        .expect("Invalid test count regex")
+});
+
+static PYTEST_SUMMARY_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^=+ .* in [\d.]+m?s.* =+$").expect("Invalid pytest regex"));
+
+static GO_RESULT_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*--- (PASS|FAIL|SKIP): ").expect("Invalid go test regex"));
+
+/// Test runners whose summary output we understand
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestRunner {
+    Cargo,
+    Pytest,
+    Jest,
+    Go,
+}
+
+impl TestRunner {
+    const ALL: [TestRunner; 4] = [Self::Cargo, Self::Pytest, Self::Jest, Self::Go];
+
+    /// Guess the runner from the command line
+    pub fn for_command(command: &str) -> Option<Self> {
+        let words: Vec<&str> = command.split_whitespace().collect();
+        let has = |w: &str| words.contains(&w);
+
+        if has("cargo") && has("test") {
+            Some(Self::Cargo)
+        } else if has("pytest") || has("py.test") {
+            Some(Self::Pytest)
+        } else if has("jest") {
+            Some(Self::Jest)
+        } else if has("go") && has("test") {
+            Some(Self::Go)
+        } else {
+            None
+        }
+    }
+
+    /// Counts reported on a single output line, if it is a summary line for this runner
+    pub fn parse_line(self, line: &str) -> Option<TestSummary> {
+        let line = line.trim();
+        match self {
+            // test result: FAILED. 41 passed; 1 failed; 0 ignored; 0 measured; ...
+            Self::Cargo => line
+                .starts_with("test result:")
+                .then(|| TestSummary::from_counts(line)),
+            // ===== 1 failed, 41 passed, 2 skipped in 0.12s =====
+            Self::Pytest => PYTEST_SUMMARY_REGEX
+                .is_match(line)
+                .then(|| TestSummary::from_counts(line)),
+            // Tests:       1 failed, 41 passed, 42 total
+            Self::Jest => line
+                .starts_with("Tests:")
+                .then(|| TestSummary::from_counts(line)),
+            // --- FAIL: TestParse (0.00s); only printed per test with -v or on failure
+            Self::Go => GO_RESULT_REGEX.captures(line).map(|caps| {
+                let mut summary = TestSummary::default();
+                match &caps[1] {
+                    "PASS" => summary.passed = 1,
+                    "FAIL" => summary.failed = 1,
+                    _ => summary.skipped = 1,
+                }
+                summary
+            }),
+        }
+    }
+}
+
+/// Aggregated test counts for one action run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TestSummary {
+    pub passed: u32,
+    pub failed: u32,
+    pub skipped: u32,
+}
+
+impl TestSummary {
+    fn from_counts(line: &str) -> Self {
+        let mut summary = Self::default();
+        for caps in COUNT_REGEX.captures_iter(line) {
+            let count: u32 = caps[1].parse().unwrap_or(0);
+            match &caps[2] {
+                "passed" => summary.passed += count,
+                "failed" | "error" | "errors" => summary.failed += count,
+                _ => summary.skipped += count,
+            }
+        }
+        summary
+    }
+
+    fn add(&mut self, other: TestSummary) {
+        self.passed += other.passed;
+        self.failed += other.failed;
+        self.skipped += other.skipped;
+    }
+}
+
+impl std::fmt::Display for TestSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} passed, {} failed", self.passed, self.failed)?;
+        if self.skipped > 0 {
+            write!(f, ", {} skipped", self.skipped)?;
+        }
+        Ok(())
+    }
+}
+
+/// Accumulates a [`TestSummary`] from an action's output lines
+///
+/// Summary lines are summed, so workspaces that print one `test result:` per crate
+/// report the total. Yields nothing when no line matched.
+#[derive(Debug, Clone)]
+pub struct TestSummaryParser {
+    runners: Vec<TestRunner>,
+    summary: Option<TestSummary>,
+}
+
+impl TestSummaryParser {
+    /// Parser for `action`, or `None` when it doesn't look like a test run.
+    /// Test actions whose runner is hidden behind a script (`npm test`) try every runner.
+    pub fn for_action(action: &Action) -> Option<Self> {
+        let runners = match TestRunner::for_command(&action.command) {
+            Some(runner) => vec![runner],
+            None if action.category == ActionCategory::Test => TestRunner::ALL.to_vec(),
+            None => return None,
+        };
+        Some(Self {
+            runners,
+            summary: None,
+        })
+    }
+
+    pub fn feed(&mut self, line: &str) {
+        if let Some(counts) = self.runners.iter().find_map(|r| r.parse_line(line)) {
+            self.summary
+                .get_or_insert_with(TestSummary::default)
+                .add(counts);
+        }
+    }
+
+    pub fn finish(self) -> Option<TestSummary> {
+        self.summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(runner: TestRunner, lines: &[&str]) -> Option<TestSummary> {
+        let mut parser = TestSummaryParser {
+            runners: vec![runner],
+            summary: None,
+        };
+        for line in lines {
+            parser.feed(line);
+        }
+        parser.finish()
+    }
+
+    fn counts(passed: u32, failed: u32, skipped: u32) -> Option<TestSummary> {
+        Some(TestSummary {
+            passed,
+            failed,
+            skipped,
+        })
+    }
+
+    #[test]
+    fn parses_cargo_results_across_crates() {
+        let lines = [
+            "running 42 tests",
+            "test result: FAILED. 41 passed; 1 failed; 2 ignored; 0 measured; 0 filtered out; finished in 0.36s",
+            "test result: ok. 3 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.01s",
+        ];
+        assert_eq!(summary(TestRunner::Cargo, &lines), counts(44, 1, 2));
+    }
+
+    #[test]
+    fn parses_pytest_summary() {
+        let lines = [
+            "tests/test_api.py ..F.                                  [100%]",
+            "=================== 1 failed, 41 passed, 2 skipped in 0.12s ===================",
+        ];
+        assert_eq!(summary(TestRunner::Pytest, &lines), counts(41, 1, 2));
+    }
+
+    #[test]
+    fn parses_jest_summary() {
+        let lines = [
+            "Test Suites: 1 failed, 3 passed, 4 total",
+            "Tests:       1 failed, 1 skipped, 41 passed, 43 total",
+        ];
+        assert_eq!(summary(TestRunner::Jest, &lines), counts(41, 1, 1));
+    }
+
+    #[test]
+    fn parses_go_test_results() {
+        let lines = [
+            "=== RUN   TestParse",
+            "--- PASS: TestParse (0.00s)",
+            "--- FAIL: TestServe (0.01s)",
+            "    --- SKIP: TestServe/slow (0.00s)",
+            "FAIL\texample.com/app\t0.02s",
+        ];
+        assert_eq!(summary(TestRunner::Go, &lines), counts(1, 1, 1));
+    }
+
+    #[test]
+    fn unmatched_output_yields_no_summary() {
+        assert_eq!(
+            summary(TestRunner::Cargo, &["error: could not compile"]),
+            None
+        );
+        assert_eq!(TestRunner::for_command("npm run build"), None);
+        assert_eq!(
+            TestRunner::for_command("cargo test --workspace"),
+            Some(TestRunner::Cargo)
+        );
+        assert_eq!(counts(42, 1, 0).unwrap().to_string(), "42 passed, 1 failed");
+    }
+}
@@ -37,6 +37,130 @@ impl Config {
     pub fn default_path() -> Option<PathBuf> {
         dirs::config_dir().map(|p| p.join("orbit").join("config.toml"))
     }
+
+    /// Write `display.layout` into the config file at `path`, keeping the rest of it
+    /// (including comments) untouched
+    pub fn persist_layout(path: &Path, layout: &str) -> Result<()> {
+        Self::persist_display(path, "layout", layout)
+    }
+
+    /// Write `display.theme` into the config file at `path`, like `persist_layout`
+    pub fn persist_theme(path: &Path, theme: &str) -> Result<()> {
+        Self::persist_display(path, "theme", theme)
+    }
+
+    fn persist_display(path: &Path, key: &str, value: &str) -> Result<()> {
+        let content = read_for_edit(path)?;
+        let mut doc: toml_edit::DocumentMut = content.parse()?;
+        let display = doc
+            .entry("display")
+            .or_insert(toml_edit::table())
+            .as_table_like_mut()
+            .ok_or_else(|| anyhow::anyhow!("`display` in {} is not a table", path.display()))?;
+        // Assigning in place keeps the comments attached to the key
+        match display.get_mut(key) {
+            Some(item) => *item = toml_edit::value(value),
+            None => {
+                display.insert(key, toml_edit::value(value));
+            }
+        }
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, doc.to_string())?;
+        Ok(())
+    }
+}
+
+/// Directory Orbit writes its own logs to
+pub fn log_dir() -> PathBuf {
+    log_dir_in(&dirs::cache_dir().unwrap_or_else(|| PathBuf::from(".")))
+}
+
+fn log_dir_in(cache_dir: &Path) -> PathBuf {
+    cache_dir.join("orbit").join("logs")
+}
+
+/// Directory dashboard snapshots are saved to
+pub fn snapshot_dir() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("orbit")
+        .join("snapshots")
+}
+
+/// Log of finished focus sessions, read by `orbit focus --stats`
+pub fn focus_history_file() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("orbit")
+        .join("focus_history.jsonl")
+}
+
+/// Scratchpad notes for a project, kept alongside Orbit's other cache files
+pub fn notes_file(project_name: &str) -> PathBuf {
+    notes_file_in(
+        &dirs::cache_dir().unwrap_or_else(|| PathBuf::from(".")),
+        project_name,
+    )
+}
+
+fn notes_file_in(cache_dir: &Path, project_name: &str) -> PathBuf {
+    project_cache_dir_in(cache_dir, project_name).join("notes.md")
+}
+
+/// Layout and focus saved on exit, restored when `general.restore_session` is on
+pub fn session_file(working_dir: &Path) -> PathBuf {
+    session_file_in(
+        &dirs::cache_dir().unwrap_or_else(|| PathBuf::from(".")),
+        working_dir,
+    )
+}
+
+/// Keyed by the canonical path, so checkouts sharing a directory name don't
+/// share a session
+fn session_file_in(cache_dir: &Path, working_dir: &Path) -> PathBuf {
+    let dir = working_dir
+        .canonicalize()
+        .unwrap_or_else(|_| working_dir.to_path_buf());
+    let name = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "project".to_string());
+    project_cache_dir_in(cache_dir, &format!("{}-{:016x}", name, path_hash(&dir)))
+        .join("session.toml")
+}
+
+/// FNV-1a of `path`, stable across builds unlike `DefaultHasher`
+fn path_hash(path: &Path) -> u64 {
+    path.to_string_lossy()
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        })
+}
+
+fn project_cache_dir_in(cache_dir: &Path, project_name: &str) -> PathBuf {
+    let dir: String = project_name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    cache_dir.join("orbit").join(dir)
+}
+
+/// Today's log file, named the way the daily rolling appender names it
+pub fn current_log_file() -> PathBuf {
+    log_dir().join(format!(
+        "orbit.log.{}",
+        chrono::Utc::now().format("%Y-%m-%d")
+    ))
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,6 +168,25 @@ impl Config {
 pub struct GeneralConfig {
     pub check_updates: bool,
     pub startup_time_target: u32,
+    /// Env var name patterns treated as secrets (globs, or `re:` regexes)
+    pub secret_patterns: Vec<String>,
+    /// Project types in order of preference when detection confidence ties
+    pub type_priority: Vec<String>,
+    /// Connect to Docker on startup (`--no-docker` turns this off)
+    pub enable_docker: bool,
+    /// Read git status and recent files (`--no-git` turns this off)
+    pub enable_git: bool,
+    /// Scan listening ports (`--no-ports` turns this off)
+    pub enable_ports: bool,
+    /// Reopen each project with the layout, panels and focus it was left with
+    pub restore_session: bool,
+    /// Shell actions run in, as a login shell; defaults to `$SHELL`
+    pub shell: Option<String>,
+    /// Actions whose name or command contains one of these words ask before running
+    pub confirm_patterns: Vec<String>,
+    /// `.orbit.toml` profile from `--profile` or `ORBIT_PROFILE`; never read from the file
+    #[serde(skip)]
+    pub profile: Option<String>,
 }
 
 impl Default for GeneralConfig {
@@ -51,6 +194,24 @@ impl Default for GeneralConfig {
         Self {
             check_updates: true,
             startup_time_target: 50,
+            secret_patterns: crate::secrets::DEFAULT_SECRET_PATTERNS
+                .iter()
+                .map(|p| p.to_string())
+                .collect(),
+            type_priority: crate::detection::DEFAULT_TYPE_PRIORITY
+                .iter()
+                .map(|k| k.to_string())
+                .collect(),
+            enable_docker: true,
+            enable_git: true,
+            enable_ports: true,
+            restore_session: false,
+            shell: None,
+            confirm_patterns: crate::actions::DEFAULT_CONFIRM_PATTERNS
+                .iter()
+                .map(|p| p.to_string())
+                .collect(),
+            profile: None,
         }
     }
 }
@@ -63,16 +224,68 @@ pub struct DisplayConfig {
     pub animations: bool,
     pub animation_speed: String,
     pub sidebar_width: u16,
+    /// Use a blue/orange status palette with text badges instead of red/green
+    pub colorblind: bool,
+    /// Moving past the last row of a list jumps back to the first
+    pub wrap_navigation: bool,
+    /// Shorten long names at the "end" or in the "middle" (keeps distinguishing suffixes)
+    pub name_truncation: Truncation,
+    /// Re-running the action that produced the latest output replaces that output
+    pub auto_clear_on_rerun: bool,
+    /// Header segments in order; the rightmost are dropped first when space runs out
+    pub header_segments: Vec<HeaderSegment>,
+    /// Panel focused on launch, overriding a restored session
+    pub startup_panel: Option<crate::core::state::FocusedPanel>,
+}
+
+/// Where the `…` goes when a name doesn't fit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Truncation {
+    /// `docker-compose-postgr…`
+    #[default]
+    End,
+    /// `dock…replica`, keeping the suffix that usually tells similar names apart
+    Middle,
+}
+
+/// A piece of the header line, listed in `display.header_segments`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HeaderSegment {
+    Project,
+    Git,
+    Types,
+    Docker,
+    Ports,
+    Time,
+}
+
+impl HeaderSegment {
+    pub const ALL: [HeaderSegment; 6] = [
+        Self::Project,
+        Self::Git,
+        Self::Types,
+        Self::Docker,
+        Self::Ports,
+        Self::Time,
+    ];
 }
 
 impl Default for DisplayConfig {
     fn default() -> Self {
         Self {
             theme: "tokyo-night".to_string(),
-            layout: "standard".to_string(),
+            layout: "auto".to_string(),
             animations: true,
             animation_speed: "normal".to_string(),
             sidebar_width: 30,
+            colorblind: false,
+            wrap_navigation: false,
+            name_truncation: Truncation::End,
+            auto_clear_on_rerun: false,
+            header_segments: HeaderSegment::ALL.to_vec(),
+            startup_panel: None,
         }
     }
 }
@@ -137,8 +350,14 @@ pub struct FocusConfig {
     pub default_duration: u32,
     pub enable_dnd: bool,
     pub minimize_windows: bool,
+    /// Apps left open when minimizing, in addition to the built-in terminals
+    pub keep_open: Vec<String>,
     pub ambient_sound: String,
     pub ambient_volume: u8,
+    /// Pause the timer after this many minutes without input
+    pub auto_pause_minutes: Option<u32>,
+    /// POST a JSON summary (project, outcome, timing) here when a session ends
+    pub webhook_url: Option<String>,
 }
 
 impl Default for FocusConfig {
@@ -147,8 +366,11 @@ impl Default for FocusConfig {
             default_duration: 25,
             enable_dnd: true,
             minimize_windows: true,
+            keep_open: Vec::new(),
             ambient_sound: "lofi".to_string(),
             ambient_volume: 30,
+            auto_pause_minutes: None,
+            webhook_url: None,
         }
     }
 }
@@ -160,6 +382,12 @@ pub struct NotificationsConfig {
     pub on_action_complete: bool,
     pub on_focus_end: bool,
     pub on_port_conflict: bool,
+    /// Keep error notifications on screen until dismissed with `X`
+    pub sticky_errors: bool,
+    /// Ring the terminal bell when an action finishes
+    pub bell: bool,
+    /// Only actions running at least this long get a native notification or bell
+    pub min_action_seconds: u64,
 }
 
 impl Default for NotificationsConfig {
@@ -169,6 +397,9 @@ impl Default for NotificationsConfig {
             on_action_complete: true,
             on_focus_end: true,
             on_port_conflict: true,
+            sticky_errors: true,
+            bell: false,
+            min_action_seconds: 10,
         }
     }
 }
@@ -183,6 +414,8 @@ pub struct ProjectConfig {
     pub secrets: ProjectSecretsConfig,
     pub ports: ProjectPortsConfig,
     pub focus: ProjectFocusConfig,
+    /// Recorded action sequences: name to action ids, replayed in order
+    pub macros: indexmap::IndexMap<String, Vec<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -210,7 +443,7 @@ impl Default for ProjectDisplayConfig {
             ports_panel: true,
             env_panel: true,
             output_expanded: false,
-            layout: "standard".to_string(),
+            layout: "auto".to_string(),
             theme: None,
         }
     }
@@ -221,6 +454,8 @@ impl Default for ProjectDisplayConfig {
 pub struct ProjectActionsConfig {
     pub custom: Vec<CustomAction>,
     pub favorites: Vec<String>,
+    /// Display names for discovered scripts, keyed by the script name
+    pub aliases: std::collections::HashMap<String, String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -232,6 +467,15 @@ pub struct CustomAction {
     pub keybinding: Option<String>,
     #[serde(default)]
     pub confirm: bool,
+    /// Require typing the action name to confirm, for truly dangerous actions
+    #[serde(default)]
+    pub confirm_phrase: bool,
+    /// Icon or emoji shown instead of the category icon
+    pub icon: Option<String>,
+    /// Hex color such as "#ff5555"
+    pub color: Option<String>,
+    /// Shell to run this action under instead of the default
+    pub shell: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -239,6 +483,10 @@ pub struct CustomAction {
 pub struct ProjectSecretsConfig {
     pub keychain: Vec<String>,
     pub allow_dotenv: Vec<String>,
+    /// Fallback resolver for keys missing from the Keychain, e.g.
+    /// `op read op://vault/{key}/password`; stdout becomes the value
+    #[serde(alias = "secret_command")]
+    pub command: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -262,17 +510,85 @@ pub struct ProjectFocusConfig {
     pub minimize_windows: Option<bool>,
 }
 
+/// The `.orbit.toml` profile for this run: `--profile`, else `ORBIT_PROFILE`
+pub fn resolve_profile(flag: Option<String>) -> Option<String> {
+    flag.or_else(|| std::env::var("ORBIT_PROFILE").ok())
+        .filter(|name| !name.is_empty())
+}
+
+/// A config file's contents for editing in place: empty when it doesn't exist
+/// yet, but any other read error is returned rather than overwriting the file
+fn read_for_edit(path: &Path) -> Result<String> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(anyhow::Error::new(e).context(format!("Can't read {}", path.display()))),
+    }
+}
+
 impl ProjectConfig {
-    pub fn load(dir: &Path) -> Result<Option<Self>> {
+    /// Load `.orbit.toml`, merging `[profiles.<profile>]` over the base sections;
+    /// naming a profile the file doesn't define is an error
+    pub fn load(dir: &Path, profile: Option<&str>) -> Result<Option<Self>> {
         let path = dir.join(".orbit.toml");
         if !path.exists() {
             return Ok(None);
         }
         let content = std::fs::read_to_string(&path)?;
-        let config: ProjectConfig = toml::from_str(&content)?;
+        let mut value: toml::Value = toml::from_str(&content)?;
+
+        let profiles = value
+            .as_table_mut()
+            .and_then(|table| table.remove("profiles"));
+        if let Some(name) = profile {
+            let overrides = profiles
+                .as_ref()
+                .and_then(|p| p.get(name))
+                .ok_or_else(|| anyhow::anyhow!("No [profiles.{}] in {}", name, path.display()))?;
+            merge_toml(&mut value, overrides);
+        }
+
+        let config: ProjectConfig = value.try_into()?;
         Ok(Some(config))
     }
 
+    /// Write `[macros] <name> = [ids]` into `.orbit.toml`, keeping the rest of the file
+    pub fn persist_macro(dir: &Path, name: &str, action_ids: &[String]) -> Result<()> {
+        let path = dir.join(".orbit.toml");
+        let content = read_for_edit(&path)?;
+        let mut doc: toml_edit::DocumentMut = content.parse()?;
+        let ids: toml_edit::Array = action_ids.iter().map(String::as_str).collect();
+        doc["macros"][name] = toml_edit::value(ids);
+        std::fs::write(&path, doc.to_string())?;
+        Ok(())
+    }
+
+    /// Append `keys` missing from `secrets.keychain` in `.orbit.toml`, keeping the rest of the file
+    pub fn persist_keychain_keys(dir: &Path, keys: &[String]) -> Result<()> {
+        let path = dir.join(".orbit.toml");
+        let content = read_for_edit(&path)?;
+        let mut doc: toml_edit::DocumentMut = content.parse()?;
+        let secrets = doc
+            .entry("secrets")
+            .or_insert(toml_edit::table())
+            .as_table_like_mut()
+            .ok_or_else(|| anyhow::anyhow!("`secrets` in {} is not a table", path.display()))?;
+        let keychain = secrets
+            .entry("keychain")
+            .or_insert(toml_edit::value(toml_edit::Array::new()))
+            .as_array_mut()
+            .ok_or_else(|| {
+                anyhow::anyhow!("`secrets.keychain` in {} is not an array", path.display())
+            })?;
+        for key in keys {
+            if !keychain.iter().any(|k| k.as_str() == Some(key)) {
+                keychain.push(key.as_str());
+            }
+        }
+        std::fs::write(&path, doc.to_string())?;
+        Ok(())
+    }
+
     pub fn save(&self, dir: &Path) -> Result<()> {
         let path = dir.join(".orbit.toml");
         let content = toml::to_string_pretty(self)?;
@@ -281,6 +597,38 @@ impl ProjectConfig {
     }
 }
 
+/// Overlay `overrides` onto `base`: tables merge key by key, anything else is replaced
+fn merge_toml(base: &mut toml::Value, overrides: &toml::Value) {
+    match (base, overrides) {
+        (toml::Value::Table(base), toml::Value::Table(overrides)) => {
+            for (key, value) in overrides {
+                match base.get_mut(key) {
+                    Some(existing) => merge_toml(existing, value),
+                    None => {
+                        base.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (base, overrides) => *base = overrides.clone(),
+    }
+}
+
+/// Commented example appended to a fresh `.orbit.toml`
+const CUSTOM_ACTION_EXAMPLE: &str = r#"
+# Custom actions show up in the actions panel and palette:
+#
+# [[actions.custom]]
+# name = "deploy"
+# command = "./scripts/deploy.sh production"
+# category = "deploy"
+# confirm = true         # ask y/n before running
+# confirm_phrase = true  # or type the action name to confirm
+#
+# Actions whose name or command contains a word from `confirm_patterns` in the
+# global config (deploy, rm, down and prune by default) always ask first.
+"#;
+
 /// Initialize a new project configuration
 pub fn init_project_config(dir: &Path, force: bool) -> Result<()> {
     let path = dir.join(".orbit.toml");
@@ -304,6 +652,200 @@ pub fn init_project_config(dir: &Path, force: bool) -> Result<()> {
     };
 
     config.save(dir)?;
+    let mut content = std::fs::read_to_string(&path)?;
+    content.push_str(CUSTOM_ACTION_EXAMPLE);
+    std::fs::write(&path, content)?;
     println!("Created .orbit.toml");
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_dir_is_under_cache_dir() {
+        let dir = log_dir_in(Path::new("/home/me/.cache"));
+        assert_eq!(dir, PathBuf::from("/home/me/.cache/orbit/logs"));
+        assert!(current_log_file()
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with("orbit.log.")));
+    }
+
+    #[test]
+    fn notes_file_sanitizes_project_name() {
+        let path = notes_file_in(Path::new("/home/me/.cache"), "@acme/web app");
+        assert_eq!(
+            path,
+            PathBuf::from("/home/me/.cache/orbit/_acme_web_app/notes.md")
+        );
+    }
+
+    #[test]
+    fn session_file_is_keyed_by_the_full_path() {
+        let cache = Path::new("/home/me/.cache");
+        let api = session_file_in(cache, Path::new("/nonexistent/work/api"));
+        let other = session_file_in(cache, Path::new("/nonexistent/personal/api"));
+        assert_ne!(api, other);
+        assert_eq!(
+            api,
+            session_file_in(cache, Path::new("/nonexistent/work/api"))
+        );
+        assert!(api.starts_with("/home/me/.cache/orbit"));
+        assert!(api
+            .parent()
+            .and_then(|d| d.file_name())
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with("api-")));
+        assert!(api.ends_with("session.toml"));
+    }
+
+    #[test]
+    fn init_documents_the_confirm_flag() {
+        let dir = tempfile::tempdir().unwrap();
+        init_project_config(dir.path(), false).unwrap();
+
+        let content = std::fs::read_to_string(dir.path().join(".orbit.toml")).unwrap();
+        assert!(content.contains("# confirm = true"));
+        assert!(ProjectConfig::load(dir.path(), None).unwrap().is_some());
+        assert!(init_project_config(dir.path(), false).is_err());
+    }
+
+    #[test]
+    fn persist_layout_keeps_comments() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(
+            &path,
+            "[display]\n# my theme\ntheme = \"nord\"\nlayout = \"auto\"\n",
+        )
+        .unwrap();
+
+        Config::persist_layout(&path, "wide").unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains("# my theme"));
+        let config = Config::load(&path).unwrap();
+        assert_eq!(config.display.layout, "wide");
+        assert_eq!(config.display.theme, "nord");
+
+        std::fs::write(&path, "display = \"wide\"\n").unwrap();
+        assert!(Config::persist_layout(&path, "wide").is_err());
+        let inline = dir.path().join("inline.toml");
+        std::fs::write(&inline, "display = { theme = \"nord\" }\n").unwrap();
+        Config::persist_layout(&inline, "compact").unwrap();
+        assert_eq!(Config::load(&inline).unwrap().display.layout, "compact");
+
+        // Unreadable files are left alone; only a missing one starts empty
+        std::fs::write(&path, [b'[', 0xff, 0xfe]).unwrap();
+        assert!(Config::persist_layout(&path, "wide").is_err());
+        assert_eq!(std::fs::read(&path).unwrap(), [b'[', 0xff, 0xfe]);
+        let fresh = dir.path().join("new").join("config.toml");
+        Config::persist_layout(&fresh, "compact").unwrap();
+        assert_eq!(Config::load(&fresh).unwrap().display.layout, "compact");
+    }
+
+    #[test]
+    fn persist_theme_keeps_the_layout() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(
+            &path,
+            "[display]\n# my theme\ntheme = \"nord\"\nlayout = \"wide\"\n",
+        )
+        .unwrap();
+
+        Config::persist_theme(&path, "gruvbox").unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains("# my theme"));
+        let config = Config::load(&path).unwrap();
+        assert_eq!(config.display.theme, "gruvbox");
+        assert_eq!(config.display.layout, "wide");
+    }
+
+    #[test]
+    fn macros_persist_without_clobbering_an_unreadable_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let steps = ["script:build".to_string(), "script:test".to_string()];
+        ProjectConfig::persist_macro(dir.path(), "ci", &steps).unwrap();
+        let config = ProjectConfig::load(dir.path(), None).unwrap().unwrap();
+        assert_eq!(config.macros["ci"], steps);
+
+        let unreadable = tempfile::tempdir().unwrap();
+        std::fs::create_dir(unreadable.path().join(".orbit.toml")).unwrap();
+        assert!(ProjectConfig::persist_macro(unreadable.path(), "ci", &steps).is_err());
+        assert!(unreadable.path().join(".orbit.toml").is_dir());
+    }
+
+    #[test]
+    fn imported_keys_join_the_keychain_list() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(".orbit.toml"),
+            "# team secrets\n[secrets]\nkeychain = [\"API_KEY\"]\n",
+        )
+        .unwrap();
+
+        let keys = ["API_KEY".to_string(), "DB_PASSWORD".to_string()];
+        ProjectConfig::persist_keychain_keys(dir.path(), &keys).unwrap();
+
+        let content = std::fs::read_to_string(dir.path().join(".orbit.toml")).unwrap();
+        assert!(content.contains("# team secrets"));
+        let config = ProjectConfig::load(dir.path(), None).unwrap().unwrap();
+        assert_eq!(config.secrets.keychain, keys);
+
+        std::fs::write(dir.path().join(".orbit.toml"), b"\xff\xfe").unwrap();
+        assert!(ProjectConfig::persist_keychain_keys(dir.path(), &keys).is_err());
+        assert_eq!(
+            std::fs::read(dir.path().join(".orbit.toml")).unwrap(),
+            b"\xff\xfe"
+        );
+    }
+
+    #[test]
+    fn profile_overrides_merge_over_base_sections() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(".orbit.toml"),
+            r#"
+[display]
+layout = "wide"
+theme = "nord"
+
+[actions]
+favorites = ["test"]
+
+[secrets]
+keychain = ["API_KEY"]
+
+[profiles.prod.actions]
+favorites = ["deploy", "logs"]
+
+[profiles.prod.display]
+theme = "dracula"
+"#,
+        )
+        .unwrap();
+
+        let base = ProjectConfig::load(dir.path(), None).unwrap().unwrap();
+        assert_eq!(base.actions.favorites, ["test"]);
+
+        let prod = ProjectConfig::load(dir.path(), Some("prod"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(prod.actions.favorites, ["deploy", "logs"]);
+        assert_eq!(prod.display.theme.as_deref(), Some("dracula"));
+        // Keys and sections the profile doesn't mention keep their base values
+        assert_eq!(prod.display.layout, "wide");
+        assert_eq!(prod.secrets.keychain, ["API_KEY"]);
+
+        assert!(ProjectConfig::load(dir.path(), Some("staging")).is_err());
+        // `--profile` wins over `ORBIT_PROFILE`
+        assert_eq!(
+            resolve_profile(Some("prod".to_string())).as_deref(),
+            Some("prod")
+        );
+    }
+}
@@ -3,24 +3,30 @@
 #![allow(dead_code)]
 
 use anyhow::Result;
-use crossterm::event::{KeyCode, KeyEvent};
-use ratatui::{backend::CrosstermBackend, Terminal};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{backend::CrosstermBackend, layout::Rect, Terminal};
 use std::io::Stdout;
 use std::path::PathBuf;
 use std::sync::Arc;
-use tokio::sync::mpsc;
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
 
-use crate::actions::{ActionExecutor, ActionRegistry};
-use crate::config::Config;
+use crate::actions::progress::{Progress, ProgressExtractor};
+use crate::actions::test_summary::{TestSummary, TestSummaryParser};
+use crate::actions::{ActionExecutor, ActionRegistry, OutputLine};
+use crate::config::{Config, ProjectConfig};
 use crate::core::events::{Event, EventHandler, EventResult, KeyBindings};
+use crate::core::session::Session;
 use crate::core::state::{
-    AppMode, AppState, FocusedPanel, LayoutPreset, NotificationLevel, OutputStream, StateChange,
-    StateStore,
+    step_selection, AppMode, AppState, FocusedPanel, LayoutPreset, NotificationLevel,
+    OutputPanelState, OutputStream, StateChange, StateStore,
 };
-use crate::detection::ProjectDetector;
+use crate::core::terminal::TerminalSession;
+use crate::detection::{ProjectContext, ProjectDetector};
 use crate::focus::FocusModeController;
 use crate::integrations::docker::DockerClient;
 use crate::integrations::system::SystemMonitor;
+use crate::ui::layout::LayoutManager;
 use crate::ui::renderer::Renderer;
 use crate::ui::theme::Theme;
 
@@ -29,12 +35,35 @@ pub struct App {
     state: StateStore,
     event_tx: mpsc::UnboundedSender<Event>,
     docker_client: Option<DockerClient>,
-    system_monitor: SystemMonitor,
+    system_monitor: Arc<parking_lot::Mutex<SystemMonitor>>,
     action_registry: Arc<ActionRegistry>,
     action_executor: ActionExecutor,
     focus_controller: Option<FocusModeController>,
     config: Config,
+    /// Parsed from `config.keybindings`
+    keys: KeyBindings,
     working_dir: PathBuf,
+    input_task: Option<tokio::task::JoinHandle<()>>,
+    /// Follows the pinned container's logs into `panels.pinned_logs`
+    pinned_logs_task: Option<tokio::task::JoinHandle<()>>,
+    /// Macro saved most recently this session, replayed by `@`
+    last_macro: Option<String>,
+    /// Port reclaim waiting on the confirmation dialog
+    pending_reclaim: Option<crate::integrations::ports::ReclaimPlan>,
+    /// CPU limits by container id; they only change when a container is recreated
+    container_cpu_limits: std::collections::HashMap<String, Option<f64>>,
+    http_addr: Option<std::net::SocketAddr>,
+    config_path: Option<PathBuf>,
+    /// Shell behind Terminal mode, kept running while the dashboard is shown
+    terminal_session: Option<TerminalSession>,
+    /// Watches the project's manifests, restarted when the watched set changes
+    file_watcher: Option<(
+        Vec<String>,
+        notify_debouncer_mini::Debouncer<notify_debouncer_mini::notify::RecommendedWatcher>,
+    )>,
+    /// Watches the global config so theme edits apply without a restart
+    config_watcher:
+        Option<notify_debouncer_mini::Debouncer<notify_debouncer_mini::notify::RecommendedWatcher>>,
 }
 
 impl App {
@@ -43,18 +72,48 @@ impl App {
         let backend = CrosstermBackend::new(std::io::stdout());
         let terminal = Terminal::new(backend)?;
 
-        // Load theme
-        let theme = Theme::from_name(&config.display.theme);
+        // Load theme, falling back to the default one if a custom theme is broken
+        let (theme, theme_error) = match Theme::load(&config.display.theme) {
+            Ok(theme) => (theme, None),
+            Err(e) => (Theme::default(), Some(e)),
+        };
+        let theme = theme
+            .with_colorblind(config.display.colorblind)
+            .with_name_truncation(config.display.name_truncation);
 
         // Build initial state
-        let initial_state = AppState::new(working_dir.clone(), theme);
+        let mut initial_state = AppState::new(working_dir.clone(), theme);
+        if let Some(e) = theme_error {
+            initial_state.add_notification(format!("{:#}", e), NotificationLevel::Warning);
+        }
+        if let Some(preset) = LayoutPreset::from_name(&config.display.layout) {
+            initial_state.layout.preset = preset;
+        }
+        initial_state.layout.header_segments = config.display.header_segments.clone();
+        initial_state.keys = KeyBindings::from_config(&config.keybindings);
+        initial_state.layout.docker_panel_visible = config.general.enable_docker;
+        initial_state.layout.ports_panel_visible = config.general.enable_ports;
+        let session = if config.general.restore_session {
+            Session::load(&session_path(&working_dir))
+        } else {
+            None
+        };
+        crate::core::session::apply_startup(
+            &mut initial_state,
+            session.as_ref(),
+            config.display.startup_panel,
+        );
         let state = StateStore::new(initial_state);
 
         // Create a placeholder sender - will be replaced in run()
         let (event_tx, _) = mpsc::unbounded_channel::<Event>();
 
         // Initialize Docker client if available
-        let docker_client = DockerClient::new().ok();
+        let docker_client = if config.general.enable_docker {
+            DockerClient::new().ok()
+        } else {
+            None
+        };
         if docker_client.is_none() {
             tracing::info!("Docker not available");
         }
@@ -63,10 +122,11 @@ impl App {
         let action_registry = Arc::new(ActionRegistry::new());
 
         // Initialize action executor
-        let action_executor = ActionExecutor::new(working_dir.clone());
+        let action_executor =
+            ActionExecutor::new(working_dir.clone()).with_shell(config.general.shell.clone());
 
         // System metrics collector
-        let system_monitor = SystemMonitor::new();
+        let system_monitor = Arc::new(parking_lot::Mutex::new(SystemMonitor::new()));
 
         Ok(Self {
             terminal,
@@ -77,28 +137,71 @@ impl App {
             action_registry,
             action_executor,
             focus_controller: None,
+            keys: KeyBindings::from_config(&config.keybindings),
             config,
             working_dir,
+            input_task: None,
+            pinned_logs_task: None,
+            last_macro: None,
+            pending_reclaim: None,
+            container_cpu_limits: std::collections::HashMap::new(),
+            http_addr: None,
+            config_path: Config::default_path(),
+            terminal_session: None,
+            file_watcher: None,
+            config_watcher: None,
         })
     }
 
+    /// Serve a read-only JSON health endpoint on `addr` while the dashboard runs
+    pub fn with_http_addr(mut self, addr: Option<std::net::SocketAddr>) -> Self {
+        self.http_addr = addr;
+        self
+    }
+
+    /// Config file that runtime preferences (like the layout) are saved back to
+    pub fn with_config_path(mut self, path: Option<PathBuf>) -> Self {
+        if path.is_some() {
+            self.config_path = path;
+        }
+        self
+    }
+
     pub async fn run(&mut self) -> Result<()> {
         // Setup terminal
         self.setup_terminal()?;
 
+        // Events sent while starting up wait in the channel for the loop
+        let (mut event_handler, event_tx) = EventHandler::new();
+        self.event_tx = event_tx.clone();
+
         // Run initial project detection
         self.detect_project().await?;
 
+        if let Some(path) = self.config_path.as_ref().filter(|p| p.exists()) {
+            match crate::core::watcher::spawn_config(path, event_tx.clone()) {
+                Ok(debouncer) => self.config_watcher = Some(debouncer),
+                Err(e) => tracing::warn!("Not watching the config file: {:#}", e),
+            }
+        }
+
         // Prime background data before first render
         self.refresh_metrics();
         self.refresh_ports().await?;
         self.refresh_docker().await?;
 
-        // Spawn background tasks
-        let (mut event_handler, event_tx) = EventHandler::new();
-        self.event_tx = event_tx.clone();
+        if let Some(addr) = self.http_addr {
+            if let Err(e) = crate::core::http::serve(addr, self.state.clone()).await {
+                tracing::warn!("{:#}", e);
+                self.state.update(|s| {
+                    s.add_notification(format!("{:#}", e), NotificationLevel::Warning);
+                    ((), None)
+                });
+            }
+        }
 
-        EventHandler::spawn_sources(event_tx.clone());
+        // Spawn background tasks
+        self.input_task = Some(EventHandler::spawn_sources(event_tx.clone()));
 
         // Initial render
         self.render()?;
@@ -128,6 +231,13 @@ impl App {
             let _ = futures::executor::block_on(controller.exit());
         }
 
+        if self.config.general.restore_session {
+            let session = Session::capture(&self.state.read());
+            if let Err(e) = session.save(&session_path(&self.working_dir)) {
+                tracing::warn!("Failed to save session: {:#}", e);
+            }
+        }
+
         crossterm::terminal::disable_raw_mode()?;
         crossterm::execute!(
             std::io::stdout(),
@@ -138,29 +248,66 @@ impl App {
         Ok(())
     }
 
-    async fn detect_project(&mut self) -> Result<()> {
-        // Show loading state
-        self.state.update(|s| {
-            s.panels
-                .output
-                .push("Detecting project...".to_string(), OutputStream::System);
-            ((), None)
-        });
+    /// (Re)start the manifest watcher if the files worth watching changed
+    fn watch_project_files(&mut self, project: &crate::detection::ProjectContext) {
+        let files = crate::core::watcher::watched_files(project);
+        if self
+            .file_watcher
+            .as_ref()
+            .is_some_and(|(watched, _)| *watched == files)
+        {
+            return;
+        }
+        self.file_watcher = None;
+        match crate::core::watcher::spawn(&self.working_dir, files.clone(), self.event_tx.clone()) {
+            Ok(debouncer) => self.file_watcher = Some((files, debouncer)),
+            Err(e) => tracing::warn!("Not watching project files: {:#}", e),
+        }
+    }
 
+    async fn detect_project(&mut self) -> Result<()> {
         // Run detection
-        let detector = ProjectDetector::new(&self.working_dir);
+        let detector = ProjectDetector::new(&self.working_dir)
+            .with_type_priority(self.config.general.type_priority.clone())
+            .with_git(self.config.general.enable_git)
+            .with_docker(self.config.general.enable_docker);
         match detector.analyze().await {
             Ok(project) => {
                 // Update action registry
-                let registry = ActionRegistry::from_project(&project);
+                let mut registry = ActionRegistry::from_project(&project);
+                let mut config_error = None;
+                match ProjectConfig::load(&self.working_dir, self.config.general.profile.as_deref())
+                {
+                    Ok(Some(project_config)) => {
+                        registry = registry
+                            .with_custom_actions(&project_config.actions.custom)
+                            .with_aliases(&project_config.actions.aliases)
+                            .with_macros(&project_config.macros)
+                            .with_favorites(&project_config.actions.favorites);
+                    }
+                    Ok(None) => {}
+                    Err(e) => config_error = Some(format!(".orbit.toml not applied: {:#}", e)),
+                }
+                registry = registry.with_confirm_patterns(&self.config.general.confirm_patterns);
                 self.action_registry = Arc::new(registry);
+                let preset = self.default_layout(Some(&project));
+                self.watch_project_files(&project);
 
                 self.state.update(|s| {
+                    // A soft refresh keeps the selection and doesn't re-announce itself
+                    let first_detection = s.project.is_none();
+                    if let Some(message) = config_error {
+                        s.add_notification(message, NotificationLevel::Error);
+                    }
+
                     // Update actions
-                    s.panels.actions.actions = self.action_registry.all_actions();
-                    s.panels.actions.update_filter(String::new());
+                    s.panels
+                        .actions
+                        .replace_actions(self.action_registry.all_actions());
 
                     // Update env vars
+                    let secrets =
+                        crate::secrets::SecretMatcher::new(&self.config.general.secret_patterns);
                     s.panels.env.variables = project
                         .env_vars
                         .required
@@ -174,10 +321,18 @@ impl App {
                                 crate::core::state::EnvSource::Missing
                             },
                             required: true,
-                            is_secret: spec.is_secret,
+                            is_secret: spec.is_secret || secrets.is_secret(&spec.name),
                         })
                         .collect();
 
+                    // Recently changed files from git
+                    let recent = project
+                        .git_info
+                        .as_ref()
+                        .map(|g| g.recent_files.clone())
+                        .unwrap_or_default();
+                    s.panels.recent_files.files = recent;
+
                     // Expected ports for Port Scout
                     s.panels.ports.expected_ports = project
                         .ports
@@ -186,25 +341,48 @@ impl App {
                         .map(crate::integrations::ports::ExpectedPort::from)
                         .collect();
 
-                    // Store project context
-                    s.project = Some(project);
+                    if first_detection {
+                        // Pick a layout unless focus mode owns it
+                        if s.mode == AppMode::Dashboard {
+                            s.layout.preset = preset;
+                        }
 
-                    s.panels.output.push(
-                        format!(
-                            "Project detected: {} types found",
-                            s.project.as_ref().map(|p| p.types.len()).unwrap_or(0)
-                        ),
-                        OutputStream::System,
+                        for advisory in &project.advisories {
+                            s.panels.output.push(advisory.clone(), OutputStream::System);
+                        }
+                    }
+
+                    s.add_notification(
+                        format!("Project detected: {} types found", project.types.len()),
+                        NotificationLevel::Info,
                     );
 
+                    // Warn about new problems only, so refreshes don't repeat them
+                    let known: &[String] =
+                        s.project.as_ref().map_or(&[], |p| p.warnings.as_slice());
+                    let fresh: Vec<String> = project
+                        .warnings
+                        .iter()
+                        .filter(|w| !known.contains(w))
+                        .cloned()
+                        .collect();
+                    for warning in fresh {
+                        s.add_notification(warning, NotificationLevel::Warning);
+                    }
+
+                    // Store project context
+                    s.project = Some(project);
+                    s.panels.clamp_selections();
+
                     ((), Some(crate::core::state::StateChange::ProjectReloaded))
                 });
+                self.refresh_open_actions();
             }
             Err(e) => {
                 self.state.update(|s| {
-                    s.panels.output.push(
+                    s.add_notification(
                         format!("Project detection failed: {}", e),
-                        OutputStream::System,
+                        NotificationLevel::Error,
                     );
                     ((), None)
                 });
@@ -214,23 +392,77 @@ impl App {
         Ok(())
     }
 
+    /// Re-derive the "open" shortcuts from the project and what is currently running
+    fn refresh_open_actions(&mut self) {
+        let open = {
+            let state = self.state.read();
+            let Some(project) = state.project.as_ref() else {
+                return;
+            };
+            crate::actions::shortcuts::open_actions(
+                project,
+                &state.panels.ports.active_ports,
+                &state.panels.docker.containers,
+            )
+        };
+
+        if Arc::make_mut(&mut self.action_registry).set_open_actions(open) {
+            let actions = self.action_registry.all_actions();
+            self.state.update(|s| {
+                s.panels.actions.replace_actions(actions);
+                ((), None)
+            });
+        }
+    }
+
+    /// Layout from config, falling back to one suited to the project
+    fn default_layout(&self, project: Option<&ProjectContext>) -> LayoutPreset {
+        LayoutPreset::from_name(&self.config.display.layout)
+            .or_else(|| project.map(LayoutPreset::for_project))
+            .unwrap_or_default()
+    }
+
+    /// Redraw after input and whenever state reports a change, instead of on a fixed tick
     async fn event_loop(&mut self, event_handler: &mut EventHandler) -> Result<()> {
+        let mut changes = self.state.subscribe();
         loop {
-            // Wait for next event
-            let Some(event) = event_handler.next().await else {
-                break;
-            };
+            tokio::select! {
+                event = event_handler.next() => {
+                    let Some(event) = event else {
+                        break;
+                    };
+                    // The housekeeping tick renders itself, and only when something expired
+                    let redraw = !matches!(event, Event::Tick);
+
+                    match self.handle_event(event).await? {
+                        EventResult::Continue => {}
+                        EventResult::Quit => break,
+                    }
 
-            // Handle event
-            match self.handle_event(event).await? {
-                EventResult::Continue => {}
-                EventResult::Quit => break,
+                    if redraw {
+                        drain_changes(&mut changes);
+                        self.render()?;
+                    }
+                }
+                change = changes.recv() => {
+                    if let Err(broadcast::error::RecvError::Closed) = change {
+                        break;
+                    }
+                    drain_changes(&mut changes);
+                    self.render()?;
+                }
             }
         }
         Ok(())
     }
 
     async fn handle_event(&mut self, event: Event) -> Result<EventResult> {
+        if let (Some(controller), Event::Key(_) | Event::Mouse(_)) =
+            (self.focus_controller.as_ref(), &event)
+        {
+            controller.record_activity();
+        }
+
         match event {
             Event::Key(key) => self.handle_key(key).await,
             Event::Mouse(_mouse) => Ok(EventResult::Continue),
@@ -239,16 +471,23 @@ impl App {
                     s.terminal_size = (w, h);
                     ((), None)
                 });
-                self.render()?;
+                if let Some(session) = self.terminal_session.as_mut() {
+                    let (rows, cols) = crate::ui::widgets::terminal_panel::grid_size((w, h));
+                    session.resize(rows, cols);
+                }
                 Ok(EventResult::Continue)
             }
             Event::Tick => {
                 // Remove expired notifications
-                self.state.update(|s| {
-                    s.remove_expired_notifications();
-                    ((), None)
+                let sticky_errors = self.config.notifications.sticky_errors;
+                let expired = self.state.update(|s| {
+                    let before = s.notifications.len();
+                    s.remove_expired_notifications(sticky_errors);
+                    (s.notifications.len() != before, None)
                 });
-                self.render()?;
+                if expired {
+                    self.render()?;
+                }
                 Ok(EventResult::Continue)
             }
             Event::SlowTick => {
@@ -273,15 +512,52 @@ impl App {
                 self.exit_focus_mode().await?;
                 Ok(EventResult::Continue)
             }
+            Event::FocusPauseChanged { paused } => {
+                let minutes = self.config.focus.auto_pause_minutes.unwrap_or(0);
+                let manual = self
+                    .focus_controller
+                    .as_ref()
+                    .is_some_and(|c| c.is_manually_paused());
+                self.state.update(|s| {
+                    if let AppMode::FocusMode { paused: p, .. } = &mut s.mode {
+                        *p = paused;
+                    }
+                    let note = if paused && manual {
+                        format!("Focus paused at {}", chrono::Local::now().format("%H:%M"))
+                    } else if paused {
+                        format!(
+                            "Focus paused at {} after {} min without input",
+                            chrono::Local::now().format("%H:%M"),
+                            minutes
+                        )
+                    } else {
+                        format!("Focus resumed at {}", chrono::Local::now().format("%H:%M"))
+                    };
+                    s.panels.output.push(note, OutputStream::System);
+                    ((), Some(StateChange::ModeChanged(s.mode.clone())))
+                });
+                Ok(EventResult::Continue)
+            }
             Event::ProjectReload => {
                 self.detect_project().await?;
                 Ok(EventResult::Continue)
             }
-            Event::Quit => Ok(EventResult::Quit),
-            Event::ForceRefresh => {
-                self.render()?;
+            Event::FileChanged(files) => {
+                self.state.update(|s| {
+                    s.panels.output.push(
+                        format!("{} changed, re-detecting project", files),
+                        OutputStream::System,
+                    );
+                    ((), None)
+                });
+                self.detect_project().await?;
                 Ok(EventResult::Continue)
             }
+            Event::ConfigChanged => {
+                self.reload_theme_from_config();
+                Ok(EventResult::Continue)
+            }
+            Event::Quit => Ok(EventResult::Quit),
             _ => Ok(EventResult::Continue),
         }
     }
@@ -293,20 +569,52 @@ impl App {
             AppMode::Dashboard => self.handle_dashboard_key(key).await,
             AppMode::CommandPalette => self.handle_palette_key(key).await,
             AppMode::Help => self.handle_help_key(key),
+            AppMode::LogViewer => self.handle_log_viewer_key(key).await,
+            AppMode::Notes => self.handle_notes_key(key).await,
             AppMode::FocusMode { .. } => self.handle_focus_key(key).await,
             AppMode::Confirm { .. } => self.handle_confirm_key(key).await,
+            AppMode::Terminal => self.handle_terminal_key(key),
             _ => Ok(EventResult::Continue),
         }
     }
 
     async fn handle_dashboard_key(&mut self, key: KeyEvent) -> Result<EventResult> {
+        // The output search input takes every key but Ctrl+C until it closes
+        if self.state.read().panels.output.search_input && !KeyBindings::quit_alt().matches(&key) {
+            self.handle_output_search_key(key);
+            return Ok(EventResult::Continue);
+        }
+
         // Check for quit
-        if KeyBindings::quit().matches(&key) || KeyBindings::quit_alt().matches(&key) {
+        if self.keys.quit.matches(&key) || KeyBindings::quit_alt().matches(&key) {
             return Ok(EventResult::Quit);
         }
 
-        // Mode switches
-        if KeyBindings::palette().matches(&key) {
+        // With the Output panel focused, `/` searches it and `n`/`N` step through matches
+        let focus = self.state.read().focus_panel;
+        if focus == FocusedPanel::Output {
+            let searching = self.state.read().panels.output.search_query.is_some();
+            let handled = self.state.update(|s| {
+                let output = &mut s.panels.output;
+                match key.code {
+                    KeyCode::Char('/') => output.start_search(),
+                    KeyCode::Char('n') if searching => output.jump_to_match(true),
+                    KeyCode::Char('N') if searching => output.jump_to_match(false),
+                    KeyCode::Esc if searching => output.clear_search(),
+                    _ => return (false, None),
+                }
+                (true, Some(StateChange::OutputAppended))
+            });
+            if handled {
+                return Ok(EventResult::Continue);
+            }
+        }
+
+        // Mode switches; space marks actions while the Actions panel has focus,
+        // so a palette bound to plain space gives way there
+        let marking = focus == FocusedPanel::Actions && self.keys.palette.is_space();
+        if (self.keys.palette.matches(&key) && !marking) || KeyBindings::palette_alt().matches(&key)
+        {
             self.state.update(|s| {
                 s.mode = AppMode::CommandPalette;
                 s.panels.actions.filter.clear();
@@ -321,7 +629,12 @@ impl App {
             return Ok(EventResult::Continue);
         }
 
-        if KeyBindings::help().matches(&key) {
+        if self.keys.terminal.matches(&key) {
+            self.open_terminal();
+            return Ok(EventResult::Continue);
+        }
+
+        if self.keys.help.matches(&key) {
             self.state.update(|s| {
                 s.mode = AppMode::Help;
                 (
@@ -332,13 +645,61 @@ impl App {
             return Ok(EventResult::Continue);
         }
 
-        if KeyBindings::focus().matches(&key) {
+        if self.keys.focus.matches(&key) {
             self.enter_focus_mode().await?;
             return Ok(EventResult::Continue);
         }
 
+        if KeyBindings::cycle_layout().matches(&key) {
+            self.cycle_layout();
+            return Ok(EventResult::Continue);
+        }
+
+        if KeyBindings::cycle_theme().matches(&key) {
+            self.cycle_theme();
+            return Ok(EventResult::Continue);
+        }
+
+        if KeyBindings::snapshot().matches(&key) {
+            self.save_snapshot();
+            return Ok(EventResult::Continue);
+        }
+
+        if KeyBindings::notes().matches(&key) {
+            self.open_notes().await;
+            return Ok(EventResult::Continue);
+        }
+
+        if KeyBindings::split_output().matches(&key) {
+            self.state.update(|s| {
+                s.layout.output_split = !s.layout.output_split;
+                if !s.layout.output_split && s.focus_panel == FocusedPanel::PinnedLogs {
+                    s.focus_panel = FocusedPanel::Output;
+                }
+                ((), None)
+            });
+            return Ok(EventResult::Continue);
+        }
+
+        if KeyBindings::record_macro().matches(&key) {
+            self.toggle_macro_recording();
+            return Ok(EventResult::Continue);
+        }
+
+        if KeyBindings::replay_macro().matches(&key) {
+            return self.replay_last_macro().await;
+        }
+
+        if KeyBindings::dismiss_notifications().matches(&key) {
+            self.state.update(|s| {
+                s.dismiss_notifications();
+                ((), None)
+            });
+            return Ok(EventResult::Continue);
+        }
+
         // Panel toggles
-        if KeyBindings::docker().matches(&key) {
+        if self.keys.toggle_docker.matches(&key) {
             self.state.update(|s| {
                 s.layout.docker_panel_visible = !s.layout.docker_panel_visible;
                 ((), None)
@@ -346,7 +707,7 @@ impl App {
             return Ok(EventResult::Continue);
         }
 
-        if KeyBindings::ports().matches(&key) {
+        if self.keys.toggle_ports.matches(&key) {
             self.state.update(|s| {
                 s.layout.ports_panel_visible = !s.layout.ports_panel_visible;
                 ((), None)
@@ -354,7 +715,7 @@ impl App {
             return Ok(EventResult::Continue);
         }
 
-        if KeyBindings::env().matches(&key) {
+        if self.keys.toggle_env.matches(&key) {
             self.state.update(|s| {
                 s.layout.env_panel_visible = !s.layout.env_panel_visible;
                 ((), None)
@@ -365,7 +726,9 @@ impl App {
         // Navigation
         if KeyBindings::tab().matches(&key) {
             self.state.update(|s| {
-                s.focus_panel = s.focus_panel.next();
+                let (width, height) = crossterm::terminal::size().unwrap_or(s.terminal_size);
+                let area = Rect::new(0, 0, width, height);
+                s.focus_panel = next_visible_panel(s, area, FocusedPanel::next);
                 (
                     (),
                     Some(crate::core::state::StateChange::PanelFocusChanged(
@@ -378,7 +741,9 @@ impl App {
 
         if KeyBindings::backtab().matches(&key) {
             self.state.update(|s| {
-                s.focus_panel = s.focus_panel.prev();
+                let (width, height) = crossterm::terminal::size().unwrap_or(s.terminal_size);
+                let area = Rect::new(0, 0, width, height);
+                s.focus_panel = next_visible_panel(s, area, FocusedPanel::prev);
                 (
                     (),
                     Some(crate::core::state::StateChange::PanelFocusChanged(
@@ -390,32 +755,177 @@ impl App {
         }
 
         // Up/Down navigation within focused panel
-        if KeyBindings::up().matches(&key) || KeyBindings::vim_up().matches(&key) {
+        if KeyBindings::up().matches(&key) || self.keys.navigate_up.matches(&key) {
             self.navigate_up();
             return Ok(EventResult::Continue);
         }
 
-        if KeyBindings::down().matches(&key) || KeyBindings::vim_down().matches(&key) {
+        if KeyBindings::down().matches(&key) || self.keys.navigate_down.matches(&key) {
             self.navigate_down();
             return Ok(EventResult::Continue);
         }
 
-        // Execute selected action
-        if KeyBindings::enter().matches(&key) {
+        // Panel-specific keys
+        let focus = self.state.read().focus_panel;
+        match (focus, key.code) {
+            (FocusedPanel::Actions, KeyCode::Char(' ')) => {
+                self.state.update(|s| {
+                    s.panels.actions.toggle_marked();
+                    ((), None)
+                });
+                return Ok(EventResult::Continue);
+            }
+            (FocusedPanel::Docker, KeyCode::Char('+') | KeyCode::Char('=')) => {
+                self.scale_selected_service(1);
+                return Ok(EventResult::Continue);
+            }
+            (FocusedPanel::Docker, KeyCode::Char('-')) => {
+                self.scale_selected_service(-1);
+                return Ok(EventResult::Continue);
+            }
+            (FocusedPanel::Docker, KeyCode::Char('R')) => {
+                self.restart_selected_container().await;
+                return Ok(EventResult::Continue);
+            }
+            (FocusedPanel::Docker, KeyCode::Char('l')) => {
+                self.show_selected_container_logs().await;
+                return Ok(EventResult::Continue);
+            }
+            (FocusedPanel::Docker, KeyCode::Char('P')) => {
+                self.pin_selected_container_logs();
+                return Ok(EventResult::Continue);
+            }
+            (FocusedPanel::Docker, KeyCode::Char('u')) => {
+                self.run_compose(true);
+                return Ok(EventResult::Continue);
+            }
+            (FocusedPanel::Docker, KeyCode::Char('D')) => {
+                self.run_compose(false);
+                return Ok(EventResult::Continue);
+            }
+            (FocusedPanel::Ports, KeyCode::Char('x')) => {
+                self.kill_selected_port();
+                return Ok(EventResult::Continue);
+            }
+            (FocusedPanel::Ports, KeyCode::Char('R')) => {
+                self.confirm_reclaim_selected_port();
+                return Ok(EventResult::Continue);
+            }
+            (FocusedPanel::Ports, KeyCode::Char('c')) => {
+                self.state.update(|s| {
+                    let ports = &mut s.panels.ports;
+                    ports.show_connections = !ports.show_connections;
+                    ports.connections.clear();
+                    ((), Some(StateChange::PortsUpdated))
+                });
+                self.refresh_ports().await?;
+                return Ok(EventResult::Continue);
+            }
+            (FocusedPanel::Ports, KeyCode::Enter) => {
+                return self.start_selected_port_script().await;
+            }
+            (FocusedPanel::RecentFiles, KeyCode::Enter) => {
+                self.open_selected_recent_file()?;
+                return Ok(EventResult::Continue);
+            }
+            (FocusedPanel::Env, KeyCode::Char('v')) => {
+                self.state.update(|s| {
+                    s.panels.env.show_values = !s.panels.env.show_values;
+                    ((), None)
+                });
+                return Ok(EventResult::Continue);
+            }
+            (FocusedPanel::Output | FocusedPanel::PinnedLogs, KeyCode::Char('o')) => {
+                self.open_output_in_pager()?;
+                return Ok(EventResult::Continue);
+            }
+            (FocusedPanel::Output | FocusedPanel::PinnedLogs, KeyCode::Char('E')) => {
+                self.state.update(|s| {
+                    let output = if focus == FocusedPanel::Output {
+                        &mut s.panels.output
+                    } else {
+                        &mut s.panels.pinned_logs
+                    };
+                    output.toggle_errors_only();
+                    ((), Some(StateChange::OutputAppended))
+                });
+                return Ok(EventResult::Continue);
+            }
+            (FocusedPanel::Output | FocusedPanel::PinnedLogs, KeyCode::Char('c')) => {
+                self.state.update(|s| {
+                    let output = if focus == FocusedPanel::Output {
+                        &mut s.panels.output
+                    } else {
+                        &mut s.panels.pinned_logs
+                    };
+                    output.lines.clear();
+                    output.scroll_offset = 0;
+                    output.auto_scroll = output.search_query.is_none();
+                    output.refresh_matches();
+                    ((), Some(StateChange::OutputAppended))
+                });
+                return Ok(EventResult::Continue);
+            }
+            _ => {}
+        }
+
+        // Execute the marked actions from the Actions panel, or else the selected one
+        if self.keys.select.matches(&key) {
+            let run_marked = {
+                let state = self.state.read();
+                state.focus_panel == FocusedPanel::Actions
+                    && !state.panels.actions.marked.is_empty()
+            };
+            if run_marked {
+                self.run_marked_actions();
+                return Ok(EventResult::Continue);
+            }
             return self.execute_selected_action().await;
         }
 
         // Refresh
-        if KeyBindings::refresh().matches(&key) {
+        if self.keys.refresh.matches(&key) {
             self.detect_project().await?;
             return Ok(EventResult::Continue);
         }
 
+        // Keys bound to custom actions in .orbit.toml, after every built-in key
+        if let Some(action) = self.action_registry.action_for_key(&key) {
+            return self.execute_action(action).await;
+        }
+
         Ok(EventResult::Continue)
     }
 
+    /// Keys typed into the output search: `Enter` keeps the query for `n`/`N`, `Esc` drops it
+    fn handle_output_search_key(&mut self, key: KeyEvent) {
+        self.state.update(|s| {
+            let output = &mut s.panels.output;
+            let mut query = output.search_query.clone().unwrap_or_default();
+            match key.code {
+                KeyCode::Esc => output.clear_search(),
+                KeyCode::Enter if query.is_empty() => output.clear_search(),
+                KeyCode::Enter => output.search_input = false,
+                KeyCode::Backspace => {
+                    query.pop();
+                    output.set_search_query(query);
+                }
+                KeyCode::Char(c)
+                    if !key
+                        .modifiers
+                        .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+                {
+                    query.push(c);
+                    output.set_search_query(query);
+                }
+                _ => return ((), None),
+            }
+            ((), Some(StateChange::OutputAppended))
+        });
+    }
+
     async fn handle_palette_key(&mut self, key: KeyEvent) -> Result<EventResult> {
-        if KeyBindings::escape().matches(&key) {
+        if self.keys.back.matches(&key) {
             self.state.update(|s| {
                 s.mode = AppMode::Dashboard;
                 s.panels.actions.filter.clear();
@@ -430,27 +940,28 @@ impl App {
             return Ok(EventResult::Continue);
         }
 
-        if KeyBindings::enter().matches(&key) {
+        if self.keys.select.matches(&key) {
             // Execute selected and close palette
             let result = self.execute_selected_action().await?;
             if result == EventResult::Quit {
                 return Ok(EventResult::Quit);
             }
             self.state.update(|s| {
-                s.mode = AppMode::Dashboard;
+                // The action may have opened another mode (confirm, help, logs)
+                if s.mode == AppMode::CommandPalette {
+                    s.mode = AppMode::Dashboard;
+                }
                 s.panels.actions.filter.clear();
                 s.panels.actions.update_filter(String::new());
                 (
                     (),
-                    Some(crate::core::state::StateChange::ModeChanged(
-                        AppMode::Dashboard,
-                    )),
+                    Some(crate::core::state::StateChange::ModeChanged(s.mode.clone())),
                 )
             });
             return Ok(EventResult::Continue);
         }
 
-        if KeyBindings::up().matches(&key) || KeyBindings::vim_up().matches(&key) {
+        if KeyBindings::up().matches(&key) || self.keys.navigate_up.matches(&key) {
             self.state.update(|s| {
                 if s.panels.actions.selected_index > 0 {
                     s.panels.actions.selected_index -= 1;
@@ -460,7 +971,7 @@ impl App {
             return Ok(EventResult::Continue);
         }
 
-        if KeyBindings::down().matches(&key) || KeyBindings::vim_down().matches(&key) {
+        if KeyBindings::down().matches(&key) || self.keys.navigate_down.matches(&key) {
             self.state.update(|s| {
                 let max = s.panels.actions.filtered_indices.len().saturating_sub(1);
                 if s.panels.actions.selected_index < max {
@@ -501,10 +1012,60 @@ impl App {
         Ok(EventResult::Continue)
     }
 
+    /// Open Terminal mode, starting a shell unless the last one is still running
+    fn open_terminal(&mut self) {
+        if !self
+            .terminal_session
+            .as_mut()
+            .is_some_and(|session| session.is_running())
+        {
+            let size = crossterm::terminal::size().unwrap_or(self.state.read().terminal_size);
+            let (rows, cols) = crate::ui::widgets::terminal_panel::grid_size(size);
+            let shell = self.action_executor.default_shell();
+            match TerminalSession::spawn(&shell, &self.working_dir, rows, cols, self.state.clone())
+            {
+                Ok(session) => self.terminal_session = Some(session),
+                Err(e) => {
+                    self.state.update(|s| {
+                        s.add_notification(
+                            format!("Couldn't start {}: {:#}", shell, e),
+                            NotificationLevel::Error,
+                        );
+                        ((), None)
+                    });
+                    return;
+                }
+            }
+        }
+
+        self.state.update(|s| {
+            s.enter_terminal();
+            ((), Some(StateChange::ModeChanged(AppMode::Terminal)))
+        });
+    }
+
+    /// Every key but `Ctrl+]` goes to the shell
+    fn handle_terminal_key(&mut self, key: KeyEvent) -> Result<EventResult> {
+        if KeyBindings::terminal_escape().matches(&key) {
+            self.state.update(|s| {
+                s.leave_terminal();
+                ((), Some(StateChange::ModeChanged(AppMode::Dashboard)))
+            });
+            return Ok(EventResult::Continue);
+        }
+
+        if let Some(session) = self.terminal_session.as_mut() {
+            if let Err(e) = session.send_key(&key) {
+                tracing::warn!("Failed to write to the terminal: {}", e);
+            }
+        }
+        Ok(EventResult::Continue)
+    }
+
     fn handle_help_key(&mut self, key: KeyEvent) -> Result<EventResult> {
-        if KeyBindings::escape().matches(&key)
-            || KeyBindings::help().matches(&key)
-            || KeyBindings::quit().matches(&key)
+        if self.keys.back.matches(&key)
+            || self.keys.help.matches(&key)
+            || self.keys.quit.matches(&key)
         {
             self.state.update(|s| {
                 s.mode = AppMode::Dashboard;
@@ -519,41 +1080,120 @@ impl App {
         Ok(EventResult::Continue)
     }
 
-    async fn handle_focus_key(&mut self, key: KeyEvent) -> Result<EventResult> {
-        if KeyBindings::escape().matches(&key) || KeyBindings::quit().matches(&key) {
-            self.exit_focus_mode().await?;
-        }
-        Ok(EventResult::Continue)
-    }
-
-    async fn handle_confirm_key(&mut self, key: KeyEvent) -> Result<EventResult> {
-        let action_id = {
-            let state = self.state.read();
-            if let AppMode::Confirm { action_id, .. } = &state.mode {
-                action_id.clone()
-            } else {
-                return Ok(EventResult::Continue);
-            }
-        };
-
-        match key.code {
-            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
-                // Execute the confirmed action
-                if let Some(action) = self.action_registry.get(&action_id) {
-                    let (tx, _rx) = tokio::sync::mpsc::channel(100);
-                    let _ = self.action_executor.execute(&action, tx).await;
-                }
-                self.state.update(|s| {
-                    s.mode = AppMode::Dashboard;
-                    (
-                        (),
-                        Some(crate::core::state::StateChange::ModeChanged(
-                            AppMode::Dashboard,
-                        )),
-                    )
+    async fn handle_log_viewer_key(&mut self, key: KeyEvent) -> Result<EventResult> {
+        if self.keys.back.matches(&key) || self.keys.quit.matches(&key) {
+            self.state.update(|s| {
+                s.mode = AppMode::Dashboard;
+                s.panels.logs = Default::default();
+                (
+                    (),
+                    Some(crate::core::state::StateChange::ModeChanged(
+                        AppMode::Dashboard,
+                    )),
+                )
+            });
+        } else if KeyBindings::up().matches(&key) || self.keys.navigate_up.matches(&key) {
+            self.state.update(|s| {
+                let logs = &mut s.panels.logs;
+                if logs.scroll_offset > 0 {
+                    logs.scroll_offset -= 1;
+                    logs.auto_scroll = false;
+                }
+                ((), None)
+            });
+        } else if KeyBindings::down().matches(&key) || self.keys.navigate_down.matches(&key) {
+            self.state.update(|s| {
+                let logs = &mut s.panels.logs;
+                if logs.scroll_offset < logs.lines.len().saturating_sub(1) {
+                    logs.scroll_offset += 1;
+                }
+                ((), None)
+            });
+        } else if self.keys.refresh.matches(&key) {
+            self.open_log_viewer().await;
+        }
+        Ok(EventResult::Continue)
+    }
+
+    /// Scratchpad file for the open project, falling back to the directory name
+    fn notes_path(&self) -> std::path::PathBuf {
+        let state = self.state.read();
+        let name = state
+            .project
+            .as_ref()
+            .map(|p| p.name.clone())
+            .or_else(|| {
+                self.working_dir
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+            })
+            .unwrap_or_else(|| "project".to_string());
+        crate::config::notes_file(&name)
+    }
+
+    async fn open_notes(&mut self) {
+        let path = self.notes_path();
+        let text = match tokio::fs::read_to_string(&path).await {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => {
+                self.state.update(|s| {
+                    s.add_notification(
+                        format!("Could not read {}: {}", path.display(), e),
+                        NotificationLevel::Error,
+                    );
+                    ((), None)
                 });
+                return;
+            }
+        };
+
+        self.state.update(|s| {
+            s.panels.notes = crate::core::state::NotesState::load(path, &text);
+            s.mode = AppMode::Notes;
+            (
+                (),
+                Some(crate::core::state::StateChange::ModeChanged(AppMode::Notes)),
+            )
+        });
+    }
+
+    /// Write the notes buffer to disk if it changed
+    async fn save_notes(&mut self) -> bool {
+        let (path, text) = {
+            let state = self.state.read();
+            let notes = &state.panels.notes;
+            match &notes.path {
+                Some(path) if notes.dirty => (path.clone(), notes.text()),
+                _ => return true,
+            }
+        };
+
+        let result = async {
+            if let Some(dir) = path.parent() {
+                tokio::fs::create_dir_all(dir).await?;
             }
-            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+            tokio::fs::write(&path, text).await
+        }
+        .await;
+
+        self.state.update(|s| {
+            match &result {
+                Ok(()) => s.panels.notes.dirty = false,
+                Err(e) => s.add_notification(
+                    format!("Failed to save notes: {}", e),
+                    NotificationLevel::Error,
+                ),
+            }
+            ((), None)
+        });
+        result.is_ok()
+    }
+
+    async fn handle_notes_key(&mut self, key: KeyEvent) -> Result<EventResult> {
+        if self.keys.back.matches(&key) {
+            // Keep the overlay open if saving failed so nothing is lost
+            if self.save_notes().await {
                 self.state.update(|s| {
                     s.mode = AppMode::Dashboard;
                     (
@@ -564,82 +1204,943 @@ impl App {
                     )
                 });
             }
-            _ => {}
+            return Ok(EventResult::Continue);
+        }
+
+        if KeyBindings::save().matches(&key) {
+            if self.save_notes().await {
+                self.state.update(|s| {
+                    s.add_notification("Notes saved".to_string(), NotificationLevel::Success);
+                    ((), None)
+                });
+            }
+            return Ok(EventResult::Continue);
+        }
+
+        if KeyBindings::external_editor().matches(&key) {
+            if self.save_notes().await {
+                let path = self.notes_path();
+                if let Some(dir) = path.parent() {
+                    let _ = std::fs::create_dir_all(dir);
+                }
+                self.run_editor(&path)?;
+                self.open_notes().await;
+            }
+            return Ok(EventResult::Continue);
+        }
+
+        self.state.update(|s| {
+            let notes = &mut s.panels.notes;
+            match key.code {
+                KeyCode::Char(c)
+                    if !key
+                        .modifiers
+                        .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+                {
+                    notes.insert_char(c)
+                }
+                KeyCode::Tab => {
+                    for _ in 0..4 {
+                        notes.insert_char(' ');
+                    }
+                }
+                KeyCode::Enter => notes.newline(),
+                KeyCode::Backspace => notes.backspace(),
+                KeyCode::Left => notes.move_left(),
+                KeyCode::Right => notes.move_right(),
+                KeyCode::Up => notes.move_up(),
+                KeyCode::Down => notes.move_down(),
+                KeyCode::Home => notes.cursor_col = 0,
+                KeyCode::End => notes.cursor_col = notes.lines[notes.cursor_row].chars().count(),
+                _ => {}
+            }
+            ((), None)
+        });
+        Ok(EventResult::Continue)
+    }
+
+    /// Load the tail of today's log into the viewer overlay
+    async fn open_log_viewer(&mut self) {
+        const TAIL_LINES: usize = 500;
+
+        let path = crate::config::current_log_file();
+        let content = tokio::fs::read_to_string(&path).await;
+
+        self.state.update(|s| {
+            let mut logs = crate::core::state::OutputPanelState::new();
+            match content {
+                Ok(content) => {
+                    let lines: Vec<&str> = content.lines().collect();
+                    for line in &lines[lines.len().saturating_sub(TAIL_LINES)..] {
+                        let stream = if line.contains(" ERROR ") || line.contains(" WARN ") {
+                            OutputStream::Stderr
+                        } else {
+                            OutputStream::Stdout
+                        };
+                        logs.push(line.to_string(), stream);
+                    }
+                }
+                Err(e) => {
+                    logs.push(
+                        format!("Could not read {}: {}", path.display(), e),
+                        OutputStream::System,
+                    );
+                }
+            }
+            s.panels.logs = logs;
+            s.mode = AppMode::LogViewer;
+            (
+                (),
+                Some(crate::core::state::StateChange::ModeChanged(
+                    AppMode::LogViewer,
+                )),
+            )
+        });
+    }
+
+    fn reveal_log_dir(&self) {
+        let dir = crate::config::log_dir();
+        let opener = if cfg!(target_os = "macos") {
+            "open"
+        } else {
+            "xdg-open"
+        };
+
+        let result = std::process::Command::new(opener)
+            .arg(&dir)
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn();
+
+        self.state.update(|s| {
+            match result {
+                Ok(_) => {
+                    s.add_notification(format!("Opened {}", dir.display()), NotificationLevel::Info)
+                }
+                Err(e) => s.add_notification(
+                    format!("Failed to open {}: {}", dir.display(), e),
+                    NotificationLevel::Error,
+                ),
+            }
+            ((), None)
+        });
+    }
+
+    fn copy_project_summary(&self) {
+        let summary = {
+            let state = self.state.read();
+            state
+                .project
+                .as_ref()
+                .map(crate::detection::markdown::project_summary)
+        };
+
+        self.state.update(|s| {
+            match summary.map(|md| copy_to_clipboard(&md)) {
+                Some(Ok(())) => s.add_notification(
+                    "Copied project summary to clipboard".to_string(),
+                    NotificationLevel::Success,
+                ),
+                Some(Err(e)) => s.add_notification(
+                    format!("Failed to copy summary: {:#}", e),
+                    NotificationLevel::Error,
+                ),
+                None => s.add_notification(
+                    "No project detected yet".to_string(),
+                    NotificationLevel::Warning,
+                ),
+            }
+            ((), None)
+        });
+    }
+
+    /// Rebuild the theme from `config.display` and swap it in
+    fn apply_theme(&mut self) {
+        let display = &self.config.display;
+        let theme = Theme::load(&display.theme).map(|theme| {
+            theme
+                .with_colorblind(display.colorblind)
+                .with_name_truncation(display.name_truncation)
+        });
+        self.state.update(|s| match theme {
+            Ok(theme) => {
+                s.theme = theme;
+                ((), Some(StateChange::ThemeChanged))
+            }
+            Err(e) => ((), s.notify(format!("{:#}", e), NotificationLevel::Warning)),
+        });
+    }
+
+    /// Switch to the next built-in theme and save it to the config file
+    fn cycle_theme(&mut self) {
+        let name = Theme::next_name(&self.config.display.theme);
+        self.config.display.theme = name.to_string();
+        self.apply_theme();
+        let saved = match &self.config_path {
+            Some(path) => Config::persist_theme(path, name),
+            None => Ok(()),
+        };
+
+        self.state.update(|s| {
+            let change = match saved {
+                Ok(()) => s.notify(format!("Theme: {}", name), NotificationLevel::Info),
+                Err(e) => s.notify(
+                    format!("Theme: {} (not saved: {:#})", name, e),
+                    NotificationLevel::Warning,
+                ),
+            };
+            ((), change)
+        });
+    }
+
+    /// Pick up theme edits made to the config file while running
+    fn reload_theme_from_config(&mut self) {
+        let Some(path) = self.config_path.as_ref().filter(|p| p.exists()) else {
+            return;
+        };
+        match Config::load(path) {
+            Ok(config) => {
+                let display = &self.config.display;
+                if config.display.theme == display.theme
+                    && config.display.colorblind == display.colorblind
+                {
+                    return;
+                }
+                self.config.display.theme = config.display.theme;
+                self.config.display.colorblind = config.display.colorblind;
+                self.apply_theme();
+            }
+            Err(e) => self.state.update(|s| {
+                let change = s.notify(
+                    format!("Config not reloaded: {:#}", e),
+                    NotificationLevel::Warning,
+                );
+                ((), change)
+            }),
+        }
+    }
+
+    /// Switch to the next layout preset and remember it in the config file
+    fn cycle_layout(&mut self) {
+        let preset = self.state.read().layout.preset.cycle();
+        self.config.display.layout = preset.name().to_string();
+        let saved = match &self.config_path {
+            Some(path) => Config::persist_layout(path, preset.name()),
+            None => Ok(()),
+        };
+
+        self.state.update(|s| {
+            s.layout.preset = preset;
+            match saved {
+                Ok(()) => s.add_notification(
+                    format!("Layout: {}", preset.name()),
+                    NotificationLevel::Info,
+                ),
+                Err(e) => s.add_notification(
+                    format!("Layout: {} (not saved: {:#})", preset.name(), e),
+                    NotificationLevel::Warning,
+                ),
+            }
+            ((), None)
+        });
+    }
+
+    /// Write the dashboard as plain text, plus an ANSI-colored copy next to it
+    fn save_snapshot(&self) {
+        let (width, height) = crossterm::terminal::size().unwrap_or((120, 40));
+        let state = self.state.snapshot();
+        let path = crate::config::snapshot_dir().join(format!(
+            "dashboard-{}.txt",
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        ));
+
+        let result = (|| -> Result<()> {
+            let buf = crate::ui::snapshot::render_buffer(&state, width, height)?;
+            std::fs::create_dir_all(crate::config::snapshot_dir())?;
+            std::fs::write(&path, crate::ui::snapshot::to_plain_text(&buf))?;
+            std::fs::write(
+                path.with_extension("ans"),
+                crate::ui::snapshot::to_ansi(&buf),
+            )?;
+            Ok(())
+        })();
+
+        self.state.update(|s| {
+            match result {
+                Ok(()) => s.add_notification(
+                    format!("Saved snapshot to {}", path.display()),
+                    NotificationLevel::Success,
+                ),
+                Err(e) => s.add_notification(
+                    format!("Failed to save snapshot: {:#}", e),
+                    NotificationLevel::Error,
+                ),
+            }
+            ((), None)
+        });
+    }
+
+    async fn handle_focus_key(&mut self, key: KeyEvent) -> Result<EventResult> {
+        if self.keys.back.matches(&key) || self.keys.quit.matches(&key) {
+            self.exit_focus_mode().await?;
+            return Ok(EventResult::Continue);
+        }
+
+        if matches!(key.code, KeyCode::Char(' ') | KeyCode::Char('p')) {
+            let idle_paused = matches!(
+                self.state.read().mode,
+                AppMode::FocusMode { paused: true, .. }
+            );
+            if let Some(controller) = self.focus_controller.as_mut() {
+                // While paused for inactivity the key press alone resumes the timer
+                if controller.is_manually_paused() || !idle_paused {
+                    controller.toggle_pause();
+                }
+            }
+            return Ok(EventResult::Continue);
+        }
+
+        let delta = match key.code {
+            KeyCode::Char('+') | KeyCode::Char('=') => 5,
+            KeyCode::Char('-') => -5,
+            _ => return Ok(EventResult::Continue),
+        };
+        let ambient_playing = matches!(
+            self.state.read().mode,
+            AppMode::FocusMode {
+                ambient_playing: true,
+                ..
+            }
+        );
+        if !ambient_playing {
+            self.state.update(|s| {
+                s.add_notification(
+                    "No ambient sound playing; set focus.ambient_sound to use +/-".to_string(),
+                    NotificationLevel::Info,
+                );
+                ((), None)
+            });
+            return Ok(EventResult::Continue);
+        }
+        if let Some(controller) = self.focus_controller.as_mut() {
+            let volume = controller.adjust_volume(delta).await;
+            self.state.update(|s| {
+                s.add_notification(format!("Volume {}%", volume), NotificationLevel::Info);
+                ((), None)
+            });
+        }
+        Ok(EventResult::Continue)
+    }
+
+    async fn handle_confirm_key(&mut self, key: KeyEvent) -> Result<EventResult> {
+        let (action_id, typed) = {
+            let state = self.state.read();
+            if let AppMode::Confirm {
+                action_id, phrase, ..
+            } = &state.mode
+            {
+                (action_id.clone(), phrase.is_some())
+            } else {
+                return Ok(EventResult::Continue);
+            }
+        };
+
+        let (confirm, cancel) = if typed {
+            // Every character goes into the phrase, so only Enter/Esc act
+            match key.code {
+                KeyCode::Char(c) => {
+                    self.update_confirm_input(|input| input.push(c));
+                    (false, false)
+                }
+                KeyCode::Backspace => {
+                    self.update_confirm_input(|input| {
+                        input.pop();
+                    });
+                    (false, false)
+                }
+                KeyCode::Enter => (self.state.read().mode.confirm_enabled(), false),
+                KeyCode::Esc => (false, true),
+                _ => (false, false),
+            }
+        } else {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => (true, false),
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => (false, true),
+                _ => (false, false),
+            }
+        };
+
+        if confirm || cancel {
+            self.state.update(|s| {
+                s.mode = AppMode::Dashboard;
+                (
+                    (),
+                    Some(crate::core::state::StateChange::ModeChanged(
+                        AppMode::Dashboard,
+                    )),
+                )
+            });
+        }
+
+        if action_id == RECLAIM_CONFIRM_ID {
+            let plan = self.pending_reclaim.take();
+            if let (true, Some(plan)) = (confirm, plan) {
+                self.reclaim_port(plan);
+            }
+            return Ok(EventResult::Continue);
+        }
+
+        // Execute the confirmed action
+        if confirm {
+            if let Some(action) = self.action_registry.get(&action_id) {
+                self.launch(action);
+            }
         }
         Ok(EventResult::Continue)
     }
 
+    fn update_confirm_input(&self, edit: impl FnOnce(&mut String)) {
+        self.state.update(|s| {
+            if let AppMode::Confirm { input, .. } = &mut s.mode {
+                edit(input);
+            }
+            ((), None)
+        });
+    }
+
     fn navigate_up(&mut self) {
+        self.navigate(false);
+    }
+
+    fn navigate_down(&mut self) {
+        self.navigate(true);
+    }
+
+    fn navigate(&mut self, down: bool) {
         let focus = self.state.read().focus_panel;
+        let wrap = self.config.display.wrap_navigation;
         self.state.update(|s| {
             match focus {
                 FocusedPanel::Actions => {
-                    if s.panels.actions.selected_index > 0 {
-                        s.panels.actions.selected_index -= 1;
-                    }
+                    let actions = &mut s.panels.actions;
+                    actions.selected_index = step_selection(
+                        actions.selected_index,
+                        actions.filtered_indices.len(),
+                        down,
+                        wrap,
+                    );
                 }
                 FocusedPanel::Docker => {
-                    if s.panels.docker.selected_index > 0 {
-                        s.panels.docker.selected_index -= 1;
-                    }
+                    let docker = &mut s.panels.docker;
+                    docker.selected_index =
+                        step_selection(docker.selected_index, docker.containers.len(), down, wrap);
                 }
                 FocusedPanel::Ports => {
-                    if s.panels.ports.selected_index > 0 {
-                        s.panels.ports.selected_index -= 1;
-                    }
+                    let len = crate::ui::widgets::ports_panel::display_items(
+                        &s.panels.ports,
+                        &s.panels.docker.containers,
+                    )
+                    .len();
+                    s.panels.ports.selected_index =
+                        step_selection(s.panels.ports.selected_index, len, down, wrap);
+                }
+                FocusedPanel::RecentFiles => {
+                    let recent = &mut s.panels.recent_files;
+                    recent.selected_index =
+                        step_selection(recent.selected_index, recent.files.len(), down, wrap);
                 }
                 FocusedPanel::Env => {
-                    if s.panels.env.selected_index > 0 {
-                        s.panels.env.selected_index -= 1;
+                    let env = &mut s.panels.env;
+                    env.selected_index =
+                        step_selection(env.selected_index, env.variables.len(), down, wrap);
+                }
+                // Scrolling output stops at the ends regardless of `wrap`
+                FocusedPanel::Output | FocusedPanel::PinnedLogs => {
+                    let output = if focus == FocusedPanel::Output {
+                        &mut s.panels.output
+                    } else {
+                        &mut s.panels.pinned_logs
+                    };
+                    let max = output.visible_lines().len().saturating_sub(1);
+                    if down && output.scroll_offset < max {
+                        output.scroll_offset += 1;
+                    } else if !down && output.scroll_offset > 0 {
+                        output.scroll_offset -= 1;
+                        output.auto_scroll = false;
                     }
                 }
-                FocusedPanel::Output => {
-                    if s.panels.output.scroll_offset > 0 {
-                        s.panels.output.scroll_offset -= 1;
-                        s.panels.output.auto_scroll = false;
+            }
+            ((), None)
+        });
+    }
+
+    fn open_selected_recent_file(&mut self) -> Result<()> {
+        let path = {
+            let state = self.state.read();
+            let recent = &state.panels.recent_files;
+            recent
+                .files
+                .get(recent.selected_index)
+                .map(|f| f.path.clone())
+        };
+
+        if let Some(path) = path {
+            let path = self.working_dir.join(path);
+            self.run_editor(&path)?;
+        }
+        Ok(())
+    }
+
+    /// Hand the terminal to `$VISUAL`/`$EDITOR` and take it back when it exits
+    fn run_editor(&mut self, path: &std::path::Path) -> Result<()> {
+        let editor = std::env::var("VISUAL")
+            .or_else(|_| std::env::var("EDITOR"))
+            .unwrap_or_else(|_| "vi".to_string());
+        self.run_suspended(&editor, path)
+    }
+
+    /// Page through the focused output buffer in `$PAGER`
+    fn open_output_in_pager(&mut self) -> Result<()> {
+        let path = {
+            let state = self.state.read();
+            let output = if state.focus_panel == FocusedPanel::PinnedLogs {
+                &state.panels.pinned_logs
+            } else {
+                &state.panels.output
+            };
+            if output.lines.is_empty() {
+                return Ok(());
+            }
+            write_output_file(output, &std::env::temp_dir())?
+        };
+
+        let pager = std::env::var("PAGER").unwrap_or_else(|_| "less".to_string());
+        let result = self.run_suspended(&pager, &path);
+        let _ = std::fs::remove_file(&path);
+        result
+    }
+
+    /// Hand the terminal to `command_line path` and take it back when it exits
+    fn run_suspended(&mut self, command_line: &str, path: &std::path::Path) -> Result<()> {
+        let mut parts = command_line.split_whitespace();
+        let Some(program) = parts.next() else {
+            return Ok(());
+        };
+
+        // Stop reading terminal input so the program gets every keystroke
+        if let Some(task) = self.input_task.take() {
+            task.abort();
+        }
+
+        crossterm::terminal::disable_raw_mode()?;
+        crossterm::execute!(
+            std::io::stdout(),
+            crossterm::terminal::LeaveAlternateScreen,
+            crossterm::event::DisableMouseCapture,
+            crossterm::cursor::Show,
+        )?;
+
+        let status = std::process::Command::new(program)
+            .args(parts)
+            .arg(path)
+            .current_dir(&self.working_dir)
+            .status();
+
+        self.setup_terminal()?;
+        self.terminal.clear()?;
+        self.input_task = Some(EventHandler::spawn_terminal_events(self.event_tx.clone()));
+
+        if let Err(e) = status {
+            self.state.update(|s| {
+                s.add_notification(
+                    format!("Failed to launch {}: {}", program, e),
+                    NotificationLevel::Error,
+                );
+                ((), None)
+            });
+        }
+        self.render()
+    }
+
+    fn selected_container(&self) -> Option<(String, String)> {
+        let state = self.state.read();
+        let docker = &state.panels.docker;
+        docker
+            .containers
+            .get(docker.selected_index)
+            .map(|c| (c.id.clone(), c.name.clone()))
+    }
+
+    async fn restart_selected_container(&mut self) {
+        let (Some(client), Some((id, name))) =
+            (self.docker_client.as_ref(), self.selected_container())
+        else {
+            return;
+        };
+
+        let result = client.restart_container(&id).await;
+        self.report_container_result(result, format!("Restarted {}", name));
+        let _ = self.refresh_docker().await;
+    }
+
+    async fn show_selected_container_logs(&mut self) {
+        let (Some(client), Some((id, name))) =
+            (self.docker_client.as_ref(), self.selected_container())
+        else {
+            return;
+        };
+
+        match client.get_logs(&id, 50).await {
+            Ok(logs) => {
+                self.state.update(|s| {
+                    s.panels
+                        .output
+                        .push(format!("Logs for {}:", name), OutputStream::System);
+                    for line in logs {
+                        s.panels
+                            .output
+                            .push(line.trim_end().to_string(), OutputStream::Stdout);
                     }
+                    ((), Some(StateChange::OutputAppended))
+                });
+            }
+            Err(e) => self.report_container_result(Err(e), String::new()),
+        }
+    }
+
+    /// Follow the selected container's logs in the split view; pinning it again unpins
+    fn pin_selected_container_logs(&mut self) {
+        let (Some(client), Some((id, name))) =
+            (self.docker_client.as_ref(), self.selected_container())
+        else {
+            return;
+        };
+
+        if let Some(task) = self.pinned_logs_task.take() {
+            task.abort();
+        }
+
+        let unpin = self.state.read().panels.pinned_container.as_deref() == Some(name.as_str());
+        if unpin {
+            self.state.update(|s| {
+                s.panels.pinned_container = None;
+                s.panels.pinned_logs = Default::default();
+                s.layout.output_split = false;
+                if s.focus_panel == FocusedPanel::PinnedLogs {
+                    s.focus_panel = FocusedPanel::Docker;
+                }
+                ((), None)
+            });
+            return;
+        }
+
+        self.state.update(|s| {
+            s.panels.pinned_container = Some(name);
+            s.panels.pinned_logs = Default::default();
+            s.layout.output_split = true;
+            ((), None)
+        });
+        self.pinned_logs_task = Some(tokio::spawn(collect_pinned_logs(
+            client.follow_logs(&id, PINNED_LOG_TAIL),
+            self.state.clone(),
+        )));
+    }
+
+    fn report_container_result(&self, result: Result<()>, success_msg: String) {
+        self.state.update(|s| {
+            match result {
+                Ok(()) => s.add_notification(success_msg, NotificationLevel::Success),
+                Err(e) => {
+                    s.add_notification(format!("Docker error: {}", e), NotificationLevel::Error)
                 }
             }
             ((), None)
         });
     }
 
-    fn navigate_down(&mut self) {
-        let focus = self.state.read().focus_panel;
-        self.state.update(|s| {
-            match focus {
-                FocusedPanel::Actions => {
-                    let max = s.panels.actions.filtered_indices.len().saturating_sub(1);
-                    if s.panels.actions.selected_index < max {
-                        s.panels.actions.selected_index += 1;
-                    }
-                }
-                FocusedPanel::Docker => {
-                    let max = s.panels.docker.containers.len().saturating_sub(1);
-                    if s.panels.docker.selected_index < max {
-                        s.panels.docker.selected_index += 1;
-                    }
-                }
-                FocusedPanel::Ports => {
-                    let max = s.panels.ports.active_ports.len().saturating_sub(1);
-                    if s.panels.ports.selected_index < max {
-                        s.panels.ports.selected_index += 1;
-                    }
-                }
-                FocusedPanel::Env => {
-                    let max = s.panels.env.variables.len().saturating_sub(1);
-                    if s.panels.env.selected_index < max {
-                        s.panels.env.selected_index += 1;
-                    }
-                }
-                FocusedPanel::Output => {
-                    let max = s.panels.output.lines.len().saturating_sub(1);
-                    if s.panels.output.scroll_offset < max {
-                        s.panels.output.scroll_offset += 1;
-                    }
-                }
+    fn kill_selected_port(&mut self) {
+        let target = {
+            let state = self.state.read();
+            let ports = &state.panels.ports;
+            crate::ui::widgets::ports_panel::display_items(ports, &state.panels.docker.containers)
+                .into_iter()
+                .nth(ports.selected_index)
+                .and_then(|item| item.pid.map(|pid| (item.port, pid, item.process)))
+        };
+
+        let Some((port, pid, process)) = target else {
+            return;
+        };
+
+        // SIGTERM gives dev servers a chance to clean up, so wait for it off the UI
+        let state = self.state.clone();
+        tokio::spawn(async move {
+            let result = crate::integrations::ports::terminate_pid(pid, STOP_GRACE_PERIOD).await;
+            let process = process.as_deref().unwrap_or("process");
+            state.update(|s| {
+                match result {
+                    Ok(signal) => {
+                        let how = match signal {
+                            crate::integrations::ports::StopSignal::Term => "",
+                            crate::integrations::ports::StopSignal::Kill => {
+                                " (SIGKILL, it ignored SIGTERM)"
+                            }
+                        };
+                        s.add_notification(
+                            format!("Stopped {} (PID {}) on port {}{}", process, pid, port, how),
+                            NotificationLevel::Success,
+                        )
+                    }
+                    Err(e) => s.add_notification(
+                        format!("Failed to stop {} on port {}: {}", process, port, e),
+                        NotificationLevel::Error,
+                    ),
+                }
+                ((), Some(StateChange::PortsUpdated))
+            });
+        });
+    }
+
+    /// `docker compose up -d` (or `down`) in the background, streaming into the output panel
+    fn run_compose(&mut self, up: bool) {
+        let verb = if up { "up -d" } else { "down" };
+        self.state.update(|s| {
+            s.panels.output.push(
+                format!("Executing: docker compose {}", verb),
+                OutputStream::System,
+            );
+            ((), Some(StateChange::OutputAppended))
+        });
+
+        let (tx, rx) = mpsc::channel(100);
+        let state = self.state.clone();
+        let collector = tokio::spawn(collect_output(
+            rx,
+            state.clone(),
+            OUTPUT_FLUSH_INTERVAL,
+            None,
+            None,
+        ));
+        let dir = self.working_dir.clone();
+        tokio::spawn(async move {
+            let result = if up {
+                crate::integrations::docker::compose_up(&dir, Some(tx)).await
+            } else {
+                crate::integrations::docker::compose_down(&dir, Some(tx)).await
+            };
+            let _ = collector.await;
+            state.update(|s| {
+                match result {
+                    Ok(()) => s.add_notification(
+                        format!("docker compose {} finished", verb),
+                        NotificationLevel::Success,
+                    ),
+                    Err(e) => {
+                        s.panels
+                            .output
+                            .push(format!("{:#}", e), OutputStream::System);
+                        s.add_notification(
+                            format!("docker compose {} failed", verb),
+                            NotificationLevel::Error,
+                        );
+                    }
+                }
+                ((), Some(StateChange::OutputAppended))
+            });
+        });
+    }
+
+    fn scale_selected_service(&mut self, delta: i32) {
+        let target = {
+            let state = self.state.read();
+            let docker = &state.panels.docker;
+            docker
+                .containers
+                .get(docker.selected_index)
+                .and_then(|c| c.compose_service.clone())
+                .map(|service| {
+                    // Stopped replicas still count toward `--scale`
+                    let replicas = docker
+                        .containers
+                        .iter()
+                        .filter(|c| c.compose_service.as_deref() == Some(service.as_str()))
+                        .count() as u32;
+                    (service, replicas)
+                })
+        };
+
+        let Some((service, current)) = target else {
+            self.state.update(|s| {
+                s.add_notification(
+                    "Selected container is not part of a compose service".to_string(),
+                    NotificationLevel::Warning,
+                );
+                ((), None)
+            });
+            return;
+        };
+
+        let count = crate::integrations::docker::step_scale(current, delta);
+        if count == current {
+            return;
+        }
+
+        self.state.update(|s| {
+            s.panels.output.push(
+                format!("Scaling {} from {} to {}...", service, current, count),
+                OutputStream::System,
+            );
+            ((), None)
+        });
+
+        let state = self.state.clone();
+        let dir = self.working_dir.clone();
+        tokio::spawn(async move {
+            let result = crate::integrations::docker::compose_scale(&dir, &service, count).await;
+            state.update(|s| {
+                match result {
+                    Ok(()) => s.add_notification(
+                        format!("Scaled {} to {}", service, count),
+                        NotificationLevel::Success,
+                    ),
+                    Err(e) => {
+                        s.panels
+                            .output
+                            .push(format!("Scale failed: {}", e), OutputStream::System);
+                        s.add_notification(
+                            format!("Failed to scale {}", service),
+                            NotificationLevel::Error,
+                        );
+                    }
+                }
+                ((), Some(StateChange::ContainersUpdated))
+            });
+        });
+    }
+
+    /// Run the script that serves the selected port, if it isn't listening yet
+    async fn start_selected_port_script(&mut self) -> Result<EventResult> {
+        let script = {
+            let state = self.state.read();
+            let ports = &state.panels.ports;
+            let scripts = state
+                .project
+                .as_ref()
+                .map(|p| p.scripts.as_slice())
+                .unwrap_or_default();
+            crate::ui::widgets::ports_panel::display_items(ports, &state.panels.docker.containers)
+                .into_iter()
+                .nth(ports.selected_index)
+                .filter(|item| item.pid.is_none())
+                .and_then(|item| ports.expected_ports.iter().find(|e| e.port == item.port))
+                .and_then(|expected| crate::integrations::ports::script_for_port(expected, scripts))
+                .cloned()
+        };
+
+        let Some(script) = script else {
+            return Ok(EventResult::Continue);
+        };
+        let action = self.action_for_script(script);
+        self.execute_action(action).await
+    }
+
+    /// The registered action for a detected script, so `.orbit.toml` overrides apply
+    fn action_for_script(
+        &self,
+        script: crate::detection::DiscoveredScript,
+    ) -> crate::actions::Action {
+        self.action_registry
+            .for_script(&script)
+            .unwrap_or_else(|| crate::actions::Action::from_script(script))
+    }
+
+    /// Ask before killing whatever squats on the selected expected port
+    fn confirm_reclaim_selected_port(&mut self) {
+        let plan = {
+            let state = self.state.read();
+            let ports = &state.panels.ports;
+            let scripts = state
+                .project
+                .as_ref()
+                .map(|p| p.scripts.as_slice())
+                .unwrap_or_default();
+            crate::ui::widgets::ports_panel::display_items(ports, &state.panels.docker.containers)
+                .into_iter()
+                .nth(ports.selected_index)
+                .and_then(|item| ports.conflicts.iter().find(|c| c.port == item.port))
+                .and_then(|conflict| {
+                    crate::integrations::ports::reclaim_plan(
+                        conflict,
+                        &ports.expected_ports,
+                        scripts,
+                        std::process::id(),
+                    )
+                })
+        };
+
+        let Some(plan) = plan else {
+            self.state.update(|s| {
+                s.add_notification(
+                    "Nothing to reclaim: select a conflicting port with a known script".to_string(),
+                    NotificationLevel::Info,
+                );
+                ((), None)
+            });
+            return;
+        };
+
+        let message = format!(
+            "Kill {} (PID {}) on port {} and run '{}'?",
+            plan.process, plan.pid, plan.port, plan.script.name
+        );
+        self.pending_reclaim = Some(plan);
+        self.state.update(|s| {
+            s.mode = AppMode::Confirm {
+                message,
+                action_id: RECLAIM_CONFIRM_ID.to_string(),
+                destructive: false,
+                phrase: None,
+                input: String::new(),
+            };
+            ((), Some(StateChange::ModeChanged(s.mode.clone())))
+        });
+    }
+
+    /// Stop the process holding the port, wait for it to free up, then start its script
+    fn reclaim_port(&mut self, plan: crate::integrations::ports::ReclaimPlan) {
+        let action = self.action_for_script(plan.script.clone());
+        let state = self.state.clone();
+        let executor = self.action_executor.clone();
+        let auto_clear = self.config.display.auto_clear_on_rerun;
+        let alerts = CompletionAlerts::from_config(&self.config.notifications);
+        tokio::spawn(async move {
+            if let Err(e) =
+                crate::integrations::ports::terminate_pid(plan.pid, STOP_GRACE_PERIOD).await
+            {
+                state.update(|s| {
+                    let change = s.notify(
+                        format!("Failed to stop PID {}: {}", plan.pid, e),
+                        NotificationLevel::Error,
+                    );
+                    ((), change)
+                });
+                return;
+            }
+
+            let deadline = tokio::time::Instant::now() + STOP_GRACE_PERIOD;
+            while crate::integrations::ports::is_port_in_use(plan.port)
+                && tokio::time::Instant::now() < deadline
+            {
+                tokio::time::sleep(Duration::from_millis(100)).await;
             }
-            ((), None)
+            state.update(|s| {
+                let change = s.notify(
+                    format!("Reclaimed port {} from {}", plan.port, plan.process),
+                    NotificationLevel::Success,
+                );
+                ((), change)
+            });
+            run_to_completion(action, state, executor, auto_clear, alerts).await;
         });
     }
 
@@ -649,111 +2150,229 @@ impl App {
             state.panels.actions.selected_action().cloned()
         };
 
-        if let Some(action) = action {
-            // Handle system actions specially
-            if action.id.starts_with("system:") {
-                return self.handle_system_action(&action.id).await;
-            }
+        match action {
+            Some(action) => self.execute_action(action).await,
+            None => Ok(EventResult::Continue),
+        }
+    }
 
-            // Skip if command is empty
-            if action.command.is_empty() {
-                self.state.update(|s| {
-                    s.panels.output.push(
-                        format!("Action '{}' has no command to execute", action.name),
-                        OutputStream::System,
-                    );
-                    ((), None)
-                });
-                let _ = self.event_tx.send(Event::ForceRefresh);
-                return Ok(EventResult::Continue);
-            }
+    async fn execute_action(&mut self, action: crate::actions::Action) -> Result<EventResult> {
+        // Handle system actions specially
+        if action.id.starts_with("system:") {
+            return self.handle_system_action(&action.id).await;
+        }
 
+        // Skip if command is empty
+        if action.command.is_empty() {
             self.state.update(|s| {
                 s.panels.output.push(
-                    format!("Executing: {}", action.command),
+                    format!("Action '{}' has no command to execute", action.name),
                     OutputStream::System,
                 );
                 ((), None)
             });
-            let _ = self.event_tx.send(Event::ForceRefresh);
-
-            // Execute action in a background task so UI remains responsive
-            let (tx, mut rx) = tokio::sync::mpsc::channel(100);
-            let state = self.state.clone();
-            let executor = ActionExecutor::new(self.working_dir.clone());
-            let event_tx = self.event_tx.clone();
-
-            // Spawn the entire execution in a background task
-            tokio::spawn(async move {
-                // Spawn output collector
-                let state_for_output = state.clone();
-                let event_tx_for_output = event_tx.clone();
-                let output_handle = tokio::spawn(async move {
-                    while let Some(line) = rx.recv().await {
-                        match line {
-                            crate::actions::OutputLine::Stdout(s) => {
-                                state_for_output.update(|st| {
-                                    st.panels.output.push(s, OutputStream::Stdout);
-                                    ((), None)
-                                });
-                                // Request UI refresh
-                                let _ = event_tx_for_output.send(Event::ForceRefresh);
-                            }
-                            crate::actions::OutputLine::Stderr(s) => {
-                                state_for_output.update(|st| {
-                                    st.panels.output.push(s, OutputStream::Stderr);
-                                    ((), None)
-                                });
-                                // Request UI refresh
-                                let _ = event_tx_for_output.send(Event::ForceRefresh);
-                            }
-                            crate::actions::OutputLine::Exit(_) => {}
-                        }
-                    }
-                });
+            return Ok(EventResult::Continue);
+        }
+
+        if action.requires_confirm {
+            let destructive = action.is_destructive();
+            let message = if let Some(phrase) = &action.confirm_phrase {
+                format!("Type '{}' to run {}", phrase, action.command)
+            } else if destructive {
+                format!(
+                    "'{}' permanently deletes data such as Docker volumes. Continue?",
+                    action.name
+                )
+            } else {
+                format!("Run '{}'?", action.name)
+            };
+            self.state.update(|s| {
+                s.mode = AppMode::Confirm {
+                    message,
+                    action_id: action.id.clone(),
+                    destructive,
+                    phrase: action.confirm_phrase.clone(),
+                    input: String::new(),
+                };
+                (
+                    (),
+                    Some(crate::core::state::StateChange::ModeChanged(s.mode.clone())),
+                )
+            });
+            return Ok(EventResult::Continue);
+        }
 
-                // Execute the action
-                match executor.execute(&action, tx).await {
-                    Ok(result) => {
-                        // Wait for output collector to finish
-                        let _ = output_handle.await;
+        self.launch(action);
+        Ok(EventResult::Continue)
+    }
 
-                        let msg = if result.success {
-                            format!("Completed in {}ms", result.duration_ms)
-                        } else {
-                            format!("Failed with code {:?}", result.exit_code)
-                        };
-                        let level = if result.success {
-                            NotificationLevel::Success
-                        } else {
-                            NotificationLevel::Error
-                        };
-                        state.update(|s| {
-                            s.panels.output.push(msg.clone(), OutputStream::System);
-                            s.add_notification(msg, level);
-                            ((), None)
-                        });
-                        // Request UI refresh for completion message
-                        let _ = event_tx.send(Event::ForceRefresh);
-                    }
+    /// Run an action in the background, streaming its output into the output panel
+    fn run_action(&mut self, action: crate::actions::Action) {
+        self.state.update(|s| {
+            if let Some(steps) = s.macro_recording.as_mut() {
+                steps.push(action.id.clone());
+            }
+            ((), None)
+        });
+
+        // Execute in a background task so the UI remains responsive
+        let executor = self.action_executor.clone();
+        tokio::spawn(run_to_completion(
+            action,
+            self.state.clone(),
+            executor,
+            self.config.display.auto_clear_on_rerun,
+            CompletionAlerts::from_config(&self.config.notifications),
+        ));
+    }
+
+    /// Replay a recorded macro's actions one after another, stopping at the first failure
+    fn run_macro(&mut self, name: &str) {
+        match self.action_registry.macro_steps(name) {
+            Ok(steps) => {
+                let executor = self.action_executor.clone();
+                tokio::spawn(run_sequence(
+                    steps,
+                    "Macro",
+                    self.state.clone(),
+                    executor,
+                    self.config.display.auto_clear_on_rerun,
+                    CompletionAlerts::from_config(&self.config.notifications),
+                ));
+            }
+            Err(e) => self.state.update(|s| {
+                s.add_notification(e.to_string(), NotificationLevel::Error);
+                ((), None)
+            }),
+        }
+    }
+
+    /// Run the marked actions one after another, stopping at the first failure
+    ///
+    /// Macros are expanded into their steps. Actions that need confirmation or are
+    /// built in can't be queued, so the batch is refused and the marks kept.
+    fn run_marked_actions(&mut self) {
+        let marked = self.state.read().panels.actions.marked_actions();
+
+        if let Some(blocked) = marked
+            .iter()
+            .find(|a| a.requires_confirm || a.id.starts_with("system:") || a.command.is_empty())
+        {
+            let message = format!("'{}' can't run in a batch; run it on its own", blocked.name);
+            self.state.update(|s| {
+                s.add_notification(message, NotificationLevel::Warning);
+                ((), None)
+            });
+            return;
+        }
+
+        let mut steps = Vec::new();
+        for action in marked {
+            match action.id.strip_prefix("macro:") {
+                Some(name) => match self.action_registry.macro_steps(name) {
+                    Ok(macro_steps) => steps.extend(macro_steps),
                     Err(e) => {
-                        state.update(|s| {
-                            s.panels
-                                .output
-                                .push(format!("Failed to execute: {}", e), OutputStream::System);
-                            s.add_notification(
-                                format!("Action failed: {}", e),
-                                NotificationLevel::Error,
-                            );
+                        self.state.update(|s| {
+                            s.add_notification(e.to_string(), NotificationLevel::Error);
                             ((), None)
                         });
-                        // Request UI refresh for error message
-                        let _ = event_tx.send(Event::ForceRefresh);
+                        return;
                     }
-                }
+                },
+                None => steps.push(action),
+            }
+        }
+
+        self.state.update(|s| {
+            s.panels.actions.marked.clear();
+            ((), None)
+        });
+        tokio::spawn(run_sequence(
+            steps,
+            "Batch",
+            self.state.clone(),
+            self.action_executor.clone(),
+            self.config.display.auto_clear_on_rerun,
+            CompletionAlerts::from_config(&self.config.notifications),
+        ));
+    }
+
+    /// Run a confirmed or unconfirmed action, replaying it if it's a macro
+    fn launch(&mut self, action: crate::actions::Action) {
+        match action.id.strip_prefix("macro:") {
+            Some(name) => self.run_macro(name),
+            None => self.run_action(action),
+        }
+    }
+
+    /// Start recording executed actions, or stop and save them as a macro
+    fn toggle_macro_recording(&mut self) {
+        let recorded = self.state.update(|s| match s.macro_recording.take() {
+            Some(steps) => (Some(steps), None),
+            None => {
+                s.macro_recording = Some(Vec::new());
+                s.add_notification(
+                    "Recording macro, press m again to stop".to_string(),
+                    NotificationLevel::Info,
+                );
+                (None, None)
+            }
+        });
+        let Some(steps) = recorded else {
+            return;
+        };
+        if steps.is_empty() {
+            self.state.update(|s| {
+                s.add_notification("Nothing recorded".to_string(), NotificationLevel::Info);
+                ((), None)
             });
+            return;
         }
-        Ok(EventResult::Continue)
+
+        let name = self.action_registry.next_macro_name();
+        let saved = ProjectConfig::persist_macro(&self.working_dir, &name, &steps);
+        Arc::make_mut(&mut self.action_registry).add_macro(name.clone(), steps.clone());
+        let actions = self.action_registry.all_actions();
+        self.last_macro = Some(name.clone());
+
+        self.state.update(|s| {
+            s.panels.actions.replace_actions(actions);
+            match saved {
+                Ok(()) => s.add_notification(
+                    format!(
+                        "Saved macro '{}' ({} actions), @ replays it",
+                        name,
+                        steps.len()
+                    ),
+                    NotificationLevel::Success,
+                ),
+                Err(e) => s.add_notification(
+                    format!("Macro '{}' not saved to .orbit.toml: {}", name, e),
+                    NotificationLevel::Warning,
+                ),
+            }
+            ((), None)
+        });
+    }
+
+    /// Replay the macro recorded last, or the last one in `.orbit.toml`
+    async fn replay_last_macro(&mut self) -> Result<EventResult> {
+        let name = self
+            .last_macro
+            .clone()
+            .or_else(|| self.action_registry.macro_names().last().cloned());
+        let Some(action) = name.and_then(|n| self.action_registry.get(&format!("macro:{}", n)))
+        else {
+            self.state.update(|s| {
+                s.add_notification(
+                    "No macros yet, press m to record one".to_string(),
+                    NotificationLevel::Info,
+                );
+                ((), None)
+            });
+            return Ok(EventResult::Continue);
+        };
+        self.execute_action(action).await
     }
 
     async fn handle_system_action(&mut self, action_id: &str) -> Result<EventResult> {
@@ -785,6 +2404,21 @@ impl App {
             "system:focus_mode" => {
                 self.enter_focus_mode().await?;
             }
+            "system:view_logs" => {
+                self.open_log_viewer().await;
+            }
+            "system:open_logs" => {
+                self.reveal_log_dir();
+            }
+            "system:copy_summary" => {
+                self.copy_project_summary();
+            }
+            "system:snapshot" => {
+                self.save_snapshot();
+            }
+            "system:next_theme" => {
+                self.cycle_theme();
+            }
             "system:help" => {
                 self.state.update(|s| {
                     s.mode = AppMode::Help;
@@ -812,6 +2446,7 @@ impl App {
             duration_minutes: self.config.focus.default_duration,
             enable_dnd: self.config.focus.enable_dnd,
             minimize_windows: self.config.focus.minimize_windows,
+            keep_open: self.config.focus.keep_open.clone(),
             ambient_sound: if self.config.focus.ambient_sound.is_empty() {
                 None
             } else {
@@ -819,6 +2454,24 @@ impl App {
                     &self.config.focus.ambient_sound,
                 ))
             },
+            ambient_volume: self.config.focus.ambient_volume.min(100),
+            auto_pause_after: self
+                .config
+                .focus
+                .auto_pause_minutes
+                .filter(|&m| m > 0)
+                .map(|m| Duration::from_secs(u64::from(m) * 60)),
+            webhook_url: self.config.focus.webhook_url.clone(),
+            notify_on_end: self.config.notifications.native
+                && self.config.notifications.on_focus_end,
+            history_file: Some(crate::config::focus_history_file()),
+            project: self
+                .state
+                .read()
+                .project
+                .as_ref()
+                .map(|p| p.name.clone())
+                .unwrap_or_default(),
         };
 
         let duration = config.duration_minutes;
@@ -830,6 +2483,7 @@ impl App {
             s.mode = AppMode::FocusMode {
                 remaining_seconds: if duration == 0 { 0 } else { duration * 60 },
                 ambient_playing: ambient_enabled,
+                paused: false,
             };
             s.layout.preset = LayoutPreset::FocusMode;
             (
@@ -846,9 +2500,10 @@ impl App {
             controller.exit().await?;
         }
 
+        let preset = self.default_layout(self.state.read().project.as_ref());
         self.state.update(|s| {
             s.mode = AppMode::Dashboard;
-            s.layout.preset = LayoutPreset::Standard;
+            s.layout.preset = preset;
             (
                 (),
                 Some(crate::core::state::StateChange::ModeChanged(
@@ -860,24 +2515,57 @@ impl App {
         Ok(())
     }
 
+    /// Sample on a blocking thread, since sysinfo refreshes can take long enough to
+    /// stall input; a tick that finds the previous sample still running is skipped
     fn refresh_metrics(&mut self) {
-        let snapshot = self.system_monitor.sample();
-        self.state.update(|s| {
-            s.panels.metrics.push_cpu(snapshot.cpu_percent);
-            s.panels.metrics.memory_used_mb = snapshot.memory_used_mb;
-            s.panels.metrics.memory_total_mb = snapshot.memory_total_mb;
-            s.panels.metrics.disk_used_percent = snapshot.disk_used_percent;
-            ((), Some(StateChange::MetricsUpdated))
+        let monitor = Arc::clone(&self.system_monitor);
+        let state = self.state.clone();
+        tokio::spawn(async move {
+            let sample = tokio::task::spawn_blocking(move || {
+                let mut monitor = monitor.try_lock()?;
+                Some(monitor.sample())
+            })
+            .await;
+            let sample = match sample {
+                Ok(Some(sample)) => sample,
+                Ok(None) => return,
+                Err(e) => {
+                    tracing::warn!("Metrics sampling failed: {}", e);
+                    None
+                }
+            };
+            state.update(|s| {
+                s.panels.metrics.apply_sample(sample);
+                ((), Some(StateChange::MetricsUpdated))
+            });
         });
     }
 
     async fn refresh_ports(&mut self) -> Result<()> {
+        if !self.config.general.enable_ports {
+            return Ok(());
+        }
+
         self.state.update(|s| {
             s.panels.ports.loading = true;
             ((), None)
         });
 
-        let expected = self.state.read().panels.ports.expected_ports.clone();
+        let (expected, show_connections) = {
+            let state = self.state.read();
+            let ports = &state.panels.ports;
+            (ports.expected_ports.clone(), ports.show_connections)
+        };
+        let connections = if show_connections {
+            crate::integrations::ports::scan_connections()
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Connection scan failed: {}", e);
+                    Vec::new()
+                })
+        } else {
+            Vec::new()
+        };
 
         match crate::integrations::ports::scan_active_ports().await {
             Ok(active_ports) => {
@@ -885,10 +2573,13 @@ impl App {
                     crate::integrations::ports::detect_conflicts(&expected, &active_ports);
                 self.state.update(|s| {
                     s.panels.ports.active_ports = active_ports;
+                    s.panels.ports.connections = connections;
                     s.panels.ports.conflicts = conflicts;
                     s.panels.ports.loading = false;
+                    s.panels.clamp_selections();
                     ((), Some(StateChange::PortsUpdated))
                 });
+                self.refresh_open_actions();
             }
             Err(e) => {
                 self.state.update(|s| {
@@ -917,17 +2608,32 @@ impl App {
 
         match client.list_containers(true).await {
             Ok(mut containers) => {
+                self.container_cpu_limits
+                    .retain(|id, _| containers.iter().any(|c| &c.id == id));
                 for container in &mut containers {
-                    if let Ok(stats) = client.get_stats(&container.id).await {
+                    if let Ok(mut stats) = client.get_stats(&container.id).await {
+                        if !self.container_cpu_limits.contains_key(&container.id) {
+                            if let Ok(limit) = client.get_cpu_limit(&container.id).await {
+                                self.container_cpu_limits
+                                    .insert(container.id.clone(), limit);
+                            }
+                        }
+                        stats.cpu_limit = self
+                            .container_cpu_limits
+                            .get(&container.id)
+                            .copied()
+                            .flatten();
                         container.stats = Some(stats);
                     }
                 }
 
                 self.state.update(|s| {
-                    s.panels.docker.containers = containers;
+                    s.panels.docker.set_containers(containers);
                     s.panels.docker.loading = false;
+                    s.panels.clamp_selections();
                     ((), Some(StateChange::ContainersUpdated))
                 });
+                self.refresh_open_actions();
             }
             Err(e) => {
                 self.state.update(|s| {
@@ -949,3 +2655,626 @@ impl App {
         Ok(())
     }
 }
+
+/// Save an output buffer for the pager, one line per output line
+///
+/// The file gets a random name, created exclusively, so nothing else can
+/// plant or swap it in a shared temp directory.
+fn write_output_file(output: &OutputPanelState, dir: &std::path::Path) -> Result<PathBuf> {
+    use std::io::Write;
+
+    let mut file = tempfile::Builder::new()
+        .prefix("orbit-output-")
+        .suffix(".log")
+        .tempfile_in(dir)?;
+    for line in &output.lines {
+        writeln!(file, "{}", line.content)?;
+    }
+    let (_, path) = file.keep()?;
+    Ok(path)
+}
+
+/// The first panel after the focused one, stepping with `step`, that the
+/// layout for `area` actually draws; focus stays put if no other panel is shown
+fn next_visible_panel(
+    state: &AppState,
+    area: Rect,
+    step: fn(FocusedPanel) -> FocusedPanel,
+) -> FocusedPanel {
+    let layout = LayoutManager::compute(area, state);
+    let mut panel = step(state.focus_panel);
+    while panel != state.focus_panel && layout.panel(panel).is_none() {
+        panel = step(panel);
+    }
+    panel
+}
+
+/// Session file for the project in `working_dir`, keyed by its canonical path
+fn session_path(working_dir: &std::path::Path) -> PathBuf {
+    crate::config::session_file(working_dir)
+}
+
+/// Lines of history shown when a container is first pinned
+const PINNED_LOG_TAIL: usize = 100;
+
+/// Confirmation id for a port reclaim, which isn't a registered action
+const RECLAIM_CONFIRM_ID: &str = "ports:reclaim";
+
+/// How long a killed or reclaimed port's process gets to exit before SIGKILL
+const STOP_GRACE_PERIOD: Duration = Duration::from_secs(3);
+
+/// Append followed container logs to the pinned buffer until the stream ends
+async fn collect_pinned_logs(logs: impl futures::Stream<Item = String>, state: StateStore) {
+    use futures::StreamExt;
+
+    let mut logs = std::pin::pin!(logs);
+    while let Some(line) = logs.next().await {
+        state.update(|s| {
+            s.panels
+                .pinned_logs
+                .push(line.trim_end().to_string(), OutputStream::Stdout);
+            ((), Some(StateChange::OutputAppended))
+        });
+    }
+}
+
+/// Discard changes queued behind the one being handled; a single render covers them all
+fn drain_changes(changes: &mut broadcast::Receiver<StateChange>) {
+    while let Ok(_) | Err(broadcast::error::TryRecvError::Lagged(_)) = changes.try_recv() {}
+}
+
+/// Pipe `text` into the platform clipboard tool
+fn copy_to_clipboard(text: &str) -> Result<()> {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let candidates: &[(&str, &[&str])] = if cfg!(target_os = "macos") {
+        &[("pbcopy", &[])]
+    } else {
+        &[
+            ("wl-copy", &[]),
+            ("xclip", &["-selection", "clipboard"]),
+            ("xsel", &["--clipboard", "--input"]),
+        ]
+    };
+
+    for (program, args) in candidates {
+        let Ok(mut child) = Command::new(program)
+            .args(*args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            continue;
+        };
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes())?;
+        }
+        let status = child.wait()?;
+        if status.success() {
+            return Ok(());
+        }
+        anyhow::bail!("{} exited with {}", program, status);
+    }
+
+    anyhow::bail!("no clipboard tool found (install wl-copy, xclip or xsel)")
+}
+
+/// How often streamed command output is flushed to the output panel (~30fps)
+const OUTPUT_FLUSH_INTERVAL: Duration = Duration::from_millis(33);
+
+/// Tells you about finished actions outside the dashboard, e.g. while you're in
+/// another window during a long build
+#[derive(Clone)]
+struct CompletionAlerts {
+    native: bool,
+    bell: bool,
+    min_duration: Duration,
+    /// Delivers a `(title, body)` notification; the OS notifier outside of tests
+    notify: Arc<dyn Fn(String, String) + Send + Sync>,
+}
+
+impl CompletionAlerts {
+    fn from_config(config: &crate::config::NotificationsConfig) -> Self {
+        Self {
+            native: config.native && config.on_action_complete,
+            bell: config.bell && config.on_action_complete,
+            min_duration: Duration::from_secs(config.min_action_seconds),
+            notify: Arc::new(|title, body| {
+                tokio::spawn(async move {
+                    if let Err(e) = crate::focus::send_notification(&title, &body).await {
+                        tracing::debug!("Native notification failed: {:#}", e);
+                    }
+                });
+            }),
+        }
+    }
+
+    fn none() -> Self {
+        Self {
+            native: false,
+            bell: false,
+            min_duration: Duration::ZERO,
+            notify: Arc::new(|_, _| {}),
+        }
+    }
+
+    fn announce(&self, action_name: &str, success: bool, summary: &str, took: Duration) {
+        if took < self.min_duration {
+            return;
+        }
+        if self.native {
+            let title = if success {
+                format!("✓ {} finished", action_name)
+            } else {
+                format!("✗ {} failed", action_name)
+            };
+            (self.notify)(title, summary.to_string());
+        }
+        if self.bell {
+            let mut stdout = std::io::stdout();
+            let _ = std::io::Write::write_all(&mut stdout, b"\x07");
+            let _ = std::io::Write::flush(&mut stdout);
+        }
+    }
+}
+
+/// Run `action` to completion, streaming output into the output panel and
+/// reporting the outcome; returns whether it succeeded
+async fn run_to_completion(
+    action: crate::actions::Action,
+    state: StateStore,
+    executor: ActionExecutor,
+    auto_clear: bool,
+    alerts: CompletionAlerts,
+) -> bool {
+    state.update(|s| {
+        s.panels.output.begin_run(&action.id, auto_clear);
+        s.panels.output.push(
+            format!("Executing: {}", action.command),
+            OutputStream::System,
+        );
+        ((), None)
+    });
+
+    // The bounded channel backpressures the PTY reader when the collector falls behind
+    let (tx, rx) = tokio::sync::mpsc::channel(100);
+    let output_handle = tokio::spawn(collect_output(
+        rx,
+        state.clone(),
+        OUTPUT_FLUSH_INTERVAL,
+        TestSummaryParser::for_action(&action),
+        ProgressExtractor::for_command(&action.command),
+    ));
+
+    match executor.execute(&action, tx).await {
+        Ok(result) => {
+            // Wait for output collector to finish
+            let tests = output_handle.await.ok().flatten();
+
+            let msg = match (tests, result.success) {
+                (Some(tests), _) => format!("{} in {}ms", tests, result.duration_ms),
+                (None, true) => format!("Completed in {}ms", result.duration_ms),
+                (None, false) => format!("Failed with code {:?}", result.exit_code),
+            };
+            let level = if result.success {
+                NotificationLevel::Success
+            } else {
+                NotificationLevel::Error
+            };
+            alerts.announce(
+                &action.name,
+                result.success,
+                &msg,
+                Duration::from_millis(result.duration_ms),
+            );
+            state.update(|s| {
+                s.panels.output.push(msg.clone(), OutputStream::System);
+                s.add_notification(msg, level);
+                ((), Some(StateChange::OutputAppended))
+            });
+            result.success
+        }
+        Err(e) => {
+            state.update(|s| {
+                s.panels
+                    .output
+                    .push(format!("Failed to execute: {}", e), OutputStream::System);
+                s.add_notification(format!("Action failed: {}", e), NotificationLevel::Error);
+                ((), Some(StateChange::OutputAppended))
+            });
+            false
+        }
+    }
+}
+
+/// Run `actions` in order, stopping at the first failure; returns how many succeeded
+///
+/// `label` ("Macro", "Batch") names the sequence in its header and failure lines.
+async fn run_sequence(
+    actions: Vec<crate::actions::Action>,
+    label: &'static str,
+    state: StateStore,
+    executor: ActionExecutor,
+    auto_clear: bool,
+    alerts: CompletionAlerts,
+) -> usize {
+    let total = actions.len();
+    for (done, action) in actions.into_iter().enumerate() {
+        let name = action.name.clone();
+        state.update(|s| {
+            s.panels.output.push(
+                format!("── {} {}/{}: {} ──", label, done + 1, total, name),
+                OutputStream::System,
+            );
+            ((), Some(StateChange::OutputAppended))
+        });
+        if !run_to_completion(
+            action,
+            state.clone(),
+            executor.clone(),
+            auto_clear,
+            alerts.clone(),
+        )
+        .await
+        {
+            let message = format!(
+                "{} stopped: '{}' failed ({}/{})",
+                label,
+                name,
+                done + 1,
+                total
+            );
+            state.update(|s| {
+                s.panels.output.push(message.clone(), OutputStream::System);
+                s.add_notification(message, NotificationLevel::Error);
+                ((), Some(StateChange::OutputAppended))
+            });
+            return done;
+        }
+    }
+    total
+}
+
+/// Buffer streamed output and flush it to state in batches
+///
+/// A chatty command can emit thousands of lines per second; pushing and redrawing per
+/// line floods the state broadcast, so lines are coalesced into one update per interval.
+/// Lines are also fed to `tests`, if given, to scrape the test runner's summary.
+/// Lines that `progress` understands drive the footer progress bar instead of
+/// scrolling the output; anything it doesn't recognize is shown as usual.
+async fn collect_output(
+    mut rx: mpsc::Receiver<OutputLine>,
+    state: StateStore,
+    flush_every: Duration,
+    mut tests: Option<TestSummaryParser>,
+    progress: Option<ProgressExtractor>,
+) -> Option<TestSummary> {
+    let mut batch: Vec<(String, OutputStream)> = Vec::new();
+    let mut latest: Option<Progress> = None;
+    let mut ticker =
+        tokio::time::interval_at(tokio::time::Instant::now() + flush_every, flush_every);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    let flush = |batch: &mut Vec<(String, OutputStream)>, latest: &mut Option<Progress>| {
+        if batch.is_empty() && latest.is_none() {
+            return;
+        }
+        state.update(|st| {
+            for (line, stream) in batch.drain(..) {
+                st.panels.output.push(line, stream);
+            }
+            if let Some(progress) = latest.take() {
+                st.panels.output.progress = Some(progress);
+            }
+            ((), Some(StateChange::OutputAppended))
+        });
+    };
+
+    loop {
+        let (s, stream) = tokio::select! {
+            line = rx.recv() => match line {
+                Some(OutputLine::Stdout(s)) => (s, OutputStream::Stdout),
+                Some(OutputLine::Stderr(s)) => (s, OutputStream::Stderr),
+                Some(OutputLine::Exit(_)) => continue,
+                None => break,
+            },
+            _ = ticker.tick() => {
+                flush(&mut batch, &mut latest);
+                continue;
+            }
+        };
+
+        if let Some(parser) = tests.as_mut() {
+            parser.feed(&s);
+        }
+        match progress.and_then(|p| p.parse_line(&s)) {
+            Some(p) => latest = Some(p),
+            None => batch.push((s, stream)),
+        }
+    }
+
+    flush(&mut batch, &mut latest);
+    if progress.is_some() {
+        state.update(|st| {
+            st.panels.output.progress = None;
+            ((), Some(StateChange::OutputAppended))
+        });
+    }
+    tests.and_then(TestSummaryParser::finish)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+
+    fn custom(name: &str, command: &str) -> crate::config::CustomAction {
+        crate::config::CustomAction {
+            name: name.to_string(),
+            command: command.to_string(),
+            category: None,
+            description: None,
+            keybinding: None,
+            confirm: false,
+            confirm_phrase: false,
+            icon: None,
+            color: None,
+            shell: None,
+        }
+    }
+
+    #[test]
+    fn tab_skips_panels_the_layout_hides() {
+        let mut state = AppState::new(PathBuf::from("."), Theme::default());
+        state.layout.preset = LayoutPreset::Standard;
+        state.layout.docker_panel_visible = false;
+        state.layout.output_split = false;
+        let area = Rect::new(0, 0, 120, 40);
+
+        assert_eq!(
+            next_visible_panel(&state, area, FocusedPanel::next),
+            FocusedPanel::Ports
+        );
+        assert_eq!(
+            next_visible_panel(&state, area, FocusedPanel::prev),
+            FocusedPanel::Output
+        );
+
+        state.layout.preset = LayoutPreset::FocusMode;
+        assert_eq!(
+            next_visible_panel(&state, area, FocusedPanel::next),
+            FocusedPanel::Actions
+        );
+    }
+
+    #[tokio::test]
+    async fn replaying_a_macro_runs_its_actions_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let macros = indexmap::IndexMap::from([(
+            "setup".to_string(),
+            vec!["custom:first".to_string(), "custom:second".to_string()],
+        )]);
+        let registry = ActionRegistry::new()
+            .with_custom_actions(&[custom("first", "echo one"), custom("second", "echo two")])
+            .with_macros(&macros);
+        let state = StateStore::new(AppState::new(dir.path().into(), Theme::default()));
+
+        let steps = registry.macro_steps("setup").unwrap();
+        let executor = ActionExecutor::new(dir.path().into());
+        assert_eq!(
+            run_sequence(
+                steps,
+                "Macro",
+                state.clone(),
+                executor,
+                false,
+                CompletionAlerts::none()
+            )
+            .await,
+            2
+        );
+
+        let output: Vec<String> = state
+            .read()
+            .panels
+            .output
+            .lines
+            .iter()
+            .map(|l| l.content.trim().to_string())
+            .collect();
+        let one = output.iter().position(|l| l == "one").expect("first ran");
+        let two = output.iter().position(|l| l == "two").expect("second ran");
+        assert!(one < two);
+    }
+
+    #[tokio::test]
+    async fn macro_stops_at_the_first_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let macros = indexmap::IndexMap::from([(
+            "broken".to_string(),
+            vec!["custom:fail".to_string(), "custom:after".to_string()],
+        )]);
+        let registry = ActionRegistry::new()
+            .with_custom_actions(&[custom("fail", "exit 3"), custom("after", "echo after")])
+            .with_macros(&macros);
+        let state = StateStore::new(AppState::new(dir.path().into(), Theme::default()));
+
+        let steps = registry.macro_steps("broken").unwrap();
+        let executor = ActionExecutor::new(dir.path().into());
+        assert_eq!(
+            run_sequence(
+                steps,
+                "Macro",
+                state.clone(),
+                executor,
+                false,
+                CompletionAlerts::none()
+            )
+            .await,
+            0
+        );
+        assert!(!state
+            .read()
+            .panels
+            .output
+            .lines
+            .iter()
+            .any(|l| l.content.trim() == "after"));
+    }
+
+    #[tokio::test]
+    async fn rapid_output_is_flushed_in_one_batch() {
+        let state = StateStore::new(AppState::new(PathBuf::from("."), Theme::default()));
+        let mut changes = state.subscribe();
+        let (tx, rx) = mpsc::channel(1000);
+
+        for i in 0..500 {
+            tx.send(OutputLine::Stdout(format!("line {}", i)))
+                .await
+                .unwrap();
+        }
+        drop(tx);
+
+        collect_output(rx, state.clone(), Duration::from_secs(5), None, None).await;
+
+        assert_eq!(state.read().panels.output.lines.len(), 500);
+        assert!(matches!(
+            changes.try_recv(),
+            Ok(StateChange::OutputAppended)
+        ));
+        assert!(
+            changes.try_recv().is_err(),
+            "expected a single state update"
+        );
+    }
+
+    #[tokio::test]
+    async fn state_change_wakes_the_render_loop() {
+        let state = StateStore::new(AppState::new(PathBuf::from("."), Theme::default()));
+        let mut changes = state.subscribe();
+
+        let writer = state.clone();
+        tokio::spawn(async move {
+            for _ in 0..3 {
+                writer.update(|s| {
+                    s.panels
+                        .output
+                        .push("line".to_string(), OutputStream::Stdout);
+                    ((), Some(StateChange::OutputAppended))
+                });
+            }
+        });
+
+        let woke = tokio::time::timeout(Duration::from_secs(1), changes.recv()).await;
+        assert!(matches!(woke, Ok(Ok(StateChange::OutputAppended))));
+
+        // Wait for the rest of the burst, then one drain coalesces it into this frame
+        while state.read().panels.output.lines.len() < 3 {
+            tokio::task::yield_now().await;
+        }
+        drain_changes(&mut changes);
+        assert!(matches!(
+            changes.try_recv(),
+            Err(broadcast::error::TryRecvError::Empty)
+        ));
+    }
+
+    #[tokio::test]
+    async fn split_mode_keeps_output_and_pinned_logs_apart() {
+        let mut initial = AppState::new(PathBuf::from("."), Theme::default());
+        initial.layout.output_split = true;
+        initial.panels.pinned_container = Some("db".to_string());
+        let state = StateStore::new(initial);
+
+        state.update(|s| {
+            s.panels
+                .output
+                .push("cargo test".to_string(), OutputStream::Stdout);
+            ((), None)
+        });
+        let logs = futures::stream::iter(["ready to accept connections\n", "checkpoint\n"])
+            .map(str::to_string);
+        collect_pinned_logs(logs, state.clone()).await;
+
+        let state = state.read();
+        let output: Vec<_> = state
+            .panels
+            .output
+            .lines
+            .iter()
+            .map(|l| &l.content)
+            .collect();
+        let pinned: Vec<_> = state
+            .panels
+            .pinned_logs
+            .lines
+            .iter()
+            .map(|l| &l.content)
+            .collect();
+        assert_eq!(output, ["cargo test"]);
+        assert_eq!(pinned, ["ready to accept connections", "checkpoint"]);
+    }
+
+    #[test]
+    fn pager_file_holds_every_output_line() {
+        let mut output = OutputPanelState::new();
+        for line in ["Compiling orbit", "warning: unused variable", "Finished"] {
+            output.push(line.to_string(), OutputStream::Stdout);
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_output_file(&output, dir.path()).unwrap();
+        assert!(path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with("orbit-output-")));
+        assert_ne!(path, write_output_file(&output, dir.path()).unwrap());
+
+        let content = std::fs::read_to_string(path).unwrap();
+        assert_eq!(
+            content,
+            "Compiling orbit\nwarning: unused variable\nFinished\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn completion_sends_a_native_notification_when_enabled() {
+        let dir = tempfile::tempdir().unwrap();
+        let sent = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let config = crate::config::NotificationsConfig {
+            min_action_seconds: 0,
+            ..Default::default()
+        };
+        let recorder = Arc::clone(&sent);
+        let alerts = CompletionAlerts {
+            notify: Arc::new(move |title, body| recorder.lock().push((title, body))),
+            ..CompletionAlerts::from_config(&config)
+        };
+        let state = StateStore::new(AppState::new(dir.path().into(), Theme::default()));
+        let executor = ActionExecutor::new(dir.path().into());
+        let action =
+            |name: &str, command: &str| crate::actions::Action::from_custom(&custom(name, command));
+
+        assert!(
+            run_to_completion(
+                action("build", "true"),
+                state.clone(),
+                executor.clone(),
+                false,
+                alerts.clone()
+            )
+            .await
+        );
+        assert!(!run_to_completion(action("lint", "exit 2"), state, executor, false, alerts).await);
+
+        let sent = sent.lock();
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent[0].0, "✓ build finished");
+        assert!(sent[0].1.starts_with("Completed in"));
+        assert_eq!(sent[1].0, "✗ lint failed");
+        assert_eq!(sent[1].1, "Failed with code Some(2)");
+    }
+}
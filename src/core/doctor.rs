@@ -0,0 +1,322 @@
+//! `orbit doctor`: check the external tools and config files Orbit relies on
+
+use std::path::Path;
+use tokio::process::Command;
+
+use crate::config::{Config, ProjectConfig};
+use crate::focus::is_command_available;
+use crate::integrations::docker::DockerClient;
+use crate::ui::theme::Theme;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    /// Orbit works, minus the feature this check covers
+    Warn,
+    /// Orbit can't work as configured; makes `orbit doctor` exit non-zero
+    Fail,
+}
+
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl Check {
+    fn new(name: &str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// An external program Orbit shells out to
+struct Tool {
+    command: &'static str,
+    /// Arguments that print a version, if the tool has any
+    version_args: &'static [&'static str],
+    /// What stops working without it
+    needed_for: &'static str,
+    critical: bool,
+}
+
+fn tools(git_enabled: bool) -> Vec<Tool> {
+    let mut tools = vec![
+        Tool {
+            command: "git",
+            version_args: &["--version"],
+            needed_for: "branch status and recent files (or run with --no-git)",
+            critical: git_enabled,
+        },
+        Tool {
+            command: "docker",
+            version_args: &["--version"],
+            needed_for: "compose up/down and container actions",
+            critical: false,
+        },
+        Tool {
+            command: "lsof",
+            version_args: &[],
+            needed_for: "port scanning and killing processes on a port",
+            critical: false,
+        },
+        Tool {
+            command: "mpv",
+            version_args: &["--version"],
+            needed_for: "ambient sound in focus mode",
+            critical: false,
+        },
+    ];
+    if cfg!(target_os = "macos") {
+        tools.push(Tool {
+            command: "osascript",
+            version_args: &[],
+            needed_for: "Do Not Disturb, notifications and window minimizing",
+            critical: false,
+        });
+    } else {
+        tools.push(Tool {
+            command: "notify-send",
+            version_args: &["--version"],
+            needed_for: "desktop notifications",
+            critical: false,
+        });
+    }
+    tools
+}
+
+/// Run every check for the project in `working_dir`, with `profile` applied to `.orbit.toml`
+///
+/// `no_git` and `no_docker` are the CLI opt-outs, applied on top of the config.
+pub async fn run_checks(
+    config_path: Option<&Path>,
+    working_dir: &Path,
+    profile: Option<&str>,
+    no_git: bool,
+    no_docker: bool,
+) -> Vec<Check> {
+    let mut checks = Vec::new();
+
+    let mut config = match config_path {
+        None => {
+            checks.push(Check::new(
+                "config",
+                CheckStatus::Pass,
+                "no config file, using defaults",
+            ));
+            Config::default()
+        }
+        Some(path) => match Config::load(path) {
+            Ok(config) => {
+                checks.push(Check::new(
+                    "config",
+                    CheckStatus::Pass,
+                    path.display().to_string(),
+                ));
+                config
+            }
+            Err(e) => {
+                checks.push(Check::new(
+                    "config",
+                    CheckStatus::Fail,
+                    format!("{}: {:#}", path.display(), e),
+                ));
+                Config::default()
+            }
+        },
+    };
+    config.general.enable_git &= !no_git;
+    config.general.enable_docker &= !no_docker;
+    checks.push(theme_check(&config.display.theme));
+    checks.push(project_config_check(working_dir, profile));
+
+    for tool in tools(config.general.enable_git) {
+        checks.push(tool_check(&tool).await);
+    }
+    if config.general.enable_docker {
+        checks.push(docker_daemon_check(config.docker.socket.as_deref()).await);
+    }
+
+    checks
+}
+
+fn theme_check(theme: &str) -> Check {
+    match Theme::load(theme) {
+        Ok(_) => Check::new("theme", CheckStatus::Pass, theme),
+        Err(e) => Check::new(
+            "theme",
+            CheckStatus::Warn,
+            format!("{:#}; using the default theme", e),
+        ),
+    }
+}
+
+fn project_config_check(working_dir: &Path, profile: Option<&str>) -> Check {
+    match ProjectConfig::load(working_dir, profile) {
+        Ok(Some(_)) => Check::new(".orbit.toml", CheckStatus::Pass, "valid"),
+        Ok(None) => Check::new(".orbit.toml", CheckStatus::Pass, "none in this project"),
+        Err(e) => Check::new(".orbit.toml", CheckStatus::Fail, format!("{:#}", e)),
+    }
+}
+
+async fn tool_check(tool: &Tool) -> Check {
+    if !is_command_available(tool.command).await {
+        let status = if tool.critical {
+            CheckStatus::Fail
+        } else {
+            CheckStatus::Warn
+        };
+        return Check::new(
+            tool.command,
+            status,
+            format!("not found; needed for {}", tool.needed_for),
+        );
+    }
+
+    let version = if tool.version_args.is_empty() {
+        None
+    } else {
+        Command::new(tool.command)
+            .args(tool.version_args)
+            .output()
+            .await
+            .ok()
+            .and_then(|o| {
+                first_line(&String::from_utf8_lossy(&o.stdout))
+                    .or_else(|| first_line(&String::from_utf8_lossy(&o.stderr)))
+            })
+    };
+    Check::new(
+        tool.command,
+        CheckStatus::Pass,
+        version.unwrap_or_else(|| "found".to_string()),
+    )
+}
+
+async fn docker_daemon_check(socket: Option<&str>) -> Check {
+    let client = match socket {
+        Some(socket) => DockerClient::with_socket(socket),
+        None => DockerClient::new(),
+    };
+    match client {
+        Ok(client) if client.is_available().await => {
+            Check::new("docker daemon", CheckStatus::Pass, "reachable")
+        }
+        Ok(_) => Check::new(
+            "docker daemon",
+            CheckStatus::Warn,
+            "not reachable; is Docker running?",
+        ),
+        Err(e) => Check::new("docker daemon", CheckStatus::Warn, format!("{:#}", e)),
+    }
+}
+
+/// First non-empty line of a version banner
+fn first_line(text: &str) -> Option<String> {
+    text.lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+/// `✓ git           git version 2.43.0`, one line per check, then a summary
+pub fn format_report(checks: &[Check]) -> String {
+    let width = checks.iter().map(|c| c.name.len()).max().unwrap_or(0);
+    let mut report = String::new();
+    for check in checks {
+        let mark = match check.status {
+            CheckStatus::Pass => "✓",
+            CheckStatus::Warn => "!",
+            CheckStatus::Fail => "✗",
+        };
+        report.push_str(&format!(
+            "{} {:<width$}  {}\n",
+            mark,
+            check.name,
+            check.detail,
+            width = width
+        ));
+    }
+
+    let count = |status| checks.iter().filter(|c| c.status == status).count();
+    let (warnings, failures) = (count(CheckStatus::Warn), count(CheckStatus::Fail));
+    report.push_str(&format!(
+        "\n{} {}, {} {}\n",
+        warnings,
+        if warnings == 1 { "warning" } else { "warnings" },
+        failures,
+        if failures == 1 { "failure" } else { "failures" },
+    ));
+    report
+}
+
+/// Print the checklist; returns whether every critical check passed
+pub async fn run(
+    config_path: Option<&Path>,
+    working_dir: &Path,
+    profile: Option<&str>,
+    no_git: bool,
+    no_docker: bool,
+) -> bool {
+    let checks = run_checks(config_path, working_dir, profile, no_git, no_docker).await;
+    print!("{}", format_report(&checks));
+    checks.iter().all(|c| c.status != CheckStatus::Fail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_project_config_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(
+            project_config_check(dir.path(), None).status,
+            CheckStatus::Pass
+        );
+
+        std::fs::write(dir.path().join(".orbit.toml"), "[actions\nname = ").unwrap();
+        let check = project_config_check(dir.path(), None);
+        assert_eq!(check.status, CheckStatus::Fail);
+        assert!(!check.detail.is_empty());
+    }
+
+    #[tokio::test]
+    async fn cli_opt_outs_relax_the_checks() {
+        let dir = tempfile::tempdir().unwrap();
+        let checks = run_checks(None, dir.path(), None, true, true).await;
+        assert!(checks.iter().all(|c| c.name != "docker daemon"));
+        assert!(checks
+            .iter()
+            .filter(|c| c.name == "git")
+            .all(|c| c.status != CheckStatus::Fail));
+        assert!(!tools(false)[0].critical);
+    }
+
+    #[test]
+    fn report_lists_checks_and_counts_problems() {
+        let checks = [
+            Check::new("git", CheckStatus::Pass, "git version 2.43.0"),
+            Check::new("mpv", CheckStatus::Warn, "not found"),
+            Check::new("config", CheckStatus::Fail, "bad"),
+        ];
+        assert_eq!(
+            format_report(&checks),
+            concat!(
+                "✓ git     git version 2.43.0\n",
+                "! mpv     not found\n",
+                "✗ config  bad\n",
+                "\n",
+                "1 warning, 1 failure\n",
+            )
+        );
+        assert_eq!(
+            first_line("\n  mpv 0.37.0 Copyright\nbuilt on\n").as_deref(),
+            Some("mpv 0.37.0 Copyright")
+        );
+    }
+}
@@ -2,10 +2,12 @@
 
 #![allow(dead_code)]
 
+use anyhow::{bail, Result};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent};
 use std::time::Duration;
 use tokio::sync::mpsc;
 
+use crate::config::KeybindingsConfig;
 use crate::core::state::Notification;
 
 /// All possible events in the system
@@ -17,7 +19,7 @@ pub enum Event {
     Resize(u16, u16),
 
     // System events
-    Tick,     // Render tick (target 60fps)
+    Tick,     // Housekeeping tick (notification expiry)
     SlowTick, // Background refresh (1Hz)
 
     // Async completion events
@@ -29,13 +31,20 @@ pub enum Event {
         id: uuid::Uuid,
         code: i32,
     },
+    /// Watched project files changed, e.g. `package.json, .env`
     FileChanged(String),
+    /// The global config file changed on disk
+    ConfigChanged,
 
     // Focus mode events
     FocusTimerTick {
         remaining: u32,
     },
     FocusModeEnded,
+    /// The focus timer paused (by the user or for inactivity) or resumed
+    FocusPauseChanged {
+        paused: bool,
+    },
 
     // Notifications
     ShowNotification(Notification),
@@ -54,7 +63,6 @@ pub enum Event {
 
     // Lifecycle
     Quit,
-    ForceRefresh,
 }
 
 /// Result of handling an event
@@ -79,15 +87,15 @@ impl EventHandler {
         self.tx.clone()
     }
 
-    /// Start all event source tasks
-    pub fn spawn_sources(event_tx: mpsc::UnboundedSender<Event>) {
+    /// Start all event source tasks, returning the terminal input task
+    pub fn spawn_sources(event_tx: mpsc::UnboundedSender<Event>) -> tokio::task::JoinHandle<()> {
         // Terminal input events
-        tokio::spawn(Self::terminal_events(event_tx.clone()));
+        let input = Self::spawn_terminal_events(event_tx.clone());
 
-        // Render tick (33ms = ~30fps for TUI)
+        // Housekeeping tick; rendering is driven by state changes and input
         tokio::spawn(Self::tick_events(
             event_tx.clone(),
-            Duration::from_millis(33),
+            Duration::from_millis(250),
             Event::Tick,
         ));
 
@@ -97,6 +105,15 @@ impl EventHandler {
             Duration::from_secs(2),
             Event::SlowTick,
         ));
+
+        input
+    }
+
+    /// (Re)start reading terminal input; abort the handle to release stdin
+    pub fn spawn_terminal_events(
+        event_tx: mpsc::UnboundedSender<Event>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(Self::terminal_events(event_tx))
     }
 
     async fn terminal_events(tx: mpsc::UnboundedSender<Event>) {
@@ -133,6 +150,7 @@ impl EventHandler {
 }
 
 /// Key binding helper
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct KeyBinding {
     pub key: KeyCode,
     pub modifiers: KeyModifiers,
@@ -153,57 +171,197 @@ impl KeyBinding {
         }
     }
 
-    pub fn matches(&self, event: &KeyEvent) -> bool {
-        event.code == self.key && event.modifiers == self.modifiers
-    }
-}
-
-/// Standard key bindings
-pub struct KeyBindings;
-
-impl KeyBindings {
-    pub fn quit() -> KeyBinding {
-        KeyBinding::new(KeyCode::Char('q'))
-    }
+    /// Parse a binding like `q`, `N`, `space`, `ctrl+c`, `alt+enter` or `f5`
+    pub fn parse(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let (modifier_part, key_part) = if s == "+" {
+            ("", "+")
+        } else if let Some(rest) = s.strip_suffix("++") {
+            (rest, "+")
+        } else {
+            s.rsplit_once('+').unwrap_or(("", s))
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for modifier in modifier_part.split('+').filter(|m| !m.is_empty()) {
+            modifiers |= match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "option" | "meta" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                other => bail!("unknown modifier `{}`", other),
+            };
+        }
 
-    pub fn quit_alt() -> KeyBinding {
-        KeyBinding::ctrl(KeyCode::Char('c'))
+        let mut chars = key_part.chars();
+        let key = match (chars.next(), chars.next()) {
+            (None, _) => bail!("missing key"),
+            (Some(c), None) => {
+                // `matches` ignores shift on characters, so fold it into the character
+                if modifiers.contains(KeyModifiers::SHIFT) {
+                    modifiers -= KeyModifiers::SHIFT;
+                    KeyCode::Char(c.to_ascii_uppercase())
+                } else {
+                    KeyCode::Char(c)
+                }
+            }
+            _ => match key_part.to_lowercase().as_str() {
+                "space" => KeyCode::Char(' '),
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backtab" => {
+                    modifiers |= KeyModifiers::SHIFT;
+                    KeyCode::BackTab
+                }
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" => KeyCode::Insert,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "pagedown" | "pgdn" => KeyCode::PageDown,
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=24) => KeyCode::F(n),
+                    _ => bail!("unknown key `{}`", key_part),
+                },
+            },
+        };
+        Ok(Self { key, modifiers })
     }
 
-    pub fn palette() -> KeyBinding {
-        KeyBinding::new(KeyCode::Char(' '))
+    pub fn matches(&self, event: &KeyEvent) -> bool {
+        // Shift is already reflected in the character itself ('N', '?'), and
+        // terminals disagree on whether they also report the modifier
+        let modifiers = match event.code {
+            KeyCode::Char(_) => event.modifiers - KeyModifiers::SHIFT,
+            _ => event.modifiers,
+        };
+        event.code == self.key && modifiers == self.modifiers
     }
 
-    pub fn focus() -> KeyBinding {
-        KeyBinding::new(KeyCode::Char('f'))
+    /// Whether this is the bare space bar, which the Actions panel uses for marking
+    pub fn is_space(&self) -> bool {
+        *self == Self::new(KeyCode::Char(' '))
     }
+}
 
-    pub fn docker() -> KeyBinding {
-        KeyBinding::new(KeyCode::Char('d'))
+impl std::fmt::Display for KeyBinding {
+    /// As shown in key hints, e.g. `q`, `Space`, `Ctrl+P`, `F5`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) && self.key != KeyCode::BackTab {
+            write!(f, "Shift+")?;
+        }
+        match self.key {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) if self.modifiers.contains(KeyModifiers::CONTROL) => {
+                write!(f, "{}", c.to_ascii_uppercase())
+            }
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::BackTab => write!(f, "Shift+Tab"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Delete => write!(f, "Del"),
+            KeyCode::Insert => write!(f, "Insert"),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            other => write!(f, "{:?}", other),
+        }
     }
+}
 
-    pub fn ports() -> KeyBinding {
-        KeyBinding::new(KeyCode::Char('p'))
-    }
+/// Key bindings; the ones under `[keybindings]` in the config can be remapped,
+/// the rest are fixed and available as associated functions
+#[derive(Debug, Clone)]
+pub struct KeyBindings {
+    pub quit: KeyBinding,
+    pub palette: KeyBinding,
+    pub focus: KeyBinding,
+    pub toggle_docker: KeyBinding,
+    pub toggle_ports: KeyBinding,
+    pub toggle_env: KeyBinding,
+    pub terminal: KeyBinding,
+    pub help: KeyBinding,
+    pub refresh: KeyBinding,
+    pub navigate_up: KeyBinding,
+    pub navigate_down: KeyBinding,
+    pub select: KeyBinding,
+    pub back: KeyBinding,
+}
 
-    pub fn env() -> KeyBinding {
-        KeyBinding::new(KeyCode::Char('e'))
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self::from_config(&KeybindingsConfig::default())
     }
+}
 
-    pub fn terminal() -> KeyBinding {
-        KeyBinding::new(KeyCode::Char('t'))
-    }
+impl KeyBindings {
+    /// Bindings from the config, keeping the default for any entry that doesn't parse
+    pub fn from_config(config: &KeybindingsConfig) -> Self {
+        let defaults = KeybindingsConfig::default();
+        let bind = |name: &str, value: &str, default: &str| {
+            KeyBinding::parse(value).unwrap_or_else(|e| {
+                tracing::warn!(
+                    "Invalid keybinding {} = {:?} ({:#}), using {:?}",
+                    name,
+                    value,
+                    e,
+                    default
+                );
+                KeyBinding::parse(default).expect("default keybindings parse")
+            })
+        };
 
-    pub fn help() -> KeyBinding {
-        KeyBinding::new(KeyCode::Char('?'))
+        Self {
+            quit: bind("quit", &config.quit, &defaults.quit),
+            palette: bind("palette", &config.palette, &defaults.palette),
+            focus: bind("focus", &config.focus, &defaults.focus),
+            toggle_docker: bind(
+                "toggle_docker",
+                &config.toggle_docker,
+                &defaults.toggle_docker,
+            ),
+            toggle_ports: bind("toggle_ports", &config.toggle_ports, &defaults.toggle_ports),
+            toggle_env: bind("toggle_env", &config.toggle_env, &defaults.toggle_env),
+            terminal: bind("terminal", &config.terminal, &defaults.terminal),
+            help: bind("help", &config.help, &defaults.help),
+            refresh: bind("refresh", &config.refresh, &defaults.refresh),
+            navigate_up: bind("navigate_up", &config.navigate_up, &defaults.navigate_up),
+            navigate_down: bind(
+                "navigate_down",
+                &config.navigate_down,
+                &defaults.navigate_down,
+            ),
+            select: bind("select", &config.select, &defaults.select),
+            back: bind("back", &config.back, &defaults.back),
+        }
     }
 
-    pub fn escape() -> KeyBinding {
-        KeyBinding::new(KeyCode::Esc)
+    pub fn quit_alt() -> KeyBinding {
+        KeyBinding::ctrl(KeyCode::Char('c'))
     }
 
-    pub fn enter() -> KeyBinding {
-        KeyBinding::new(KeyCode::Enter)
+    /// Opens the palette even where space means something else
+    pub fn palette_alt() -> KeyBinding {
+        KeyBinding::new(KeyCode::Char('/'))
     }
 
     pub fn tab() -> KeyBinding {
@@ -225,15 +383,107 @@ impl KeyBindings {
         KeyBinding::new(KeyCode::Down)
     }
 
-    pub fn vim_up() -> KeyBinding {
-        KeyBinding::new(KeyCode::Char('k'))
+    pub fn notes() -> KeyBinding {
+        KeyBinding::new(KeyCode::Char('N'))
+    }
+
+    pub fn cycle_layout() -> KeyBinding {
+        KeyBinding::new(KeyCode::Char('z'))
+    }
+
+    pub fn cycle_theme() -> KeyBinding {
+        KeyBinding::new(KeyCode::Char('T'))
+    }
+
+    pub fn snapshot() -> KeyBinding {
+        KeyBinding::new(KeyCode::Char('S'))
+    }
+
+    pub fn split_output() -> KeyBinding {
+        KeyBinding::new(KeyCode::Char('|'))
+    }
+
+    /// Leaves Terminal mode; every other key goes to the shell
+    pub fn terminal_escape() -> KeyBinding {
+        KeyBinding::ctrl(KeyCode::Char(']'))
+    }
+
+    pub fn record_macro() -> KeyBinding {
+        KeyBinding::new(KeyCode::Char('m'))
+    }
+
+    pub fn replay_macro() -> KeyBinding {
+        KeyBinding::new(KeyCode::Char('@'))
+    }
+
+    pub fn dismiss_notifications() -> KeyBinding {
+        KeyBinding::new(KeyCode::Char('X'))
+    }
+
+    pub fn save() -> KeyBinding {
+        KeyBinding::ctrl(KeyCode::Char('s'))
+    }
+
+    pub fn external_editor() -> KeyBinding {
+        KeyBinding::ctrl(KeyCode::Char('e'))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_key_binding_strings() {
+        let parse = |s: &str| KeyBinding::parse(s).unwrap();
+        assert_eq!(parse("q"), KeyBinding::new(KeyCode::Char('q')));
+        assert_eq!(parse("space"), KeyBinding::new(KeyCode::Char(' ')));
+        assert_eq!(parse("Enter"), KeyBinding::new(KeyCode::Enter));
+        assert_eq!(parse("esc"), KeyBinding::new(KeyCode::Esc));
+        assert_eq!(parse("ctrl+c"), KeyBinding::ctrl(KeyCode::Char('c')));
+        assert_eq!(parse("ctrl++"), KeyBinding::ctrl(KeyCode::Char('+')));
+        assert_eq!(parse("shift+n"), KeyBinding::new(KeyCode::Char('N')));
+        assert_eq!(parse("f5"), KeyBinding::new(KeyCode::F(5)));
+        assert_eq!(
+            parse("alt+enter"),
+            KeyBinding {
+                key: KeyCode::Enter,
+                modifiers: KeyModifiers::ALT,
+            }
+        );
+
+        assert!(KeyBinding::parse("").is_err());
+        assert!(KeyBinding::parse("hyper+x").is_err());
+        assert!(KeyBinding::parse("spacebar").is_err());
     }
 
-    pub fn vim_down() -> KeyBinding {
-        KeyBinding::new(KeyCode::Char('j'))
+    #[test]
+    fn key_bindings_display_as_hints() {
+        let hint = |s: &str| KeyBinding::parse(s).unwrap().to_string();
+        assert_eq!(hint("q"), "q");
+        assert_eq!(hint("shift+n"), "N");
+        assert_eq!(hint("space"), "Space");
+        assert_eq!(hint("ctrl+p"), "Ctrl+P");
+        assert_eq!(hint("alt+enter"), "Alt+Enter");
+        assert_eq!(hint("backtab"), "Shift+Tab");
+        assert_eq!(hint("f5"), "F5");
     }
 
-    pub fn refresh() -> KeyBinding {
-        KeyBinding::new(KeyCode::Char('r'))
+    #[test]
+    fn invalid_config_entries_keep_their_defaults() {
+        let config = KeybindingsConfig {
+            quit: "ctrl+q".to_string(),
+            palette: "not-a-key".to_string(),
+            ..KeybindingsConfig::default()
+        };
+        let keys = KeyBindings::from_config(&config);
+
+        assert!(keys
+            .quit
+            .matches(&KeyEvent::new(KeyCode::Char('q'), KeyModifiers::CONTROL)));
+        assert!(!keys
+            .quit
+            .matches(&KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE)));
+        assert_eq!(keys.palette, KeyBindings::default().palette);
     }
 }
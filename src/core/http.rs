@@ -0,0 +1,141 @@
+//! Read-only HTTP health endpoint for status walls and monitoring
+
+use anyhow::{Context, Result};
+use axum::{extract::State, routing::get, Json, Router};
+use serde::Serialize;
+use std::net::{Ipv4Addr, SocketAddr};
+
+use crate::core::state::{AppMode, AppState, StateStore};
+use crate::integrations::docker::ContainerStatus;
+
+/// Address used when `--http` is given without a value
+pub const DEFAULT_HTTP_ADDR: &str = "127.0.0.1:7878";
+
+/// Dashboard summary served as JSON
+#[derive(Debug, Serialize)]
+pub struct HealthReport {
+    pub project: Option<String>,
+    pub types: Vec<String>,
+    pub port_conflicts: usize,
+    pub docker: DockerSummary,
+    pub missing_env: usize,
+    pub focus: FocusSummary,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DockerSummary {
+    pub running: usize,
+    pub total: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FocusSummary {
+    pub active: bool,
+    pub remaining_seconds: Option<u32>,
+}
+
+impl HealthReport {
+    pub fn from_state(state: &AppState) -> Self {
+        let project = state.project.as_ref();
+        let containers = &state.panels.docker.containers;
+        let remaining_seconds = match state.mode {
+            AppMode::FocusMode {
+                remaining_seconds, ..
+            } => Some(remaining_seconds),
+            _ => None,
+        };
+
+        Self {
+            project: project.map(|p| p.name.clone()),
+            types: project
+                .map(|p| p.types.iter().map(|t| t.kind.key().to_string()).collect())
+                .unwrap_or_default(),
+            port_conflicts: state.panels.ports.conflicts.len(),
+            docker: DockerSummary {
+                running: containers
+                    .iter()
+                    .filter(|c| c.status == ContainerStatus::Running)
+                    .count(),
+                total: containers.len(),
+            },
+            missing_env: project
+                .map(|p| p.env_vars.missing_required.len())
+                .unwrap_or(0),
+            focus: FocusSummary {
+                active: remaining_seconds.is_some(),
+                remaining_seconds,
+            },
+        }
+    }
+}
+
+/// Parse an `--http` value; a bare port binds to localhost
+pub fn parse_addr(value: &str) -> Result<SocketAddr> {
+    if let Ok(port) = value.parse::<u16>() {
+        return Ok(SocketAddr::from((Ipv4Addr::LOCALHOST, port)));
+    }
+    value
+        .parse()
+        .with_context(|| format!("Invalid HTTP address '{}'", value))
+}
+
+async fn health(State(state): State<StateStore>) -> Json<HealthReport> {
+    Json(HealthReport::from_state(&state.read()))
+}
+
+/// Bind `addr` and serve `GET /health` from the shared state until the app exits
+pub async fn serve(addr: SocketAddr, state: StateStore) -> Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("Failed to bind HTTP endpoint on {}", addr))?;
+
+    let router = Router::new()
+        .route("/", get(health))
+        .route("/health", get(health))
+        .with_state(state);
+
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, router).await {
+            tracing::warn!("HTTP endpoint stopped: {}", e);
+        }
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::docker::ContainerInfo;
+    use crate::integrations::ports::PortConflict;
+    use crate::ui::theme::Theme;
+
+    #[test]
+    fn report_includes_conflicts_and_docker_counts() {
+        let mut state = AppState::new("/tmp".into(), Theme::from_name("tokyo-night"));
+        state.panels.ports.conflicts.push(PortConflict {
+            port: 3000,
+            expected_service: "dev-server".to_string(),
+            actual_process: "node".to_string(),
+            actual_pid: 42,
+        });
+        state.panels.docker.containers = vec![
+            ContainerInfo::fixture("db", ContainerStatus::Running),
+            ContainerInfo::fixture("cache", ContainerStatus::Exited),
+        ];
+
+        let json = serde_json::to_value(HealthReport::from_state(&state)).unwrap();
+        assert_eq!(json["port_conflicts"], 1);
+        assert_eq!(json["docker"]["running"], 1);
+        assert_eq!(json["docker"]["total"], 2);
+        assert_eq!(json["focus"]["active"], false);
+    }
+
+    #[test]
+    fn bare_port_binds_localhost() {
+        let addr = parse_addr("9000").unwrap();
+        assert!(addr.ip().is_loopback());
+        assert_eq!(addr.port(), 9000);
+        assert!(parse_addr("not an address").is_err());
+    }
+}
@@ -1,5 +1,11 @@
 //! Core application logic and state management
 
 pub mod app;
+pub mod doctor;
 pub mod events;
+pub mod http;
+pub mod session;
 pub mod state;
+pub mod status;
+pub mod terminal;
+pub mod watcher;
@@ -0,0 +1,109 @@
+//! Per-project dashboard session: layout, panel visibility and focus
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::core::state::{AppState, FocusedPanel, LayoutPreset};
+
+/// What the dashboard looked like when it was closed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Session {
+    pub focus_panel: FocusedPanel,
+    pub layout: LayoutPreset,
+    pub docker_panel_visible: bool,
+    pub ports_panel_visible: bool,
+    pub env_panel_visible: bool,
+    pub output_split: bool,
+}
+
+impl Session {
+    pub fn capture(state: &AppState) -> Self {
+        Self {
+            focus_panel: state.focus_panel,
+            layout: state
+                .panels
+                .terminal
+                .return_layout
+                .unwrap_or(state.layout.preset),
+            docker_panel_visible: state.layout.docker_panel_visible,
+            ports_panel_visible: state.layout.ports_panel_visible,
+            env_panel_visible: state.layout.env_panel_visible,
+            output_split: state.layout.output_split,
+        }
+    }
+
+    /// Restore onto `state`; panels turned off in the config stay hidden
+    pub fn apply(&self, state: &mut AppState) {
+        state.focus_panel = self.focus_panel;
+        state.layout.preset = self.layout;
+        state.layout.docker_panel_visible &= self.docker_panel_visible;
+        state.layout.ports_panel_visible &= self.ports_panel_visible;
+        state.layout.env_panel_visible = self.env_panel_visible;
+        state.layout.output_split = self.output_split;
+    }
+
+    /// The saved session, or `None` if there isn't a readable one
+    pub fn load(path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        match toml::from_str(&content) {
+            Ok(session) => Some(session),
+            Err(e) => {
+                tracing::warn!("Ignoring session file {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, toml::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Apply a restored session, then the configured startup panel on top of it
+pub fn apply_startup(
+    state: &mut AppState,
+    session: Option<&Session>,
+    startup_panel: Option<FocusedPanel>,
+) {
+    if let Some(session) = session {
+        session.apply(state);
+    }
+    if let Some(panel) = startup_panel {
+        state.focus_panel = panel;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::theme::Theme;
+
+    #[test]
+    fn session_round_trips_and_restores_focus() {
+        let mut state = AppState::new("/tmp/api".into(), Theme::default());
+        state.focus_panel = FocusedPanel::RecentFiles;
+        state.layout.preset = LayoutPreset::Wide;
+        state.layout.env_panel_visible = false;
+        let session = Session::capture(&state);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("api").join("session.toml");
+        session.save(&path).unwrap();
+        assert_eq!(Session::load(&path), Some(session.clone()));
+
+        let mut fresh = AppState::new("/tmp/api".into(), Theme::default());
+        apply_startup(&mut fresh, Session::load(&path).as_ref(), None);
+        assert_eq!(fresh.focus_panel, FocusedPanel::RecentFiles);
+        assert_eq!(fresh.layout.preset, LayoutPreset::Wide);
+        assert!(!fresh.layout.env_panel_visible);
+
+        // An explicit startup panel wins over the restored focus
+        apply_startup(&mut fresh, Some(&session), Some(FocusedPanel::Output));
+        assert_eq!(fresh.focus_panel, FocusedPanel::Output);
+    }
+}
@@ -2,22 +2,28 @@
 
 #![allow(dead_code)]
 
+use once_cell::sync::Lazy;
 use parking_lot::RwLock;
-use std::collections::VecDeque;
+use regex::Regex;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::broadcast;
 
+use crate::actions::progress::Progress;
 use crate::actions::Action;
-use crate::detection::ProjectContext;
+use crate::config::HeaderSegment;
+use crate::detection::{ProjectContext, RecentFile};
 use crate::integrations::docker::ContainerInfo;
 use crate::integrations::ports::{ActivePort, ExpectedPort, PortConflict};
+use crate::integrations::system::SystemMetrics;
 use crate::ui::theme::Theme;
 
 /// Top-level application mode
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub enum AppMode {
     /// Normal dashboard view
+    #[default]
     Dashboard,
     /// Command palette is open
     CommandPalette,
@@ -25,6 +31,8 @@ pub enum AppMode {
     FocusMode {
         remaining_seconds: u32,
         ambient_playing: bool,
+        /// Timer stopped with space/`p`, or after no input for `focus.auto_pause_minutes`
+        paused: bool,
     },
     /// Embedded terminal is active
     Terminal,
@@ -33,24 +41,50 @@ pub enum AppMode {
     /// Secret input modal
     SecretInput { key: String },
     /// Confirmation dialog
-    Confirm { message: String, action_id: String },
+    Confirm {
+        message: String,
+        action_id: String,
+        destructive: bool,
+        /// When set, the user must type this exactly before confirming
+        phrase: Option<String>,
+        /// What has been typed so far for `phrase`
+        input: String,
+    },
+    /// Orbit's own log file
+    LogViewer,
+    /// Per-project notes scratchpad
+    Notes,
 }
 
-impl Default for AppMode {
-    fn default() -> Self {
-        Self::Dashboard
+impl AppMode {
+    /// Whether a confirm dialog may proceed: always for y/n prompts,
+    /// only on an exact match for type-to-confirm prompts
+    pub fn confirm_enabled(&self) -> bool {
+        match self {
+            Self::Confirm {
+                phrase: Some(phrase),
+                input,
+                ..
+            } => input == phrase,
+            Self::Confirm { .. } => true,
+            _ => false,
+        }
     }
 }
 
 /// Which panel currently has focus
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum FocusedPanel {
     #[default]
     Actions,
     Docker,
     Ports,
+    RecentFiles,
     Env,
     Output,
+    /// Right half of the output area in split mode
+    PinnedLogs,
 }
 
 impl FocusedPanel {
@@ -58,18 +92,22 @@ impl FocusedPanel {
         match self {
             Self::Actions => Self::Docker,
             Self::Docker => Self::Ports,
-            Self::Ports => Self::Env,
+            Self::Ports => Self::RecentFiles,
+            Self::RecentFiles => Self::Env,
             Self::Env => Self::Output,
-            Self::Output => Self::Actions,
+            Self::Output => Self::PinnedLogs,
+            Self::PinnedLogs => Self::Actions,
         }
     }
 
     pub fn prev(self) -> Self {
         match self {
-            Self::Actions => Self::Output,
+            Self::Actions => Self::PinnedLogs,
+            Self::PinnedLogs => Self::Output,
             Self::Docker => Self::Actions,
             Self::Ports => Self::Docker,
-            Self::Env => Self::Ports,
+            Self::RecentFiles => Self::Ports,
+            Self::Env => Self::RecentFiles,
             Self::Output => Self::Env,
         }
     }
@@ -84,6 +122,41 @@ pub struct DockerPanelState {
     pub loading: bool,
     pub last_updated: Option<chrono::DateTime<chrono::Utc>>,
     pub error: Option<String>,
+    /// Recent CPU% samples per container id, oldest first
+    pub cpu_history: HashMap<String, VecDeque<f64>>,
+    /// Recent memory usage in MB per container id, oldest first
+    pub memory_history: HashMap<String, VecDeque<f64>>,
+}
+
+impl DockerPanelState {
+    /// Samples kept per container, about 40 seconds at the Docker refresh rate
+    pub const STATS_HISTORY_LEN: usize = 20;
+
+    /// Replace the container list, extending each container's stats history and
+    /// dropping the history of containers that are gone
+    pub fn set_containers(&mut self, containers: Vec<ContainerInfo>) {
+        self.cpu_history
+            .retain(|id, _| containers.iter().any(|c| &c.id == id));
+        self.memory_history
+            .retain(|id, _| containers.iter().any(|c| &c.id == id));
+
+        for container in &containers {
+            let Some(stats) = &container.stats else {
+                continue;
+            };
+            for (histories, value) in [
+                (&mut self.cpu_history, stats.cpu_percent),
+                (&mut self.memory_history, stats.memory_usage_mb),
+            ] {
+                let history = histories.entry(container.id.clone()).or_default();
+                history.push_back(value);
+                if history.len() > Self::STATS_HISTORY_LEN {
+                    history.pop_front();
+                }
+            }
+        }
+        self.containers = containers;
+    }
 }
 
 /// Port panel state
@@ -94,6 +167,10 @@ pub struct PortPanelState {
     pub conflicts: Vec<PortConflict>,
     pub selected_index: usize,
     pub loading: bool,
+    /// List TCP connections below the listeners, toggled with `c`
+    pub show_connections: bool,
+    /// Non-listening TCP connections, scanned only while `show_connections` is on
+    pub connections: Vec<ActivePort>,
 }
 
 /// Actions panel state
@@ -103,30 +180,28 @@ pub struct ActionPanelState {
     pub selected_index: usize,
     pub filter: String,
     pub filtered_indices: Vec<usize>,
+    /// Indices into `actions` queued with space, run together on Enter
+    pub marked: HashSet<usize>,
 }
 
 impl ActionPanelState {
+    /// Keep the actions fuzzy-matching `filter`, best match first
+    ///
+    /// Equal scores keep their original order, as does an empty filter.
     pub fn update_filter(&mut self, filter: String) {
         self.filter = filter.clone();
-        let filter_lower = filter.to_lowercase();
 
         if filter.is_empty() {
             self.filtered_indices = (0..self.actions.len()).collect();
         } else {
-            self.filtered_indices = self
+            let mut scored: Vec<(i64, usize)> = self
                 .actions
                 .iter()
                 .enumerate()
-                .filter(|(_, a)| {
-                    a.name.to_lowercase().contains(&filter_lower)
-                        || a.command.to_lowercase().contains(&filter_lower)
-                        || a.description
-                            .as_ref()
-                            .map(|d| d.to_lowercase().contains(&filter_lower))
-                            .unwrap_or(false)
-                })
-                .map(|(i, _)| i)
+                .filter_map(|(i, a)| a.fuzzy_score(&filter).map(|score| (score, i)))
                 .collect();
+            scored.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+            self.filtered_indices = scored.into_iter().map(|(_, i)| i).collect();
         }
 
         self.selected_index = 0;
@@ -137,6 +212,193 @@ impl ActionPanelState {
             .get(self.selected_index)
             .and_then(|&idx| self.actions.get(idx))
     }
+
+    /// Mark or unmark the selected action, returning how many are marked
+    pub fn toggle_marked(&mut self) -> usize {
+        if let Some(&idx) = self.filtered_indices.get(self.selected_index) {
+            if !self.marked.remove(&idx) {
+                self.marked.insert(idx);
+            }
+        }
+        self.marked.len()
+    }
+
+    /// Marked actions in list order
+    pub fn marked_actions(&self) -> Vec<Action> {
+        let mut indices: Vec<usize> = self.marked.iter().copied().collect();
+        indices.sort_unstable();
+        indices
+            .into_iter()
+            .filter_map(|idx| self.actions.get(idx).cloned())
+            .collect()
+    }
+
+    /// Swap in a new action list, keeping the selection on the same action id if it survives
+    pub fn replace_actions(&mut self, actions: Vec<Action>) {
+        let selected_id = self.selected_action().map(|a| a.id.clone());
+        let marked_ids: Vec<String> = self
+            .marked
+            .iter()
+            .filter_map(|&idx| self.actions.get(idx).map(|a| a.id.clone()))
+            .collect();
+
+        self.actions = actions;
+        self.marked = self
+            .actions
+            .iter()
+            .enumerate()
+            .filter(|(_, a)| marked_ids.contains(&a.id))
+            .map(|(idx, _)| idx)
+            .collect();
+        self.update_filter(self.filter.clone());
+
+        if let Some(id) = selected_id {
+            if let Some(pos) = self
+                .filtered_indices
+                .iter()
+                .position(|&idx| self.actions[idx].id == id)
+            {
+                self.selected_index = pos;
+            }
+        }
+    }
+}
+
+/// Recently changed files panel state
+#[derive(Debug, Clone, Default)]
+pub struct RecentFilesPanelState {
+    pub files: Vec<RecentFile>,
+    pub selected_index: usize,
+}
+
+/// Embedded shell shown in Terminal mode
+#[derive(Debug, Clone, Default)]
+pub struct TerminalPanelState {
+    /// The shell's screen as of its latest output; `None` until it prints
+    pub screen: Option<vt100::Screen>,
+    /// Layout to restore when leaving Terminal mode
+    pub return_layout: Option<LayoutPreset>,
+}
+
+/// Notes scratchpad being edited in the notes overlay
+///
+/// `cursor_col` counts characters, not bytes. There is always at least one line.
+#[derive(Debug, Clone)]
+pub struct NotesState {
+    pub lines: Vec<String>,
+    pub cursor_row: usize,
+    pub cursor_col: usize,
+    pub path: Option<PathBuf>,
+    pub dirty: bool,
+}
+
+impl Default for NotesState {
+    fn default() -> Self {
+        Self {
+            lines: vec![String::new()],
+            cursor_row: 0,
+            cursor_col: 0,
+            path: None,
+            dirty: false,
+        }
+    }
+}
+
+impl NotesState {
+    pub fn load(path: PathBuf, text: &str) -> Self {
+        let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+        Self {
+            lines,
+            path: Some(path),
+            ..Self::default()
+        }
+    }
+
+    pub fn text(&self) -> String {
+        let mut text = self.lines.join("\n");
+        text.push('\n');
+        text
+    }
+
+    fn byte_index(&self) -> usize {
+        let line = &self.lines[self.cursor_row];
+        line.char_indices()
+            .nth(self.cursor_col)
+            .map(|(i, _)| i)
+            .unwrap_or(line.len())
+    }
+
+    fn line_len(&self, row: usize) -> usize {
+        self.lines[row].chars().count()
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        let at = self.byte_index();
+        self.lines[self.cursor_row].insert(at, c);
+        self.cursor_col += 1;
+        self.dirty = true;
+    }
+
+    pub fn newline(&mut self) {
+        let at = self.byte_index();
+        let rest = self.lines[self.cursor_row].split_off(at);
+        self.cursor_row += 1;
+        self.lines.insert(self.cursor_row, rest);
+        self.cursor_col = 0;
+        self.dirty = true;
+    }
+
+    /// Delete the character before the cursor, joining lines at the start of a line
+    pub fn backspace(&mut self) {
+        if self.cursor_col > 0 {
+            self.cursor_col -= 1;
+            let at = self.byte_index();
+            self.lines[self.cursor_row].remove(at);
+        } else if self.cursor_row > 0 {
+            let line = self.lines.remove(self.cursor_row);
+            self.cursor_row -= 1;
+            self.cursor_col = self.line_len(self.cursor_row);
+            self.lines[self.cursor_row].push_str(&line);
+        } else {
+            return;
+        }
+        self.dirty = true;
+    }
+
+    pub fn move_left(&mut self) {
+        if self.cursor_col > 0 {
+            self.cursor_col -= 1;
+        } else if self.cursor_row > 0 {
+            self.cursor_row -= 1;
+            self.cursor_col = self.line_len(self.cursor_row);
+        }
+    }
+
+    pub fn move_right(&mut self) {
+        if self.cursor_col < self.line_len(self.cursor_row) {
+            self.cursor_col += 1;
+        } else if self.cursor_row + 1 < self.lines.len() {
+            self.cursor_row += 1;
+            self.cursor_col = 0;
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        if self.cursor_row > 0 {
+            self.cursor_row -= 1;
+            self.cursor_col = self.cursor_col.min(self.line_len(self.cursor_row));
+        }
+    }
+
+    pub fn move_down(&mut self) {
+        if self.cursor_row + 1 < self.lines.len() {
+            self.cursor_row += 1;
+            self.cursor_col = self.cursor_col.min(self.line_len(self.cursor_row));
+        }
+    }
 }
 
 /// Environment panel state
@@ -171,17 +433,58 @@ pub struct MetricsPanelState {
     pub cpu_history: VecDeque<f32>,
     pub memory_used_mb: u64,
     pub memory_total_mb: u64,
+    /// Memory use as a percentage of total, one entry per sample
+    pub memory_history: VecDeque<f32>,
     pub disk_used_percent: f32,
+    pub disk_history: VecDeque<f32>,
+    /// The last sample failed; the values shown are from the last good one, if any
+    pub unavailable: bool,
 }
 
 impl MetricsPanelState {
-    pub fn push_cpu(&mut self, value: f32) {
-        self.cpu_history.push_back(value);
-        if self.cpu_history.len() > 60 {
-            self.cpu_history.pop_front();
+    /// Samples kept per sparkline, about two minutes at the slow tick
+    const HISTORY_LEN: usize = 60;
+
+    /// Record a sample; a failed one keeps the previous values and histories
+    pub fn apply_sample(&mut self, sample: Option<SystemMetrics>) {
+        let Some(sample) = sample else {
+            self.unavailable = true;
+            return;
+        };
+        self.unavailable = false;
+        self.push_cpu(sample.cpu_percent);
+        self.memory_used_mb = sample.memory_used_mb;
+        self.memory_total_mb = sample.memory_total_mb;
+        let memory_percent = self.memory_percent();
+        Self::push_capped(&mut self.memory_history, memory_percent);
+        self.disk_used_percent = sample.disk_used_percent;
+        Self::push_capped(&mut self.disk_history, sample.disk_used_percent);
+    }
+
+    pub fn memory_percent(&self) -> f32 {
+        if self.memory_total_mb > 0 {
+            (self.memory_used_mb as f32 / self.memory_total_mb as f32) * 100.0
+        } else {
+            0.0
         }
+    }
+
+    /// Whether there is any good sample to show
+    pub fn has_sample(&self) -> bool {
+        !self.cpu_history.is_empty()
+    }
+
+    pub fn push_cpu(&mut self, value: f32) {
+        Self::push_capped(&mut self.cpu_history, value);
         self.cpu_percent = value;
     }
+
+    fn push_capped(history: &mut VecDeque<f32>, value: f32) {
+        history.push_back(value);
+        if history.len() > Self::HISTORY_LEN {
+            history.pop_front();
+        }
+    }
 }
 
 /// Output panel state
@@ -191,6 +494,22 @@ pub struct OutputPanelState {
     pub scroll_offset: usize,
     pub auto_scroll: bool,
     pub max_lines: usize,
+    /// Progress of the running action, shown in the footer instead of its progress lines
+    pub progress: Option<Progress>,
+    /// Action whose run produced the tail of the buffer
+    pub last_action: Option<String>,
+    /// Index of the first line of that run
+    last_run_start: usize,
+    /// Show only error and warning lines; the buffer itself is kept
+    pub errors_only: bool,
+    /// Scrollback search, `Some` from `/` until `Esc`
+    pub search_query: Option<String>,
+    /// Whether keys are going into the search input rather than the panel
+    pub search_input: bool,
+    /// Indices into `visible_lines()` of the lines matching the query
+    pub match_indices: Vec<usize>,
+    /// Position in `match_indices` of the match `n`/`N` last jumped to
+    pub current_match: Option<usize>,
 }
 
 impl Default for OutputPanelState {
@@ -220,7 +539,157 @@ impl OutputPanelState {
             scroll_offset: 0,
             auto_scroll: true,
             max_lines: 1000,
+            progress: None,
+            last_action: None,
+            last_run_start: 0,
+            errors_only: false,
+            search_query: None,
+            search_input: false,
+            match_indices: Vec::new(),
+            current_match: None,
+        }
+    }
+
+    /// Whether `line` looks like an error or warning worth keeping in the filtered view
+    pub fn is_error_line(line: &OutputLine) -> bool {
+        static ERROR_PATTERN: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(r"(?i:\b(?:errors?|warnings?|failed)\b)|(?:^|\s)E:")
+                .expect("Invalid error line regex")
+        });
+        line.stream == OutputStream::Stderr || ERROR_PATTERN.is_match(&line.content)
+    }
+
+    /// The lines the panel shows, after the errors-only filter
+    pub fn visible_lines(&self) -> Vec<&OutputLine> {
+        self.lines
+            .iter()
+            .filter(|line| !self.errors_only || Self::is_error_line(line))
+            .collect()
+    }
+
+    /// Toggle the errors-only view, jumping back to the newest line
+    pub fn toggle_errors_only(&mut self) {
+        self.errors_only = !self.errors_only;
+        self.auto_scroll = true;
+        self.scroll_offset = self.visible_lines().len().saturating_sub(1);
+        self.refresh_matches();
+    }
+
+    /// Open the search input; the view stops following new output until the search closes
+    pub fn start_search(&mut self) {
+        self.search_query = Some(String::new());
+        self.search_input = true;
+        self.auto_scroll = false;
+        self.refresh_matches();
+    }
+
+    /// Replace the query and jump to the first match at or below the current position
+    pub fn set_search_query(&mut self, query: String) {
+        self.search_query = Some(query);
+        self.refresh_matches();
+        self.current_match = self
+            .match_indices
+            .iter()
+            .position(|&i| i >= self.scroll_offset)
+            .or((!self.match_indices.is_empty()).then_some(0));
+        if let Some(current) = self.current_match {
+            self.scroll_offset = self.match_indices[current];
+        }
+    }
+
+    /// Jump to the next match below (or above), wrapping around the buffer
+    pub fn jump_to_match(&mut self, forward: bool) {
+        let count = self.match_indices.len();
+        if count == 0 {
+            return;
+        }
+        let next = match self.current_match {
+            Some(current) if forward => (current + 1) % count,
+            Some(current) => (current + count - 1) % count,
+            None if forward => 0,
+            None => count - 1,
+        };
+        self.current_match = Some(next);
+        self.scroll_offset = self.match_indices[next];
+        self.auto_scroll = false;
+    }
+
+    /// Close the search and follow the output again
+    pub fn clear_search(&mut self) {
+        self.search_query = None;
+        self.search_input = false;
+        self.match_indices.clear();
+        self.current_match = None;
+        self.auto_scroll = true;
+        self.scroll_offset = self.visible_lines().len().saturating_sub(1);
+    }
+
+    /// Recompute `match_indices` after the query or the visible lines change
+    pub fn refresh_matches(&mut self) {
+        let query = self.search_query.as_deref().unwrap_or_default();
+        self.match_indices = if query.is_empty() {
+            Vec::new()
+        } else {
+            self.visible_lines()
+                .iter()
+                .enumerate()
+                .filter(|(_, line)| !Self::match_ranges(&line.content, query).is_empty())
+                .map(|(i, _)| i)
+                .collect()
+        };
+        if self
+            .current_match
+            .is_some_and(|current| current >= self.match_indices.len())
+        {
+            self.current_match = None;
+        }
+    }
+
+    /// Byte ranges of case-insensitive occurrences of `query` in `content`
+    pub fn match_ranges(content: &str, query: &str) -> Vec<std::ops::Range<usize>> {
+        let fold = |c: char| c.to_lowercase().next().unwrap_or(c);
+        let needle: Vec<char> = query.chars().map(fold).collect();
+        let haystack: Vec<(usize, char)> =
+            content.char_indices().map(|(i, c)| (i, fold(c))).collect();
+        if needle.is_empty() || needle.len() > haystack.len() {
+            return Vec::new();
         }
+
+        let mut ranges = Vec::new();
+        let mut start = 0;
+        while start + needle.len() <= haystack.len() {
+            let window = &haystack[start..start + needle.len()];
+            if window.iter().map(|(_, c)| *c).eq(needle.iter().copied()) {
+                let end = haystack
+                    .get(start + needle.len())
+                    .map_or(content.len(), |(i, _)| *i);
+                ranges.push(haystack[start].0..end);
+                start += needle.len();
+            } else {
+                start += 1;
+            }
+        }
+        ranges
+    }
+
+    /// Whether starting `action_id` should first drop the previous run's output
+    pub fn should_clear_before_run(
+        last_action: Option<&str>,
+        action_id: &str,
+        auto_clear: bool,
+    ) -> bool {
+        auto_clear && last_action == Some(action_id)
+    }
+
+    /// Mark the start of a run of `action_id`; with `auto_clear`, a rerun of the
+    /// action that produced the tail replaces that output and keeps everything before it
+    pub fn begin_run(&mut self, action_id: &str, auto_clear: bool) {
+        if Self::should_clear_before_run(self.last_action.as_deref(), action_id, auto_clear) {
+            self.lines.truncate(self.last_run_start);
+            self.scroll_offset = self.lines.len().saturating_sub(1);
+        }
+        self.last_action = Some(action_id.to_string());
+        self.last_run_start = self.lines.len();
     }
 
     pub fn push(&mut self, content: String, stream: OutputStream) {
@@ -229,12 +698,41 @@ impl OutputPanelState {
             timestamp: chrono::Utc::now(),
             stream,
         });
-        if self.lines.len() > self.max_lines {
+        let rotated = self.lines.len() > self.max_lines;
+        if rotated {
             self.lines.pop_front();
+            self.last_run_start = self.last_run_start.saturating_sub(1);
         }
         if self.auto_scroll {
             self.scroll_offset = self.lines.len().saturating_sub(1);
         }
+        if self.search_query.is_some() {
+            self.update_matches_after_push(rotated);
+        }
+    }
+
+    /// Keep `match_indices` current without rescanning the whole buffer on every line
+    fn update_matches_after_push(&mut self, rotated: bool) {
+        if self.errors_only {
+            self.refresh_matches();
+            return;
+        }
+        if rotated {
+            // Every line moved up one; a match on the dropped line is gone
+            if self.match_indices.first() == Some(&0) {
+                self.match_indices.remove(0);
+                self.current_match = self.current_match.and_then(|c| c.checked_sub(1));
+            }
+            for index in &mut self.match_indices {
+                *index -= 1;
+            }
+            self.scroll_offset = self.scroll_offset.saturating_sub(1);
+        }
+        let query = self.search_query.as_deref().unwrap_or_default();
+        let newest = self.lines.len() - 1;
+        if !query.is_empty() && !Self::match_ranges(&self.lines[newest].content, query).is_empty() {
+            self.match_indices.push(newest);
+        }
     }
 }
 
@@ -275,7 +773,11 @@ pub struct LayoutConfig {
     pub ports_panel_visible: bool,
     pub env_panel_visible: bool,
     pub output_panel_expanded: bool,
+    /// Output area split between command output and pinned container logs
+    pub output_split: bool,
     pub sidebar_width_percent: u16,
+    /// Header segments, in display order
+    pub header_segments: Vec<HeaderSegment>,
 }
 
 impl Default for LayoutConfig {
@@ -286,12 +788,15 @@ impl Default for LayoutConfig {
             ports_panel_visible: true,
             env_panel_visible: true,
             output_panel_expanded: false,
+            output_split: false,
             sidebar_width_percent: 30,
+            header_segments: HeaderSegment::ALL.to_vec(),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum LayoutPreset {
     #[default]
     Standard,
@@ -301,6 +806,55 @@ pub enum LayoutPreset {
     TerminalFocus,
 }
 
+impl LayoutPreset {
+    /// Parse a configured layout name; `None` means pick one automatically
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "standard" => Some(Self::Standard),
+            "compact" => Some(Self::Compact),
+            "wide" => Some(Self::Wide),
+            _ => None,
+        }
+    }
+
+    /// Name used for `display.layout` in the config file
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Standard => "standard",
+            Self::Compact => "compact",
+            Self::Wide => "wide",
+            Self::FocusMode => "focus",
+            Self::TerminalFocus => "terminal",
+        }
+    }
+
+    /// Next preset in the user-selectable cycle; mode-specific presets restart it
+    pub fn cycle(self) -> Self {
+        match self {
+            Self::Standard => Self::Compact,
+            Self::Compact => Self::Wide,
+            Self::Wide | Self::FocusMode | Self::TerminalFocus => Self::Standard,
+        }
+    }
+
+    /// Pick a sensible default layout for a detected project
+    pub fn for_project(project: &ProjectContext) -> Self {
+        let docker_primary = project
+            .types
+            .iter()
+            .find(|t| t.primary)
+            .is_some_and(|t| matches!(t.kind, crate::detection::ProjectKind::Docker { .. }));
+
+        if docker_primary || project.docker_services.len() >= 4 {
+            Self::Wide
+        } else if project.docker_services.is_empty() && project.ports.is_empty() {
+            Self::Compact
+        } else {
+            Self::Standard
+        }
+    }
+}
+
 /// Granular view state for all panels
 #[derive(Debug, Clone, Default)]
 pub struct PanelStates {
@@ -310,6 +864,55 @@ pub struct PanelStates {
     pub env: EnvPanelState,
     pub metrics: MetricsPanelState,
     pub output: OutputPanelState,
+    pub logs: OutputPanelState,
+    /// Followed logs of the pinned container, shown beside the output in split mode
+    pub pinned_logs: OutputPanelState,
+    pub pinned_container: Option<String>,
+    pub recent_files: RecentFilesPanelState,
+    pub notes: NotesState,
+    pub terminal: TerminalPanelState,
+}
+
+impl PanelStates {
+    /// Pull every list selection back inside its list after a refresh shrank it
+    pub fn clamp_selections(&mut self) {
+        let ports_len =
+            crate::ui::widgets::ports_panel::display_items(&self.ports, &self.docker.containers)
+                .len();
+
+        clamp_selection(
+            &mut self.actions.selected_index,
+            self.actions.filtered_indices.len(),
+        );
+        clamp_selection(
+            &mut self.docker.selected_index,
+            self.docker.containers.len(),
+        );
+        clamp_selection(&mut self.ports.selected_index, ports_len);
+        clamp_selection(
+            &mut self.recent_files.selected_index,
+            self.recent_files.files.len(),
+        );
+        clamp_selection(&mut self.env.selected_index, self.env.variables.len());
+    }
+}
+
+fn clamp_selection(index: &mut usize, len: usize) {
+    *index = (*index).min(len.saturating_sub(1));
+}
+
+/// Selection after moving one row up or down a list of `len` rows,
+/// stopping at the ends unless `wrap` is set
+pub fn step_selection(index: usize, len: usize, down: bool, wrap: bool) -> usize {
+    let last = len.saturating_sub(1);
+    let index = index.min(last);
+    match (down, wrap) {
+        (true, _) if index < last => index + 1,
+        (true, true) => 0,
+        (false, _) if index > 0 => index - 1,
+        (false, true) => last,
+        _ => index,
+    }
 }
 
 /// Main application state
@@ -325,6 +928,10 @@ pub struct AppState {
     pub command_history: Vec<CommandHistoryEntry>,
     pub working_dir: PathBuf,
     pub terminal_size: (u16, u16),
+    /// Ids of the actions run since macro recording started
+    pub macro_recording: Option<Vec<String>>,
+    /// Configured key bindings, for the hints in the footer and help overlay
+    pub keys: crate::core::events::KeyBindings,
 }
 
 impl AppState {
@@ -340,6 +947,27 @@ impl AppState {
             command_history: Vec::new(),
             working_dir,
             terminal_size: (80, 24),
+            macro_recording: None,
+            keys: crate::core::events::KeyBindings::default(),
+        }
+    }
+
+    /// Give the output area to the embedded shell
+    pub fn enter_terminal(&mut self) {
+        if self.mode != AppMode::Terminal {
+            self.panels.terminal.return_layout = Some(self.layout.preset);
+        }
+        self.layout.preset = LayoutPreset::TerminalFocus;
+        self.mode = AppMode::Terminal;
+    }
+
+    /// Back to the dashboard in the layout it had before the terminal opened
+    pub fn leave_terminal(&mut self) {
+        if let Some(preset) = self.panels.terminal.return_layout.take() {
+            self.layout.preset = preset;
+        }
+        if self.mode == AppMode::Terminal {
+            self.mode = AppMode::Dashboard;
         }
     }
 
@@ -354,13 +982,31 @@ impl AppState {
         self.notifications.push(notification);
     }
 
-    pub fn remove_expired_notifications(&mut self) {
+    /// Add a notification and return the change that redraws it, for updates
+    /// made from background tasks that nothing else will render
+    pub fn notify(&mut self, message: String, level: NotificationLevel) -> Option<StateChange> {
+        self.add_notification(message, level);
+        self.notifications
+            .last()
+            .map(|n| StateChange::NotificationAdded(n.id))
+    }
+
+    /// Drop notifications past their duration; errors stay until dismissed when `sticky_errors`
+    pub fn remove_expired_notifications(&mut self, sticky_errors: bool) {
         let now = chrono::Utc::now();
         self.notifications.retain(|n| {
+            if sticky_errors && n.level == NotificationLevel::Error {
+                return true;
+            }
             let elapsed = now.signed_duration_since(n.created_at).num_milliseconds() as u64;
             elapsed < n.duration_ms
         });
     }
+
+    /// Acknowledge every notification, sticky ones included
+    pub fn dismiss_notifications(&mut self) {
+        self.notifications.clear();
+    }
 }
 
 /// Reactive state changes via broadcast channel
@@ -441,6 +1087,7 @@ impl Clone for StateStore {
 mod tests {
     use super::*;
     use crate::actions::{Action, ActionCategory, ActionSource};
+    use crate::detection::{DockerService, ProjectKind, ProjectType};
 
     fn sample_action(id: &str, name: &str) -> Action {
         Action {
@@ -452,19 +1099,25 @@ mod tests {
             source: ActionSource::Detected,
             keybinding: None,
             requires_confirm: false,
+            confirm_phrase: None,
             env_required: vec![],
             working_dir: None,
+            icon: None,
+            color: None,
+            shell: None,
         }
     }
 
     #[test]
     fn action_filter_matches_and_resets_selection() {
-        let mut panel = ActionPanelState::default();
-        panel.actions = vec![
-            sample_action("1", "build"),
-            sample_action("2", "dev server"),
-            sample_action("3", "test"),
-        ];
+        let mut panel = ActionPanelState {
+            actions: vec![
+                sample_action("1", "build"),
+                sample_action("2", "dev server"),
+                sample_action("3", "test"),
+            ],
+            ..Default::default()
+        };
 
         panel.update_filter("dev".to_string());
         assert_eq!(panel.filtered_indices, vec![1]);
@@ -475,13 +1128,477 @@ mod tests {
         );
     }
 
+    #[test]
+    fn fuzzy_filter_ranks_by_relevance() {
+        let mut panel = ActionPanelState {
+            actions: vec![
+                sample_action("1", "cargo clean"),
+                sample_action("2", "cargo check"),
+                sample_action("3", "cargo run"),
+            ],
+            ..Default::default()
+        };
+
+        panel.update_filter("crun".to_string());
+        let rank = |name: &str| {
+            panel
+                .filtered_indices
+                .iter()
+                .position(|&i| panel.actions[i].name == name)
+        };
+        assert_eq!(rank("cargo run"), Some(0));
+        assert!(rank("cargo clean").is_none_or(|r| r > 0));
+        assert_eq!(
+            crate::actions::fuzzy_indices("cargo run", "crun"),
+            [0, 6, 7, 8]
+        );
+
+        // An empty filter lists everything in the original order
+        panel.update_filter(String::new());
+        assert_eq!(panel.filtered_indices, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn marked_actions_run_in_list_order_and_survive_reloads() {
+        let mut panel = ActionPanelState {
+            actions: vec![
+                sample_action("lint", "lint"),
+                sample_action("test", "test"),
+                sample_action("build", "build"),
+            ],
+            ..Default::default()
+        };
+        panel.update_filter(String::new());
+
+        panel.selected_index = 2;
+        assert_eq!(panel.toggle_marked(), 1);
+        panel.selected_index = 0;
+        assert_eq!(panel.toggle_marked(), 2);
+        panel.selected_index = 1;
+        panel.toggle_marked();
+        assert_eq!(panel.toggle_marked(), 2, "space again unmarks");
+
+        let names = |panel: &ActionPanelState| -> Vec<String> {
+            panel.marked_actions().into_iter().map(|a| a.name).collect()
+        };
+        assert_eq!(names(&panel), ["lint", "build"]);
+
+        // A re-detection that reorders actions keeps the same ones marked
+        panel.replace_actions(vec![
+            sample_action("build", "build"),
+            sample_action("dev", "dev"),
+            sample_action("lint", "lint"),
+        ]);
+        assert_eq!(names(&panel), ["build", "lint"]);
+    }
+
+    #[test]
+    fn background_notifications_announce_a_change() {
+        let store = StateStore::new(AppState::new(PathBuf::from("."), Theme::default()));
+        let mut changes = store.subscribe();
+        store.update(|s| {
+            let change = s.notify(
+                "Reclaimed port 3000".to_string(),
+                NotificationLevel::Success,
+            );
+            ((), change)
+        });
+        let id = store.read().notifications[0].id;
+        assert!(
+            matches!(changes.try_recv(), Ok(StateChange::NotificationAdded(added)) if added == id)
+        );
+    }
+
     #[test]
     fn action_filter_handles_no_matches() {
-        let mut panel = ActionPanelState::default();
-        panel.actions = vec![sample_action("1", "build")];
+        let mut panel = ActionPanelState {
+            actions: vec![sample_action("1", "build")],
+            ..Default::default()
+        };
 
         panel.update_filter("deploy".to_string());
         assert!(panel.filtered_indices.is_empty());
         assert!(panel.selected_action().is_none());
     }
+
+    #[test]
+    fn replacing_actions_keeps_selected_id() {
+        let mut panel = ActionPanelState {
+            actions: vec![
+                sample_action("1", "build"),
+                sample_action("2", "dev server"),
+                sample_action("3", "test"),
+            ],
+            ..Default::default()
+        };
+        panel.update_filter(String::new());
+        panel.selected_index = 2;
+
+        panel.replace_actions(vec![
+            sample_action("0", "lint"),
+            sample_action("1", "build"),
+            sample_action("3", "test"),
+        ]);
+        assert_eq!(panel.selected_action().map(|a| a.id.as_str()), Some("3"));
+
+        panel.replace_actions(vec![sample_action("9", "deploy")]);
+        assert_eq!(panel.selected_index, 0, "missing id falls back to the top");
+    }
+
+    fn project_with(kind: ProjectKind, services: usize) -> ProjectContext {
+        ProjectContext {
+            root: PathBuf::from("."),
+            name: "demo".to_string(),
+            types: vec![ProjectType {
+                kind,
+                confidence: 1.0,
+                primary: true,
+            }],
+            scripts: vec![],
+            env_vars: Default::default(),
+            ports: vec![],
+            docker_services: (0..services)
+                .map(|i| DockerService {
+                    name: format!("svc{}", i),
+                    image: None,
+                    ports: vec![],
+                    depends_on: vec![],
+                })
+                .collect(),
+            git_info: None,
+            advisories: vec![],
+            warnings: vec![],
+            member_manifests: vec![],
+        }
+    }
+
+    #[test]
+    fn default_layout_follows_project_shape() {
+        let rust_lib = ProjectKind::Rust {
+            workspace: false,
+            binary_count: 0,
+        };
+        assert_eq!(
+            LayoutPreset::for_project(&project_with(rust_lib.clone(), 5)),
+            LayoutPreset::Wide
+        );
+        assert_eq!(
+            LayoutPreset::for_project(&project_with(rust_lib, 0)),
+            LayoutPreset::Compact
+        );
+    }
+
+    #[test]
+    fn notes_edit_operations() {
+        let mut notes = NotesState::default();
+        for c in "héy".chars() {
+            notes.insert_char(c);
+        }
+        notes.move_left();
+        notes.newline();
+        assert_eq!(notes.lines, vec!["hé", "y"]);
+        assert_eq!((notes.cursor_row, notes.cursor_col), (1, 0));
+
+        notes.backspace();
+        assert_eq!(notes.lines, vec!["héy"]);
+        assert_eq!((notes.cursor_row, notes.cursor_col), (0, 2));
+
+        notes.backspace();
+        assert_eq!(notes.lines, vec!["hy"]);
+        assert!(notes.dirty);
+        assert_eq!(notes.text(), "hy\n");
+    }
+
+    #[test]
+    fn typed_confirmation_requires_exact_phrase() {
+        let confirm = |input: &str| AppMode::Confirm {
+            message: String::new(),
+            action_id: "custom:deploy-prod".to_string(),
+            destructive: false,
+            phrase: Some("deploy-prod".to_string()),
+            input: input.to_string(),
+        };
+
+        assert!(!confirm("").confirm_enabled());
+        assert!(!confirm("deploy").confirm_enabled());
+        assert!(!confirm("Deploy-Prod").confirm_enabled());
+        assert!(!confirm("deploy-prod ").confirm_enabled());
+        assert!(confirm("deploy-prod").confirm_enabled());
+    }
+
+    fn container(name: &str) -> ContainerInfo {
+        ContainerInfo::fixture(name, crate::integrations::docker::ContainerStatus::Running)
+    }
+
+    #[test]
+    fn shrinking_containers_clamps_selection() {
+        let mut panels = PanelStates::default();
+        panels.docker.containers = vec![container("a"), container("b"), container("c")];
+        panels.docker.selected_index = 2;
+
+        panels.docker.containers.truncate(1);
+        panels.clamp_selections();
+        assert_eq!(panels.docker.selected_index, 0);
+
+        panels.docker.containers.clear();
+        panels.clamp_selections();
+        assert_eq!(panels.docker.selected_index, 0);
+    }
+
+    #[test]
+    fn step_selection_clamps_or_wraps() {
+        assert_eq!(step_selection(2, 3, true, false), 2);
+        assert_eq!(step_selection(2, 3, true, true), 0);
+        assert_eq!(step_selection(0, 3, false, false), 0);
+        assert_eq!(step_selection(0, 3, false, true), 2);
+        assert_eq!(step_selection(1, 3, true, true), 2);
+        // A stale index past the end recovers instead of moving further out
+        assert_eq!(step_selection(7, 3, false, false), 1);
+        assert_eq!(step_selection(0, 0, true, true), 0);
+    }
+
+    #[test]
+    fn layout_cycle_skips_mode_presets() {
+        let mut preset = LayoutPreset::Standard;
+        let mut seen = Vec::new();
+        for _ in 0..4 {
+            preset = preset.cycle();
+            seen.push(preset);
+        }
+        assert_eq!(
+            seen,
+            [
+                LayoutPreset::Compact,
+                LayoutPreset::Wide,
+                LayoutPreset::Standard,
+                LayoutPreset::Compact
+            ]
+        );
+        assert_eq!(LayoutPreset::FocusMode.cycle(), LayoutPreset::Standard);
+        assert_eq!(LayoutPreset::TerminalFocus.cycle(), LayoutPreset::Standard);
+        assert_eq!(
+            LayoutPreset::from_name(LayoutPreset::Wide.name()),
+            Some(LayoutPreset::Wide)
+        );
+    }
+
+    #[test]
+    fn terminal_hands_back_the_layout_it_found() {
+        let mut state = AppState::new(PathBuf::from("."), Theme::default());
+        state.layout.preset = LayoutPreset::Wide;
+        state.enter_terminal();
+        state.enter_terminal();
+        assert_eq!(state.layout.preset, LayoutPreset::TerminalFocus);
+        assert_eq!(state.mode, AppMode::Terminal);
+        state.leave_terminal();
+        assert_eq!(state.layout.preset, LayoutPreset::Wide);
+        assert_eq!(state.mode, AppMode::Dashboard);
+    }
+
+    #[test]
+    fn error_notifications_outlive_the_expiry_window() {
+        let mut state = AppState::new("/tmp".into(), Theme::default());
+        state.add_notification("saved".to_string(), NotificationLevel::Info);
+        state.add_notification("build failed".to_string(), NotificationLevel::Error);
+        let expired = chrono::Utc::now() - chrono::Duration::seconds(10);
+        for n in &mut state.notifications {
+            n.created_at = expired;
+        }
+
+        state.remove_expired_notifications(true);
+        let levels: Vec<_> = state.notifications.iter().map(|n| n.level).collect();
+        assert_eq!(levels, [NotificationLevel::Error]);
+
+        state.remove_expired_notifications(false);
+        assert!(state.notifications.is_empty());
+    }
+
+    #[test]
+    fn rerunning_the_tail_action_replaces_its_output() {
+        let test = "script:test";
+        assert!(OutputPanelState::should_clear_before_run(
+            Some(test),
+            test,
+            true
+        ));
+        assert!(!OutputPanelState::should_clear_before_run(
+            Some("script:lint"),
+            test,
+            true
+        ));
+        assert!(!OutputPanelState::should_clear_before_run(None, test, true));
+        assert!(!OutputPanelState::should_clear_before_run(
+            Some(test),
+            test,
+            false
+        ));
+
+        let mut output = OutputPanelState::new();
+        output.begin_run("script:lint", true);
+        output.push("lint ok".to_string(), OutputStream::Stdout);
+        output.begin_run(test, true);
+        output.push("1 failed".to_string(), OutputStream::Stdout);
+        output.begin_run(test, true);
+        output.push("all passed".to_string(), OutputStream::Stdout);
+
+        let lines: Vec<&str> = output.lines.iter().map(|l| l.content.as_str()).collect();
+        assert_eq!(lines, ["lint ok", "all passed"]);
+    }
+
+    #[test]
+    fn errors_only_filter_keeps_errors_and_warnings() {
+        let mut output = OutputPanelState::new();
+        let lines = [
+            ("   Compiling orbit v0.1.0", OutputStream::Stdout),
+            ("error[E0308]: mismatched types", OutputStream::Stdout),
+            ("warning: unused variable: `x`", OutputStream::Stdout),
+            ("ERROR in ./src/index.ts", OutputStream::Stdout),
+            ("E: Unable to locate package foo", OutputStream::Stdout),
+            (
+                "test parser::tests::roundtrip ... FAILED",
+                OutputStream::Stdout,
+            ),
+            ("npm notice New minor version", OutputStream::Stderr),
+            ("Finished dev profile in 2.1s", OutputStream::Stdout),
+            ("terror-free zone: ok", OutputStream::Stdout),
+            ("Action completed", OutputStream::System),
+        ];
+        for (content, stream) in lines {
+            output.push(content.to_string(), stream);
+        }
+
+        let flagged: Vec<&str> = output
+            .lines
+            .iter()
+            .filter(|l| OutputPanelState::is_error_line(l))
+            .map(|l| l.content.as_str())
+            .collect();
+        assert_eq!(
+            flagged,
+            [
+                "error[E0308]: mismatched types",
+                "warning: unused variable: `x`",
+                "ERROR in ./src/index.ts",
+                "E: Unable to locate package foo",
+                "test parser::tests::roundtrip ... FAILED",
+                "npm notice New minor version",
+            ]
+        );
+
+        output.toggle_errors_only();
+        assert_eq!(output.visible_lines().len(), 6);
+        assert_eq!(output.lines.len(), 10);
+        output.toggle_errors_only();
+        assert_eq!(output.visible_lines().len(), 10);
+    }
+
+    #[test]
+    fn search_steps_through_matches_and_tracks_new_output() {
+        let mut output = OutputPanelState::new();
+        output.max_lines = 5;
+        for line in [
+            "Compiling",
+            "ERROR: db down",
+            "retrying",
+            "error again",
+            "ok",
+        ] {
+            output.push(line.to_string(), OutputStream::Stdout);
+        }
+
+        output.start_search();
+        assert!(!output.auto_scroll);
+        output.set_search_query("error".to_string());
+        assert_eq!(output.match_indices, [1, 3]);
+        // Nothing matches below the bottom, so the search wraps to the first match
+        assert_eq!(output.current_match, Some(0));
+        assert_eq!(output.scroll_offset, 1);
+
+        output.jump_to_match(true);
+        assert_eq!(output.scroll_offset, 3);
+        output.jump_to_match(true);
+        assert_eq!(output.scroll_offset, 1);
+        output.jump_to_match(false);
+        assert_eq!(output.scroll_offset, 3);
+
+        // The buffer rotates: the first match scrolls out and a new one arrives
+        output.push("another Error".to_string(), OutputStream::Stderr);
+        output.push("fine".to_string(), OutputStream::Stdout);
+        assert_eq!(output.match_indices, [1, 3]);
+        assert_eq!(output.scroll_offset, 1);
+        assert_eq!(output.current_match, Some(0));
+
+        assert_eq!(
+            OutputPanelState::match_ranges("Ärger: ÄRGER", "ärger"),
+            [0..6, 8..14]
+        );
+
+        output.clear_search();
+        assert!(output.auto_scroll && output.match_indices.is_empty());
+        assert_eq!(output.scroll_offset, 4);
+    }
+
+    #[test]
+    fn container_stats_history_is_capped_and_evicted() {
+        use crate::integrations::docker::ContainerStats;
+
+        let sampled = |id: &str, cpu: f64| ContainerInfo {
+            stats: Some(ContainerStats {
+                cpu_percent: cpu,
+                memory_usage_mb: 256.0,
+                ..Default::default()
+            }),
+            ..container(id)
+        };
+
+        let mut docker = DockerPanelState::default();
+        for i in 0..30 {
+            docker.set_containers(vec![sampled("db", i as f64), sampled("cache", 1.0)]);
+        }
+        let db = &docker.cpu_history["db"];
+        assert_eq!(db.len(), DockerPanelState::STATS_HISTORY_LEN);
+        assert_eq!(db.back(), Some(&29.0));
+        assert_eq!(docker.memory_history["cache"].back(), Some(&256.0));
+
+        // `cache` went away; its history goes with it
+        docker.set_containers(vec![sampled("db", 5.0)]);
+        assert!(!docker.cpu_history.contains_key("cache"));
+        assert!(!docker.memory_history.contains_key("cache"));
+        assert_eq!(docker.containers.len(), 1);
+    }
+
+    #[test]
+    fn failed_metrics_sample_keeps_previous_values() {
+        let mut metrics = MetricsPanelState::default();
+        metrics.apply_sample(None);
+        assert!(metrics.unavailable);
+        assert!(!metrics.has_sample());
+
+        metrics.apply_sample(Some(SystemMetrics {
+            cpu_percent: 42.0,
+            memory_used_mb: 4096,
+            memory_total_mb: 16384,
+            disk_used_percent: 55.0,
+        }));
+        assert!(!metrics.unavailable);
+
+        metrics.apply_sample(None);
+        assert!(metrics.unavailable);
+        assert_eq!(metrics.cpu_percent, 42.0);
+        assert_eq!(metrics.memory_total_mb, 16384);
+        assert_eq!(metrics.cpu_history, [42.0]);
+        assert_eq!(metrics.memory_history, [25.0]);
+        assert_eq!(metrics.disk_history, [55.0]);
+
+        for _ in 0..100 {
+            metrics.apply_sample(Some(SystemMetrics {
+                cpu_percent: 10.0,
+                memory_used_mb: 8192,
+                memory_total_mb: 16384,
+                disk_used_percent: 55.0,
+            }));
+        }
+        assert_eq!(metrics.memory_history.len(), 60);
+        assert_eq!(metrics.memory_history.back(), Some(&50.0));
+    }
 }
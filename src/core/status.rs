@@ -0,0 +1,149 @@
+//! Single-line project status for tmux status bars and shell prompts
+
+use std::path::Path;
+
+use crate::core::state::AppState;
+use crate::detection::ProjectDetector;
+use crate::integrations::docker::DockerClient;
+use crate::ui::theme::Theme;
+use crate::ui::widgets::header::{docker_counts, git_summary};
+
+const BOLD: &str = "1";
+const GREEN: &str = "32";
+const YELLOW: &str = "33";
+const RED: &str = "31";
+const MAGENTA: &str = "35";
+
+/// Collect just what the status line shows; Docker and port scans are optional
+/// because they dominate the run time
+pub async fn gather(
+    working_dir: &Path,
+    detector: ProjectDetector,
+    docker: bool,
+    ports: bool,
+) -> AppState {
+    let mut state = AppState::new(working_dir.to_path_buf(), Theme::default());
+
+    match detector.analyze().await {
+        Ok(project) => {
+            state.panels.ports.expected_ports = project
+                .ports
+                .iter()
+                .cloned()
+                .map(crate::integrations::ports::ExpectedPort::from)
+                .collect();
+            state.project = Some(project);
+        }
+        Err(e) => tracing::warn!("Project detection failed: {:#}", e),
+    }
+
+    if docker {
+        if let Ok(client) = DockerClient::new() {
+            state.panels.docker.containers = client.list_containers(true).await.unwrap_or_default();
+        }
+    }
+
+    if ports {
+        if let Ok(active) = crate::integrations::ports::scan_active_ports().await {
+            state.panels.ports.conflicts = crate::integrations::ports::detect_conflicts(
+                &state.panels.ports.expected_ports,
+                &active,
+            );
+            state.panels.ports.active_ports = active;
+        }
+    }
+
+    state
+}
+
+/// `name │ branch* │ 🐳 2/3 │ ⚡ 1 conflict │ 2 env missing`, with ANSI colors if `color`
+pub fn status_line(state: &AppState, color: bool) -> String {
+    let project = state.project.as_ref();
+    let mut segments: Vec<(String, &str)> = vec![(
+        project.map_or_else(|| "no project".to_string(), |p| p.name.clone()),
+        BOLD,
+    )];
+
+    if let Some(git) = project.and_then(|p| p.git_info.as_ref()) {
+        segments.push((git_summary(git), MAGENTA));
+    }
+
+    if let Some((running, total)) = docker_counts(&state.panels.docker.containers) {
+        let code = if running == total { GREEN } else { YELLOW };
+        segments.push((format!("🐳 {}/{}", running, total), code));
+    }
+
+    let conflicts = state.panels.ports.conflicts.len();
+    if conflicts > 0 {
+        let noun = if conflicts == 1 {
+            "conflict"
+        } else {
+            "conflicts"
+        };
+        segments.push((format!("⚡ {} {}", conflicts, noun), RED));
+    }
+
+    let missing = project.map_or(0, |p| p.env_vars.missing_required.len());
+    if missing > 0 {
+        segments.push((format!("{} env missing", missing), YELLOW));
+    }
+
+    segments
+        .into_iter()
+        .map(|(text, code)| {
+            if color {
+                format!("\x1b[{}m{}\x1b[0m", code, text)
+            } else {
+                text
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" │ ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::{EnvVarAnalysis, GitInfo, ProjectContext};
+    use crate::integrations::docker::{ContainerInfo, ContainerStatus};
+
+    #[test]
+    fn status_line_includes_branch_and_docker_ratio() {
+        let mut state = AppState::new("/tmp/api".into(), Theme::default());
+        state.project = Some(ProjectContext {
+            root: "/tmp/api".into(),
+            name: "api".to_string(),
+            types: vec![],
+            scripts: vec![],
+            env_vars: EnvVarAnalysis {
+                missing_required: vec!["DATABASE_URL".to_string()],
+                ..Default::default()
+            },
+            ports: vec![],
+            docker_services: vec![],
+            git_info: Some(GitInfo {
+                branch: "main".to_string(),
+                remote: None,
+                ahead: 0,
+                behind: 0,
+                dirty: true,
+                recent_files: vec![],
+            }),
+            advisories: vec![],
+            warnings: vec![],
+            member_manifests: vec![],
+        });
+        state.panels.docker.containers = vec![
+            ContainerInfo::fixture("db", ContainerStatus::Running),
+            ContainerInfo::fixture("cache", ContainerStatus::Running),
+            ContainerInfo::fixture("worker", ContainerStatus::Exited),
+        ];
+
+        let line = status_line(&state, false);
+        assert_eq!(line, "api │ main * │ 🐳 2/3 │ 1 env missing");
+        assert!(!line.contains('\n'));
+
+        let colored = status_line(&state, true);
+        assert!(colored.contains("\x1b[35mmain *\x1b[0m"));
+    }
+}
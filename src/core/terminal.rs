@@ -0,0 +1,186 @@
+//! Embedded shell for Terminal mode, running on its own PTY
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::actions::pty;
+use crate::core::state::{StateChange, StateStore};
+
+/// A login shell whose screen is mirrored into `panels.terminal`
+///
+/// The shell outlives leaving Terminal mode, so `t` returns to the same session.
+/// When it exits, the reader thread closes Terminal mode itself.
+pub struct TerminalSession {
+    process: pty::PtyProcess,
+    writer: Box<dyn Write + Send>,
+    parser: Arc<Mutex<vt100::Parser>>,
+}
+
+impl TerminalSession {
+    pub fn spawn(
+        shell: &str,
+        working_dir: &Path,
+        rows: u16,
+        cols: u16,
+        state: StateStore,
+    ) -> Result<Self> {
+        let process = pty::spawn(shell, &["-l"], working_dir, &HashMap::new(), rows, cols)?;
+        let mut reader = process.master.try_clone_reader()?;
+        let writer = process.master.take_writer()?;
+        let parser = Arc::new(Mutex::new(vt100::Parser::new(rows.max(1), cols.max(1), 0)));
+
+        let screen_parser = parser.clone();
+        std::thread::spawn(move || {
+            let mut buf = [0u8; 8192];
+            // EOF, or EIO on Linux, once the shell and everything it started are gone
+            while let Ok(n @ 1..) = reader.read(&mut buf) {
+                let screen = {
+                    let mut parser = screen_parser.lock();
+                    parser.process(&buf[..n]);
+                    parser.screen().clone()
+                };
+                state.update(|s| {
+                    s.panels.terminal.screen = Some(screen);
+                    ((), Some(StateChange::OutputAppended))
+                });
+            }
+            state.update(|s| {
+                s.panels.terminal.screen = None;
+                s.leave_terminal();
+                ((), Some(StateChange::ModeChanged(s.mode.clone())))
+            });
+        });
+
+        Ok(Self {
+            process,
+            writer,
+            parser,
+        })
+    }
+
+    pub fn is_running(&mut self) -> bool {
+        matches!(self.process.child.try_wait(), Ok(None))
+    }
+
+    /// Forward a key press to the shell; keys with no terminal encoding are dropped
+    pub fn send_key(&mut self, key: &KeyEvent) -> Result<()> {
+        let application_cursor = self.parser.lock().screen().application_cursor();
+        if let Some(bytes) = key_bytes(key, application_cursor) {
+            self.writer.write_all(&bytes)?;
+            self.writer.flush()?;
+        }
+        Ok(())
+    }
+
+    pub fn resize(&mut self, rows: u16, cols: u16) {
+        if self.parser.lock().screen().size() == (rows, cols) {
+            return;
+        }
+        if let Err(e) = self.process.master.resize(pty::size(rows, cols)) {
+            tracing::warn!("Failed to resize terminal: {}", e);
+        }
+        self.parser.lock().set_size(rows.max(1), cols.max(1));
+    }
+}
+
+impl Drop for TerminalSession {
+    fn drop(&mut self) {
+        let _ = self.process.child.kill();
+    }
+}
+
+/// The bytes an xterm sends for `key`; arrows use SS3 (`ESC O A`) when the
+/// program asked for application cursor keys
+pub fn key_bytes(key: &KeyEvent, application_cursor: bool) -> Option<Vec<u8>> {
+    let csi = |s: &str| format!("\x1b[{}", s).into_bytes();
+    let arrow = |c: char| {
+        if application_cursor {
+            format!("\x1bO{}", c).into_bytes()
+        } else {
+            format!("\x1b[{}", c).into_bytes()
+        }
+    };
+
+    let bytes = match key.code {
+        KeyCode::Char(c) if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            let byte = match c.to_ascii_lowercase() {
+                c @ 'a'..='z' => c as u8 - b'a' + 1,
+                '@' | ' ' | '2' => 0,
+                '[' | '3' => 0x1b,
+                '\\' | '4' => 0x1c,
+                ']' | '5' => 0x1d,
+                '^' | '6' => 0x1e,
+                '_' | '7' | '/' => 0x1f,
+                _ => return None,
+            };
+            vec![byte]
+        }
+        KeyCode::Char(c) => c.to_string().into_bytes(),
+        KeyCode::Enter => vec![b'\r'],
+        KeyCode::Backspace => vec![0x7f],
+        KeyCode::Tab => vec![b'\t'],
+        KeyCode::BackTab => csi("Z"),
+        KeyCode::Esc => vec![0x1b],
+        KeyCode::Up => arrow('A'),
+        KeyCode::Down => arrow('B'),
+        KeyCode::Right => arrow('C'),
+        KeyCode::Left => arrow('D'),
+        KeyCode::Home => arrow('H'),
+        KeyCode::End => arrow('F'),
+        KeyCode::Insert => csi("2~"),
+        KeyCode::Delete => csi("3~"),
+        KeyCode::PageUp => csi("5~"),
+        KeyCode::PageDown => csi("6~"),
+        KeyCode::F(n @ 1..=4) => format!("\x1bO{}", (b'P' + n - 1) as char).into_bytes(),
+        KeyCode::F(n @ 5..=12) => {
+            let code = [15, 17, 18, 19, 20, 21, 23, 24][n as usize - 5];
+            csi(&format!("{}~", code))
+        }
+        _ => return None,
+    };
+
+    // Alt sends the key prefixed with ESC
+    if key.modifiers.contains(KeyModifiers::ALT) {
+        let mut prefixed = vec![0x1b];
+        prefixed.extend(bytes);
+        Some(prefixed)
+    } else {
+        Some(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_encode_like_xterm() {
+        let key = |code, modifiers| KeyEvent::new(code, modifiers);
+        let none = KeyModifiers::NONE;
+        let bytes = |k: KeyEvent| key_bytes(&k, false);
+
+        assert_eq!(bytes(key(KeyCode::Char('é'), none)), Some("é".into()));
+        assert_eq!(
+            bytes(key(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            Some(vec![3])
+        );
+        assert_eq!(
+            bytes(key(KeyCode::Char('b'), KeyModifiers::ALT)),
+            Some(b"\x1bb".to_vec())
+        );
+        assert_eq!(bytes(key(KeyCode::Enter, none)), Some(b"\r".to_vec()));
+        assert_eq!(bytes(key(KeyCode::Up, none)), Some(b"\x1b[A".to_vec()));
+        assert_eq!(
+            key_bytes(&key(KeyCode::Up, none), true),
+            Some(b"\x1bOA".to_vec())
+        );
+        assert_eq!(bytes(key(KeyCode::F(2), none)), Some(b"\x1bOQ".to_vec()));
+        assert_eq!(bytes(key(KeyCode::F(5), none)), Some(b"\x1b[15~".to_vec()));
+        assert_eq!(bytes(key(KeyCode::CapsLock, none)), None);
+    }
+}
@@ -0,0 +1,218 @@
+//! Re-detects the project when the manifests its analyzers read change
+
+use anyhow::Result;
+use notify_debouncer_mini::notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, Debouncer};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+use crate::core::events::Event;
+use crate::detection::{ProjectContext, ProjectKind};
+
+/// Editors write a file in several steps; wait for them to settle
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+const COMPOSE_FILES: &[&str] = &[
+    "docker-compose.yml",
+    "docker-compose.yaml",
+    "compose.yml",
+    "compose.yaml",
+];
+
+/// Files worth re-detecting for, relative to the project root, based on the
+/// analyzers that matched
+///
+/// `.env` is always included since every project type reads it, as are the
+/// manifests of detected workspace packages.
+pub fn watched_files(project: &ProjectContext) -> Vec<String> {
+    let mut files: Vec<&str> = vec![".env", ".env.example", ".orbit.toml"];
+    for project_type in &project.types {
+        match &project_type.kind {
+            ProjectKind::Node { .. } => files.push("package.json"),
+            ProjectKind::Rust { .. } => files.push("Cargo.toml"),
+            ProjectKind::Python { .. } => {
+                files.extend(["pyproject.toml", "requirements.txt", "Pipfile"])
+            }
+            ProjectKind::Go { .. } => files.push("go.mod"),
+            ProjectKind::Ruby { .. } => files.extend(["Gemfile", "Rakefile"]),
+            ProjectKind::Php { .. } => files.push("composer.json"),
+            ProjectKind::Jvm { .. } => files.extend([
+                "build.gradle",
+                "build.gradle.kts",
+                "settings.gradle",
+                "settings.gradle.kts",
+                "pom.xml",
+            ]),
+            ProjectKind::OCaml => files.push("dune-project"),
+            ProjectKind::Docker { .. } => files.extend(COMPOSE_FILES),
+            _ => {}
+        }
+    }
+    if !project.docker_services.is_empty() {
+        files.extend(COMPOSE_FILES);
+    }
+    if project.root.join("Makefile").exists() {
+        files.push("Makefile");
+    }
+
+    let mut files: Vec<String> = files.into_iter().map(str::to_string).collect();
+    files.extend(project.member_manifests.iter().filter_map(|manifest| {
+        let relative = manifest.strip_prefix(&project.root).ok()?;
+        Some(relative.to_string_lossy().to_string())
+    }));
+    files.sort();
+    files.dedup();
+    files
+}
+
+/// The watched files among `paths`, relative to `root`, in order and without repeats
+fn changed_files(root: &Path, paths: &[PathBuf], watched: &[String]) -> Vec<String> {
+    let mut changed: Vec<String> = Vec::new();
+    for path in paths {
+        let Ok(name) = path
+            .strip_prefix(root)
+            .map(|p| p.to_string_lossy().to_string())
+        else {
+            continue;
+        };
+        if watched.contains(&name) && !changed.contains(&name) {
+            changed.push(name);
+        }
+    }
+    changed
+}
+
+/// Watch `root` and send one [`Event::FileChanged`] per debounced batch touching `files`
+///
+/// Directories are watched rather than each file so a newly created `.env` is seen
+/// too: the root, plus each workspace package holding a watched manifest. Watching
+/// stops when the returned debouncer is dropped.
+pub fn spawn(
+    root: &Path,
+    files: Vec<String>,
+    event_tx: mpsc::UnboundedSender<Event>,
+) -> Result<Debouncer<RecommendedWatcher>> {
+    let dirs = watched_dirs(root, &files);
+    let prefix = root.to_path_buf();
+    let mut debouncer = new_debouncer(DEBOUNCE, move |result: DebounceEventResult| {
+        let Ok(events) = result else {
+            return;
+        };
+        let paths: Vec<PathBuf> = events.into_iter().map(|e| e.path).collect();
+        let changed = changed_files(&prefix, &paths, &files);
+        if !changed.is_empty() {
+            let _ = event_tx.send(Event::FileChanged(changed.join(", ")));
+        }
+    })?;
+    for dir in dirs {
+        debouncer
+            .watcher()
+            .watch(&dir, RecursiveMode::NonRecursive)?;
+    }
+    Ok(debouncer)
+}
+
+/// The root and every directory below it that holds one of `files`
+fn watched_dirs(root: &Path, files: &[String]) -> Vec<PathBuf> {
+    let mut dirs = vec![root.to_path_buf()];
+    for file in files {
+        let Some(dir) = root.join(file).parent().map(Path::to_path_buf) else {
+            continue;
+        };
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+/// Watch the global config and send [`Event::ConfigChanged`] when it's written
+///
+/// Its directory is watched since editors often replace the file instead of writing it.
+pub fn spawn_config(
+    path: &Path,
+    event_tx: mpsc::UnboundedSender<Event>,
+) -> Result<Debouncer<RecommendedWatcher>> {
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let name = path
+        .file_name()
+        .map(|n| n.to_os_string())
+        .unwrap_or_default();
+    let mut debouncer = new_debouncer(DEBOUNCE, move |result: DebounceEventResult| {
+        let Ok(events) = result else {
+            return;
+        };
+        if events
+            .iter()
+            .any(|e| e.path.file_name() == Some(name.as_os_str()))
+        {
+            let _ = event_tx.send(Event::ConfigChanged);
+        }
+    })?;
+    debouncer
+        .watcher()
+        .watch(dir, RecursiveMode::NonRecursive)?;
+    Ok(debouncer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::{EnvVarAnalysis, PackageManager, ProjectType};
+
+    #[test]
+    fn watches_the_manifests_of_matched_analyzers() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Makefile"), "dev:\n\tnpm run dev\n").unwrap();
+        let project = ProjectContext {
+            root: dir.path().to_path_buf(),
+            name: "web".to_string(),
+            types: vec![ProjectType {
+                kind: ProjectKind::Node {
+                    package_manager: PackageManager::Pnpm,
+                    framework: None,
+                },
+                confidence: 1.0,
+                primary: true,
+            }],
+            scripts: vec![],
+            env_vars: EnvVarAnalysis::default(),
+            ports: vec![],
+            docker_services: vec![],
+            git_info: None,
+            advisories: vec![],
+            warnings: vec![],
+            member_manifests: vec![dir.path().join("packages/web/package.json")],
+        };
+
+        let files = watched_files(&project);
+        assert_eq!(
+            files,
+            [
+                ".env",
+                ".env.example",
+                ".orbit.toml",
+                "Makefile",
+                "package.json",
+                "packages/web/package.json"
+            ]
+        );
+        assert_eq!(
+            watched_dirs(dir.path(), &files),
+            [dir.path().to_path_buf(), dir.path().join("packages/web")]
+        );
+
+        let paths = [
+            dir.path().join("package.json"),
+            dir.path().join("README.md"),
+            dir.path().join("packages/web/package.json"),
+            dir.path().join(".env"),
+            dir.path().join("package.json"),
+        ];
+        assert_eq!(
+            changed_files(dir.path(), &paths, &files),
+            ["package.json", "packages/web/package.json", ".env"]
+        );
+    }
+}
@@ -0,0 +1,99 @@
+//! Bazel and Buck2 build system analyzer
+
+use anyhow::Result;
+use std::path::Path;
+
+use super::AnalyzerResult;
+use crate::detection::{DiscoveredScript, ProjectKind, ScriptCategory, ScriptSource};
+
+/// Files that mark a Bazel workspace root
+const BAZEL_ROOT_FILES: &[&str] = &["MODULE.bazel", "WORKSPACE", "WORKSPACE.bazel"];
+
+/// Files that mark a Buck2 project root
+const BUCK_ROOT_FILES: &[&str] = &[".buckconfig", "BUCK"];
+
+pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
+    let has_workspace = BAZEL_ROOT_FILES.iter().any(|f| root.join(f).exists());
+    // A bare BUILD file is also used by Pants and others, so it counts for less
+    let has_build = root.join("BUILD.bazel").exists() || root.join("BUILD").exists();
+    let has_buck = BUCK_ROOT_FILES.iter().any(|f| root.join(f).exists());
+
+    if !has_workspace && !has_build && !has_buck {
+        return Ok(None);
+    }
+
+    let bazel = has_workspace || (has_build && !has_buck);
+    let confidence = if has_workspace || has_buck { 0.9 } else { 0.5 };
+    let mut result = AnalyzerResult::new(ProjectKind::Bazel { buck2: !bazel }, confidence);
+
+    if bazel {
+        push_target_scripts(&mut result, "bazel");
+        result.scripts.push(DiscoveredScript {
+            name: "bazel run".to_string(),
+            command: "bazel run //...".to_string(),
+            source: ScriptSource::Detected,
+            category: ScriptCategory::Dev,
+            description: Some("Run a target (replace //... with its label)".to_string()),
+            ports: vec![],
+            env_required: vec![],
+            working_dir: None,
+        });
+    }
+    if has_buck {
+        push_target_scripts(&mut result, "buck2");
+    }
+
+    Ok(Some(result))
+}
+
+/// `<tool> build //...` and `<tool> test //...`
+fn push_target_scripts(result: &mut AnalyzerResult, tool: &str) {
+    result.scripts.push(DiscoveredScript {
+        name: format!("{} build", tool),
+        command: format!("{} build //...", tool),
+        source: ScriptSource::Detected,
+        category: ScriptCategory::Build,
+        description: Some("Build every target".to_string()),
+        ports: vec![],
+        env_required: vec![],
+        working_dir: None,
+    });
+    result.scripts.push(DiscoveredScript {
+        name: format!("{} test", tool),
+        command: format!("{} test //...", tool),
+        source: ScriptSource::Detected,
+        category: ScriptCategory::Test,
+        description: Some("Run every test target".to_string()),
+        ports: vec![],
+        env_required: vec![],
+        working_dir: None,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn workspace_yields_bazel_build_test_and_run() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("WORKSPACE"),
+            "workspace(name = \"monorepo\")\n",
+        )
+        .unwrap();
+
+        let result = analyze(dir.path())
+            .await
+            .expect("analyze should succeed")
+            .expect("should detect bazel workspace");
+
+        assert_eq!(result.project_kind, ProjectKind::Bazel { buck2: false });
+        let commands: Vec<&str> = result.scripts.iter().map(|s| s.command.as_str()).collect();
+        assert_eq!(
+            commands,
+            ["bazel build //...", "bazel test //...", "bazel run //..."]
+        );
+    }
+}
@@ -1,13 +1,22 @@
 //! Docker project analyzer
 
 use anyhow::Result;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use super::AnalyzerResult;
 use crate::detection::{
     DiscoveredScript, DockerService, ExpectedPort, ProjectKind, ScriptCategory, ScriptSource,
 };
 
+/// Directories that bloat a build context when `.dockerignore` doesn't exclude them
+const HEAVY_DIRS: &[&str] = &["node_modules", ".git", "target", ".venv"];
+
+/// Size above which an unignored heavy directory is worth a warning
+const LARGE_CONTEXT_BYTES: u64 = 50 * 1024 * 1024;
+
+/// Stop sizing a directory after this many entries so huge trees don't stall detection
+const MAX_WALK_ENTRIES: usize = 10_000;
+
 pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
     let compose_files = [
         "docker-compose.yml",
@@ -54,25 +63,15 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
                     .and_then(|i| i.as_str())
                     .map(String::from);
 
+                // Container-only ports get a random host port, so there's nothing to expect
                 let ports: Vec<(u16, u16)> = config
                     .get("ports")
                     .and_then(|p| p.as_sequence())
-                    .map(|seq| {
-                        seq.iter()
-                            .filter_map(|p| {
-                                let s = p.as_str()?;
-                                let parts: Vec<&str> = s.split(':').collect();
-                                if parts.len() >= 2 {
-                                    let host = parts[0].parse().ok()?;
-                                    let container = parts[1].split('/').next()?.parse().ok()?;
-                                    Some((host, container))
-                                } else {
-                                    None
-                                }
-                            })
-                            .collect()
-                    })
-                    .unwrap_or_default();
+                    .into_iter()
+                    .flatten()
+                    .flat_map(parse_compose_port)
+                    .filter_map(|p| Some((p.host?, p.container)))
+                    .collect();
 
                 // Add to expected ports
                 for (host_port, _) in &ports {
@@ -135,6 +134,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
             description: Some("Start all services".to_string()),
             ports: vec![],
             env_required: vec![],
+            working_dir: None,
         });
 
         result.scripts.push(DiscoveredScript {
@@ -145,6 +145,18 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
             description: Some("Stop all services".to_string()),
             ports: vec![],
             env_required: vec![],
+            working_dir: None,
+        });
+
+        result.scripts.push(DiscoveredScript {
+            name: "compose down --volumes".to_string(),
+            command: "docker compose down --volumes".to_string(),
+            source: ScriptSource::DockerCompose,
+            category: ScriptCategory::Docker,
+            description: Some("Stop services and delete their volumes".to_string()),
+            ports: vec![],
+            env_required: vec![],
+            working_dir: None,
         });
 
         result.scripts.push(DiscoveredScript {
@@ -155,6 +167,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
             description: Some("Follow logs".to_string()),
             ports: vec![],
             env_required: vec![],
+            working_dir: None,
         });
 
         result.scripts.push(DiscoveredScript {
@@ -165,6 +178,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
             description: Some("List containers".to_string()),
             ports: vec![],
             env_required: vec![],
+            working_dir: None,
         });
 
         result.scripts.push(DiscoveredScript {
@@ -175,6 +189,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
             description: Some("Restart all services".to_string()),
             ports: vec![],
             env_required: vec![],
+            working_dir: None,
         });
 
         result.scripts.push(DiscoveredScript {
@@ -185,6 +200,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
             description: Some("Build images".to_string()),
             ports: vec![],
             env_required: vec![],
+            working_dir: None,
         });
     }
 
@@ -197,8 +213,241 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
             description: Some("Build Docker image".to_string()),
             ports: vec![],
             env_required: vec![],
+            working_dir: None,
         });
+
+        result
+            .advisories
+            .extend(build_context_advisories(root).await);
     }
 
     Ok(Some(result))
 }
+
+/// Warnings for large directories that `docker build .` would upload
+async fn build_context_advisories(root: &Path) -> Vec<String> {
+    let dockerignore = tokio::fs::read_to_string(root.join(".dockerignore"))
+        .await
+        .ok();
+    let mut advisories = Vec::new();
+
+    for dir in HEAVY_DIRS {
+        let path = root.join(dir);
+        if !path.is_dir() || dockerignore.as_deref().is_some_and(|c| ignores(c, dir)) {
+            continue;
+        }
+
+        let Ok((bytes, truncated)) = tokio::task::spawn_blocking(move || dir_size(path)).await
+        else {
+            continue;
+        };
+        if bytes < LARGE_CONTEXT_BYTES && !truncated {
+            continue;
+        }
+
+        let size = format!(
+            "{}{} MB",
+            bytes / (1024 * 1024),
+            if truncated { "+" } else { "" }
+        );
+        advisories.push(match dockerignore {
+            None => format!(
+                "No .dockerignore: Docker build context includes {} ({})",
+                dir, size
+            ),
+            Some(_) => format!(
+                ".dockerignore doesn't exclude {} ({}) from the Docker build context",
+                dir, size
+            ),
+        });
+    }
+
+    advisories
+}
+
+/// One container port from a compose `ports:` entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ComposePort {
+    /// `None` when compose picks an ephemeral host port
+    host: Option<u16>,
+    container: u16,
+}
+
+/// Every port an entry in a compose `ports:` list maps
+///
+/// Handles the short syntax (`"3000"`, `"8080:80"`, `"127.0.0.1:8080:80/tcp"`,
+/// `"[::1]:8080:80"`, ranges like `"9000-9001:9000-9001"`) and the long syntax
+/// (`target`/`published` mappings).
+fn parse_compose_port(entry: &serde_yaml::Value) -> Vec<ComposePort> {
+    if let Some(port) = entry.as_u64() {
+        return u16::try_from(port)
+            .map(|container| {
+                vec![ComposePort {
+                    host: None,
+                    container,
+                }]
+            })
+            .unwrap_or_default();
+    }
+
+    if let Some(short) = entry.as_str() {
+        let spec = short.split('/').next().unwrap_or_default();
+        // rsplitn keeps an IPv4 or bracketed IPv6 host address in the last piece
+        let mut parts = spec.rsplitn(3, ':');
+        let container = parts.next().unwrap_or_default();
+        let host = parts.next().unwrap_or_default();
+        return pair_ranges(host, container);
+    }
+
+    let as_port_string = |key: &str| match entry.get(key) {
+        Some(serde_yaml::Value::Number(n)) => n.to_string(),
+        Some(serde_yaml::Value::String(s)) => s.clone(),
+        _ => String::new(),
+    };
+    pair_ranges(&as_port_string("published"), &as_port_string("target"))
+}
+
+/// Pair a host port (or range, or empty) with a container port (or range)
+fn pair_ranges(host: &str, container: &str) -> Vec<ComposePort> {
+    let range = |spec: &str| -> Option<(u16, u16)> {
+        let spec = spec.trim();
+        match spec.split_once('-') {
+            Some((start, end)) => Some((start.parse().ok()?, end.parse().ok()?)),
+            None => spec.parse().ok().map(|port| (port, port)),
+        }
+    };
+
+    let Some((first, last)) = range(container) else {
+        return Vec::new();
+    };
+    let host_start = range(host).map(|(start, _)| start);
+    (first..=last)
+        .enumerate()
+        .map(|(offset, container)| ComposePort {
+            host: host_start.and_then(|start| start.checked_add(offset as u16)),
+            container,
+        })
+        .collect()
+}
+
+/// Whether a `.dockerignore` excludes the top-level directory `dir`
+fn ignores(dockerignore: &str, dir: &str) -> bool {
+    dockerignore
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#') && !l.starts_with('!'))
+        .map(|l| {
+            l.trim_start_matches("**/")
+                .trim_start_matches('/')
+                .trim_end_matches("/**")
+                .trim_end_matches('/')
+        })
+        .any(|pattern| pattern == dir || pattern == "*")
+}
+
+/// Total file size under `dir`, and whether the walk stopped at [`MAX_WALK_ENTRIES`]
+fn dir_size(dir: PathBuf) -> (u64, bool) {
+    let mut bytes = 0;
+    let mut visited = 0;
+    let mut pending = vec![dir];
+
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            visited += 1;
+            if visited > MAX_WALK_ENTRIES {
+                return (bytes, true);
+            }
+            // symlink_metadata so links out of the tree aren't followed
+            let Ok(meta) = entry.path().symlink_metadata() else {
+                continue;
+            };
+            if meta.is_dir() {
+                pending.push(entry.path());
+            } else {
+                bytes += meta.len();
+            }
+        }
+    }
+
+    (bytes, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn flags_large_node_modules_without_dockerignore() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("Dockerfile"), "FROM node:20\nCOPY . .\n").unwrap();
+        let pkg = dir.path().join("node_modules").join("huge-dep");
+        std::fs::create_dir_all(&pkg).unwrap();
+        // Sparse file: reports its length without writing the bytes
+        std::fs::File::create(pkg.join("bundle.js"))
+            .unwrap()
+            .set_len(LARGE_CONTEXT_BYTES + 1)
+            .unwrap();
+
+        let result = analyze(dir.path()).await.unwrap().unwrap();
+        assert!(result
+            .advisories
+            .iter()
+            .any(|a| a.starts_with("No .dockerignore") && a.contains("node_modules")));
+
+        std::fs::write(dir.path().join(".dockerignore"), "# deps\n/node_modules/\n").unwrap();
+        let result = analyze(dir.path()).await.unwrap().unwrap();
+        assert!(result.advisories.is_empty());
+    }
+
+    fn ports(yaml: &str) -> Vec<ComposePort> {
+        let value: serde_yaml::Value = serde_yaml::from_str(yaml).unwrap();
+        parse_compose_port(&value)
+    }
+
+    fn published(host: u16, container: u16) -> ComposePort {
+        ComposePort {
+            host: Some(host),
+            container,
+        }
+    }
+
+    #[test]
+    fn parses_short_compose_port_forms() {
+        assert_eq!(ports(r#""8080:80""#), [published(8080, 80)]);
+        assert_eq!(ports(r#""127.0.0.1:8080:80/tcp""#), [published(8080, 80)]);
+        assert_eq!(ports(r#""[::1]:5432:5432""#), [published(5432, 5432)]);
+        assert_eq!(
+            ports(r#""9000-9001:8000-8001""#),
+            [published(9000, 8000), published(9001, 8001)]
+        );
+
+        let ephemeral = ComposePort {
+            host: None,
+            container: 3000,
+        };
+        assert_eq!(ports(r#""3000""#), [ephemeral]);
+        assert_eq!(ports("3000"), [ephemeral]);
+        assert_eq!(ports(r#""127.0.0.1::3000""#), [ephemeral]);
+    }
+
+    #[test]
+    fn parses_long_compose_port_mapping() {
+        let long = "target: 80\npublished: 8080\nhost_ip: 127.0.0.1\nprotocol: tcp\n";
+        assert_eq!(ports(long), [published(8080, 80)]);
+
+        let quoted = "target: 80\npublished: \"8080\"\n";
+        assert_eq!(ports(quoted), [published(8080, 80)]);
+
+        assert_eq!(
+            ports("target: 6379\n"),
+            [ComposePort {
+                host: None,
+                container: 6379,
+            }]
+        );
+    }
+}
@@ -3,40 +3,107 @@
 use anyhow::Result;
 use std::path::Path;
 
+use crate::detection::EnvVarSpec;
+use crate::secrets::parse_dotenv;
+
+const EXAMPLE_FILE: &str = ".env.example";
+
 /// Result of environment analysis
 pub struct EnvAnalysisResult {
     pub dotenv_vars: Vec<String>,
+    /// Keys listed in `.env.example`, which the project expects to be set
+    pub documented: Vec<EnvVarSpec>,
+    /// Keys in `.env` that `.env.example` doesn't list
+    pub undocumented: Vec<String>,
 }
 
 pub async fn analyze(root: &Path) -> Result<EnvAnalysisResult> {
     let mut dotenv_vars = Vec::new();
+    let mut local_keys = Vec::new();
 
     // Check for various .env files
-    let env_files = [".env", ".env.local", ".env.development", ".env.example"];
+    let env_files = [".env", ".env.local", ".env.development"];
 
     for env_file in env_files {
-        let path = root.join(env_file);
-        if path.exists() {
-            if let Ok(content) = tokio::fs::read_to_string(&path).await {
-                for line in content.lines() {
-                    let line = line.trim();
-
-                    // Skip comments and empty lines
-                    if line.is_empty() || line.starts_with('#') {
-                        continue;
-                    }
-
-                    // Extract variable name
-                    if let Some(eq_pos) = line.find('=') {
-                        let var_name = line[..eq_pos].trim().to_string();
-                        if !var_name.is_empty() && !dotenv_vars.contains(&var_name) {
-                            dotenv_vars.push(var_name);
-                        }
-                    }
-                }
+        let Ok(content) = tokio::fs::read_to_string(root.join(env_file)).await else {
+            continue;
+        };
+        for (key, _) in parse_dotenv(&content) {
+            if key.is_empty() {
+                continue;
+            }
+            if env_file == ".env" && !local_keys.contains(&key) {
+                local_keys.push(key.clone());
+            }
+            if !dotenv_vars.contains(&key) {
+                dotenv_vars.push(key);
             }
         }
     }
 
-    Ok(EnvAnalysisResult { dotenv_vars })
+    let mut documented = Vec::new();
+    let mut undocumented = Vec::new();
+    if let Ok(example) = tokio::fs::read_to_string(root.join(EXAMPLE_FILE)).await {
+        for (key, value) in parse_dotenv(&example) {
+            let value = (!value.is_empty()).then_some(value);
+            add_env_var(&mut documented, &key, EXAMPLE_FILE, value);
+        }
+        undocumented = local_keys
+            .into_iter()
+            .filter(|key| !documented.iter().any(|spec| &spec.name == key))
+            .collect();
+    }
+
+    Ok(EnvAnalysisResult {
+        dotenv_vars,
+        documented,
+        undocumented,
+    })
+}
+
+/// Add `name` to `specs` unless it's already there; secret-looking example values are dropped
+pub fn add_env_var(specs: &mut Vec<EnvVarSpec>, name: &str, source: &str, example: Option<String>) {
+    if specs.iter().any(|spec| spec.name == name) {
+        return;
+    }
+    let is_secret = crate::secrets::SecretMatcher::default().is_secret(name);
+    specs.push(EnvVarSpec {
+        name: name.to_string(),
+        description: None,
+        source: source.to_string(),
+        example_value: example.filter(|_| !is_secret),
+        is_secret,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn example_file_is_diffed_against_dotenv() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(".env.example"),
+            "# Copy to .env\nDATABASE_URL=postgres://localhost/app\nexport API_KEY=\"\"\nPORT=3000\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join(".env"),
+            "DATABASE_URL='postgres://db/app'\nDEBUG_SQL=1\n",
+        )
+        .unwrap();
+
+        let result = analyze(dir.path()).await.unwrap();
+        assert_eq!(result.dotenv_vars, ["DATABASE_URL", "DEBUG_SQL"]);
+        assert_eq!(result.undocumented, ["DEBUG_SQL"]);
+
+        let names: Vec<&str> = result.documented.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["DATABASE_URL", "API_KEY", "PORT"]);
+        // Example values are kept for display unless the key looks secret
+        assert!(result.documented[0].is_secret);
+        assert_eq!(result.documented[0].example_value, None);
+        assert_eq!(result.documented[2].example_value.as_deref(), Some("3000"));
+    }
 }
@@ -9,6 +9,11 @@ use crate::detection::{DiscoveredScript, ProjectKind, ScriptCategory, ScriptSour
 pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
     let mut scripts = Vec::new();
 
+    // Parse cargo-make tasks (Makefile.toml is TOML, not a classic Makefile)
+    if let Some(cargo_make_scripts) = parse_cargo_make(root).await? {
+        scripts.extend(cargo_make_scripts);
+    }
+
     // Parse Makefile
     if let Some(makefile_scripts) = parse_makefile(root).await? {
         scripts.extend(makefile_scripts);
@@ -35,6 +40,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
                 description: None,
                 ports: vec![],
                 env_required: vec![],
+                working_dir: None,
             });
         }
     }
@@ -55,6 +61,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
                             description: None,
                             ports: vec![],
                             env_required: vec![],
+                            working_dir: None,
                         });
                     }
                 }
@@ -82,41 +89,64 @@ async fn parse_makefile(root: &Path) -> Result<Option<Vec<DiscoveredScript>>> {
     };
 
     let content = tokio::fs::read_to_string(&makefile_path).await?;
-    let mut scripts = Vec::new();
+    let scripts: Vec<DiscoveredScript> = makefile_targets(&content)
+        .into_iter()
+        .map(|target| DiscoveredScript {
+            command: format!("make {}", target),
+            source: ScriptSource::Makefile,
+            category: categorize_task(&target),
+            name: target,
+            description: None,
+            ports: vec![],
+            env_required: vec![],
+            working_dir: None,
+        })
+        .collect();
 
-    for line in content.lines() {
-        // Match target definitions like "target:" or "target: deps"
-        if let Some(target) = line.strip_suffix(':').or_else(|| {
-            line.split(':')
-                .next()
-                .filter(|s| !s.contains('\t') && !s.starts_with('#') && !s.starts_with('.'))
-        }) {
-            let target = target.trim();
-
-            // Skip internal targets and empty lines
-            if target.is_empty()
-                || target.starts_with('.')
-                || target.starts_with('#')
-                || target.contains(' ')
-                || target.contains('$')
-            {
-                continue;
-            }
+    if scripts.is_empty() {
+        return Ok(None);
+    }
 
-            let category = categorize_make_target(target);
+    Ok(Some(scripts))
+}
 
-            scripts.push(DiscoveredScript {
-                name: target.to_string(),
-                command: format!("make {}", target),
-                source: ScriptSource::Makefile,
-                category,
-                description: None,
-                ports: vec![],
-                env_required: vec![],
-            });
-        }
+async fn parse_cargo_make(root: &Path) -> Result<Option<Vec<DiscoveredScript>>> {
+    let path = root.join("Makefile.toml");
+    if !path.exists() {
+        return Ok(None);
     }
 
+    let content = tokio::fs::read_to_string(&path).await?;
+    let toml: toml::Value = content.parse()?;
+
+    let Some(tasks) = toml.get("tasks").and_then(|t| t.as_table()) else {
+        return Ok(None);
+    };
+
+    let scripts: Vec<DiscoveredScript> = tasks
+        .iter()
+        .filter(|(_, task)| {
+            // Private tasks are implementation details of other tasks
+            !task
+                .get("private")
+                .and_then(|p| p.as_bool())
+                .unwrap_or(false)
+        })
+        .map(|(name, task)| DiscoveredScript {
+            name: format!("make {}", name),
+            command: format!("cargo make {}", name),
+            source: ScriptSource::Makefile,
+            category: categorize_task(name),
+            description: task
+                .get("description")
+                .and_then(|d| d.as_str())
+                .map(str::to_string),
+            ports: vec![],
+            env_required: vec![],
+            working_dir: None,
+        })
+        .collect();
+
     if scripts.is_empty() {
         return Ok(None);
     }
@@ -124,7 +154,48 @@ async fn parse_makefile(root: &Path) -> Result<Option<Vec<DiscoveredScript>>> {
     Ok(Some(scripts))
 }
 
-fn categorize_make_target(target: &str) -> ScriptCategory {
+/// Runnable targets declared in a Makefile, in order and without duplicates
+///
+/// Skips recipes, variable assignments (`=`, `:=`, `?=`, `+=`), pattern rules (`%.o:`)
+/// and special targets (`.PHONY:`). Double-colon rules (`target::`) count like `target:`.
+fn makefile_targets(content: &str) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    let mut targets = Vec::new();
+
+    for line in content.lines() {
+        if line.starts_with('\t') {
+            continue;
+        }
+        let line = line.split('#').next().unwrap_or_default();
+        if line.contains('=') {
+            continue;
+        }
+        let Some((head, _)) = line.split_once(':') else {
+            continue;
+        };
+        if head.contains('%') {
+            continue;
+        }
+        let Some(target) = head.split_whitespace().next() else {
+            continue;
+        };
+        if is_make_identifier(target) && seen.insert(target.to_string()) {
+            targets.push(target.to_string());
+        }
+    }
+
+    targets
+}
+
+fn is_make_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '/'))
+}
+
+pub(super) fn categorize_task(target: &str) -> ScriptCategory {
     let target_lower = target.to_lowercase();
 
     if target_lower.contains("dev")
@@ -194,4 +265,55 @@ lint:
         assert!(scripts.contains(&("build".to_string(), ScriptCategory::Build)));
         assert!(scripts.contains(&("lint".to_string(), ScriptCategory::Lint)));
     }
+
+    #[tokio::test]
+    async fn parses_cargo_make_tasks() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("Makefile.toml"),
+            r#"
+[tasks.ci]
+description = "Run the full CI pipeline"
+dependencies = ["fmt", "test"]
+
+[tasks.internal]
+private = true
+"#,
+        )
+        .unwrap();
+
+        let result = analyze(dir.path())
+            .await
+            .expect("analyze should succeed")
+            .expect("should detect cargo-make tasks");
+
+        let ci = result
+            .scripts
+            .iter()
+            .find(|s| s.command == "cargo make ci")
+            .expect("ci task present");
+        assert_eq!(ci.description.as_deref(), Some("Run the full CI pipeline"));
+        assert!(!result
+            .scripts
+            .iter()
+            .any(|s| s.command.contains("internal")));
+    }
+
+    #[test]
+    fn makefile_skips_assignments_and_pattern_rules() {
+        let content = "\
+CC := gcc
+CFLAGS ?= -O2
+SRC = main.c
+%.o: %.c
+\t$(CC) -c $< -o $@
+.PHONY: build
+build: main.o
+\t$(CC) main.o -o app
+clean::
+\trm -f *.o
+build: extra.o
+";
+        assert_eq!(makefile_targets(content), vec!["build", "clean"]);
+    }
 }
@@ -4,7 +4,7 @@ use anyhow::Result;
 use std::path::Path;
 use tokio::process::Command;
 
-use crate::detection::GitInfo;
+use crate::detection::{DiscoveredScript, GitInfo, RecentFile, ScriptCategory, ScriptSource};
 
 pub async fn analyze(root: &Path) -> Result<Option<GitInfo>> {
     let git_dir = root.join(".git");
@@ -12,25 +12,35 @@ pub async fn analyze(root: &Path) -> Result<Option<GitInfo>> {
         return Ok(None);
     }
 
-    // Get current branch
-    let branch_output = Command::new("git")
-        .args(["branch", "--show-current"])
+    // One status call covers branch, upstream and dirtiness; skipping untracked
+    // files keeps it fast on large repos, and optional locks are off so polling
+    // never contends with the user's own git commands
+    let output = Command::new("git")
+        .args([
+            "status",
+            "--porcelain=v2",
+            "--branch",
+            "--untracked-files=no",
+            "--ignore-submodules=dirty",
+        ])
+        .env("GIT_OPTIONAL_LOCKS", "0")
         .current_dir(root)
         .output()
-        .await?;
+        .await;
 
-    // Check if git command succeeded
-    if !branch_output.status.success() {
+    // A missing git binary or a broken repo just means no git info
+    let Some(output) = output.ok().filter(|o| o.status.success()) else {
         return Ok(None);
-    }
-
-    let branch = String::from_utf8_lossy(&branch_output.stdout)
-        .trim()
-        .to_string();
+    };
+    let status = parse_status(&String::from_utf8_lossy(&output.stdout));
 
-    // Get remote URL
+    let remote_name = status
+        .upstream
+        .as_deref()
+        .and_then(|upstream| upstream.split_once('/'))
+        .map_or("origin", |(remote, _)| remote);
     let remote_output = Command::new("git")
-        .args(["remote", "get-url", "origin"])
+        .args(["remote", "get-url", remote_name])
         .current_dir(root)
         .output()
         .await;
@@ -40,53 +50,218 @@ pub async fn analyze(root: &Path) -> Result<Option<GitInfo>> {
         .filter(|o| o.status.success())
         .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string());
 
-    // Get ahead/behind counts
-    let status_output = Command::new("git")
-        .args(["status", "-sb"])
-        .current_dir(root)
-        .output()
-        .await?;
-
-    let status_line = String::from_utf8_lossy(&status_output.stdout);
-    let (ahead, behind) = parse_ahead_behind(&status_line);
-
-    // Check if dirty
-    let dirty_output = Command::new("git")
-        .args(["status", "--porcelain"])
-        .current_dir(root)
-        .output()
-        .await?;
-
-    let dirty = !dirty_output.stdout.is_empty();
+    let recent_files = recent_files(root, RECENT_FILES_LIMIT).await;
 
     Ok(Some(GitInfo {
-        branch,
+        branch: status.branch,
         remote,
-        ahead,
-        behind,
-        dirty,
+        ahead: status.ahead,
+        behind: status.behind,
+        dirty: status.dirty,
+        recent_files,
     }))
 }
 
-fn parse_ahead_behind(status: &str) -> (u32, u32) {
-    let mut ahead = 0;
-    let mut behind = 0;
-
-    if let Some(line) = status.lines().next() {
-        // Parse patterns like [ahead 2, behind 1] or [ahead 2] or [behind 1]
-        if let Some(bracket_start) = line.find('[') {
-            if let Some(bracket_end) = line.find(']') {
-                let bracket_content = &line[bracket_start + 1..bracket_end];
-                for part in bracket_content.split(", ") {
-                    if let Some(num) = part.strip_prefix("ahead ") {
-                        ahead = num.parse().unwrap_or(0);
-                    } else if let Some(num) = part.strip_prefix("behind ") {
-                        behind = num.parse().unwrap_or(0);
+/// What `git status --porcelain=v2 --branch` reports about the current branch
+#[derive(Debug, Default, PartialEq)]
+struct BranchStatus {
+    /// Empty when HEAD is detached
+    branch: String,
+    /// e.g. `origin/main`
+    upstream: Option<String>,
+    ahead: u32,
+    behind: u32,
+    /// Any staged, unstaged or conflicted change to a tracked file
+    dirty: bool,
+}
+
+fn parse_status(output: &str) -> BranchStatus {
+    let mut status = BranchStatus::default();
+    for line in output.lines() {
+        if let Some(header) = line.strip_prefix("# ") {
+            let (key, value) = header.split_once(' ').unwrap_or((header, ""));
+            match key {
+                "branch.head" if value != "(detached)" => status.branch = value.to_string(),
+                "branch.upstream" => status.upstream = Some(value.to_string()),
+                "branch.ab" => {
+                    for part in value.split_whitespace() {
+                        if let Some(n) = part.strip_prefix('+') {
+                            status.ahead = n.parse().unwrap_or(0);
+                        } else if let Some(n) = part.strip_prefix('-') {
+                            status.behind = n.parse().unwrap_or(0);
+                        }
                     }
                 }
+                _ => {}
             }
+        } else if line.starts_with("1 ") || line.starts_with("2 ") || line.starts_with("u ") {
+            status.dirty = true;
         }
     }
+    status
+}
+
+/// Everyday git commands, offered when nothing else in the repo provides actions
+pub fn fallback_scripts() -> Vec<DiscoveredScript> {
+    [
+        ("git status", "git status", "Show the working tree status"),
+        (
+            "git pull",
+            "git pull",
+            "Fetch and integrate the upstream branch",
+        ),
+        ("git push", "git push", "Push the current branch"),
+        ("git fetch", "git fetch --all --prune", "Fetch every remote"),
+        (
+            "git log",
+            "git log --oneline -20",
+            "Show the last 20 commits",
+        ),
+    ]
+    .into_iter()
+    .map(|(name, command, description)| DiscoveredScript {
+        name: name.to_string(),
+        command: command.to_string(),
+        source: ScriptSource::Detected,
+        category: ScriptCategory::Utility,
+        description: Some(description.to_string()),
+        ports: vec![],
+        env_required: vec![],
+        working_dir: None,
+    })
+    .collect()
+}
+
+/// How many recently touched files to surface
+const RECENT_FILES_LIMIT: usize = 10;
+
+/// Files under `root` touched in recent commits that still exist, newest mtime first
+async fn recent_files(root: &Path, limit: usize) -> Vec<RecentFile> {
+    // `--relative` reports paths from `root` rather than the repository top level
+    let output = Command::new("git")
+        .args([
+            "log",
+            "--name-only",
+            "--relative",
+            "--pretty=format:",
+            "-n",
+            "20",
+        ])
+        .current_dir(root)
+        .output()
+        .await;
 
-    (ahead, behind)
+    let Some(output) = output.ok().filter(|o| o.status.success()) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<RecentFile> = parse_recent_files(&String::from_utf8_lossy(&output.stdout))
+        .into_iter()
+        .filter_map(|path| {
+            let modified = std::fs::metadata(root.join(&path)).ok()?.modified().ok()?;
+            Some(RecentFile {
+                path,
+                modified: Some(modified.into()),
+            })
+        })
+        .collect();
+
+    files.sort_by_key(|f| std::cmp::Reverse(f.modified));
+    files.truncate(limit);
+    files
+}
+
+/// Parse `git log --name-only --pretty=format:` output into unique paths, most recent first
+fn parse_recent_files(output: &str) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .filter(|line| seen.insert(*line))
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_recent_files_deduped_in_order() {
+        let output = "src/main.rs\nsrc/app.rs\n\nREADME.md\nsrc/main.rs\n\nCargo.toml\n";
+        assert_eq!(
+            parse_recent_files(output),
+            vec!["src/main.rs", "src/app.rs", "README.md", "Cargo.toml"]
+        );
+    }
+
+    #[tokio::test]
+    async fn recent_files_are_relative_to_the_working_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .args([
+                    "-c",
+                    "user.name=orbit",
+                    "-c",
+                    "user.email=orbit@example.com",
+                ])
+                .args(args)
+                .current_dir(dir.path())
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {:?} failed", args);
+        };
+        std::fs::create_dir(dir.path().join("app")).unwrap();
+        std::fs::write(dir.path().join("app/main.rs"), "").unwrap();
+        std::fs::write(dir.path().join("README.md"), "").unwrap();
+        git(&["init", "-q"]);
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", "init"]);
+
+        let files = recent_files(&dir.path().join("app"), 10).await;
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, ["main.rs"]);
+    }
+
+    #[test]
+    fn parses_porcelain_v2_branch_headers() {
+        let output = concat!(
+            "# branch.oid 4b1d2c0e9f\n",
+            "# branch.head feature/login\n",
+            "# branch.upstream upstream/feature/login\n",
+            "# branch.ab +2 -5\n",
+            "1 .M N... 100644 100644 100644 3f2a 3f2a src/main.rs\n",
+        );
+        assert_eq!(
+            parse_status(output),
+            BranchStatus {
+                branch: "feature/login".to_string(),
+                upstream: Some("upstream/feature/login".to_string()),
+                ahead: 2,
+                behind: 5,
+                dirty: true,
+            }
+        );
+
+        // Fresh repo: no commits, no upstream, nothing changed
+        let clean = parse_status("# branch.oid (initial)\n# branch.head main\n");
+        assert_eq!(clean.branch, "main");
+        assert_eq!((clean.ahead, clean.behind, clean.dirty), (0, 0, false));
+        assert!(clean.upstream.is_none());
+
+        assert_eq!(parse_status("# branch.head (detached)\n").branch, "");
+    }
+
+    #[tokio::test]
+    async fn non_git_directory_has_no_git_info() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(analyze(dir.path()).await.unwrap().is_none());
+
+        // A stray `.git` that isn't a repository must not fail detection either
+        std::fs::write(dir.path().join(".git"), "gitdir: /nonexistent\n").unwrap();
+        assert!(analyze(dir.path()).await.unwrap().is_none());
+    }
 }
@@ -37,6 +37,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
         description: Some("Run the application".to_string()),
         ports: vec![],
         env_required: vec![],
+        working_dir: None,
     });
 
     result.scripts.push(DiscoveredScript {
@@ -47,6 +48,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
         description: Some("Build the application".to_string()),
         ports: vec![],
         env_required: vec![],
+        working_dir: None,
     });
 
     result.scripts.push(DiscoveredScript {
@@ -57,6 +59,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
         description: Some("Run all tests".to_string()),
         ports: vec![],
         env_required: vec![],
+        working_dir: None,
     });
 
     result.scripts.push(DiscoveredScript {
@@ -67,6 +70,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
         description: Some("Run tests with verbose output".to_string()),
         ports: vec![],
         env_required: vec![],
+        working_dir: None,
     });
 
     result.scripts.push(DiscoveredScript {
@@ -77,6 +81,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
         description: Some("Format code".to_string()),
         ports: vec![],
         env_required: vec![],
+        working_dir: None,
     });
 
     result.scripts.push(DiscoveredScript {
@@ -87,6 +92,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
         description: Some("Run go vet".to_string()),
         ports: vec![],
         env_required: vec![],
+        working_dir: None,
     });
 
     result.scripts.push(DiscoveredScript {
@@ -97,6 +103,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
         description: Some("Tidy module dependencies".to_string()),
         ports: vec![],
         env_required: vec![],
+        working_dir: None,
     });
 
     result.scripts.push(DiscoveredScript {
@@ -107,6 +114,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
         description: Some("Download dependencies".to_string()),
         ports: vec![],
         env_required: vec![],
+        working_dir: None,
     });
 
     // Check for common tools
@@ -119,6 +127,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
             description: Some("Run golangci-lint".to_string()),
             ports: vec![],
             env_required: vec![],
+            working_dir: None,
         });
     }
 
@@ -132,6 +141,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
             description: Some("Run with hot reload (air)".to_string()),
             ports: vec![],
             env_required: vec![],
+            working_dir: None,
         });
     }
 
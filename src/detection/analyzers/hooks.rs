@@ -0,0 +1,143 @@
+//! pre-commit and git hooks analyzer
+
+use anyhow::Result;
+use std::path::Path;
+
+use super::AnalyzerResult;
+use crate::detection::{DiscoveredScript, ProjectKind, ScriptCategory, ScriptSource};
+
+const PRE_COMMIT_CONFIG: &str = ".pre-commit-config.yaml";
+
+pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
+    let config_path = root.join(PRE_COMMIT_CONFIG);
+    let has_pre_commit = config_path.exists();
+    let git_hooks = installed_git_hooks(root).await;
+
+    if !has_pre_commit && git_hooks.is_empty() {
+        return Ok(None);
+    }
+
+    let mut result = AnalyzerResult::new(ProjectKind::Generic, 0.3);
+
+    if has_pre_commit {
+        result.scripts.push(DiscoveredScript {
+            name: "pre-commit run".to_string(),
+            command: "pre-commit run --all-files".to_string(),
+            source: ScriptSource::Detected,
+            category: ScriptCategory::Lint,
+            description: Some("Run pre-commit hooks on every file".to_string()),
+            ports: vec![],
+            env_required: vec![],
+            working_dir: None,
+        });
+        result.scripts.push(DiscoveredScript {
+            name: "pre-commit install".to_string(),
+            command: "pre-commit install".to_string(),
+            source: ScriptSource::Detected,
+            category: ScriptCategory::Utility,
+            description: Some("Install the pre-commit git hook".to_string()),
+            ports: vec![],
+            env_required: vec![],
+            working_dir: None,
+        });
+
+        let content = tokio::fs::read_to_string(&config_path)
+            .await
+            .unwrap_or_default();
+        let ids = pre_commit_hook_ids(&content);
+        if !ids.is_empty() {
+            result
+                .advisories
+                .push(format!("pre-commit hooks: {}", ids.join(", ")));
+        }
+    }
+
+    if !git_hooks.is_empty() {
+        result
+            .advisories
+            .push(format!("Git hooks installed: {}", git_hooks.join(", ")));
+    }
+
+    Ok(Some(result))
+}
+
+/// Hook ids from every repo in a `.pre-commit-config.yaml`, in file order
+fn pre_commit_hook_ids(content: &str) -> Vec<String> {
+    let Ok(yaml) = serde_yaml::from_str::<serde_yaml::Value>(content) else {
+        return Vec::new();
+    };
+
+    let mut ids: Vec<String> = Vec::new();
+    let repos = yaml.get("repos").and_then(|r| r.as_sequence());
+    for repo in repos.into_iter().flatten() {
+        let hooks = repo.get("hooks").and_then(|h| h.as_sequence());
+        for hook in hooks.into_iter().flatten() {
+            if let Some(id) = hook.get("id").and_then(|id| id.as_str()) {
+                if !ids.iter().any(|known| known == id) {
+                    ids.push(id.to_string());
+                }
+            }
+        }
+    }
+    ids
+}
+
+/// Hooks in `.git/hooks`, ignoring the `.sample` files git ships with
+async fn installed_git_hooks(root: &Path) -> Vec<String> {
+    let Ok(mut entries) = tokio::fs::read_dir(root.join(".git").join("hooks")).await else {
+        return Vec::new();
+    };
+
+    let mut hooks = Vec::new();
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.ends_with(".sample") || name.starts_with('.') {
+            continue;
+        }
+        if entry.file_type().await.is_ok_and(|t| t.is_file()) {
+            hooks.push(name);
+        }
+    }
+    hooks.sort();
+    hooks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn pre_commit_config_offers_run_all_files() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(PRE_COMMIT_CONFIG),
+            concat!(
+                "repos:\n",
+                "  - repo: https://github.com/astral-sh/ruff-pre-commit\n",
+                "    rev: v0.4.4\n",
+                "    hooks:\n",
+                "      - id: ruff\n",
+                "      - id: ruff-format\n",
+            ),
+        )
+        .unwrap();
+
+        let result = analyze(dir.path())
+            .await
+            .expect("analyze should succeed")
+            .expect("should detect pre-commit");
+
+        let run = result
+            .scripts
+            .iter()
+            .find(|s| s.command == "pre-commit run --all-files")
+            .expect("run hooks action");
+        assert_eq!(run.category, ScriptCategory::Lint);
+        assert!(result
+            .scripts
+            .iter()
+            .any(|s| s.command == "pre-commit install" && s.category == ScriptCategory::Utility));
+        assert_eq!(result.advisories, ["pre-commit hooks: ruff, ruff-format"]);
+    }
+}
@@ -0,0 +1,279 @@
+//! Gradle / Maven (Java, Kotlin) project analyzer
+
+use anyhow::Result;
+use std::path::Path;
+
+use super::AnalyzerResult;
+use crate::detection::{
+    BuildTool, DiscoveredScript, ExpectedPort, ProjectKind, ScriptCategory, ScriptSource,
+};
+
+/// Port a Spring Boot app serves on unless `server.port` says otherwise
+const SPRING_BOOT_DEFAULT_PORT: u16 = 8080;
+
+const GRADLE_BUILD_FILES: &[&str] = &[
+    "build.gradle",
+    "build.gradle.kts",
+    "settings.gradle",
+    "settings.gradle.kts",
+];
+
+pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
+    let gradle_file = GRADLE_BUILD_FILES
+        .iter()
+        .map(|name| root.join(name))
+        .find(|path| path.exists());
+    let has_gradlew = root.join("gradlew").exists();
+    let has_pom = root.join("pom.xml").exists();
+
+    let build_tool = if gradle_file.is_some() || has_gradlew {
+        BuildTool::Gradle
+    } else if has_pom || root.join("mvnw").exists() {
+        BuildTool::Maven
+    } else {
+        return Ok(None);
+    };
+
+    let build_file = match build_tool {
+        BuildTool::Gradle => gradle_file,
+        BuildTool::Maven => has_pom.then(|| root.join("pom.xml")),
+    };
+    let build_content = match &build_file {
+        Some(path) => tokio::fs::read_to_string(path).await.unwrap_or_default(),
+        None => String::new(),
+    };
+    let spring_boot = build_content.contains("org.springframework.boot");
+
+    let mut result = AnalyzerResult::new(ProjectKind::Jvm { build_tool }, 0.9);
+    let program = wrapper_or(root, build_tool);
+    let script =
+        |name: &str, args: &str, category: ScriptCategory, description: &str| DiscoveredScript {
+            name: name.to_string(),
+            command: format!("{} {}", program, args),
+            source: ScriptSource::Detected,
+            category,
+            description: Some(description.to_string()),
+            ports: vec![],
+            env_required: vec![],
+            working_dir: None,
+        };
+
+    match build_tool {
+        BuildTool::Gradle => {
+            result.scripts.extend([
+                script("build", "build", ScriptCategory::Build, "Build and test"),
+                script("test", "test", ScriptCategory::Test, "Run tests"),
+                script(
+                    "clean",
+                    "clean",
+                    ScriptCategory::Utility,
+                    "Delete build outputs",
+                ),
+            ]);
+            if spring_boot {
+                result.scripts.push(script(
+                    "bootRun",
+                    "bootRun",
+                    ScriptCategory::Dev,
+                    "Run the Spring Boot application",
+                ));
+            } else if has_application_plugin(&build_content) {
+                result.scripts.push(script(
+                    "run",
+                    "run",
+                    ScriptCategory::Dev,
+                    "Run the application",
+                ));
+            }
+        }
+        BuildTool::Maven => {
+            result.scripts.extend([
+                script(
+                    "package",
+                    "package",
+                    ScriptCategory::Build,
+                    "Build and test",
+                ),
+                script("test", "test", ScriptCategory::Test, "Run tests"),
+                script(
+                    "clean",
+                    "clean",
+                    ScriptCategory::Utility,
+                    "Delete build outputs",
+                ),
+            ]);
+            if spring_boot {
+                result.scripts.push(script(
+                    "spring-boot:run",
+                    "spring-boot:run",
+                    ScriptCategory::Dev,
+                    "Run the Spring Boot application",
+                ));
+            }
+        }
+    }
+
+    if spring_boot {
+        let port = spring_server_port(root)
+            .await
+            .unwrap_or(SPRING_BOOT_DEFAULT_PORT);
+        if let Some(run) = result
+            .scripts
+            .iter_mut()
+            .find(|s| s.category == ScriptCategory::Dev)
+        {
+            run.ports.push(port);
+        }
+        result.expected_ports.push(ExpectedPort {
+            port,
+            source: "Spring Boot".to_string(),
+            service_name: "spring-boot".to_string(),
+        });
+    }
+
+    Ok(Some(result))
+}
+
+/// `./gradlew` or `./mvnw` when the project ships a wrapper, else the installed tool
+fn wrapper_or(root: &Path, build_tool: BuildTool) -> &'static str {
+    match build_tool {
+        BuildTool::Gradle if root.join("gradlew").exists() => "./gradlew",
+        BuildTool::Gradle => "gradle",
+        BuildTool::Maven if root.join("mvnw").exists() => "./mvnw",
+        BuildTool::Maven => "mvn",
+    }
+}
+
+/// Whether a Gradle build applies the `application` plugin, which adds `run`
+fn has_application_plugin(build: &str) -> bool {
+    build.lines().any(|line| {
+        let line = line.trim();
+        line == "application"
+            || line == "id 'application'"
+            || line == "id \"application\""
+            || line == "id(\"application\")"
+            || line == "apply plugin: 'application'"
+    })
+}
+
+/// `server.port` from `application.properties` or `application.yml`
+async fn spring_server_port(root: &Path) -> Option<u16> {
+    let resources = root.join("src").join("main").join("resources");
+
+    if let Ok(content) = tokio::fs::read_to_string(resources.join("application.properties")).await {
+        let port = content.lines().find_map(|line| {
+            let (key, value) = line.split_once(['=', ':'])?;
+            (key.trim() == "server.port").then(|| value.trim().parse().ok())?
+        });
+        if port.is_some() {
+            return port;
+        }
+    }
+
+    for name in ["application.yml", "application.yaml"] {
+        let Ok(content) = tokio::fs::read_to_string(resources.join(name)).await else {
+            continue;
+        };
+        let Ok(yaml) = serde_yaml::from_str::<serde_yaml::Value>(&content) else {
+            continue;
+        };
+        let port = yaml
+            .get("server")
+            .and_then(|server| server.get("port"))
+            .and_then(|port| port.as_u64())
+            .and_then(|port| u16::try_from(port).ok());
+        if port.is_some() {
+            return port;
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn commands(result: &AnalyzerResult) -> Vec<(&str, &str)> {
+        result
+            .scripts
+            .iter()
+            .map(|s| (s.name.as_str(), s.command.as_str()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn spring_boot_gradle_project_uses_the_wrapper() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("build.gradle.kts"),
+            concat!(
+                "plugins {\n",
+                "    id(\"org.springframework.boot\") version \"3.3.0\"\n",
+                "    kotlin(\"jvm\") version \"2.0.0\"\n",
+                "}\n",
+            ),
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("gradlew"), "#!/bin/sh\n").unwrap();
+
+        let result = analyze(dir.path())
+            .await
+            .expect("analyze should succeed")
+            .expect("should detect a Gradle project");
+
+        assert_eq!(
+            result.project_kind,
+            ProjectKind::Jvm {
+                build_tool: BuildTool::Gradle
+            }
+        );
+        assert_eq!(
+            commands(&result),
+            [
+                ("build", "./gradlew build"),
+                ("test", "./gradlew test"),
+                ("clean", "./gradlew clean"),
+                ("bootRun", "./gradlew bootRun"),
+            ]
+        );
+        let boot_run = result.scripts.iter().find(|s| s.name == "bootRun").unwrap();
+        assert_eq!(boot_run.ports, [SPRING_BOOT_DEFAULT_PORT]);
+        assert_eq!(result.expected_ports[0].port, SPRING_BOOT_DEFAULT_PORT);
+    }
+
+    #[tokio::test]
+    async fn maven_project_without_wrapper_reads_server_port() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("pom.xml"),
+            concat!(
+                "<project>\n",
+                "  <parent>\n",
+                "    <groupId>org.springframework.boot</groupId>\n",
+                "    <artifactId>spring-boot-starter-parent</artifactId>\n",
+                "  </parent>\n",
+                "</project>\n",
+            ),
+        )
+        .unwrap();
+        let resources = dir.path().join("src/main/resources");
+        std::fs::create_dir_all(&resources).unwrap();
+        std::fs::write(resources.join("application.yml"), "server:\n  port: 9090\n").unwrap();
+
+        let result = analyze(dir.path()).await.unwrap().unwrap();
+
+        assert_eq!(
+            commands(&result),
+            [
+                ("package", "mvn package"),
+                ("test", "mvn test"),
+                ("clean", "mvn clean"),
+                ("spring-boot:run", "mvn spring-boot:run"),
+            ]
+        );
+        assert_eq!(result.expected_ports[0].port, 9090);
+
+        assert!(analyze(tempdir().unwrap().path()).await.unwrap().is_none());
+    }
+}
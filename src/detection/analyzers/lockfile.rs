@@ -0,0 +1,97 @@
+//! Lockfile drift detection (manifest edited without refreshing the lockfile)
+
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+/// Checkouts and copies touch files a moment apart; ignore differences below this
+const MTIME_TOLERANCE: Duration = Duration::from_secs(2);
+
+/// A manifest/lockfile pair and the command that brings them back in sync
+struct LockPair {
+    manifest: &'static str,
+    lockfile: &'static str,
+    fix: &'static str,
+}
+
+const LOCK_PAIRS: &[LockPair] = &[
+    LockPair {
+        manifest: "package.json",
+        lockfile: "package-lock.json",
+        fix: "npm install",
+    },
+    LockPair {
+        manifest: "package.json",
+        lockfile: "yarn.lock",
+        fix: "yarn install",
+    },
+    LockPair {
+        manifest: "package.json",
+        lockfile: "pnpm-lock.yaml",
+        fix: "pnpm install",
+    },
+    LockPair {
+        manifest: "package.json",
+        lockfile: "bun.lockb",
+        fix: "bun install",
+    },
+    LockPair {
+        manifest: "Cargo.toml",
+        lockfile: "Cargo.lock",
+        fix: "cargo build",
+    },
+    LockPair {
+        manifest: "pyproject.toml",
+        lockfile: "poetry.lock",
+        fix: "poetry lock",
+    },
+];
+
+/// Whether a manifest modified at `manifest` is newer than its lockfile modified at `lock`
+pub fn is_drifted(manifest: SystemTime, lock: SystemTime) -> bool {
+    manifest
+        .duration_since(lock)
+        .is_ok_and(|ahead| ahead > MTIME_TOLERANCE)
+}
+
+/// Warnings for every lockfile that is older than its manifest
+pub async fn analyze(root: &Path) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    for pair in LOCK_PAIRS {
+        let (Some(manifest), Some(lock)) = (
+            modified(&root.join(pair.manifest)).await,
+            modified(&root.join(pair.lockfile)).await,
+        ) else {
+            continue;
+        };
+
+        if is_drifted(manifest, lock) {
+            warnings.push(format!(
+                "{} is older than {} — run {}",
+                pair.lockfile, pair.manifest, pair.fix
+            ));
+        }
+    }
+
+    warnings
+}
+
+async fn modified(path: &Path) -> Option<SystemTime> {
+    tokio::fs::metadata(path).await.ok()?.modified().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drift_needs_manifest_newer_than_lockfile() {
+        let lock = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+
+        assert!(is_drifted(lock + Duration::from_secs(60), lock));
+        assert!(!is_drifted(lock, lock));
+        assert!(!is_drifted(lock - Duration::from_secs(60), lock));
+        // Files written together by a checkout land within the tolerance
+        assert!(!is_drifted(lock + Duration::from_secs(1), lock));
+    }
+}
@@ -0,0 +1,128 @@
+//! mise tasks and tool versions analyzer
+
+use anyhow::Result;
+use std::path::Path;
+
+use super::generic::categorize_task;
+use super::AnalyzerResult;
+use crate::detection::{DiscoveredScript, ProjectKind, ScriptSource};
+
+/// Config files mise reads from the project root, in precedence order
+const MISE_CONFIG_FILES: &[&str] = &[".mise.toml", "mise.toml", ".config/mise.toml"];
+
+pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
+    let Some(path) = MISE_CONFIG_FILES
+        .iter()
+        .map(|f| root.join(f))
+        .find(|p| p.exists())
+    else {
+        return Ok(None);
+    };
+
+    let content = tokio::fs::read_to_string(&path).await?;
+    let toml: toml::Value = content.parse()?;
+
+    let mut result = AnalyzerResult::new(ProjectKind::Generic, 0.3);
+    result.scripts = mise_tasks(&toml);
+
+    let tools = mise_tools(&toml);
+    if !tools.is_empty() {
+        result
+            .advisories
+            .push(format!("mise tools: {}", tools.join(", ")));
+    }
+
+    Ok(Some(result))
+}
+
+/// `mise run <task>` for every visible task in `[tasks]`
+fn mise_tasks(toml: &toml::Value) -> Vec<DiscoveredScript> {
+    let Some(tasks) = toml.get("tasks").and_then(|t| t.as_table()) else {
+        return Vec::new();
+    };
+
+    tasks
+        .iter()
+        .filter(|(_, task)| !task.get("hide").and_then(|h| h.as_bool()).unwrap_or(false))
+        .map(|(name, task)| DiscoveredScript {
+            name: name.clone(),
+            command: format!("mise run {}", name),
+            source: ScriptSource::Detected,
+            category: categorize_task(name),
+            description: task
+                .get("description")
+                .and_then(|d| d.as_str())
+                .map(str::to_string),
+            ports: vec![],
+            env_required: vec![],
+            working_dir: None,
+        })
+        .collect()
+}
+
+/// `node 20` style entries from `[tools]`; a tool can pin a version, a list of
+/// versions, or a table with a `version` key
+fn mise_tools(toml: &toml::Value) -> Vec<String> {
+    let Some(tools) = toml.get("tools").and_then(|t| t.as_table()) else {
+        return Vec::new();
+    };
+
+    tools
+        .iter()
+        .map(|(tool, spec)| {
+            let version = match spec {
+                toml::Value::String(v) => v.clone(),
+                toml::Value::Array(versions) => versions
+                    .iter()
+                    .filter_map(|v| v.as_str())
+                    .collect::<Vec<_>>()
+                    .join("/"),
+                other => other
+                    .get("version")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+            };
+            format!("{} {}", tool, version).trim_end().to_string()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::ScriptCategory;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn parses_tasks_and_tool_versions() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(".mise.toml"),
+            concat!(
+                "[tools]\n",
+                "node = \"20\"\n",
+                "python = [\"3.11\", \"3.12\"]\n\n",
+                "[tasks.dev]\n",
+                "description = \"Start the dev server\"\n",
+                "run = \"npm run dev\"\n\n",
+                "[tasks.internal]\n",
+                "run = \"./scripts/setup.sh\"\n",
+                "hide = true\n",
+            ),
+        )
+        .unwrap();
+
+        let result = analyze(dir.path())
+            .await
+            .expect("analyze should succeed")
+            .expect("should detect mise config");
+
+        assert_eq!(result.scripts.len(), 1);
+        let dev = &result.scripts[0];
+        assert_eq!(dev.command, "mise run dev");
+        assert_eq!(dev.description.as_deref(), Some("Start the dev server"));
+        assert_eq!(dev.category, ScriptCategory::Dev);
+        assert_eq!(result.advisories, ["mise tools: node 20, python 3.11/3.12"]);
+    }
+}
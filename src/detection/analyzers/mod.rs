@@ -1,13 +1,25 @@
 //! Project analyzers for different project types
 
+pub mod bazel;
 pub mod docker;
 pub mod env;
 pub mod generic;
 pub mod git;
 pub mod go;
+pub mod hooks;
+pub mod jvm;
+pub mod lockfile;
+pub mod mise;
+pub mod nim;
 pub mod node;
+pub mod ocaml;
+pub mod php;
 pub mod python;
+pub mod ruby;
 pub mod rust;
+pub mod workspace;
+
+use std::path::PathBuf;
 
 use super::{DiscoveredScript, DockerService, EnvVarSpec, ExpectedPort, ProjectKind};
 
@@ -20,6 +32,10 @@ pub struct AnalyzerResult {
     pub env_vars: Vec<EnvVarSpec>,
     pub expected_ports: Vec<ExpectedPort>,
     pub docker_services: Vec<DockerService>,
+    /// Toolchain notes worth showing the user (e.g. required Node version)
+    pub advisories: Vec<String>,
+    /// Manifests of the workspace packages the scripts were read from
+    pub member_manifests: Vec<PathBuf>,
 }
 
 impl AnalyzerResult {
@@ -31,6 +47,8 @@ impl AnalyzerResult {
             env_vars: Vec::new(),
             expected_ports: Vec::new(),
             docker_services: Vec::new(),
+            advisories: Vec::new(),
+            member_manifests: Vec::new(),
         }
     }
 }
@@ -0,0 +1,205 @@
+//! Nim / Nimble project analyzer
+
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+use super::AnalyzerResult;
+use crate::detection::{DiscoveredScript, ExpectedPort, ProjectKind, ScriptCategory, ScriptSource};
+
+/// Port Jester listens on unless `settings:` overrides it
+const JESTER_DEFAULT_PORT: u16 = 5000;
+
+pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
+    let nimble_file = find_nimble_file(root).await;
+    let has_config_nims = root.join("config.nims").exists();
+
+    if nimble_file.is_none() && !has_config_nims {
+        return Ok(None);
+    }
+
+    let content = match &nimble_file {
+        Some(path) => tokio::fs::read_to_string(path).await.unwrap_or_default(),
+        None => String::new(),
+    };
+    let bins = parse_bins(&content);
+    let uses_jester = requires_package(&content, "jester");
+    let server_ports = if uses_jester {
+        vec![JESTER_DEFAULT_PORT]
+    } else {
+        vec![]
+    };
+
+    let mut result = AnalyzerResult::new(ProjectKind::Nim, 0.9);
+
+    result.scripts.push(DiscoveredScript {
+        name: "build".to_string(),
+        command: "nimble build".to_string(),
+        source: ScriptSource::Detected,
+        category: ScriptCategory::Build,
+        description: Some("Build the project".to_string()),
+        ports: vec![],
+        env_required: vec![],
+        working_dir: None,
+    });
+
+    result.scripts.push(DiscoveredScript {
+        name: "test".to_string(),
+        command: "nimble test".to_string(),
+        source: ScriptSource::Detected,
+        category: ScriptCategory::Test,
+        description: Some("Run tests".to_string()),
+        ports: vec![],
+        env_required: vec![],
+        working_dir: None,
+    });
+
+    result.scripts.push(DiscoveredScript {
+        name: "run".to_string(),
+        command: "nimble run".to_string(),
+        source: ScriptSource::Detected,
+        category: ScriptCategory::Dev,
+        description: Some(if uses_jester {
+            "Run the Jester server".to_string()
+        } else {
+            "Run the default binary".to_string()
+        }),
+        ports: server_ports.clone(),
+        env_required: vec![],
+        working_dir: None,
+    });
+
+    // `nimble run` alone fails once a package declares several binaries
+    for bin in &bins {
+        result.scripts.push(DiscoveredScript {
+            name: format!("run {}", bin),
+            command: format!("nimble run {}", bin),
+            source: ScriptSource::Detected,
+            category: ScriptCategory::Dev,
+            description: Some(format!("Run the {} binary", bin)),
+            ports: vec![],
+            env_required: vec![],
+            working_dir: None,
+        });
+    }
+
+    result.scripts.push(DiscoveredScript {
+        name: "install".to_string(),
+        command: "nimble install".to_string(),
+        source: ScriptSource::Detected,
+        category: ScriptCategory::Utility,
+        description: Some("Install the package and its dependencies".to_string()),
+        ports: vec![],
+        env_required: vec![],
+        working_dir: None,
+    });
+
+    if let Some(path) = &nimble_file {
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let service_name = path
+            .file_stem()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        for port in server_ports {
+            result.expected_ports.push(ExpectedPort {
+                port,
+                source: file_name.clone(),
+                service_name: service_name.clone(),
+            });
+        }
+    }
+
+    Ok(Some(result))
+}
+
+async fn find_nimble_file(root: &Path) -> Option<PathBuf> {
+    let mut entries = tokio::fs::read_dir(root).await.ok()?;
+
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        if path.extension().is_some_and(|ext| ext == "nimble") {
+            return Some(path);
+        }
+    }
+
+    None
+}
+
+/// Binary names from a `bin = @["a", "b"]` line
+fn parse_bins(content: &str) -> Vec<String> {
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        if key.trim() != "bin" {
+            continue;
+        }
+
+        let list = value.trim().trim_start_matches('@');
+        let list = list.trim_start_matches('[').trim_end_matches(']');
+        return list
+            .split(',')
+            .map(|item| item.trim().trim_matches('"').to_string())
+            .filter(|item| !item.is_empty())
+            .collect();
+    }
+
+    Vec::new()
+}
+
+/// Whether a `requires "..."` line names `package`
+fn requires_package(content: &str, package: &str) -> bool {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with("requires"))
+        .flat_map(|line| line.split('"').skip(1).step_by(2))
+        .any(|spec| {
+            spec.split(|c: char| c.is_whitespace() || "<>=@#".contains(c))
+                .next()
+                .is_some_and(|name| name.eq_ignore_ascii_case(package))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn generates_run_commands_per_binary() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("webapp.nimble"),
+            concat!(
+                "version       = \"0.1.0\"\n",
+                "srcDir        = \"src\"\n",
+                "bin           = @[\"server\", \"migrate\"]\n\n",
+                "requires \"nim >= 2.0.0\", \"jester >= 0.6.0\"\n",
+            ),
+        )
+        .unwrap();
+
+        let result = analyze(dir.path())
+            .await
+            .expect("analyze should succeed")
+            .expect("should detect nimble project");
+
+        assert_eq!(result.project_kind, ProjectKind::Nim);
+        assert!(result
+            .scripts
+            .iter()
+            .any(|s| s.name == "run server" && s.command == "nimble run server"));
+        assert!(result
+            .scripts
+            .iter()
+            .any(|s| s.command == "nimble run migrate"));
+        assert!(result.scripts.iter().any(|s| s.command == "nimble test"));
+        assert!(result
+            .expected_ports
+            .iter()
+            .any(|p| p.port == JESTER_DEFAULT_PORT && p.source == "webapp.nimble"));
+    }
+}
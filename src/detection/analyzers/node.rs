@@ -4,9 +4,10 @@ use anyhow::Result;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::Deserialize;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use super::AnalyzerResult;
+use super::env::add_env_var;
+use super::{workspace, AnalyzerResult};
 use crate::detection::{
     DiscoveredScript, EnvVarSpec, ExpectedPort, NodeFramework, PackageManager, ProjectKind,
     ScriptCategory, ScriptSource,
@@ -16,27 +17,115 @@ use crate::detection::{
 static PORT_REGEX: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"(?:--port|PORT=|-p)\s*(\d+)").expect("Invalid port regex"));
 
-#[derive(Deserialize)]
+/// `port: 4000` inside a framework config, e.g. `server: { port: 4000 }`
+static CONFIG_PORT_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\bport\s*:\s*(\d+)\b").expect("Invalid config port regex"));
+
+/// `PORT=4000` in a `.env` file
+static DOTENV_PORT_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?m)^\s*(?:export\s+)?PORT\s*=\s*["']?(\d+)"#).expect("Invalid .env port regex")
+});
+
+/// `$VAR`, `${VAR}`, `${VAR:-default}`, `process.env.VAR` and `process.env["VAR"]`
+static ENV_REF_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"\$\{([A-Za-z_][A-Za-z0-9_]*)(:?[-=?+])?[^}]*\}|\$([A-Za-z_][A-Za-z0-9_]*)|process\.env(?:\.([A-Za-z_][A-Za-z0-9_]*)|\[["']([A-Za-z_][A-Za-z0-9_]*)["']\])"#,
+    )
+    .expect("Invalid env reference regex")
+});
+
+/// Variables the shell or package manager always provides, never worth flagging
+const BUILTIN_ENV_VARS: &[&str] = &[
+    "PATH", "HOME", "USER", "PWD", "OLDPWD", "SHELL", "TERM", "LANG", "TMPDIR", "HOSTNAME", "IFS",
+    "UID", "RANDOM", "CI", "INIT_CWD",
+];
+
+/// Framework configs that can override the dev server port, in lookup order
+const FRAMEWORK_CONFIG_FILES: &[&str] = &[
+    "vite.config.ts",
+    "vite.config.js",
+    "vite.config.mts",
+    "vite.config.mjs",
+    "nuxt.config.ts",
+    "nuxt.config.js",
+    "astro.config.mjs",
+    "astro.config.ts",
+    "next.config.js",
+    "next.config.mjs",
+    "next.config.ts",
+];
+
+#[derive(Deserialize, Default)]
 struct PackageJson {
     name: Option<String>,
     scripts: Option<indexmap::IndexMap<String, String>>,
     dependencies: Option<indexmap::IndexMap<String, String>>,
     #[serde(rename = "devDependencies")]
     dev_dependencies: Option<indexmap::IndexMap<String, String>>,
+    #[serde(rename = "packageManager")]
+    package_manager: Option<String>,
+    engines: Option<indexmap::IndexMap<String, String>>,
+    workspaces: Option<Workspaces>,
+}
+
+/// npm/Yarn `workspaces`: a list of globs, or Yarn's `{ "packages": [...] }` form
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Workspaces {
+    List(Vec<String>),
+    Config { packages: Vec<String> },
+}
+
+#[derive(Deserialize)]
+struct PnpmWorkspace {
+    #[serde(default)]
+    packages: Vec<String>,
+}
+
+/// A sub-package of a monorepo, with the scripts it contributes
+struct Member {
+    dir: PathBuf,
+    label: String,
+    pkg: PackageJson,
+    package_manager: PackageManager,
 }
 
 pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
     let pkg_path = root.join("package.json");
-    if !pkg_path.exists() {
+    let has_root_package = pkg_path.exists();
+    let pkg: PackageJson = if has_root_package {
+        let content = tokio::fs::read_to_string(&pkg_path).await?;
+        serde_json::from_str(&content)?
+    } else {
+        PackageJson::default()
+    };
+
+    // Corepack's `packageManager` field is authoritative; lockfiles are a fallback
+    let pinned = pkg
+        .package_manager
+        .as_deref()
+        .and_then(parse_package_manager_field);
+    let package_manager = pinned
+        .as_ref()
+        .map(|(pm, _)| pm.clone())
+        .unwrap_or_else(|| detect_package_manager(root));
+
+    let patterns = workspace_patterns(root, &pkg).await;
+    let members = workspace_members(root, patterns.as_deref(), &package_manager).await;
+    if !has_root_package && members.is_empty() {
         return Ok(None);
     }
 
-    let content = tokio::fs::read_to_string(&pkg_path).await?;
-    let pkg: PackageJson = serde_json::from_str(&content)?;
-
-    let package_manager = detect_package_manager(root);
-    let framework = detect_framework(&pkg);
-    let confidence = 0.95;
+    let framework = detect_framework(&pkg).or_else(|| {
+        members
+            .iter()
+            .find_map(|member| detect_framework(&member.pkg))
+    });
+    let confidence = if has_root_package || patterns.is_some() {
+        0.95
+    } else {
+        workspace::SIBLING_CONFIDENCE
+    };
 
     let mut result = AnalyzerResult::new(
         ProjectKind::Node {
@@ -46,42 +135,181 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
         confidence,
     );
 
-    // Parse scripts
-    let run_cmd = match &package_manager {
+    add_scripts(&mut result, &pkg, &package_manager, None);
+
+    if let Some((pm, version)) = &pinned {
+        result
+            .advisories
+            .push(format!("Pinned to {} {} (packageManager)", pm, version));
+    }
+
+    if let Some(node) = pkg.engines.as_ref().and_then(|e| e.get("node")) {
+        result
+            .advisories
+            .push(format!("Requires Node {} (engines.node)", node));
+    }
+
+    // Add common env vars
+    if !result.env_vars.iter().any(|v| v.name == "NODE_ENV") {
+        result.env_vars.push(EnvVarSpec {
+            name: "NODE_ENV".to_string(),
+            description: Some("Node environment mode".to_string()),
+            source: "Node.js".to_string(),
+            example_value: Some("development".to_string()),
+            is_secret: false,
+        });
+    }
+
+    // Add framework-specific ports
+    if has_root_package {
+        let (port, source) = configured_port(root).await.unwrap_or((
+            default_port(detect_framework(&pkg)),
+            "package.json".to_string(),
+        ));
+        result.expected_ports.push(ExpectedPort {
+            port,
+            source,
+            service_name: pkg.name.clone().unwrap_or_else(|| "app".to_string()),
+        });
+    }
+
+    for member in &members {
+        result
+            .member_manifests
+            .push(member.dir.join("package.json"));
+        add_scripts(
+            &mut result,
+            &member.pkg,
+            &member.package_manager,
+            Some((&member.label, &member.dir)),
+        );
+
+        // Only packages built on a known framework are expected to serve anything
+        if let Some(framework) = detect_framework(&member.pkg) {
+            let relative = member.dir.strip_prefix(root).unwrap_or(&member.dir);
+            let (port, source) = match configured_port(&member.dir).await {
+                Some((port, file)) => (port, relative.join(file).display().to_string()),
+                None => (
+                    default_port(Some(framework)),
+                    relative.join("package.json").display().to_string(),
+                ),
+            };
+            result.expected_ports.push(ExpectedPort {
+                port,
+                source,
+                service_name: member.label.clone(),
+            });
+        }
+    }
+
+    Ok(Some(result))
+}
+
+fn run_command(package_manager: &PackageManager) -> &'static str {
+    match package_manager {
         PackageManager::Npm => "npm run",
         PackageManager::Yarn => "yarn",
         PackageManager::Pnpm => "pnpm run",
         PackageManager::Bun => "bun run",
-    };
+    }
+}
 
-    if let Some(scripts) = pkg.scripts {
-        for (name, command) in scripts {
-            let category = categorize_script(&name, &command);
-            let ports = extract_ports_from_command(&command);
-
-            result.scripts.push(DiscoveredScript {
-                name: name.clone(),
-                command: format!("{} {}", run_cmd, name),
-                source: ScriptSource::PackageJson,
-                category,
-                description: None,
-                ports,
-                env_required: vec![],
-            });
+/// Turn a package's `scripts` into actions, scoped to `package` when it's a workspace member
+fn add_scripts(
+    result: &mut AnalyzerResult,
+    pkg: &PackageJson,
+    package_manager: &PackageManager,
+    package: Option<(&str, &Path)>,
+) {
+    let run_cmd = run_command(package_manager);
+    for (name, command) in pkg.scripts.iter().flatten() {
+        let category = categorize_script(name, command);
+        let ports = extract_ports_from_command(command);
+        let env_required = env_references(command);
+
+        let source = match package {
+            Some((label, _)) => format!("{} {} script", label, name),
+            None => format!("{} script", name),
+        };
+        for var in &env_required {
+            add_env_var(&mut result.env_vars, var, &source, None);
         }
+        let script = DiscoveredScript {
+            name: name.clone(),
+            command: format!("{} {}", run_cmd, name),
+            source: ScriptSource::PackageJson,
+            category,
+            description: None,
+            ports,
+            env_required,
+            working_dir: None,
+        };
+        result.scripts.push(match package {
+            Some((label, dir)) => script.in_package(label, dir),
+            None => script,
+        });
     }
+}
 
-    // Add common env vars
-    result.env_vars.push(EnvVarSpec {
-        name: "NODE_ENV".to_string(),
-        description: Some("Node environment mode".to_string()),
-        source: "Node.js".to_string(),
-        example_value: Some("development".to_string()),
-        is_secret: false,
-    });
+/// Workspace globs from `package.json` or `pnpm-workspace.yaml`, if the repo declares any
+async fn workspace_patterns(root: &Path, pkg: &PackageJson) -> Option<Vec<String>> {
+    match &pkg.workspaces {
+        Some(Workspaces::List(patterns)) => return Some(patterns.clone()),
+        Some(Workspaces::Config { packages }) => return Some(packages.clone()),
+        None => {}
+    }
 
-    // Add framework-specific ports
-    let default_port = match &framework {
+    let content = tokio::fs::read_to_string(root.join("pnpm-workspace.yaml"))
+        .await
+        .ok()?;
+    serde_yaml::from_str::<PnpmWorkspace>(&content)
+        .ok()
+        .map(|ws| ws.packages)
+}
+
+/// Packages matched by the workspace globs, or sibling directories with their
+/// own `package.json` when no workspace is declared
+async fn workspace_members(
+    root: &Path,
+    patterns: Option<&[String]>,
+    root_manager: &PackageManager,
+) -> Vec<Member> {
+    let dirs = match patterns {
+        Some(patterns) => workspace::members(root, patterns, "package.json"),
+        None => workspace::sibling_packages(root, "package.json"),
+    };
+
+    let mut members = Vec::new();
+    for dir in dirs {
+        let Ok(content) = tokio::fs::read_to_string(dir.join("package.json")).await else {
+            continue;
+        };
+        let Ok(pkg) = serde_json::from_str::<PackageJson>(&content) else {
+            continue;
+        };
+        // Workspace members share the root's manager; independent packages bring their own
+        let package_manager = if patterns.is_some() {
+            root_manager.clone()
+        } else {
+            pkg.package_manager
+                .as_deref()
+                .and_then(parse_package_manager_field)
+                .map(|(pm, _)| pm)
+                .or_else(|| lockfile_package_manager(&dir))
+                .unwrap_or_else(|| root_manager.clone())
+        };
+        members.push(Member {
+            label: workspace::package_label(pkg.name.as_deref(), &dir),
+            dir,
+            pkg,
+            package_manager,
+        });
+    }
+    members
+}
+
+fn default_port(framework: Option<NodeFramework>) -> u16 {
+    match framework {
         Some(NodeFramework::NextJs) => 3000,
         Some(NodeFramework::Vite) => 5173,
         Some(NodeFramework::NestJs) => 3000,
@@ -89,29 +317,63 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
         Some(NodeFramework::SvelteKit) => 5173,
         Some(NodeFramework::Nuxt) => 3000,
         _ => 3000,
-    };
-
-    result.expected_ports.push(ExpectedPort {
-        port: default_port,
-        source: "package.json".to_string(),
-        service_name: pkg.name.unwrap_or_else(|| "app".to_string()),
-    });
+    }
+}
 
-    Ok(Some(result))
+/// Env vars a script command reads, in order, skipping shell builtins and
+/// `${VAR:-default}` forms that don't need the variable set
+fn env_references(command: &str) -> Vec<String> {
+    let mut vars: Vec<String> = Vec::new();
+    for caps in ENV_REF_REGEX.captures_iter(command) {
+        if caps.get(2).is_some() {
+            continue;
+        }
+        let Some(name) = [1, 3, 4, 5].into_iter().find_map(|i| caps.get(i)) else {
+            continue;
+        };
+        let name = name.as_str();
+        if BUILTIN_ENV_VARS.contains(&name) || name.starts_with("npm_") {
+            continue;
+        }
+        if !vars.iter().any(|v| v == name) {
+            vars.push(name.to_string());
+        }
+    }
+    vars
 }
 
+/// Package manager from the lockfile in `root`, npm when there is none
 fn detect_package_manager(root: &Path) -> PackageManager {
-    if root.join("bun.lockb").exists() {
-        PackageManager::Bun
-    } else if root.join("pnpm-lock.yaml").exists() {
-        PackageManager::Pnpm
-    } else if root.join("yarn.lock").exists() {
-        PackageManager::Yarn
+    lockfile_package_manager(root).unwrap_or(PackageManager::Npm)
+}
+
+/// The manager whose lockfile sits in `dir`, other than npm's
+fn lockfile_package_manager(dir: &Path) -> Option<PackageManager> {
+    if dir.join("bun.lockb").exists() {
+        Some(PackageManager::Bun)
+    } else if dir.join("pnpm-lock.yaml").exists() {
+        Some(PackageManager::Pnpm)
+    } else if dir.join("yarn.lock").exists() {
+        Some(PackageManager::Yarn)
     } else {
-        PackageManager::Npm
+        None
     }
 }
 
+/// Parse a Corepack `packageManager` value like `pnpm@9.1.0+sha512.abc`
+fn parse_package_manager_field(value: &str) -> Option<(PackageManager, String)> {
+    let (name, version) = value.split_once('@')?;
+    let manager = match name.trim() {
+        "npm" => PackageManager::Npm,
+        "yarn" => PackageManager::Yarn,
+        "pnpm" => PackageManager::Pnpm,
+        "bun" => PackageManager::Bun,
+        _ => return None,
+    };
+    let version = version.split('+').next().unwrap_or(version).to_string();
+    Some((manager, version))
+}
+
 fn detect_framework(pkg: &PackageJson) -> Option<NodeFramework> {
     let all_deps: Vec<&str> = pkg
         .dependencies
@@ -181,6 +443,33 @@ fn categorize_script(name: &str, command: &str) -> ScriptCategory {
     }
 }
 
+/// Port set explicitly in a framework config or `.env`, with the file it came from
+async fn configured_port(root: &Path) -> Option<(u16, String)> {
+    for file in FRAMEWORK_CONFIG_FILES {
+        let Ok(content) = tokio::fs::read_to_string(root.join(file)).await else {
+            continue;
+        };
+        if let Some(port) = config_port(&content) {
+            return Some((port, file.to_string()));
+        }
+    }
+
+    let content = tokio::fs::read_to_string(root.join(".env")).await.ok()?;
+    dotenv_port(&content).map(|port| (port, ".env".to_string()))
+}
+
+/// First `port: N` in a JS/TS config, skipping commented-out lines
+fn config_port(content: &str) -> Option<u16> {
+    content
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("//"))
+        .find_map(|line| CONFIG_PORT_REGEX.captures(line)?[1].parse().ok())
+}
+
+fn dotenv_port(content: &str) -> Option<u16> {
+    DOTENV_PORT_REGEX.captures(content)?[1].parse().ok()
+}
+
 fn extract_ports_from_command(command: &str) -> Vec<u16> {
     let mut ports = Vec::new();
 
@@ -199,6 +488,25 @@ mod tests {
     use super::*;
     use tempfile::tempdir;
 
+    #[test]
+    fn finds_env_references_in_script_commands() {
+        assert_eq!(
+            env_references(
+                "prisma migrate deploy --url $DATABASE_URL && node -e 'process.env.FOO' ${STRIPE_KEY}"
+            ),
+            ["DATABASE_URL", "FOO", "STRIPE_KEY"]
+        );
+        assert_eq!(
+            env_references(r#"node -p "process.env['API_URL']" $API_URL"#),
+            ["API_URL"]
+        );
+        // Builtins, npm-provided vars and defaulted expansions aren't requirements
+        assert!(env_references(
+            "PATH=$PATH:./bin $HOME/bin/x $npm_package_version ${PORT:-3000} $1"
+        )
+        .is_empty());
+    }
+
     #[tokio::test]
     async fn detects_node_project_with_ports_and_package_manager() {
         let dir = tempdir().unwrap();
@@ -251,4 +559,131 @@ mod tests {
             .iter()
             .any(|p| p.port == 3000 && p.service_name == "example-app"));
     }
+
+    #[tokio::test]
+    async fn package_manager_field_overrides_lockfile() {
+        let dir = tempdir().unwrap();
+        let pkg_json = serde_json::json!({
+            "name": "corepack-app",
+            "packageManager": "yarn@4.0.0",
+            "engines": { "node": ">=20" },
+            "scripts": { "build": "tsc" }
+        });
+        std::fs::write(
+            dir.path().join("package.json"),
+            serde_json::to_string_pretty(&pkg_json).unwrap(),
+        )
+        .unwrap();
+        // Stray npm lockfile should not win over the pinned manager
+        std::fs::write(dir.path().join("package-lock.json"), "{}").unwrap();
+
+        let result = analyze(dir.path())
+            .await
+            .expect("analyze should succeed")
+            .expect("should detect node project");
+
+        assert!(matches!(
+            result.project_kind,
+            ProjectKind::Node {
+                package_manager: PackageManager::Yarn,
+                ..
+            }
+        ));
+        let build = result.scripts.iter().find(|s| s.name == "build").unwrap();
+        assert_eq!(build.command, "yarn build");
+        assert!(result.advisories.iter().any(|a| a.contains("yarn 4.0.0")));
+        assert!(result.advisories.iter().any(|a| a.contains(">=20")));
+    }
+
+    #[test]
+    fn reads_explicit_port_from_vite_config_and_dotenv() {
+        let vite = concat!(
+            "import { defineConfig } from 'vite'\n",
+            "export default defineConfig({\n",
+            "  // server: { port: 3333 },\n",
+            "  server: { port: 4000, strictPort: true },\n",
+            "})\n",
+        );
+        assert_eq!(config_port(vite), Some(4000));
+        assert_eq!(config_port("export default defineConfig({})"), None);
+
+        assert_eq!(dotenv_port("NODE_ENV=development\nPORT=4000\n"), Some(4000));
+        assert_eq!(dotenv_port("API_PORT=9000\n"), None);
+    }
+
+    #[tokio::test]
+    async fn workspace_packages_run_from_their_own_directory() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{"name": "monorepo", "workspaces": ["apps/*", "!apps/legacy"], "scripts": {"lint": "eslint ."}}"#,
+        )
+        .unwrap();
+        for (app, manifest) in [
+            (
+                "web",
+                r#"{"name": "@acme/web", "scripts": {"dev": "vite"}, "devDependencies": {"vite": "^5"}}"#,
+            ),
+            (
+                "legacy",
+                r#"{"name": "legacy", "scripts": {"dev": "gulp"}}"#,
+            ),
+        ] {
+            std::fs::create_dir_all(dir.path().join("apps").join(app)).unwrap();
+            std::fs::write(
+                dir.path().join("apps").join(app).join("package.json"),
+                manifest,
+            )
+            .unwrap();
+        }
+        std::fs::write(dir.path().join("pnpm-lock.yaml"), "").unwrap();
+
+        let result = analyze(dir.path()).await.unwrap().unwrap();
+
+        let scripts: Vec<(&str, &str, Option<&Path>)> = result
+            .scripts
+            .iter()
+            .map(|s| {
+                (
+                    s.name.as_str(),
+                    s.command.as_str(),
+                    s.working_dir.as_deref(),
+                )
+            })
+            .collect();
+        let web = dir.path().join("apps").join("web");
+        assert_eq!(
+            scripts,
+            [
+                ("lint", "pnpm run lint", None),
+                ("web:dev", "pnpm run dev", Some(web.as_path())),
+            ]
+        );
+        assert!(result
+            .expected_ports
+            .iter()
+            .any(|p| p.port == 5173 && p.service_name == "web"));
+    }
+
+    #[tokio::test]
+    async fn configured_port_overrides_framework_default() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{"name": "web", "devDependencies": {"vite": "^5.0.0"}}"#,
+        )
+        .unwrap();
+
+        let result = analyze(dir.path()).await.unwrap().unwrap();
+        assert_eq!(result.expected_ports[0].port, 5173);
+
+        std::fs::write(
+            dir.path().join("vite.config.ts"),
+            "export default { server: { port: 4000 } }\n",
+        )
+        .unwrap();
+        let result = analyze(dir.path()).await.unwrap().unwrap();
+        assert_eq!(result.expected_ports[0].port, 4000);
+        assert_eq!(result.expected_ports[0].source, "vite.config.ts");
+    }
 }
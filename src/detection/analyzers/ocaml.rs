@@ -0,0 +1,182 @@
+//! OCaml / Dune project analyzer
+
+use anyhow::Result;
+use std::path::Path;
+
+use super::AnalyzerResult;
+use crate::detection::{DiscoveredScript, ProjectKind, ScriptCategory, ScriptSource};
+
+pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
+    let has_dune_project = root.join("dune-project").exists();
+    let has_opam = has_opam_file(root).await;
+
+    if !has_dune_project && !has_opam {
+        return Ok(None);
+    }
+
+    let mut result = AnalyzerResult::new(ProjectKind::OCaml, 0.9);
+
+    result.scripts.push(DiscoveredScript {
+        name: "build".to_string(),
+        command: "dune build".to_string(),
+        source: ScriptSource::Detected,
+        category: ScriptCategory::Build,
+        description: Some("Build the project".to_string()),
+        ports: vec![],
+        env_required: vec![],
+        working_dir: None,
+    });
+
+    result.scripts.push(DiscoveredScript {
+        name: "test".to_string(),
+        command: "dune test".to_string(),
+        source: ScriptSource::Detected,
+        category: ScriptCategory::Test,
+        description: Some("Run tests (alias runtest)".to_string()),
+        ports: vec![],
+        env_required: vec![],
+        working_dir: None,
+    });
+
+    for name in find_executables(root).await {
+        result.scripts.push(DiscoveredScript {
+            name: format!("exec {}", name),
+            command: format!("dune exec {}", name),
+            source: ScriptSource::Detected,
+            category: ScriptCategory::Dev,
+            description: Some(format!("Run the {} executable", name)),
+            ports: vec![],
+            env_required: vec![],
+            working_dir: None,
+        });
+    }
+
+    result.scripts.push(DiscoveredScript {
+        name: "fmt".to_string(),
+        command: "dune fmt".to_string(),
+        source: ScriptSource::Detected,
+        category: ScriptCategory::Lint,
+        description: Some("Format code".to_string()),
+        ports: vec![],
+        env_required: vec![],
+        working_dir: None,
+    });
+
+    if has_opam {
+        result.scripts.push(DiscoveredScript {
+            name: "deps".to_string(),
+            command: "opam install . --deps-only".to_string(),
+            source: ScriptSource::Detected,
+            category: ScriptCategory::Utility,
+            description: Some("Install opam dependencies".to_string()),
+            ports: vec![],
+            env_required: vec![],
+            working_dir: None,
+        });
+    }
+
+    Ok(Some(result))
+}
+
+async fn has_opam_file(root: &Path) -> bool {
+    let Ok(mut entries) = tokio::fs::read_dir(root).await else {
+        return false;
+    };
+
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        if entry.path().extension().is_some_and(|ext| ext == "opam") {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Collect executable names from `dune` files in the root and its direct subdirectories
+async fn find_executables(root: &Path) -> Vec<String> {
+    let mut dune_files = vec![root.join("dune")];
+
+    if let Ok(mut entries) = tokio::fs::read_dir(root).await {
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            let hidden = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with('.') || n == "_build");
+            if path.is_dir() && !hidden {
+                dune_files.push(path.join("dune"));
+            }
+        }
+    }
+
+    let mut names = Vec::new();
+    for file in dune_files {
+        if let Ok(content) = tokio::fs::read_to_string(&file).await {
+            names.extend(parse_executable_names(&content));
+        }
+    }
+
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Best-effort extraction of `(executable (name x))` and `(executables (names a b))` stanzas
+fn parse_executable_names(content: &str) -> Vec<String> {
+    let mut names = Vec::new();
+
+    for (idx, _) in content.match_indices("(executable") {
+        let stanza = &content[idx..];
+        let stanza = stanza[1..]
+            .find("(executable")
+            .map(|next| &stanza[..next + 1])
+            .unwrap_or(stanza);
+
+        if let Some(list) = field_value(stanza, "(names ") {
+            names.extend(list.split_whitespace().map(str::to_string));
+        } else if let Some(name) = field_value(stanza, "(name ") {
+            names.push(name.trim().to_string());
+        }
+    }
+
+    names
+}
+
+fn field_value<'a>(stanza: &'a str, field: &str) -> Option<&'a str> {
+    let start = stanza.find(field)? + field.len();
+    let end = stanza[start..].find(')')? + start;
+    Some(&stanza[start..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn detects_dune_executables() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("dune-project"), "(lang dune 3.0)\n").unwrap();
+        std::fs::create_dir(dir.path().join("bin")).unwrap();
+        std::fs::write(
+            dir.path().join("bin").join("dune"),
+            "(executable\n (public_name hello)\n (name main)\n (libraries hello))\n",
+        )
+        .unwrap();
+
+        let result = analyze(dir.path())
+            .await
+            .expect("analyze should succeed")
+            .expect("should detect dune project");
+
+        assert_eq!(result.project_kind, ProjectKind::OCaml);
+        assert!(result.scripts.iter().any(|s| s.command == "dune exec main"));
+        assert!(result.scripts.iter().any(|s| s.command == "dune build"));
+    }
+
+    #[test]
+    fn parses_multiple_executables_stanza() {
+        let names = parse_executable_names("(executables (names foo bar))");
+        assert_eq!(names, vec!["foo".to_string(), "bar".to_string()]);
+    }
+}
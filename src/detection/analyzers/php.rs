@@ -0,0 +1,225 @@
+//! PHP / Composer / Laravel / Symfony project analyzer
+
+use anyhow::Result;
+use serde::Deserialize;
+use std::path::Path;
+
+use super::generic::categorize_task;
+use super::AnalyzerResult;
+use crate::detection::{
+    DiscoveredScript, ExpectedPort, PhpFramework, ProjectKind, ScriptCategory, ScriptSource,
+};
+
+/// Port `php artisan serve` binds unless `--port` overrides it
+const ARTISAN_DEFAULT_PORT: u16 = 8000;
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct ComposerJson {
+    require: indexmap::IndexMap<String, serde_json::Value>,
+    #[serde(rename = "require-dev")]
+    require_dev: indexmap::IndexMap<String, serde_json::Value>,
+    /// A command line, or a list of them run in order
+    scripts: indexmap::IndexMap<String, serde_json::Value>,
+    #[serde(rename = "scripts-descriptions")]
+    scripts_descriptions: indexmap::IndexMap<String, String>,
+}
+
+impl ComposerJson {
+    fn depends_on(&self, package: &str) -> bool {
+        self.require.contains_key(package) || self.require_dev.contains_key(package)
+    }
+}
+
+pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
+    let has_composer = root.join("composer.json").exists();
+    let has_artisan = root.join("artisan").exists();
+
+    if !has_composer && !has_artisan {
+        return Ok(None);
+    }
+
+    let composer: ComposerJson = match tokio::fs::read_to_string(root.join("composer.json")).await {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
+        Err(_) => ComposerJson::default(),
+    };
+    let framework = detect_framework(&composer, has_artisan);
+
+    let mut result = AnalyzerResult::new(
+        ProjectKind::Php {
+            framework: framework.clone(),
+        },
+        0.9,
+    );
+
+    if has_composer {
+        result.scripts.push(DiscoveredScript {
+            name: "install".to_string(),
+            command: "composer install".to_string(),
+            source: ScriptSource::Detected,
+            category: ScriptCategory::Utility,
+            description: Some("Install Composer dependencies".to_string()),
+            ports: vec![],
+            env_required: vec![],
+            working_dir: None,
+        });
+    }
+
+    for name in composer.scripts.keys() {
+        // `pre-install-cmd`, `post-autoload-dump` and friends are Composer event hooks
+        if name.starts_with("pre-") || name.starts_with("post-") {
+            continue;
+        }
+        result.scripts.push(DiscoveredScript {
+            name: name.clone(),
+            command: format!("composer run {}", name),
+            source: ScriptSource::ComposerJson,
+            category: categorize_task(name),
+            description: composer.scripts_descriptions.get(name).cloned(),
+            ports: vec![],
+            env_required: vec![],
+            working_dir: None,
+        });
+    }
+
+    if has_artisan {
+        result.scripts.push(DiscoveredScript {
+            name: "serve".to_string(),
+            command: "php artisan serve".to_string(),
+            source: ScriptSource::Detected,
+            category: ScriptCategory::Dev,
+            description: Some("Start the Laravel development server".to_string()),
+            ports: vec![ARTISAN_DEFAULT_PORT],
+            env_required: vec![],
+            working_dir: None,
+        });
+        result.scripts.push(DiscoveredScript {
+            name: "migrate".to_string(),
+            command: "php artisan migrate".to_string(),
+            source: ScriptSource::Detected,
+            category: ScriptCategory::Database,
+            description: Some("Run database migrations".to_string()),
+            ports: vec![],
+            env_required: vec![],
+            working_dir: None,
+        });
+        result.expected_ports.push(ExpectedPort {
+            port: ARTISAN_DEFAULT_PORT,
+            source: "artisan".to_string(),
+            service_name: "laravel".to_string(),
+        });
+    }
+
+    if framework == Some(PhpFramework::Symfony) && root.join("bin").join("console").exists() {
+        result.scripts.push(DiscoveredScript {
+            name: "cache:clear".to_string(),
+            command: "php bin/console cache:clear".to_string(),
+            source: ScriptSource::Detected,
+            category: ScriptCategory::Utility,
+            description: Some("Clear the Symfony cache".to_string()),
+            ports: vec![],
+            env_required: vec![],
+            working_dir: None,
+        });
+    }
+
+    if composer.depends_on("phpunit/phpunit") && !composer.scripts.contains_key("test") {
+        result.scripts.push(DiscoveredScript {
+            name: "test".to_string(),
+            command: "vendor/bin/phpunit".to_string(),
+            source: ScriptSource::Detected,
+            category: ScriptCategory::Test,
+            description: Some("Run PHPUnit".to_string()),
+            ports: vec![],
+            env_required: vec![],
+            working_dir: None,
+        });
+    }
+
+    Ok(Some(result))
+}
+
+/// Laravel or Symfony, from the framework package the project requires
+fn detect_framework(composer: &ComposerJson, has_artisan: bool) -> Option<PhpFramework> {
+    if composer.depends_on("laravel/framework") || has_artisan {
+        Some(PhpFramework::Laravel)
+    } else if composer.depends_on("symfony/framework-bundle") {
+        Some(PhpFramework::Symfony)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn composer_scripts_and_artisan_commands() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("composer.json"),
+            r#"{
+                "require": { "php": "^8.2", "laravel/framework": "^11.0" },
+                "require-dev": { "phpunit/phpunit": "^11.0" },
+                "scripts": {
+                    "post-autoload-dump": ["@php artisan package:discover --ansi"],
+                    "lint": "pint --test",
+                    "dev": ["Composer\\Config::disableProcessTimeout", "npx concurrently \"php artisan serve\" \"npm run dev\""]
+                },
+                "scripts-descriptions": { "lint": "Check code style" }
+            }"#,
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("artisan"), "#!/usr/bin/env php\n").unwrap();
+
+        let result = analyze(dir.path())
+            .await
+            .expect("analyze should succeed")
+            .expect("should detect a PHP project");
+
+        assert_eq!(
+            result.project_kind,
+            ProjectKind::Php {
+                framework: Some(PhpFramework::Laravel)
+            }
+        );
+        let composer: Vec<(&str, &str)> = result
+            .scripts
+            .iter()
+            .filter(|s| s.source == ScriptSource::ComposerJson)
+            .map(|s| (s.name.as_str(), s.command.as_str()))
+            .collect();
+        assert_eq!(
+            composer,
+            [("lint", "composer run lint"), ("dev", "composer run dev")]
+        );
+        let lint = result.scripts.iter().find(|s| s.name == "lint").unwrap();
+        assert_eq!(lint.description.as_deref(), Some("Check code style"));
+
+        let command = |name: &str| {
+            result
+                .scripts
+                .iter()
+                .find(|s| s.name == name)
+                .map(|s| s.command.as_str())
+        };
+        assert_eq!(command("serve"), Some("php artisan serve"));
+        assert_eq!(command("migrate"), Some("php artisan migrate"));
+        assert_eq!(command("test"), Some("vendor/bin/phpunit"));
+        assert_eq!(result.expected_ports[0].port, ARTISAN_DEFAULT_PORT);
+    }
+
+    #[test]
+    fn symfony_from_framework_bundle() {
+        let composer: ComposerJson =
+            serde_json::from_str(r#"{ "require": { "symfony/framework-bundle": "7.1.*" } }"#)
+                .unwrap();
+        assert_eq!(
+            detect_framework(&composer, false),
+            Some(PhpFramework::Symfony)
+        );
+        assert_eq!(detect_framework(&ComposerJson::default(), false), None);
+    }
+}
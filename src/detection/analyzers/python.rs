@@ -1,9 +1,9 @@
 //! Python project analyzer
 
 use anyhow::Result;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use super::AnalyzerResult;
+use super::{workspace, AnalyzerResult};
 use crate::detection::{
     DiscoveredScript, EnvVarSpec, ExpectedPort, ProjectKind, PythonEnvType, PythonFramework,
     ScriptCategory, ScriptSource,
@@ -16,19 +16,33 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
     let has_setup_py = root.join("setup.py").exists();
     let has_pipfile = root.join("Pipfile").exists();
 
-    if !has_pyproject && !has_requirements && !has_setup_py && !has_pipfile {
+    let is_package = has_pyproject || has_requirements || has_setup_py || has_pipfile;
+
+    let members = workspace_members(root).await;
+    if !is_package && members.is_empty() {
         return Ok(None);
     }
 
     let env_type = detect_env_type(root);
-    let framework = detect_framework(root).await?;
+    let root_framework = detect_framework(root).await?;
+    let mut framework = root_framework.clone();
+    for (dir, _) in &members {
+        if framework.is_some() {
+            break;
+        }
+        framework = detect_framework(dir).await?;
+    }
 
     let mut result = AnalyzerResult::new(
         ProjectKind::Python {
             env_type: env_type.clone(),
             framework: framework.clone(),
         },
-        0.9,
+        if is_package {
+            0.9
+        } else {
+            workspace::SIBLING_CONFIDENCE
+        },
     );
 
     // Add environment activation
@@ -42,6 +56,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
                 description: Some("Activate Poetry environment".to_string()),
                 ports: vec![],
                 env_required: vec![],
+                working_dir: None,
             });
             result.scripts.push(DiscoveredScript {
                 name: "install".to_string(),
@@ -51,6 +66,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
                 description: Some("Install dependencies".to_string()),
                 ports: vec![],
                 env_required: vec![],
+                working_dir: None,
             });
         }
         PythonEnvType::Pipenv => {
@@ -62,6 +78,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
                 description: Some("Activate Pipenv environment".to_string()),
                 ports: vec![],
                 env_required: vec![],
+                working_dir: None,
             });
             result.scripts.push(DiscoveredScript {
                 name: "install".to_string(),
@@ -71,6 +88,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
                 description: Some("Install dependencies".to_string()),
                 ports: vec![],
                 env_required: vec![],
+                working_dir: None,
             });
         }
         PythonEnvType::Uv => {
@@ -82,6 +100,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
                 description: Some("Sync dependencies".to_string()),
                 ports: vec![],
                 env_required: vec![],
+                working_dir: None,
             });
         }
         PythonEnvType::Venv => {
@@ -93,6 +112,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
                 description: Some("Activate virtual environment".to_string()),
                 ports: vec![],
                 env_required: vec![],
+                working_dir: None,
             });
             result.scripts.push(DiscoveredScript {
                 name: "install".to_string(),
@@ -102,13 +122,66 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
                 description: Some("Install dependencies".to_string()),
                 ports: vec![],
                 env_required: vec![],
+                working_dir: None,
             });
         }
         _ => {}
     }
 
-    // Add framework-specific commands
-    match &framework {
+    if is_package {
+        add_framework_commands(&mut result, root_framework.as_ref());
+        add_common_commands(&mut result);
+    }
+
+    // Each workspace package gets its own server and test commands, run from its directory
+    for (dir, label) in &members {
+        result.member_manifests.push(dir.join("pyproject.toml"));
+        let mut package = AnalyzerResult::new(result.project_kind.clone(), 0.0);
+        add_framework_commands(&mut package, detect_framework(dir).await?.as_ref());
+        add_common_commands(&mut package);
+
+        result.scripts.extend(
+            package
+                .scripts
+                .into_iter()
+                .map(|script| script.in_package(label, dir)),
+        );
+        for mut port in package.expected_ports {
+            port.service_name = label.clone();
+            result.expected_ports.push(port);
+        }
+        for var in package.env_vars {
+            if !result.env_vars.iter().any(|v| v.name == var.name) {
+                result.env_vars.push(var);
+            }
+        }
+    }
+
+    // Poetry doesn't activate its virtualenv in the shell, so our commands go through it
+    if env_type == PythonEnvType::Poetry {
+        for script in &mut result.scripts {
+            script.command = poetry_run(&script.command);
+        }
+    }
+
+    Ok(Some(result))
+}
+
+/// `command` run inside the Poetry virtualenv; compound commands are wrapped in
+/// `sh -c` so every part of them runs there, not just the first program
+fn poetry_run(command: &str) -> String {
+    if command.split_whitespace().next() == Some("poetry") {
+        command.to_string()
+    } else if command.contains(['&', '|', ';', '<', '>', '$', '`', '(', '\n']) {
+        format!("poetry run sh -c '{}'", command.replace('\'', r"'\''"))
+    } else {
+        format!("poetry run {}", command)
+    }
+}
+
+/// Dev server, migration and env requirements for a known web framework
+fn add_framework_commands(result: &mut AnalyzerResult, framework: Option<&PythonFramework>) {
+    match framework {
         Some(PythonFramework::Django) => {
             result.scripts.push(DiscoveredScript {
                 name: "runserver".to_string(),
@@ -118,6 +191,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
                 description: Some("Start Django development server".to_string()),
                 ports: vec![8000],
                 env_required: vec![],
+                working_dir: None,
             });
             result.scripts.push(DiscoveredScript {
                 name: "migrate".to_string(),
@@ -127,6 +201,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
                 description: Some("Run database migrations".to_string()),
                 ports: vec![],
                 env_required: vec![],
+                working_dir: None,
             });
             result.scripts.push(DiscoveredScript {
                 name: "makemigrations".to_string(),
@@ -136,6 +211,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
                 description: Some("Create new migrations".to_string()),
                 ports: vec![],
                 env_required: vec![],
+                working_dir: None,
             });
             result.expected_ports.push(ExpectedPort {
                 port: 8000,
@@ -152,6 +228,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
                 description: Some("Start Flask development server".to_string()),
                 ports: vec![5000],
                 env_required: vec!["FLASK_APP".to_string()],
+                working_dir: None,
             });
             result.expected_ports.push(ExpectedPort {
                 port: 5000,
@@ -175,6 +252,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
                 description: Some("Start FastAPI development server".to_string()),
                 ports: vec![8000],
                 env_required: vec![],
+                working_dir: None,
             });
             result.expected_ports.push(ExpectedPort {
                 port: 8000,
@@ -184,8 +262,10 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
         }
         _ => {}
     }
+}
 
-    // Common Python commands
+/// pytest and ruff, which apply to any Python package
+fn add_common_commands(result: &mut AnalyzerResult) {
     result.scripts.push(DiscoveredScript {
         name: "test".to_string(),
         command: "pytest".to_string(),
@@ -194,6 +274,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
         description: Some("Run tests with pytest".to_string()),
         ports: vec![],
         env_required: vec![],
+        working_dir: None,
     });
 
     result.scripts.push(DiscoveredScript {
@@ -204,9 +285,44 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
         description: Some("Run linter".to_string()),
         ports: vec![],
         env_required: vec![],
+        working_dir: None,
     });
+}
 
-    Ok(Some(result))
+/// uv workspace members, or sibling directories with their own `pyproject.toml`,
+/// paired with the label their actions are prefixed with
+async fn workspace_members(root: &Path) -> Vec<(PathBuf, String)> {
+    let pyproject = tokio::fs::read_to_string(root.join("pyproject.toml"))
+        .await
+        .ok()
+        .and_then(|content| content.parse::<toml::Table>().ok());
+    let patterns: Option<Vec<String>> = pyproject
+        .as_ref()
+        .and_then(|t| t.get("tool")?.get("uv")?.get("workspace")?.get("members"))
+        .and_then(|m| m.as_array())
+        .map(|members| {
+            members
+                .iter()
+                .filter_map(|m| m.as_str().map(str::to_string))
+                .collect()
+        });
+
+    let dirs = match patterns {
+        Some(patterns) => workspace::members(root, &patterns, "pyproject.toml"),
+        None => workspace::sibling_packages(root, "pyproject.toml"),
+    };
+
+    let mut members = Vec::new();
+    for dir in dirs {
+        let name = tokio::fs::read_to_string(dir.join("pyproject.toml"))
+            .await
+            .ok()
+            .and_then(|content| content.parse::<toml::Table>().ok())
+            .and_then(|t| t.get("project")?.get("name")?.as_str().map(str::to_string));
+        let label = workspace::package_label(name.as_deref(), &dir);
+        members.push((dir, label));
+    }
+    members
 }
 
 fn detect_env_type(root: &Path) -> PythonEnvType {
@@ -262,3 +378,62 @@ async fn detect_framework(root: &Path) -> Result<Option<PythonFramework>> {
 
     Ok(None)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn sibling_pyprojects_become_scoped_packages() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("api")).unwrap();
+        std::fs::write(
+            dir.path().join("api").join("pyproject.toml"),
+            "[project]\nname = \"billing-api\"\ndependencies = [\"fastapi\"]\n",
+        )
+        .unwrap();
+
+        let result = analyze(dir.path())
+            .await
+            .expect("analyze should succeed")
+            .expect("should detect the api package");
+
+        let api = dir.path().join("api");
+        let dev = result
+            .scripts
+            .iter()
+            .find(|s| s.name == "billing-api:dev")
+            .expect("scoped dev server");
+        assert_eq!(dev.working_dir.as_deref(), Some(api.as_path()));
+        assert!(result.scripts.iter().all(|s| s.working_dir.is_some()));
+        assert_eq!(result.expected_ports[0].service_name, "billing-api");
+    }
+
+    #[tokio::test]
+    async fn poetry_projects_run_their_commands_through_poetry() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("pyproject.toml"),
+            "[tool.poetry]\nname = \"shop\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("poetry.lock"), "").unwrap();
+
+        let result = analyze(dir.path()).await.unwrap().unwrap();
+        let command = |name: &str| {
+            result
+                .scripts
+                .iter()
+                .find(|s| s.name == name)
+                .map(|s| s.command.as_str())
+        };
+        assert_eq!(command("test"), Some("poetry run pytest"));
+        assert_eq!(command("install"), Some("poetry install"));
+
+        assert_eq!(
+            poetry_run("pytest && echo 'done'"),
+            r#"poetry run sh -c 'pytest && echo '\''done'\'''"#
+        );
+    }
+}
@@ -0,0 +1,309 @@
+//! Ruby / Rails / Sinatra project analyzer
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::Path;
+
+use super::generic::categorize_task;
+use super::AnalyzerResult;
+use crate::detection::{
+    DiscoveredScript, ExpectedPort, ProjectKind, RubyFramework, ScriptCategory, ScriptSource,
+};
+
+/// Port `rails server` binds unless `-p` or `PORT` overrides it
+const RAILS_DEFAULT_PORT: u16 = 3000;
+/// Port `rackup` binds for Sinatra and other Rack apps
+const RACKUP_DEFAULT_PORT: u16 = 9292;
+
+/// `task :name`, `task "name"`, `task name: deps` and `task(:name)`
+static RAKE_TASK_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^\s*task\s*\(?\s*(?::([\w?!]+)|["']([\w:-]+)["']|([\w?!]+):\s)"#)
+        .expect("Invalid rake task regex")
+});
+/// `namespace :db do`
+static RAKE_NAMESPACE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^(\s*)namespace\s*\(?\s*(?::(\w+)|["'](\w+)["'])"#)
+        .expect("Invalid rake namespace regex")
+});
+/// `desc "Seed the database"`
+static RAKE_DESC_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"^\s*desc\s*\(?\s*["'](.*)["']"#).expect("Invalid rake desc regex"));
+
+pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
+    let has_gemfile = root.join("Gemfile").exists();
+    let has_rakefile = root.join("Rakefile").exists();
+    let has_config_ru = root.join("config.ru").exists();
+
+    if !has_gemfile && !has_rakefile && !has_config_ru {
+        return Ok(None);
+    }
+
+    let gemfile = tokio::fs::read_to_string(root.join("Gemfile"))
+        .await
+        .unwrap_or_default();
+    let framework = detect_framework(&gemfile);
+    // Run through Bundler so the Gemfile's versions are used
+    let exec = if has_gemfile { "bundle exec " } else { "" };
+
+    let mut result = AnalyzerResult::new(
+        ProjectKind::Ruby {
+            framework: framework.clone(),
+        },
+        0.9,
+    );
+
+    if has_gemfile {
+        result.scripts.push(DiscoveredScript {
+            name: "install".to_string(),
+            command: "bundle install".to_string(),
+            source: ScriptSource::Detected,
+            category: ScriptCategory::Utility,
+            description: Some("Install gems".to_string()),
+            ports: vec![],
+            env_required: vec![],
+            working_dir: None,
+        });
+    }
+
+    result.scripts.push(DiscoveredScript {
+        name: "test".to_string(),
+        command: format!("{}rake test", exec),
+        source: ScriptSource::Detected,
+        category: ScriptCategory::Test,
+        description: Some("Run tests".to_string()),
+        ports: vec![],
+        env_required: vec![],
+        working_dir: None,
+    });
+
+    match framework {
+        Some(RubyFramework::Rails) => {
+            let rails = if root.join("bin").join("rails").exists() {
+                "bin/rails".to_string()
+            } else {
+                format!("{}rails", exec)
+            };
+            result.scripts.push(DiscoveredScript {
+                name: "server".to_string(),
+                command: format!("{} server", rails),
+                source: ScriptSource::Detected,
+                category: ScriptCategory::Dev,
+                description: Some("Start Rails development server".to_string()),
+                ports: vec![RAILS_DEFAULT_PORT],
+                env_required: vec![],
+                working_dir: None,
+            });
+            result.scripts.push(DiscoveredScript {
+                name: "console".to_string(),
+                command: format!("{} console", rails),
+                source: ScriptSource::Detected,
+                category: ScriptCategory::Dev,
+                description: Some("Open the Rails console".to_string()),
+                ports: vec![],
+                env_required: vec![],
+                working_dir: None,
+            });
+            result.scripts.push(DiscoveredScript {
+                name: "migrate".to_string(),
+                command: format!("{} db:migrate", rails),
+                source: ScriptSource::Detected,
+                category: ScriptCategory::Database,
+                description: Some("Run database migrations".to_string()),
+                ports: vec![],
+                env_required: vec![],
+                working_dir: None,
+            });
+            result.expected_ports.push(ExpectedPort {
+                port: RAILS_DEFAULT_PORT,
+                source: "Rails".to_string(),
+                service_name: "rails".to_string(),
+            });
+        }
+        Some(RubyFramework::Sinatra) if has_config_ru => {
+            result.scripts.push(DiscoveredScript {
+                name: "server".to_string(),
+                command: format!("{}rackup", exec),
+                source: ScriptSource::Detected,
+                category: ScriptCategory::Dev,
+                description: Some("Start Sinatra with rackup".to_string()),
+                ports: vec![RACKUP_DEFAULT_PORT],
+                env_required: vec![],
+                working_dir: None,
+            });
+            result.expected_ports.push(ExpectedPort {
+                port: RACKUP_DEFAULT_PORT,
+                source: "config.ru".to_string(),
+                service_name: "sinatra".to_string(),
+            });
+        }
+        _ => {}
+    }
+
+    if has_rakefile {
+        let content = tokio::fs::read_to_string(root.join("Rakefile"))
+            .await
+            .unwrap_or_default();
+        for (task, description) in rake_tasks(&content) {
+            result.scripts.push(DiscoveredScript {
+                name: format!("rake {}", task),
+                command: format!("{}rake {}", exec, task),
+                source: ScriptSource::Rakefile,
+                category: categorize_task(&task),
+                description,
+                ports: vec![],
+                env_required: vec![],
+                working_dir: None,
+            });
+        }
+    }
+
+    Ok(Some(result))
+}
+
+/// Rails or Sinatra, from the gems the Gemfile declares
+fn detect_framework(gemfile: &str) -> Option<RubyFramework> {
+    let declares = |gem: &str| {
+        gemfile.lines().any(|line| {
+            let line = line.trim_start();
+            line.strip_prefix("gem ")
+                .map(|rest| rest.trim_start().trim_start_matches(['"', '\'']))
+                .is_some_and(|rest| {
+                    rest.strip_prefix(gem)
+                        .is_some_and(|end| end.starts_with(['"', '\'']))
+                })
+        })
+    };
+
+    if declares("rails") {
+        Some(RubyFramework::Rails)
+    } else if declares("sinatra") {
+        Some(RubyFramework::Sinatra)
+    } else {
+        None
+    }
+}
+
+/// Task names (with their `namespace:` prefix) and `desc` lines from a Rakefile, in order
+fn rake_tasks(content: &str) -> Vec<(String, Option<String>)> {
+    let mut tasks: Vec<(String, Option<String>)> = Vec::new();
+    // Open namespaces with the indentation of their `namespace` line, closed by an
+    // `end` at the same indentation
+    let mut namespaces: Vec<(usize, String)> = Vec::new();
+    let mut description = None;
+
+    for line in content.lines() {
+        let indent = line.len() - line.trim_start().len();
+        if line.trim() == "end" && namespaces.last().is_some_and(|(i, _)| *i == indent) {
+            namespaces.pop();
+            continue;
+        }
+        if let Some(caps) = RAKE_NAMESPACE_REGEX.captures(line) {
+            let name = caps.get(2).or(caps.get(3)).map_or("", |m| m.as_str());
+            namespaces.push((indent, name.to_string()));
+            continue;
+        }
+        if let Some(caps) = RAKE_DESC_REGEX.captures(line) {
+            description = Some(caps[1].to_string());
+            continue;
+        }
+        if let Some(caps) = RAKE_TASK_REGEX.captures(line) {
+            let name = caps
+                .get(1)
+                .or(caps.get(2))
+                .or(caps.get(3))
+                .map_or("", |m| m.as_str());
+            let mut full: Vec<&str> = namespaces.iter().map(|(_, n)| n.as_str()).collect();
+            full.push(name);
+            let full = full.join(":");
+            if !tasks.iter().any(|(known, _)| *known == full) {
+                tasks.push((full, description.take()));
+            }
+        }
+    }
+
+    tasks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn detects_rails_with_server_port_and_rake_tasks() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("Gemfile"),
+            concat!(
+                "source \"https://rubygems.org\"\n",
+                "gem \"rails\", \"~> 7.1\"\n",
+                "gem 'rails-html-sanitizer'\n",
+            ),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("Rakefile"),
+            concat!(
+                "require_relative \"config/application\"\n",
+                "Rails.application.load_tasks\n\n",
+                "desc \"Rebuild search indexes\"\n",
+                "task :reindex => :environment do\n",
+                "  Search.reindex!\n",
+                "end\n\n",
+                "namespace :db do\n",
+                "  task seed_demo: :environment do\n",
+                "  end\n",
+                "end\n",
+                "task \"lint\" do\n",
+                "end\n",
+            ),
+        )
+        .unwrap();
+
+        let result = analyze(dir.path())
+            .await
+            .expect("analyze should succeed")
+            .expect("should detect a Ruby project");
+
+        assert_eq!(
+            result.project_kind,
+            ProjectKind::Ruby {
+                framework: Some(RubyFramework::Rails)
+            }
+        );
+        let command = |name: &str| {
+            result
+                .scripts
+                .iter()
+                .find(|s| s.name == name)
+                .map(|s| s.command.as_str())
+        };
+        assert_eq!(command("install"), Some("bundle install"));
+        assert_eq!(command("test"), Some("bundle exec rake test"));
+        assert_eq!(command("server"), Some("bundle exec rails server"));
+        assert_eq!(result.expected_ports[0].port, RAILS_DEFAULT_PORT);
+
+        let rake: Vec<&str> = result
+            .scripts
+            .iter()
+            .filter(|s| s.source == ScriptSource::Rakefile)
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(rake, ["rake reindex", "rake db:seed_demo", "rake lint"]);
+        let reindex = result.scripts.iter().find(|s| s.name == "rake reindex");
+        assert_eq!(
+            reindex.and_then(|s| s.description.as_deref()),
+            Some("Rebuild search indexes")
+        );
+    }
+
+    #[test]
+    fn tells_sinatra_from_rails() {
+        assert_eq!(
+            detect_framework("gem 'sinatra'\ngem 'puma'\n"),
+            Some(RubyFramework::Sinatra)
+        );
+        assert_eq!(detect_framework("gem 'rails-html-sanitizer'\n"), None);
+    }
+}
@@ -39,6 +39,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
         description: Some("Build the project".to_string()),
         ports: vec![],
         env_required: vec![],
+        working_dir: None,
     });
 
     result.scripts.push(DiscoveredScript {
@@ -49,6 +50,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
         description: Some("Build for release".to_string()),
         ports: vec![],
         env_required: vec![],
+        working_dir: None,
     });
 
     result.scripts.push(DiscoveredScript {
@@ -59,6 +61,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
         description: Some("Run the project".to_string()),
         ports: vec![],
         env_required: vec![],
+        working_dir: None,
     });
 
     result.scripts.push(DiscoveredScript {
@@ -69,6 +72,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
         description: Some("Run tests".to_string()),
         ports: vec![],
         env_required: vec![],
+        working_dir: None,
     });
 
     result.scripts.push(DiscoveredScript {
@@ -79,6 +83,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
         description: Some("Run clippy lints".to_string()),
         ports: vec![],
         env_required: vec![],
+        working_dir: None,
     });
 
     result.scripts.push(DiscoveredScript {
@@ -89,6 +94,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
         description: Some("Format code".to_string()),
         ports: vec![],
         env_required: vec![],
+        working_dir: None,
     });
 
     result.scripts.push(DiscoveredScript {
@@ -99,6 +105,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
         description: Some("Clean build artifacts".to_string()),
         ports: vec![],
         env_required: vec![],
+        working_dir: None,
     });
 
     result.scripts.push(DiscoveredScript {
@@ -109,6 +116,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
         description: Some("Generate and open documentation".to_string()),
         ports: vec![],
         env_required: vec![],
+        working_dir: None,
     });
 
     // Add a test action for debugging output streaming
@@ -121,6 +129,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
         description: Some("Test output streaming".to_string()),
         ports: vec![],
         env_required: vec![],
+        working_dir: None,
     });
 
     // Check for common tools in dev-dependencies
@@ -133,6 +142,7 @@ pub async fn analyze(root: &Path) -> Result<Option<AnalyzerResult>> {
             description: Some("Watch and run on changes".to_string()),
             ports: vec![],
             env_required: vec![],
+            working_dir: None,
         });
     }
 
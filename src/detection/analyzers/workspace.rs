@@ -0,0 +1,70 @@
+//! Monorepo sub-package discovery shared by the language analyzers
+
+use std::path::{Path, PathBuf};
+
+/// Confidence for a language found only in undeclared sibling packages, low
+/// enough that any analyzer matching the root itself is preferred as primary
+pub const SIBLING_CONFIDENCE: f32 = 0.4;
+
+/// Directories that never hold workspace packages worth listing
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target", "dist", "build", "vendor", "venv"];
+
+/// Package directories matching workspace globs like `packages/*`, keeping only
+/// those with a `manifest`; `!`-prefixed patterns exclude matches
+pub fn members(root: &Path, patterns: &[String], manifest: &str) -> Vec<PathBuf> {
+    let (excludes, includes): (Vec<&String>, Vec<&String>) =
+        patterns.iter().partition(|p| p.starts_with('!'));
+    let excluded: Vec<glob::Pattern> = excludes
+        .iter()
+        .filter_map(|p| glob::Pattern::new(p.trim_start_matches('!').trim_end_matches('/')).ok())
+        .collect();
+
+    let mut dirs = Vec::new();
+    for pattern in includes {
+        let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+        let Ok(paths) = glob::glob(&root.join(pattern).to_string_lossy()) else {
+            continue;
+        };
+        for dir in paths.flatten() {
+            let Ok(relative) = dir.strip_prefix(root) else {
+                continue;
+            };
+            if dir == root
+                || !dir.join(manifest).is_file()
+                || is_skipped(relative)
+                || excluded.iter().any(|e| e.matches_path(relative))
+                || dirs.contains(&dir)
+            {
+                continue;
+            }
+            dirs.push(dir);
+        }
+    }
+    dirs.sort();
+    dirs
+}
+
+/// Immediate subdirectories with their own `manifest`, for repos that keep
+/// several packages side by side without declaring a workspace
+pub fn sibling_packages(root: &Path, manifest: &str) -> Vec<PathBuf> {
+    members(root, &["*".to_string()], manifest)
+}
+
+/// Short label for a package: its declared name without an npm scope, else the directory name
+pub fn package_label(declared: Option<&str>, dir: &Path) -> String {
+    declared
+        .map(|name| name.rsplit('/').next().unwrap_or(name).to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| {
+            dir.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default()
+        })
+}
+
+fn is_skipped(relative: &Path) -> bool {
+    relative.components().any(|c| {
+        let name = c.as_os_str().to_string_lossy();
+        name.starts_with('.') || SKIPPED_DIRS.contains(&name.as_ref())
+    })
+}
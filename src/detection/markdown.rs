@@ -0,0 +1,169 @@
+//! Markdown export of a detected project, for READMEs and onboarding docs
+
+use std::fmt::Write;
+
+use super::{ProjectContext, ProjectKind, ScriptCategory};
+
+/// Category order used for the "Scripts" section
+const CATEGORY_ORDER: &[(ScriptCategory, &str)] = &[
+    (ScriptCategory::Dev, "Development"),
+    (ScriptCategory::Build, "Build"),
+    (ScriptCategory::Test, "Test"),
+    (ScriptCategory::Lint, "Lint"),
+    (ScriptCategory::Database, "Database"),
+    (ScriptCategory::Docker, "Docker"),
+    (ScriptCategory::Deploy, "Deploy"),
+    (ScriptCategory::Utility, "Utility"),
+];
+
+/// Render what was detected about `project` as a Markdown document
+pub fn project_summary(project: &ProjectContext) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# {}", project.name);
+
+    if !project.types.is_empty() {
+        let _ = writeln!(out, "\n## Project Types\n");
+        for t in &project.types {
+            let primary = if t.primary { " (primary)" } else { "" };
+            let _ = writeln!(out, "- {}{}", describe_kind(&t.kind), primary);
+        }
+    }
+
+    if !project.scripts.is_empty() {
+        let _ = writeln!(out, "\n## Scripts");
+        for (category, title) in CATEGORY_ORDER {
+            let scripts: Vec<_> = project
+                .scripts
+                .iter()
+                .filter(|s| s.category == *category)
+                .collect();
+            if scripts.is_empty() {
+                continue;
+            }
+
+            let _ = writeln!(out, "\n### {}\n", title);
+            for script in scripts {
+                let _ = write!(out, "- **{}**: `{}`", script.name, script.command);
+                if let Some(desc) = &script.description {
+                    let _ = write!(out, " — {}", desc);
+                }
+                out.push('\n');
+            }
+        }
+    }
+
+    if !project.ports.is_empty() {
+        let _ = writeln!(out, "\n## Ports\n");
+        for port in &project.ports {
+            let _ = writeln!(
+                out,
+                "- `{}` — {} ({})",
+                port.port, port.service_name, port.source
+            );
+        }
+    }
+
+    if !project.env_vars.required.is_empty() {
+        let _ = writeln!(out, "\n## Environment Variables\n");
+        for var in &project.env_vars.required {
+            let _ = write!(out, "- `{}`", var.name);
+            if let Some(desc) = &var.description {
+                let _ = write!(out, " — {}", desc);
+            }
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+fn describe_kind(kind: &ProjectKind) -> String {
+    match kind {
+        ProjectKind::Node {
+            package_manager,
+            framework,
+        } => match framework {
+            Some(fw) => format!("Node.js ({}, {:?})", package_manager, fw),
+            None => format!("Node.js ({})", package_manager),
+        },
+        ProjectKind::Rust {
+            workspace: true, ..
+        } => "Rust (workspace)".to_string(),
+        ProjectKind::Rust { .. } => "Rust".to_string(),
+        ProjectKind::Python { framework, .. } => match framework {
+            Some(fw) => format!("Python ({:?})", fw),
+            None => "Python".to_string(),
+        },
+        ProjectKind::Go { module_name } => format!("Go (`{}`)", module_name),
+        ProjectKind::Ruby { framework } => match framework {
+            Some(fw) => format!("Ruby ({:?})", fw),
+            None => "Ruby".to_string(),
+        },
+        ProjectKind::Php { framework } => match framework {
+            Some(fw) => format!("PHP ({:?})", fw),
+            None => "PHP".to_string(),
+        },
+        ProjectKind::Jvm { build_tool } => format!("JVM ({})", build_tool),
+        ProjectKind::OCaml => "OCaml".to_string(),
+        ProjectKind::Nim => "Nim".to_string(),
+        ProjectKind::Bazel { buck2: false } => "Bazel".to_string(),
+        ProjectKind::Bazel { buck2: true } => "Buck2".to_string(),
+        ProjectKind::Docker { compose: true, .. } => "Docker Compose".to_string(),
+        ProjectKind::Docker { .. } => "Docker".to_string(),
+        ProjectKind::Git => "Git".to_string(),
+        ProjectKind::Generic => "Generic".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::{
+        DiscoveredScript, EnvVarAnalysis, ExpectedPort, PackageManager, ProjectType, ScriptSource,
+    };
+
+    #[test]
+    fn node_summary_lists_dev_script() {
+        let project = ProjectContext {
+            root: "/tmp/web".into(),
+            name: "web".to_string(),
+            types: vec![ProjectType {
+                kind: ProjectKind::Node {
+                    package_manager: PackageManager::Pnpm,
+                    framework: None,
+                },
+                confidence: 1.0,
+                primary: true,
+            }],
+            scripts: vec![DiscoveredScript {
+                name: "dev".to_string(),
+                command: "pnpm run dev".to_string(),
+                source: ScriptSource::PackageJson,
+                category: ScriptCategory::Dev,
+                description: None,
+                ports: vec![3000],
+                env_required: vec![],
+                working_dir: None,
+            }],
+            env_vars: EnvVarAnalysis::default(),
+            ports: vec![ExpectedPort {
+                port: 3000,
+                source: "package.json".to_string(),
+                service_name: "dev".to_string(),
+            }],
+            docker_services: vec![],
+            git_info: None,
+            advisories: vec![],
+            warnings: vec![],
+            member_manifests: vec![],
+        };
+
+        let md = project_summary(&project);
+        assert!(md.starts_with("# web\n"));
+        assert!(md.contains("- Node.js (pnpm) (primary)"));
+        assert!(md.contains("## Scripts"));
+        assert!(md.contains("- **dev**: `pnpm run dev`"));
+        assert!(md.contains("- `3000` — dev (package.json)"));
+        assert!(!md.contains("## Environment Variables"));
+    }
+}
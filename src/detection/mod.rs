@@ -3,6 +3,7 @@
 #![allow(dead_code)]
 
 pub mod analyzers;
+pub mod markdown;
 
 use anyhow::Result;
 use std::path::{Path, PathBuf};
@@ -36,6 +37,22 @@ pub enum ProjectKind {
     Go {
         module_name: String,
     },
+    Ruby {
+        framework: Option<RubyFramework>,
+    },
+    Php {
+        framework: Option<PhpFramework>,
+    },
+    /// Java or Kotlin, built with Gradle or Maven
+    Jvm {
+        build_tool: BuildTool,
+    },
+    OCaml,
+    Nim,
+    /// Bazel workspace, or a Buck2 project when `buck2` is set
+    Bazel {
+        buck2: bool,
+    },
     Docker {
         compose: bool,
         services: Vec<String>,
@@ -44,6 +61,115 @@ pub enum ProjectKind {
     Generic,
 }
 
+impl ProjectKind {
+    /// Short name used in config, e.g. for `general.type_priority`
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::Node { .. } => "node",
+            Self::Rust { .. } => "rust",
+            Self::Python { .. } => "python",
+            Self::Go { .. } => "go",
+            Self::Ruby { .. } => "ruby",
+            Self::Php { .. } => "php",
+            Self::Jvm { .. } => "jvm",
+            Self::OCaml => "ocaml",
+            Self::Nim => "nim",
+            Self::Bazel { buck2: false } => "bazel",
+            Self::Bazel { buck2: true } => "buck2",
+            Self::Docker { .. } => "docker",
+            Self::Git => "git",
+            Self::Generic => "generic",
+        }
+    }
+}
+
+/// Order used to pick the primary type when confidences tie
+pub const DEFAULT_TYPE_PRIORITY: &[&str] = &[
+    "node", "rust", "python", "go", "ruby", "php", "jvm", "ocaml", "nim", "docker",
+];
+
+/// Mark the highest-confidence type as primary, breaking ties by `priority`.
+/// Kinds missing from `priority` rank after every listed kind.
+pub fn select_primary(types: &mut [ProjectType], priority: &[String]) {
+    let rank = |kind: &ProjectKind| {
+        priority
+            .iter()
+            .position(|p| p.eq_ignore_ascii_case(kind.key()))
+            .unwrap_or(priority.len())
+    };
+
+    let mut best: Option<usize> = None;
+    for (i, t) in types.iter().enumerate() {
+        best = match best {
+            Some(b)
+                if types[b].confidence > t.confidence
+                    || (types[b].confidence == t.confidence
+                        && rank(&types[b].kind) <= rank(&t.kind)) =>
+            {
+                Some(b)
+            }
+            _ => Some(i),
+        };
+    }
+
+    for (i, t) in types.iter_mut().enumerate() {
+        t.primary = Some(i) == best;
+    }
+}
+
+/// Sort scripts by name, dropping only exact repeats; same-named scripts that
+/// run different commands are all kept, under their original names
+fn disambiguate_scripts(mut scripts: Vec<DiscoveredScript>) -> Vec<DiscoveredScript> {
+    // Stable, so the first analyzer to report a name keeps its place
+    scripts.sort_by(|a, b| a.name.cmp(&b.name));
+    let mut unique: Vec<DiscoveredScript> = Vec::with_capacity(scripts.len());
+    for script in scripts {
+        if !unique.iter().any(|s| {
+            s.name == script.name
+                && s.source == script.source
+                && s.command == script.command
+                && s.working_dir == script.working_dir
+        }) {
+            unique.push(script);
+        }
+    }
+    unique
+}
+
+/// "Show dependency tree" for the ecosystems whose tooling has one
+fn dependency_tree_script(kind: &ProjectKind) -> Option<DiscoveredScript> {
+    let command = match kind {
+        ProjectKind::Node {
+            package_manager, ..
+        } => match package_manager {
+            PackageManager::Npm => "npm ls",
+            PackageManager::Yarn => "yarn list",
+            PackageManager::Pnpm => "pnpm list",
+            PackageManager::Bun => "bun pm ls",
+        },
+        ProjectKind::Rust { .. } => "cargo tree",
+        ProjectKind::Python { env_type, .. } => match env_type {
+            PythonEnvType::Poetry => "poetry show --tree",
+            PythonEnvType::Uv => "uv tree",
+            PythonEnvType::Pipenv => "pipenv graph",
+            _ => "pip list",
+        },
+        ProjectKind::Go { .. } => "go mod graph",
+        _ => return None,
+    };
+
+    Some(DiscoveredScript {
+        name: "deps tree".to_string(),
+        command: command.to_string(),
+        source: ScriptSource::Detected,
+        category: ScriptCategory::Utility,
+        description: Some("Show the dependency tree".to_string()),
+        ports: vec![],
+        env_required: vec![],
+        working_dir: None,
+    })
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum PackageManager {
     Npm,
@@ -93,6 +219,33 @@ pub enum PythonFramework {
     Starlette,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RubyFramework {
+    Rails,
+    Sinatra,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PhpFramework {
+    Laravel,
+    Symfony,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BuildTool {
+    Gradle,
+    Maven,
+}
+
+impl std::fmt::Display for BuildTool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Gradle => write!(f, "Gradle"),
+            Self::Maven => write!(f, "Maven"),
+        }
+    }
+}
+
 /// Full project context after analysis
 #[derive(Debug, Clone)]
 pub struct ProjectContext {
@@ -104,10 +257,15 @@ pub struct ProjectContext {
     pub ports: Vec<ExpectedPort>,
     pub docker_services: Vec<DockerService>,
     pub git_info: Option<GitInfo>,
+    pub advisories: Vec<String>,
+    /// Problems worth a warning when detected (e.g. stale lockfiles)
+    pub warnings: Vec<String>,
+    /// Manifests of detected workspace packages, outside the project root
+    pub member_manifests: Vec<PathBuf>,
 }
 
 /// Discovered runnable script/command
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct DiscoveredScript {
     pub name: String,
     pub command: String,
@@ -116,14 +274,27 @@ pub struct DiscoveredScript {
     pub description: Option<String>,
     pub ports: Vec<u16>,
     pub env_required: Vec<String>,
+    /// Workspace package the script belongs to; `None` runs it from the project root
+    pub working_dir: Option<PathBuf>,
+}
+
+impl DiscoveredScript {
+    /// Move the script into a workspace package, prefixing its name with the package's
+    pub fn in_package(mut self, package: &str, dir: &Path) -> Self {
+        self.name = format!("{}:{}", package, self.name);
+        self.working_dir = Some(dir.to_path_buf());
+        self
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ScriptSource {
     PackageJson,
     Makefile,
+    Rakefile,
     CargoToml,
     PyProjectToml,
+    ComposerJson,
     DockerCompose,
     OrbitConfig,
     Detected,
@@ -134,8 +305,10 @@ impl std::fmt::Display for ScriptSource {
         match self {
             Self::PackageJson => write!(f, "package.json"),
             Self::Makefile => write!(f, "Makefile"),
+            Self::Rakefile => write!(f, "Rakefile"),
             Self::CargoToml => write!(f, "Cargo.toml"),
             Self::PyProjectToml => write!(f, "pyproject.toml"),
+            Self::ComposerJson => write!(f, "composer.json"),
             Self::DockerCompose => write!(f, "docker-compose"),
             Self::OrbitConfig => write!(f, ".orbit.toml"),
             Self::Detected => write!(f, "detected"),
@@ -143,6 +316,13 @@ impl std::fmt::Display for ScriptSource {
     }
 }
 
+impl serde::Serialize for ScriptSource {
+    /// As its display name, e.g. `package.json`, so JSON output matches the tables
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ScriptCategory {
     Dev,
@@ -170,6 +350,12 @@ impl std::fmt::Display for ScriptCategory {
     }
 }
 
+impl serde::Serialize for ScriptCategory {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
 /// Environment variable analysis
 #[derive(Debug, Clone, Default)]
 pub struct EnvVarAnalysis {
@@ -178,9 +364,11 @@ pub struct EnvVarAnalysis {
     pub set_in_shell: Vec<String>,
     pub set_in_dotenv: Vec<String>,
     pub missing_required: Vec<String>,
+    /// Keys in `.env` that `.env.example` doesn't document
+    pub undocumented: Vec<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct EnvVarSpec {
     pub name: String,
     pub description: Option<String>,
@@ -190,7 +378,7 @@ pub struct EnvVarSpec {
 }
 
 /// Expected port from configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct ExpectedPort {
     pub port: u16,
     pub source: String,
@@ -214,37 +402,77 @@ pub struct GitInfo {
     pub ahead: u32,
     pub behind: u32,
     pub dirty: bool,
+    pub recent_files: Vec<RecentFile>,
+}
+
+/// A tracked file touched in recent commits
+#[derive(Debug, Clone)]
+pub struct RecentFile {
+    /// Path relative to the project's working directory
+    pub path: String,
+    pub modified: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 /// Main project detector
 pub struct ProjectDetector {
     root: PathBuf,
+    type_priority: Vec<String>,
+    git: bool,
+    docker: bool,
 }
 
 impl ProjectDetector {
     pub fn new(root: &Path) -> Self {
         Self {
             root: root.to_path_buf(),
+            type_priority: DEFAULT_TYPE_PRIORITY
+                .iter()
+                .map(|k| k.to_string())
+                .collect(),
+            git: true,
+            docker: true,
         }
     }
 
+    /// Override the order used to break primary-type confidence ties
+    pub fn with_type_priority(mut self, priority: Vec<String>) -> Self {
+        self.type_priority = priority;
+        self
+    }
+
+    /// Skip the git analyzer (and its `git` subprocesses) when `false`
+    pub fn with_git(mut self, enabled: bool) -> Self {
+        self.git = enabled;
+        self
+    }
+
+    /// Skip Dockerfile/compose detection when `false`
+    pub fn with_docker(mut self, enabled: bool) -> Self {
+        self.docker = enabled;
+        self
+    }
+
     pub async fn analyze(&self) -> Result<ProjectContext> {
         let mut types = Vec::new();
         let mut scripts = Vec::new();
         let mut env_vars = EnvVarAnalysis::default();
         let mut ports = Vec::new();
         let mut docker_services = Vec::new();
+        let mut advisories = Vec::new();
+        let mut member_manifests = Vec::new();
 
         // Node.js detection
         if let Some(result) = analyzers::node::analyze(&self.root).await? {
             types.push(ProjectType {
                 kind: result.project_kind,
                 confidence: result.confidence,
-                primary: types.is_empty(),
+                primary: false,
             });
             scripts.extend(result.scripts);
             env_vars.required.extend(result.env_vars);
             ports.extend(result.expected_ports);
+            advisories.extend(result.advisories);
+            member_manifests.extend(result.member_manifests);
         }
 
         // Rust detection
@@ -252,7 +480,7 @@ impl ProjectDetector {
             types.push(ProjectType {
                 kind: result.project_kind,
                 confidence: result.confidence,
-                primary: types.is_empty(),
+                primary: false,
             });
             scripts.extend(result.scripts);
         }
@@ -262,10 +490,11 @@ impl ProjectDetector {
             types.push(ProjectType {
                 kind: result.project_kind,
                 confidence: result.confidence,
-                primary: types.is_empty(),
+                primary: false,
             });
             scripts.extend(result.scripts);
             env_vars.required.extend(result.env_vars);
+            member_manifests.extend(result.member_manifests);
         }
 
         // Go detection
@@ -273,34 +502,152 @@ impl ProjectDetector {
             types.push(ProjectType {
                 kind: result.project_kind,
                 confidence: result.confidence,
-                primary: types.is_empty(),
+                primary: false,
+            });
+            scripts.extend(result.scripts);
+        }
+
+        // Ruby detection
+        if let Some(result) = analyzers::ruby::analyze(&self.root).await? {
+            types.push(ProjectType {
+                kind: result.project_kind,
+                confidence: result.confidence,
+                primary: false,
+            });
+            scripts.extend(result.scripts);
+            ports.extend(result.expected_ports);
+        }
+
+        // PHP detection
+        if let Some(result) = analyzers::php::analyze(&self.root).await? {
+            types.push(ProjectType {
+                kind: result.project_kind,
+                confidence: result.confidence,
+                primary: false,
+            });
+            scripts.extend(result.scripts);
+            ports.extend(result.expected_ports);
+        }
+
+        // Gradle / Maven detection
+        if let Some(result) = analyzers::jvm::analyze(&self.root).await? {
+            types.push(ProjectType {
+                kind: result.project_kind,
+                confidence: result.confidence,
+                primary: false,
+            });
+            scripts.extend(result.scripts);
+            ports.extend(result.expected_ports);
+        }
+
+        // OCaml detection
+        if let Some(result) = analyzers::ocaml::analyze(&self.root).await? {
+            types.push(ProjectType {
+                kind: result.project_kind,
+                confidence: result.confidence,
+                primary: false,
+            });
+            scripts.extend(result.scripts);
+        }
+
+        // Nim detection
+        if let Some(result) = analyzers::nim::analyze(&self.root).await? {
+            types.push(ProjectType {
+                kind: result.project_kind,
+                confidence: result.confidence,
+                primary: false,
+            });
+            scripts.extend(result.scripts);
+            ports.extend(result.expected_ports);
+        }
+
+        // Bazel / Buck2 detection
+        if let Some(result) = analyzers::bazel::analyze(&self.root).await? {
+            types.push(ProjectType {
+                kind: result.project_kind,
+                confidence: result.confidence,
+                primary: false,
             });
             scripts.extend(result.scripts);
         }
 
         // Docker detection
-        if let Some(result) = analyzers::docker::analyze(&self.root).await? {
+        let docker = if self.docker {
+            analyzers::docker::analyze(&self.root).await?
+        } else {
+            None
+        };
+        if let Some(result) = docker {
             types.push(ProjectType {
                 kind: result.project_kind.clone(),
                 confidence: result.confidence,
-                primary: types.is_empty(),
+                primary: false,
             });
             scripts.extend(result.scripts);
             docker_services = result.docker_services;
             ports.extend(result.expected_ports);
+            advisories.extend(result.advisories);
+        }
+
+        select_primary(&mut types, &self.type_priority);
+        if let Some(script) = types
+            .iter()
+            .find(|t| t.primary)
+            .and_then(|t| dependency_tree_script(&t.kind))
+        {
+            scripts.push(script);
         }
 
         // Git detection
-        let git_info = analyzers::git::analyze(&self.root).await?;
+        let git_info = if self.git {
+            analyzers::git::analyze(&self.root).await?
+        } else {
+            None
+        };
 
         // Generic (Makefile) detection
         if let Some(result) = analyzers::generic::analyze(&self.root).await? {
             scripts.extend(result.scripts);
         }
 
+        // mise tasks and tool versions
+        if let Some(result) = analyzers::mise::analyze(&self.root).await? {
+            scripts.extend(result.scripts);
+            advisories.extend(result.advisories);
+        }
+
+        // pre-commit and git hooks
+        if let Some(result) = analyzers::hooks::analyze(&self.root).await? {
+            scripts.extend(result.scripts);
+            advisories.extend(result.advisories);
+        }
+
+        // A repo with nothing else to run still gets git actions
+        if types.is_empty() && scripts.is_empty() && git_info.is_some() {
+            types.push(ProjectType {
+                kind: ProjectKind::Git,
+                confidence: 0.1,
+                primary: true,
+            });
+            scripts.extend(analyzers::git::fallback_scripts());
+        }
+
+        // Manifest/lockfile drift
+        let warnings = analyzers::lockfile::analyze(&self.root).await;
+
         // Environment analysis
         let env_result = analyzers::env::analyze(&self.root).await?;
         env_vars.set_in_dotenv = env_result.dotenv_vars;
+        env_vars.undocumented = env_result.undocumented;
+        for spec in env_result.documented {
+            if !env_vars
+                .required
+                .iter()
+                .any(|known| known.name == spec.name)
+            {
+                env_vars.required.push(spec);
+            }
+        }
         env_vars.set_in_shell = std::env::vars().map(|(k, _)| k).collect();
 
         // Check for missing required vars
@@ -312,9 +659,7 @@ impl ProjectDetector {
             }
         }
 
-        // Deduplicate scripts by name
-        scripts.sort_by(|a, b| a.name.cmp(&b.name));
-        scripts.dedup_by(|a, b| a.name == b.name);
+        let scripts = disambiguate_scripts(scripts);
 
         // Detect project name
         let name = self.detect_project_name(&types).await;
@@ -328,6 +673,9 @@ impl ProjectDetector {
             ports,
             docker_services,
             git_info,
+            advisories,
+            warnings,
+            member_manifests,
         })
     }
 
@@ -368,3 +716,337 @@ impl ProjectDetector {
         Ok(context.scripts)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project_type(kind: ProjectKind, confidence: f32) -> ProjectType {
+        ProjectType {
+            kind,
+            confidence,
+            primary: false,
+        }
+    }
+
+    #[test]
+    fn confidence_ties_resolve_by_type_priority() {
+        let mut types = vec![
+            project_type(
+                ProjectKind::Node {
+                    package_manager: PackageManager::Npm,
+                    framework: None,
+                },
+                0.9,
+            ),
+            project_type(
+                ProjectKind::Python {
+                    env_type: PythonEnvType::None,
+                    framework: None,
+                },
+                0.9,
+            ),
+        ];
+
+        select_primary(&mut types, &["python".to_string(), "node".to_string()]);
+        assert!(!types[0].primary);
+        assert!(types[1].primary);
+
+        let defaults: Vec<String> = DEFAULT_TYPE_PRIORITY
+            .iter()
+            .map(|k| k.to_string())
+            .collect();
+        select_primary(&mut types, &defaults);
+        assert!(types[0].primary);
+        assert!(!types[1].primary);
+    }
+
+    /// Detect `files` written into a fresh directory, returning the primary type's key
+    async fn primary_kind(files: &[(&str, &str)]) -> &'static str {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, content) in files {
+            let path = dir.path().join(name);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        let context = ProjectDetector::new(dir.path())
+            .with_git(false)
+            .analyze()
+            .await
+            .unwrap();
+        context
+            .types
+            .iter()
+            .find(|t| t.primary)
+            .map_or("none", |t| t.kind.key())
+    }
+
+    #[tokio::test]
+    async fn containerised_apps_keep_their_language_as_primary() {
+        let dockerfile = ("Dockerfile", "FROM scratch\n");
+        let rails = primary_kind(&[
+            (
+                "Gemfile",
+                "source \"https://rubygems.org\"\ngem \"rails\", \"~> 7.1\"\n",
+            ),
+            dockerfile,
+        ])
+        .await;
+        assert_eq!(rails, "ruby");
+
+        let laravel = primary_kind(&[
+            (
+                "composer.json",
+                r#"{"require": {"laravel/framework": "^11.0"}}"#,
+            ),
+            dockerfile,
+        ])
+        .await;
+        assert_eq!(laravel, "php");
+
+        let spring = primary_kind(&[
+            (
+                "build.gradle.kts",
+                "plugins {\n    id(\"org.springframework.boot\") version \"3.3.0\"\n}\n",
+            ),
+            dockerfile,
+        ])
+        .await;
+        assert_eq!(spring, "jvm");
+    }
+
+    #[tokio::test]
+    async fn sibling_packages_do_not_outrank_the_root_project() {
+        let crate_with_extension = primary_kind(&[
+            (
+                "Cargo.toml",
+                "[package]\nname = \"tool\"\nversion = \"0.1.0\"\n",
+            ),
+            ("vscode/package.json", r#"{"name": "tool-vscode"}"#),
+            ("bindings/pyproject.toml", "[project]\nname = \"tool-py\"\n"),
+        ])
+        .await;
+        assert_eq!(crate_with_extension, "rust");
+
+        let side_by_side = primary_kind(&[
+            ("web/package.json", r#"{"name": "web"}"#),
+            ("api/package.json", r#"{"name": "api"}"#),
+        ])
+        .await;
+        assert_eq!(side_by_side, "node");
+    }
+
+    #[tokio::test]
+    async fn same_named_scripts_from_different_sources_are_both_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{"name": "web", "scripts": {"test": "vitest", "lint": "eslint ."}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("Makefile"),
+            "test:\n\tgo test ./...\n\nbuild:\n\tgo build\n",
+        )
+        .unwrap();
+
+        let context = ProjectDetector::new(dir.path())
+            .with_git(false)
+            .analyze()
+            .await
+            .unwrap();
+        let commands = |name: &str| -> Vec<&str> {
+            context
+                .scripts
+                .iter()
+                .filter(|s| s.name == name)
+                .map(|s| s.command.as_str())
+                .collect()
+        };
+        assert_eq!(commands("test"), ["npm run test", "make test"]);
+        assert_eq!(commands("lint"), ["npm run lint"]);
+        assert_eq!(commands("build"), ["make build"]);
+
+        // The first `test` keeps the id macros, aliases and favorites refer to
+        let registry = crate::actions::ActionRegistry::from_project(&context);
+        let make_test = registry.get("script:Makefile:test").unwrap();
+        assert_eq!(make_test.command, "make test");
+        assert_eq!(
+            make_test.display_name(&registry.all_actions()),
+            "test (Makefile)"
+        );
+        let registry = registry
+            .with_aliases(&std::collections::HashMap::from([(
+                "test".to_string(),
+                "Unit tests".to_string(),
+            )]))
+            .with_macros(&indexmap::IndexMap::from([(
+                "check".to_string(),
+                vec!["script:test".to_string(), "script:lint".to_string()],
+            )]));
+        let test = registry.get("script:test").unwrap();
+        assert_eq!(test.command, "npm run test");
+        assert_eq!(test.name, "Unit tests");
+        let steps: Vec<String> = registry
+            .macro_steps("check")
+            .unwrap()
+            .into_iter()
+            .map(|a| a.command)
+            .collect();
+        assert_eq!(steps, ["npm run test", "npm run lint"]);
+    }
+
+    #[tokio::test]
+    async fn poetry_prefix_stays_on_python_scripts() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("pyproject.toml"),
+            "[tool.poetry]\nname = \"shop\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("poetry.lock"), "").unwrap();
+        std::fs::write(dir.path().join("Makefile"), "deploy:\n\t./deploy.sh\n").unwrap();
+
+        let context = ProjectDetector::new(dir.path())
+            .with_git(false)
+            .analyze()
+            .await
+            .unwrap();
+        let command = |name: &str| {
+            context
+                .scripts
+                .iter()
+                .find(|s| s.name == name)
+                .map(|s| s.command.as_str())
+        };
+        assert_eq!(command("lint"), Some("poetry run ruff check ."));
+        assert_eq!(command("deploy"), Some("make deploy"));
+    }
+
+    #[test]
+    fn same_named_detected_scripts_from_different_analyzers_are_both_kept() {
+        let script = |command: &str| DiscoveredScript {
+            name: "test".to_string(),
+            command: command.to_string(),
+            source: ScriptSource::Detected,
+            category: ScriptCategory::Test,
+            description: None,
+            ports: vec![],
+            env_required: vec![],
+            working_dir: None,
+        };
+        let scripts = disambiguate_scripts(vec![
+            script("cargo test"),
+            script("go test ./..."),
+            script("cargo test"),
+        ]);
+        let commands: Vec<&str> = scripts.iter().map(|s| s.command.as_str()).collect();
+        assert_eq!(commands, ["cargo test", "go test ./..."]);
+    }
+
+    #[tokio::test]
+    async fn docker_build_context_advisories_reach_the_project() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Dockerfile"), "FROM node:20\nCOPY . .\n").unwrap();
+        let deps = dir.path().join("node_modules");
+        std::fs::create_dir_all(&deps).unwrap();
+        // Sparse file: reports its length without writing the bytes
+        std::fs::File::create(deps.join("bundle.js"))
+            .unwrap()
+            .set_len(200 * 1024 * 1024)
+            .unwrap();
+
+        let context = ProjectDetector::new(dir.path())
+            .with_git(false)
+            .analyze()
+            .await
+            .unwrap();
+        assert!(context
+            .advisories
+            .iter()
+            .any(|a| a.starts_with("No .dockerignore") && a.contains("node_modules")));
+    }
+
+    #[tokio::test]
+    async fn disabled_git_skips_the_git_analyzer() {
+        let dir = tempfile::tempdir().unwrap();
+        let initialized = std::process::Command::new("git")
+            .args(["init", "--quiet"])
+            .current_dir(dir.path())
+            .status()
+            .is_ok_and(|s| s.success());
+
+        let context = ProjectDetector::new(dir.path())
+            .with_git(false)
+            .analyze()
+            .await
+            .unwrap();
+        assert!(context.git_info.is_none());
+
+        // Sanity check that the repository would otherwise be read
+        if initialized {
+            let context = ProjectDetector::new(dir.path()).analyze().await.unwrap();
+            assert!(context.git_info.is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn plain_git_repo_offers_git_actions() {
+        let dir = tempfile::tempdir().unwrap();
+        let status = std::process::Command::new("git")
+            .args(["init", "--quiet"])
+            .current_dir(dir.path())
+            .status()
+            .expect("git should be installed");
+        assert!(status.success(), "git init failed: {}", status);
+
+        let context = ProjectDetector::new(dir.path()).analyze().await.unwrap();
+        assert_eq!(context.types.len(), 1);
+        assert_eq!(context.types[0].kind, ProjectKind::Git);
+        for command in ["git status", "git pull", "git push"] {
+            let script = context
+                .scripts
+                .iter()
+                .find(|s| s.command == command)
+                .unwrap_or_else(|| panic!("missing {}", command));
+            assert_eq!(script.category, ScriptCategory::Utility);
+            assert_eq!(script.source, ScriptSource::Detected);
+        }
+
+        // Makefile targets are enough to run, so no git fallback
+        std::fs::write(dir.path().join("Makefile"), "build:\n\tcc main.c\n").unwrap();
+        let context = ProjectDetector::new(dir.path()).analyze().await.unwrap();
+        assert!(context.types.is_empty());
+        assert!(!context.scripts.iter().any(|s| s.command == "git status"));
+    }
+
+    #[tokio::test]
+    async fn primary_type_gets_a_dependency_tree_action() {
+        let tree_command = |context: &ProjectContext| {
+            context
+                .scripts
+                .iter()
+                .find(|s| s.name == "deps tree")
+                .map(|s| s.command.clone())
+        };
+
+        let rust = tempfile::tempdir().unwrap();
+        std::fs::write(
+            rust.path().join("Cargo.toml"),
+            "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        let context = ProjectDetector::new(rust.path()).analyze().await.unwrap();
+        assert_eq!(tree_command(&context).as_deref(), Some("cargo tree"));
+
+        let pnpm = tempfile::tempdir().unwrap();
+        std::fs::write(pnpm.path().join("package.json"), r#"{"name": "web"}"#).unwrap();
+        std::fs::write(
+            pnpm.path().join("pnpm-lock.yaml"),
+            "lockfileVersion: '9.0'\n",
+        )
+        .unwrap();
+        let context = ProjectDetector::new(pnpm.path()).analyze().await.unwrap();
+        assert_eq!(tree_command(&context).as_deref(), Some("pnpm list"));
+    }
+}
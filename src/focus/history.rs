@@ -0,0 +1,210 @@
+//! Log of past focus sessions, one JSON record per line
+
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// One finished focus session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FocusSessionRecord {
+    pub started_at: DateTime<Utc>,
+    /// Planned length; 0 for an open-ended session
+    pub duration_minutes: u32,
+    pub completed: bool,
+    /// When the session was ended early
+    pub interrupted_at: Option<DateTime<Utc>>,
+    /// Seconds the timer ran, leaving out manual and idle pauses; absent in older records
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub focused_seconds: Option<u32>,
+}
+
+impl FocusSessionRecord {
+    pub fn new(
+        started_at: DateTime<Utc>,
+        duration_minutes: u32,
+        focused_seconds: u32,
+        completed: bool,
+        ended_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            started_at,
+            duration_minutes,
+            completed,
+            interrupted_at: (!completed).then_some(ended_at),
+            focused_seconds: Some(focused_seconds),
+        }
+    }
+
+    /// Minutes actually spent: the full duration, or the time focused before the
+    /// interruption (wall-clock time for records that predate `focused_seconds`)
+    pub fn focused_minutes(&self) -> i64 {
+        let planned = i64::from(self.duration_minutes);
+        let spent = match (self.focused_seconds, self.interrupted_at) {
+            _ if self.completed => return planned,
+            (Some(seconds), _) => i64::from(seconds / 60),
+            (None, Some(at)) => (at - self.started_at).num_minutes().max(0),
+            (None, None) => return planned,
+        };
+        if planned > 0 {
+            spent.min(planned)
+        } else {
+            spent
+        }
+    }
+}
+
+/// Append `record` to the history at `path`, creating the file and its directory
+///
+/// A last line cut short by a crash is closed off first so the new record stays readable.
+pub fn append_record(path: &Path, record: &FocusSessionRecord) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .read(true)
+        .append(true)
+        .create(true)
+        .open(path)?;
+
+    let mut line = serde_json::to_string(record)?;
+    line.push('\n');
+    if file.metadata()?.len() > 0 {
+        let mut last = [0u8; 1];
+        file.seek(SeekFrom::End(-1))?;
+        file.read_exact(&mut last)?;
+        if last[0] != b'\n' {
+            line.insert(0, '\n');
+        }
+    }
+    file.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+/// Every readable record at `path`; a missing file is an empty history
+pub fn load_records(path: &Path) -> Vec<FocusSessionRecord> {
+    std::fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// What `orbit focus --stats` prints
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FocusStats {
+    pub today_minutes: i64,
+    /// Since Monday
+    pub week_minutes: i64,
+    /// Most consecutive days with at least one completed session
+    pub longest_streak_days: usize,
+}
+
+impl FocusStats {
+    /// Totals for `records` as of `now`, with days counted in `now`'s time zone
+    pub fn compute<Tz: TimeZone>(records: &[FocusSessionRecord], now: DateTime<Tz>) -> Self {
+        let zone = now.timezone();
+        let today = now.date_naive();
+        let week_start = today - Duration::days(i64::from(today.weekday().num_days_from_monday()));
+
+        let mut stats = Self::default();
+        let mut completed_days: Vec<NaiveDate> = Vec::new();
+        for record in records {
+            let day = record.started_at.with_timezone(&zone).date_naive();
+            if day == today {
+                stats.today_minutes += record.focused_minutes();
+            }
+            if day >= week_start && day <= today {
+                stats.week_minutes += record.focused_minutes();
+            }
+            if record.completed {
+                completed_days.push(day);
+            }
+        }
+
+        completed_days.sort();
+        completed_days.dedup();
+        let mut streak = 0;
+        let mut previous: Option<NaiveDate> = None;
+        for day in completed_days {
+            streak = match previous {
+                Some(prev) if day - prev == Duration::days(1) => streak + 1,
+                _ => 1,
+            };
+            stats.longest_streak_days = stats.longest_streak_days.max(streak);
+            previous = Some(day);
+        }
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn history_survives_a_torn_line_and_sums_by_day() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("orbit").join("focus_history.jsonl");
+
+        let monday = FocusSessionRecord::new(
+            at("2024-05-06T09:00:00Z"),
+            25,
+            25 * 60,
+            true,
+            at("2024-05-06T09:25:00Z"),
+        );
+        append_record(&path, &monday).unwrap();
+        // A crash mid-write leaves half a record behind
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(br#"{"started_at":"2024-05-07"#)
+            .unwrap();
+        let tuesday = FocusSessionRecord::new(
+            at("2024-05-07T10:00:00Z"),
+            25,
+            25 * 60,
+            true,
+            at("2024-05-07T10:25:00Z"),
+        );
+        // Ten and a half minutes on the clock, four of them paused
+        let cut_short = FocusSessionRecord::new(
+            at("2024-05-07T14:00:00Z"),
+            50,
+            6 * 60 + 30,
+            false,
+            at("2024-05-07T14:10:30Z"),
+        );
+        append_record(&path, &tuesday).unwrap();
+        append_record(&path, &cut_short).unwrap();
+
+        let records = load_records(&path);
+        assert_eq!(records, [monday, tuesday, cut_short.clone()]);
+        assert_eq!(cut_short.interrupted_at, Some(at("2024-05-07T14:10:30Z")));
+
+        let stats = FocusStats::compute(&records, at("2024-05-07T18:00:00Z"));
+        assert_eq!(
+            stats,
+            FocusStats {
+                today_minutes: 31,
+                week_minutes: 56,
+                longest_streak_days: 2,
+            }
+        );
+        assert!(load_records(&dir.path().join("missing.jsonl")).is_empty());
+
+        let legacy: FocusSessionRecord = serde_json::from_str(
+            r#"{"started_at":"2024-05-07T14:00:00Z","duration_minutes":50,"completed":false,"interrupted_at":"2024-05-07T14:10:30Z"}"#,
+        )
+        .unwrap();
+        assert_eq!(legacy.focused_minutes(), 10);
+    }
+}
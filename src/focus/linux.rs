@@ -0,0 +1,218 @@
+//! Linux focus backend: `notify-send`, GNOME's notification banners or dunst/mako's
+//! pause switches, `paplay`/`ffplay`
+
+use anyhow::Result;
+use async_trait::async_trait;
+use std::sync::Mutex;
+use tokio::process::Command;
+
+use super::FocusBackend;
+
+/// freedesktop sound theme shipped by most desktops
+const SOUND_THEME_DIR: &str = "/usr/share/sounds/freedesktop/stereo";
+
+/// Mako mode that hides notifications; users add `[mode=do-not-disturb] invisible=1`
+const MAKO_DND_MODE: &str = "do-not-disturb";
+
+/// Focus backend for Linux desktops
+#[derive(Debug, Default)]
+pub struct LinuxBackend {
+    /// Daemons we silenced, so ending the session only undoes our own changes
+    silenced: Mutex<Vec<DndDaemon>>,
+}
+
+/// Where Do Not Disturb lives on a given desktop
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DndDaemon {
+    /// GNOME's `show-banners` setting, written to dconf over D-Bus
+    Gnome,
+    Dunst,
+    Mako,
+}
+
+impl DndDaemon {
+    /// GNOME's setting only means something on GNOME (it succeeds anywhere dconf runs);
+    /// elsewhere try the standalone daemons, whichever is running
+    fn for_desktop(desktop: &str) -> Vec<Self> {
+        let gnome = desktop.split(':').any(|d| {
+            matches!(
+                d.to_ascii_lowercase().as_str(),
+                "gnome" | "unity" | "budgie"
+            )
+        });
+        if gnome {
+            vec![Self::Gnome]
+        } else {
+            vec![Self::Dunst, Self::Mako]
+        }
+    }
+
+    /// Whether notifications are already silenced, e.g. by the user
+    async fn is_silenced(self) -> Result<bool> {
+        let output = match self {
+            Self::Gnome => {
+                output(
+                    "gsettings",
+                    &["get", "org.gnome.desktop.notifications", "show-banners"],
+                )
+                .await?
+            }
+            Self::Dunst => output("dunstctl", &["is-paused"]).await?,
+            Self::Mako => output("makoctl", &["mode"]).await?,
+        };
+        Ok(match self {
+            Self::Gnome => output.trim() == "false",
+            Self::Dunst => output.trim() == "true",
+            Self::Mako => output.lines().any(|mode| mode.trim() == MAKO_DND_MODE),
+        })
+    }
+
+    async fn set_silenced(self, silenced: bool) -> Result<()> {
+        match self {
+            Self::Gnome => {
+                let show_banners = if silenced { "false" } else { "true" };
+                run(
+                    "gsettings",
+                    &[
+                        "set",
+                        "org.gnome.desktop.notifications",
+                        "show-banners",
+                        show_banners,
+                    ],
+                )
+                .await
+            }
+            Self::Dunst => {
+                let paused = if silenced { "true" } else { "false" };
+                run("dunstctl", &["set-paused", paused]).await
+            }
+            Self::Mako => {
+                let flag = if silenced { "-a" } else { "-r" };
+                run("makoctl", &["mode", flag, MAKO_DND_MODE]).await
+            }
+        }
+    }
+}
+
+/// The sound file for a named sound, or `sound` itself if it's a path
+fn sound_file(sound: &str) -> String {
+    let name = match sound {
+        "complete" | "done" => "complete.oga",
+        "alert" => "bell.oga",
+        "start" => "dialog-information.oga",
+        path if path.starts_with('/') => return path.to_string(),
+        _ => "complete.oga",
+    };
+    format!("{}/{}", SOUND_THEME_DIR, name)
+}
+
+/// Run `program`, failing if it's missing or exits nonzero
+async fn run(program: &str, args: &[&str]) -> Result<()> {
+    output(program, args).await.map(|_| ())
+}
+
+/// Run `program` and capture its stdout, failing if it's missing or exits nonzero
+async fn output(program: &str, args: &[&str]) -> Result<String> {
+    let output = Command::new(program).args(args).output().await?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "{} failed: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[async_trait]
+impl FocusBackend for LinuxBackend {
+    /// Silence every daemon for this desktop that answers, remembering the ones
+    /// that weren't silenced already; fails only if none of them could be reached
+    async fn enable_dnd(&self) -> Result<()> {
+        let desktop = std::env::var("XDG_CURRENT_DESKTOP").unwrap_or_default();
+        let mut silenced = Vec::new();
+        let mut reached = false;
+        let mut last_error = None;
+        for daemon in DndDaemon::for_desktop(&desktop) {
+            let result = match daemon.is_silenced().await {
+                Ok(true) => Ok(()),
+                Ok(false) => daemon
+                    .set_silenced(true)
+                    .await
+                    .map(|()| silenced.push(daemon)),
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(()) => reached = true,
+                Err(e) => last_error = Some(e),
+            }
+        }
+        self.silenced
+            .lock()
+            .expect("DND state lock poisoned")
+            .extend(silenced);
+        match last_error {
+            Some(e) if !reached => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    /// Turn notifications back on where we turned them off, and nowhere else
+    async fn disable_dnd(&self) -> Result<()> {
+        let silenced = std::mem::take(&mut *self.silenced.lock().expect("DND state lock poisoned"));
+        let mut result = Ok(());
+        for daemon in silenced {
+            if let Err(e) = daemon.set_silenced(false).await {
+                result = Err(e);
+            }
+        }
+        result
+    }
+
+    async fn send_notification(&self, title: &str, message: &str) -> Result<()> {
+        run("notify-send", &["--app-name=orbit", title, message]).await
+    }
+
+    async fn play_sound(&self, sound: &str) -> Result<()> {
+        let file = sound_file(sound);
+        if run("paplay", &[&file]).await.is_ok() {
+            return Ok(());
+        }
+        run(
+            "ffplay",
+            &["-nodisp", "-autoexit", "-loglevel", "quiet", &file],
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn named_sounds_map_to_the_freedesktop_theme() {
+        assert_eq!(
+            sound_file("complete"),
+            "/usr/share/sounds/freedesktop/stereo/complete.oga"
+        );
+        assert_eq!(
+            sound_file("alert"),
+            "/usr/share/sounds/freedesktop/stereo/bell.oga"
+        );
+        assert_eq!(sound_file("/tmp/ding.wav"), "/tmp/ding.wav");
+    }
+
+    #[test]
+    fn gnome_settings_only_apply_on_gnome_desktops() {
+        assert_eq!(DndDaemon::for_desktop("ubuntu:GNOME"), [DndDaemon::Gnome]);
+        assert_eq!(
+            DndDaemon::for_desktop("sway"),
+            [DndDaemon::Dunst, DndDaemon::Mako]
+        );
+        assert_eq!(
+            DndDaemon::for_desktop(""),
+            [DndDaemon::Dunst, DndDaemon::Mako]
+        );
+    }
+}
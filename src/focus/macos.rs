@@ -0,0 +1,69 @@
+//! macOS focus backend: `defaults` for Do Not Disturb, AppleScript, `afplay`
+
+use anyhow::Result;
+use async_trait::async_trait;
+use tokio::process::Command;
+
+use super::{run_applescript, FocusBackend};
+
+/// Focus backend for macOS
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MacBackend;
+
+impl MacBackend {
+    async fn set_dnd(enabled: bool) -> Result<()> {
+        Command::new("defaults")
+            .args([
+                "-currentHost",
+                "write",
+                "com.apple.notificationcenterui",
+                "doNotDisturb",
+                "-boolean",
+                if enabled { "true" } else { "false" },
+            ])
+            .output()
+            .await?;
+
+        // Restart NotificationCenter to apply
+        let _ = Command::new("killall")
+            .args(["NotificationCenter"])
+            .output()
+            .await;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl FocusBackend for MacBackend {
+    async fn enable_dnd(&self) -> Result<()> {
+        Self::set_dnd(true).await
+    }
+
+    async fn disable_dnd(&self) -> Result<()> {
+        Self::set_dnd(false).await
+    }
+
+    async fn send_notification(&self, title: &str, message: &str) -> Result<()> {
+        let script = format!(
+            r#"display notification "{}" with title "{}""#,
+            message.replace('"', "\\\""),
+            title.replace('"', "\\\"")
+        );
+        run_applescript(&script).await?;
+        Ok(())
+    }
+
+    async fn play_sound(&self, sound: &str) -> Result<()> {
+        let sound_file = match sound {
+            "complete" | "done" => "/System/Library/Sounds/Glass.aiff",
+            "alert" => "/System/Library/Sounds/Ping.aiff",
+            "start" => "/System/Library/Sounds/Pop.aiff",
+            path if path.starts_with('/') => path,
+            _ => "/System/Library/Sounds/Glass.aiff",
+        };
+
+        Command::new("afplay").arg(sound_file).output().await?;
+        Ok(())
+    }
+}
@@ -1,14 +1,47 @@
-//! Focus mode - Distraction-free work sessions with macOS integration
+//! Focus mode - Distraction-free work sessions with macOS and Linux integration
 
 #![allow(dead_code)]
 
+pub mod history;
+#[cfg(not(target_os = "macos"))]
+mod linux;
+#[cfg(target_os = "macos")]
+mod macos;
+
 use anyhow::Result;
+use async_trait::async_trait;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
 use tokio::process::Command;
-use tokio::sync::mpsc;
-use tokio::time::{interval, Duration};
+use tokio::sync::{mpsc, watch};
+use tokio::time::{interval, Duration, Instant};
 
 use crate::core::events::Event;
+use history::{FocusSessionRecord, FocusStats};
+
+/// Desktop integration a focus session needs, implemented per platform
+#[async_trait]
+pub trait FocusBackend: Send + Sync {
+    async fn enable_dnd(&self) -> Result<()>;
+    async fn disable_dnd(&self) -> Result<()>;
+    async fn send_notification(&self, title: &str, message: &str) -> Result<()>;
+    /// Play `complete`, `alert`, `start`, or an absolute path to a sound file
+    async fn play_sound(&self, sound: &str) -> Result<()>;
+}
+
+#[cfg(target_os = "macos")]
+pub use macos::MacBackend as PlatformBackend;
+
+#[cfg(not(target_os = "macos"))]
+pub use linux::LinuxBackend as PlatformBackend;
+
+/// The backend for the platform orbit was built for
+pub fn platform_backend() -> Arc<dyn FocusBackend> {
+    Arc::new(PlatformBackend::default())
+}
 
 /// Ambient sound presets
 #[derive(Debug, Clone)]
@@ -60,7 +93,21 @@ pub struct FocusModeConfig {
     pub duration_minutes: u32,
     pub enable_dnd: bool,
     pub minimize_windows: bool,
+    /// Apps left open on top of the built-in terminal list
+    pub keep_open: Vec<String>,
     pub ambient_sound: Option<AmbientSound>,
+    /// Ambient playback volume (0-100)
+    pub ambient_volume: u8,
+    /// Pause the timer after this long without input
+    pub auto_pause_after: Option<Duration>,
+    /// Where to POST a [`SessionSummary`] when the session ends
+    pub webhook_url: Option<String>,
+    /// Project name reported to the webhook
+    pub project: String,
+    /// Notify and play a sound when the timer runs out
+    pub notify_on_end: bool,
+    /// Where finished sessions are logged; `None` keeps no history
+    pub history_file: Option<PathBuf>,
 }
 
 impl Default for FocusModeConfig {
@@ -69,7 +116,14 @@ impl Default for FocusModeConfig {
             duration_minutes: 25,
             enable_dnd: true,
             minimize_windows: true,
+            keep_open: Vec::new(),
             ambient_sound: None,
+            ambient_volume: 30,
+            auto_pause_after: None,
+            webhook_url: None,
+            project: String::new(),
+            notify_on_end: true,
+            history_file: None,
         }
     }
 }
@@ -78,65 +132,179 @@ impl Default for FocusModeConfig {
 pub struct FocusModeController {
     session: FocusSession,
     config: FocusModeConfig,
+    backend: Arc<dyn FocusBackend>,
     event_tx: mpsc::UnboundedSender<Event>,
     cancel_tx: Option<tokio::sync::oneshot::Sender<()>>,
+    /// Time of the last key press, read by the timer for auto-pause
+    last_input: watch::Sender<Instant>,
+    /// Paused by the user; the timer holds until this is cleared
+    manual_pause: watch::Sender<bool>,
+    /// Focused seconds so far, excluding pauses; written by the timer task
+    elapsed: Arc<AtomicU32>,
+    started_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// What a focus session's webhook receives when it ends
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionSummary {
+    pub project: String,
+    /// `completed`, or `cancelled` when the session ended early
+    pub outcome: &'static str,
+    /// 0 for an open-ended session
+    pub planned_minutes: u32,
+    pub focused_seconds: u32,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub ended_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl SessionSummary {
+    pub fn new(
+        project: &str,
+        planned_minutes: u32,
+        focused_seconds: u32,
+        started_at: chrono::DateTime<chrono::Utc>,
+        ended_at: chrono::DateTime<chrono::Utc>,
+    ) -> Self {
+        let completed =
+            planned_minutes > 0 && focused_seconds >= planned_minutes.saturating_mul(60);
+        Self {
+            project: project.to_string(),
+            outcome: if completed { "completed" } else { "cancelled" },
+            planned_minutes,
+            focused_seconds,
+            started_at,
+            ended_at,
+        }
+    }
+}
+
+/// POST `summary` as JSON to `url`
+pub async fn post_session_summary(url: &str, summary: &SessionSummary) -> Result<()> {
+    reqwest::Client::new()
+        .post(url)
+        .timeout(Duration::from_secs(10))
+        .json(summary)
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+/// Post `summary` in the background; a down webhook only gets a log line
+fn spawn_session_webhook(url: Option<String>, summary: SessionSummary) {
+    let Some(url) = url.filter(|u| !u.is_empty()) else {
+        return;
+    };
+    tokio::spawn(async move {
+        if let Err(e) = post_session_summary(&url, &summary).await {
+            tracing::warn!("Focus webhook failed: {:#}", e);
+        }
+    });
+}
+
+/// Whether the session should be paused, given the last input and the auto-pause threshold
+pub fn is_inactive(last_input: Instant, now: Instant, threshold: Option<Duration>) -> bool {
+    threshold.is_some_and(|threshold| now.saturating_duration_since(last_input) >= threshold)
 }
 
 impl FocusModeController {
     pub async fn enter(
         config: FocusModeConfig,
         event_tx: mpsc::UnboundedSender<Event>,
+    ) -> Result<Self> {
+        Self::enter_with_backend(config, platform_backend(), event_tx).await
+    }
+
+    pub async fn enter_with_backend(
+        config: FocusModeConfig,
+        backend: Arc<dyn FocusBackend>,
+        event_tx: mpsc::UnboundedSender<Event>,
     ) -> Result<Self> {
         // Enable DND if configured
         if config.enable_dnd {
-            let _ = enable_dnd().await;
+            let _ = backend.enable_dnd().await;
         }
 
         // Minimize windows if configured
         if config.minimize_windows {
-            let _ = minimize_windows().await;
+            let _ = minimize_windows(&config.keep_open).await;
         }
 
         // Start ambient sound if configured
-        if let Some(ref sound) = config.ambient_sound {
-            let url = sound.url().to_string();
+        if let Some(sound) = config.ambient_sound.clone() {
+            let volume = config.ambient_volume;
             tokio::spawn(async move {
-                let _ = Command::new("open").arg(&url).output().await;
+                let _ = play_ambient_sound(&sound, volume).await;
             });
         }
 
         let session = FocusSession::new(config.duration_minutes);
         let (cancel_tx, cancel_rx) = tokio::sync::oneshot::channel();
+        let (last_input, input_rx) = watch::channel(Instant::now());
+        let (manual_pause, pause_rx) = watch::channel(false);
+        let elapsed = Arc::new(AtomicU32::new(0));
 
         // Spawn timer task
         let tx = event_tx.clone();
         let duration = config.duration_minutes;
+        let auto_pause_after = config.auto_pause_after;
+        let timer_elapsed = elapsed.clone();
         tokio::spawn(async move {
-            Self::timer_task(tx, duration, cancel_rx).await;
+            Self::timer_task(
+                tx,
+                duration,
+                auto_pause_after,
+                input_rx,
+                pause_rx,
+                timer_elapsed,
+                cancel_rx,
+            )
+            .await;
         });
 
         Ok(Self {
             session,
             config,
+            backend,
             event_tx,
             cancel_tx: Some(cancel_tx),
+            last_input,
+            manual_pause,
+            elapsed,
+            started_at: chrono::Utc::now(),
         })
     }
 
     async fn timer_task(
         event_tx: mpsc::UnboundedSender<Event>,
         duration_minutes: u32,
+        auto_pause_after: Option<Duration>,
+        last_input: watch::Receiver<Instant>,
+        mut manual_pause: watch::Receiver<bool>,
+        focused: Arc<AtomicU32>,
         mut cancel_rx: tokio::sync::oneshot::Receiver<()>,
     ) {
         let total_seconds = duration_minutes.saturating_mul(60);
         let indefinite = total_seconds == 0;
         let mut elapsed = 0u32;
+        let mut paused = false;
         let mut ticker = interval(Duration::from_secs(1));
 
         loop {
             tokio::select! {
                 _ = ticker.tick() => {
+                    let inactive = is_inactive(*last_input.borrow(), Instant::now(), auto_pause_after);
+                    let now_paused = inactive || *manual_pause.borrow();
+                    if now_paused != paused {
+                        paused = now_paused;
+                        let _ = event_tx.send(Event::FocusPauseChanged { paused });
+                    }
+                    if paused {
+                        continue;
+                    }
+
                     elapsed += 1;
+                    focused.store(elapsed, Ordering::Relaxed);
                     let remaining = if indefinite {
                         0
                     } else {
@@ -150,6 +318,16 @@ impl FocusModeController {
                         break;
                     }
                 }
+                Ok(()) = manual_pause.changed() => {
+                    let now_paused = *manual_pause.borrow_and_update()
+                        || is_inactive(*last_input.borrow(), Instant::now(), auto_pause_after);
+                    if now_paused != paused {
+                        paused = now_paused;
+                        let _ = event_tx.send(Event::FocusPauseChanged { paused });
+                    }
+                    // A full second of focus before the next tick counts
+                    ticker.reset();
+                }
                 _ = &mut cancel_rx => {
                     break;
                 }
@@ -165,18 +343,72 @@ impl FocusModeController {
 
         // Disable DND
         if self.config.enable_dnd {
-            let _ = disable_dnd().await;
+            let _ = self.backend.disable_dnd().await;
         }
 
         // Stop ambient sound
         let _ = stop_ambient_sound().await;
 
+        let ended_at = chrono::Utc::now();
+        let summary = SessionSummary::new(
+            &self.config.project,
+            self.config.duration_minutes,
+            self.elapsed.load(Ordering::Relaxed),
+            self.started_at,
+            ended_at,
+        );
+        if let Some(path) = &self.config.history_file {
+            let record = FocusSessionRecord::new(
+                self.started_at,
+                self.config.duration_minutes,
+                self.elapsed.load(Ordering::Relaxed),
+                summary.outcome == "completed",
+                ended_at,
+            );
+            if let Err(e) = history::append_record(path, &record) {
+                tracing::warn!("Could not record focus session: {:#}", e);
+            }
+        }
+        if self.config.notify_on_end && summary.outcome == "completed" {
+            let _ = self
+                .backend
+                .send_notification("Focus Session Complete", "Great work! Time for a break.")
+                .await;
+            let _ = self.backend.play_sound("complete").await;
+        }
+        spawn_session_webhook(self.config.webhook_url.clone(), summary);
+
         Ok(())
     }
 
     pub fn remaining_seconds(&self) -> u32 {
         self.session.remaining_seconds()
     }
+
+    /// Note user input, resuming the timer if it was paused for inactivity
+    pub fn record_activity(&self) {
+        self.last_input.send_replace(Instant::now());
+    }
+
+    /// Pause or resume the countdown, returning whether it's now paused
+    pub fn toggle_pause(&mut self) -> bool {
+        let paused = !*self.manual_pause.borrow();
+        self.manual_pause.send_replace(paused);
+        self.session.paused = paused;
+        paused
+    }
+
+    pub fn is_manually_paused(&self) -> bool {
+        *self.manual_pause.borrow()
+    }
+
+    /// Nudge the ambient volume by `delta`, returning the new clamped level
+    pub async fn adjust_volume(&mut self, delta: i16) -> u8 {
+        let volume = clamp_volume(self.config.ambient_volume as i16 + delta);
+        self.config.ambient_volume = volume;
+        let _ = set_ambient_volume(volume).await;
+        volume
+    }
 }
 
 /// Focus session state
@@ -239,26 +471,48 @@ pub enum FocusEvent {
     Cancelled,
 }
 
+/// Print today's and this week's focused minutes and the longest streak (CLI command)
+pub fn print_focus_stats() {
+    let records = history::load_records(&crate::config::focus_history_file());
+    let stats = FocusStats::compute(&records, chrono::Local::now());
+    println!("Sessions logged: {}", records.len());
+    println!("Focused today:   {} min", stats.today_minutes);
+    println!("This week:       {} min", stats.week_minutes);
+    let days = if stats.longest_streak_days == 1 {
+        "day"
+    } else {
+        "days"
+    };
+    println!("Longest streak:  {} {}", stats.longest_streak_days, days);
+}
+
 /// Enter focus mode (CLI command)
-pub async fn enter_focus_mode(duration: u32, ambient: bool, sound: &str) -> Result<()> {
+pub async fn enter_focus_mode(
+    duration: u32,
+    ambient: bool,
+    sound: &str,
+    config: &crate::config::FocusConfig,
+    project: &str,
+) -> Result<()> {
     println!("Starting focus mode for {} minutes...", duration);
+    let backend = platform_backend();
 
     // Enable Do Not Disturb
-    if let Err(e) = enable_dnd().await {
+    if let Err(e) = backend.enable_dnd().await {
         eprintln!("Warning: Could not enable DND: {}", e);
     } else {
         println!("Do Not Disturb enabled");
     }
 
     // Minimize other windows
-    if let Err(e) = minimize_windows().await {
+    if let Err(e) = minimize_windows(&config.keep_open).await {
         eprintln!("Warning: Could not minimize windows: {}", e);
     }
 
     // Start ambient sound
     if ambient {
         let sound_preset = AmbientSound::from_str(sound);
-        if let Err(e) = play_ambient_sound(&sound_preset).await {
+        if let Err(e) = play_ambient_sound(&sound_preset, config.ambient_volume).await {
             eprintln!("Warning: Could not start ambient sound: {}", e);
         } else {
             println!("Playing ambient sound: {}", sound);
@@ -268,11 +522,18 @@ pub async fn enter_focus_mode(duration: u32, ambient: bool, sound: &str) -> Resu
     // Run timer
     let mut session = FocusSession::new(duration);
     let mut ticker = interval(Duration::from_secs(1));
+    let started_at = chrono::Utc::now();
 
     println!("\nFocus session started. Press Ctrl+C to end early.\n");
 
     loop {
-        ticker.tick().await;
+        tokio::select! {
+            _ = ticker.tick() => {}
+            _ = tokio::signal::ctrl_c() => {
+                println!("\nFocus session ended early.");
+                break;
+            }
+        }
 
         if session.paused {
             continue;
@@ -281,7 +542,7 @@ pub async fn enter_focus_mode(duration: u32, ambient: bool, sound: &str) -> Resu
         session.elapsed_seconds += 1;
 
         // Update display every minute
-        if session.elapsed_seconds % 60 == 0 {
+        if session.elapsed_seconds.is_multiple_of(60) {
             let remaining = session.remaining_seconds() / 60;
             println!("{} minutes remaining...", remaining);
         }
@@ -291,14 +552,40 @@ pub async fn enter_focus_mode(duration: u32, ambient: bool, sound: &str) -> Resu
         }
     }
 
-    // Session complete
-    println!("\nFocus session complete!");
+    let ended_at = chrono::Utc::now();
+    let summary = SessionSummary::new(
+        project,
+        duration,
+        session.elapsed_seconds,
+        started_at,
+        ended_at,
+    );
+    let record = FocusSessionRecord::new(
+        started_at,
+        duration,
+        session.elapsed_seconds,
+        session.is_complete(),
+        ended_at,
+    );
+    if let Err(e) = history::append_record(&crate::config::focus_history_file(), &record) {
+        eprintln!("Warning: Could not record focus session: {}", e);
+    }
+    if let Some(url) = config.webhook_url.as_deref().filter(|u| !u.is_empty()) {
+        if let Err(e) = post_session_summary(url, &summary).await {
+            eprintln!("Warning: Could not post focus summary: {}", e);
+        }
+    }
 
-    // Send notification
-    send_notification("Focus Session Complete", "Great work! Time for a break.").await?;
+    if session.is_complete() {
+        println!("\nFocus session complete!");
+        backend
+            .send_notification("Focus Session Complete", "Great work! Time for a break.")
+            .await?;
+        let _ = backend.play_sound("complete").await;
+    }
 
     // Disable DND
-    if let Err(e) = disable_dnd().await {
+    if let Err(e) = backend.disable_dnd().await {
         eprintln!("Warning: Could not disable DND: {}", e);
     }
 
@@ -310,93 +597,164 @@ pub async fn enter_focus_mode(duration: u32, ambient: bool, sound: &str) -> Resu
     Ok(())
 }
 
-/// Enable macOS Do Not Disturb
-pub async fn enable_dnd() -> Result<()> {
-    // macOS Monterey+ uses Focus system
-    // AppleScript approach (commented out, using defaults write instead):
-    // tell application "System Events"
-    //     tell process "ControlCenter"
-    //         click menu bar item "Control Center" of menu bar 1
-    //         ...
-    //     end tell
-    // end tell
-
-    // Use defaults write (more reliable)
-    let _ = Command::new("defaults")
-        .args([
-            "-currentHost",
-            "write",
-            "com.apple.notificationcenterui",
-            "doNotDisturb",
-            "-boolean",
-            "true",
-        ])
-        .output()
-        .await?;
-
-    // Restart NotificationCenter to apply
-    let _ = Command::new("killall")
-        .args(["NotificationCenter"])
-        .output()
-        .await;
-
-    Ok(())
+/// Apps that stay open during focus mode regardless of `focus.keep_open`
+const DEFAULT_KEEP_OPEN: &[&str] = &[
+    "Finder",
+    "Terminal",
+    "iTerm2",
+    "Alacritty",
+    "kitty",
+    "WezTerm",
+    "gnome-terminal",
+    "konsole",
+    "xterm",
+];
+
+/// The built-in terminal list followed by `configured`, without duplicates
+pub fn keep_open_apps(configured: &[String]) -> Vec<String> {
+    let mut apps: Vec<String> = DEFAULT_KEEP_OPEN.iter().map(|s| s.to_string()).collect();
+    for app in configured {
+        if !apps.iter().any(|known| known.eq_ignore_ascii_case(app)) {
+            apps.push(app.clone());
+        }
+    }
+    apps
 }
 
-/// Disable macOS Do Not Disturb
-pub async fn disable_dnd() -> Result<()> {
-    let _ = Command::new("defaults")
-        .args([
-            "-currentHost",
-            "write",
-            "com.apple.notificationcenterui",
-            "doNotDisturb",
-            "-boolean",
-            "false",
-        ])
-        .output()
-        .await?;
-
-    // Restart NotificationCenter to apply
-    let _ = Command::new("killall")
-        .args(["NotificationCenter"])
-        .output()
-        .await;
-
-    Ok(())
-}
+/// AppleScript that minimizes every visible app except the frontmost one and `keep_open`
+fn minimize_script(keep_open: &[String]) -> String {
+    let exceptions: String = keep_open
+        .iter()
+        .map(|app| {
+            format!(
+                " and appName is not \"{}\"",
+                app.replace('\\', "\\\\").replace('"', "\\\"")
+            )
+        })
+        .collect();
 
-/// Minimize all windows except Terminal
-pub async fn minimize_windows() -> Result<()> {
-    let script = r#"
+    format!(
+        r#"
         tell application "System Events"
             set frontApp to name of first application process whose frontmost is true
             set visibleApps to every application process whose visible is true
             repeat with theApp in visibleApps
                 set appName to name of theApp
-                if appName is not frontApp and appName is not "Finder" and appName is not "Terminal" and appName is not "iTerm2" and appName is not "Alacritty" and appName is not "kitty" and appName is not "WezTerm" then
+                if appName is not frontApp{} then
                     try
                         tell application appName to set miniaturized of every window to true
                     end try
                 end if
             end repeat
         end tell
-    "#;
+    "#,
+        exceptions
+    )
+}
+
+/// Parse an X11 window id such as `0x03a00003`
+fn parse_window_id(id: &str) -> Option<u64> {
+    u64::from_str_radix(id.trim().trim_start_matches("0x"), 16).ok()
+}
+
+/// The focused window from `xprop -root _NET_ACTIVE_WINDOW` output
+fn active_window_id(xprop_output: &str) -> Option<u64> {
+    let id = xprop_output.rsplit('#').next()?;
+    parse_window_id(id.split(',').next()?).filter(|&id| id != 0)
+}
+
+/// Window ids from `wmctrl -lx` whose class doesn't match an app in `keep_open`
+///
+/// Sticky windows (desktop `-1`) are docks and panels, so they're left alone,
+/// as is `active`, the window Orbit itself is running in.
+fn windows_to_minimize(
+    wmctrl_output: &str,
+    keep_open: &[String],
+    active: Option<u64>,
+) -> Vec<String> {
+    wmctrl_output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let id = fields.next()?;
+            let desktop = fields.next()?;
+            let class = fields.next()?.to_lowercase();
+            let keep = desktop == "-1"
+                || (active.is_some() && parse_window_id(id) == active)
+                || keep_open
+                    .iter()
+                    .any(|app| class.contains(&app.to_lowercase()));
+            (!keep).then(|| id.to_string())
+        })
+        .collect()
+}
+
+/// Minimize all windows except terminals and the `keep_open` apps
+pub async fn minimize_windows(keep_open: &[String]) -> Result<()> {
+    let keep_open = keep_open_apps(keep_open);
 
-    run_applescript(script).await?;
+    if cfg!(target_os = "linux") {
+        let output = Command::new("wmctrl").arg("-lx").output().await?;
+        if !output.status.success() {
+            anyhow::bail!("wmctrl failed: {}", String::from_utf8_lossy(&output.stderr));
+        }
+        let listing = String::from_utf8_lossy(&output.stdout);
+        let active = match Command::new("xprop")
+            .args(["-root", "_NET_ACTIVE_WINDOW"])
+            .output()
+            .await
+        {
+            Ok(out) if out.status.success() => {
+                active_window_id(&String::from_utf8_lossy(&out.stdout))
+            }
+            _ => None,
+        };
+        for id in windows_to_minimize(&listing, &keep_open, active) {
+            let _ = Command::new("wmctrl")
+                .args(["-i", "-r", &id, "-b", "add,hidden"])
+                .output()
+                .await;
+        }
+        return Ok(());
+    }
+
+    run_applescript(&minimize_script(&keep_open)).await?;
     Ok(())
 }
 
-/// Play ambient sound
-pub async fn play_ambient_sound(sound: &AmbientSound) -> Result<()> {
+/// Socket mpv listens on so the volume can be changed while it plays
+fn mpv_ipc_socket() -> PathBuf {
+    std::env::temp_dir().join("orbit-mpv.sock")
+}
+
+fn clamp_volume(level: i16) -> u8 {
+    level.clamp(0, 100) as u8
+}
+
+/// Arguments for playing `url` in mpv at `volume`
+pub fn mpv_args(url: &str, volume: u8, ipc_socket: Option<&Path>) -> Vec<String> {
+    let mut args = vec![
+        "--no-video".to_string(),
+        "--really-quiet".to_string(),
+        format!("--volume={}", volume.min(100)),
+    ];
+    if let Some(socket) = ipc_socket {
+        args.push(format!("--input-ipc-server={}", socket.display()));
+    }
+    args.push(url.to_string());
+    args
+}
+
+/// Play ambient sound at `volume` (0-100)
+pub async fn play_ambient_sound(sound: &AmbientSound, volume: u8) -> Result<()> {
     let url = sound.url().to_string();
 
     // Try to use mpv if available (for YouTube)
     if is_command_available("mpv").await {
-        let url_clone = url.clone();
+        let args = mpv_args(&url, volume, Some(&mpv_ipc_socket()));
         tokio::spawn(async move {
             let _ = Command::new("mpv")
-                .args(["--no-video", "--really-quiet", "--volume=30", &url_clone])
+                .args(&args)
                 .stdout(Stdio::null())
                 .stderr(Stdio::null())
                 .spawn();
@@ -404,12 +762,39 @@ pub async fn play_ambient_sound(sound: &AmbientSound) -> Result<()> {
         return Ok(());
     }
 
-    // Fallback: Open in browser
-    Command::new("open").arg(&url).output().await?;
+    // Fallback: Open in browser, which only follows the system volume
+    let _ = set_volume(volume).await;
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    Command::new(opener).arg(&url).output().await?;
 
     Ok(())
 }
 
+/// Change the volume of the playing ambient sound
+///
+/// Talks to mpv over its IPC socket, falling back to the system volume.
+pub async fn set_ambient_volume(level: u8) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use tokio::io::AsyncWriteExt;
+
+        if let Ok(mut stream) = tokio::net::UnixStream::connect(mpv_ipc_socket()).await {
+            let command = format!(
+                "{{\"command\": [\"set_property\", \"volume\", {}]}}\n",
+                level.min(100)
+            );
+            stream.write_all(command.as_bytes()).await?;
+            return Ok(());
+        }
+    }
+
+    set_volume(level).await
+}
+
 /// Stop ambient sound
 pub async fn stop_ambient_sound() -> Result<()> {
     // Kill mpv if running
@@ -417,35 +802,14 @@ pub async fn stop_ambient_sound() -> Result<()> {
         .args(["-f", "mpv.*youtube"])
         .output()
         .await;
+    let _ = std::fs::remove_file(mpv_ipc_socket());
 
     Ok(())
 }
 
-/// Send macOS notification
+/// Send a desktop notification through the platform backend
 pub async fn send_notification(title: &str, message: &str) -> Result<()> {
-    let script = format!(
-        r#"display notification "{}" with title "{}""#,
-        message.replace('"', "\\\""),
-        title.replace('"', "\\\"")
-    );
-
-    run_applescript(&script).await?;
-    Ok(())
-}
-
-/// Play system sound
-pub async fn play_sound(sound: &str) -> Result<()> {
-    let sound_file = match sound {
-        "complete" | "done" => "/System/Library/Sounds/Glass.aiff",
-        "alert" => "/System/Library/Sounds/Ping.aiff",
-        "start" => "/System/Library/Sounds/Pop.aiff",
-        path if path.starts_with('/') => path,
-        _ => "/System/Library/Sounds/Glass.aiff",
-    };
-
-    Command::new("afplay").arg(sound_file).output().await?;
-
-    Ok(())
+    platform_backend().send_notification(title, message).await
 }
 
 /// Run an AppleScript
@@ -465,7 +829,7 @@ async fn run_applescript(script: &str) -> Result<String> {
 }
 
 /// Check if a command is available
-async fn is_command_available(cmd: &str) -> bool {
+pub async fn is_command_available(cmd: &str) -> bool {
     Command::new("which")
         .arg(cmd)
         .output()
@@ -507,6 +871,191 @@ mod tests {
         assert!(session.is_complete());
     }
 
+    #[test]
+    fn mpv_args_use_configured_volume() {
+        let args = mpv_args("https://example.com/rain", 55, None);
+        assert!(args.contains(&"--volume=55".to_string()));
+        assert_eq!(
+            args.last().map(String::as_str),
+            Some("https://example.com/rain")
+        );
+
+        let socket = Path::new("/tmp/orbit-mpv.sock");
+        let args = mpv_args("https://example.com/rain", 250, Some(socket));
+        assert!(args.contains(&"--volume=100".to_string()));
+        assert!(args.contains(&"--input-ipc-server=/tmp/orbit-mpv.sock".to_string()));
+
+        assert_eq!(clamp_volume(-5), 0);
+        assert_eq!(clamp_volume(105), 100);
+    }
+
+    #[test]
+    fn keep_open_apps_are_excluded_from_minimizing() {
+        let keep_open = keep_open_apps(&["Spotify".to_string(), "iterm2".to_string()]);
+        assert_eq!(keep_open.first().map(String::as_str), Some("Finder"));
+        assert_eq!(keep_open.last().map(String::as_str), Some("Spotify"));
+        assert_eq!(
+            keep_open
+                .iter()
+                .filter(|a| a.eq_ignore_ascii_case("iTerm2"))
+                .count(),
+            1
+        );
+
+        let script = minimize_script(&keep_open);
+        assert!(script.contains(r#"appName is not "Spotify""#));
+        assert!(script.contains(r#"appName is not "Terminal""#));
+        assert!(script.contains(r#"appName is not "WezTerm""#));
+
+        let listing = concat!(
+            "0x01e00003 -1 xfce4-panel.Xfce4-panel  host xfce4-panel\n",
+            "0x03a00003  0 gnome-terminal-server.Gnome-terminal  host ~/api\n",
+            "0x04200007  0 spotify.Spotify  host Spotify Premium\n",
+            "0x05000002  0 Navigator.firefox  host Docs - Mozilla Firefox\n",
+        );
+        assert_eq!(
+            windows_to_minimize(listing, &keep_open, None),
+            ["0x05000002"]
+        );
+
+        // The focused window is where Orbit runs, whatever its class
+        let active = active_window_id("_NET_ACTIVE_WINDOW(WINDOW): window id # 0x5000002\n");
+        assert_eq!(active, Some(0x05000002));
+        assert!(windows_to_minimize(listing, &keep_open, active).is_empty());
+        assert_eq!(
+            active_window_id("_NET_ACTIVE_WINDOW(WINDOW): window id # 0x0\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn pauses_only_after_the_inactivity_threshold() {
+        let last_input = Instant::now();
+        let threshold = Some(Duration::from_secs(5 * 60));
+
+        let soon = last_input + Duration::from_secs(4 * 60 + 59);
+        assert!(!is_inactive(last_input, soon, threshold));
+        let later = last_input + Duration::from_secs(5 * 60);
+        assert!(is_inactive(last_input, later, threshold));
+
+        // Disabled unless configured
+        assert!(!is_inactive(last_input, later, None));
+        // Input newer than `now` (clock races) never counts as idle
+        assert!(!is_inactive(later, last_input, threshold));
+    }
+
+    #[test]
+    fn completed_session_summary_json() {
+        let started_at = chrono::DateTime::parse_from_rfc3339("2024-05-01T09:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let ended_at = started_at + chrono::Duration::minutes(25);
+
+        let summary = SessionSummary::new("api", 25, 1500, started_at, ended_at);
+        assert_eq!(
+            serde_json::to_value(&summary).unwrap(),
+            serde_json::json!({
+                "project": "api",
+                "outcome": "completed",
+                "planned_minutes": 25,
+                "focused_seconds": 1500,
+                "started_at": "2024-05-01T09:00:00Z",
+                "ended_at": "2024-05-01T09:25:00Z",
+            })
+        );
+
+        let early = SessionSummary::new("api", 25, 600, started_at, ended_at);
+        assert_eq!(early.outcome, "cancelled");
+    }
+
+    #[derive(Default)]
+    struct RecordingBackend(parking_lot::Mutex<Vec<String>>);
+
+    #[async_trait]
+    impl FocusBackend for RecordingBackend {
+        async fn enable_dnd(&self) -> Result<()> {
+            self.0.lock().push("dnd on".to_string());
+            Ok(())
+        }
+        async fn disable_dnd(&self) -> Result<()> {
+            self.0.lock().push("dnd off".to_string());
+            Ok(())
+        }
+        async fn send_notification(&self, title: &str, _message: &str) -> Result<()> {
+            self.0.lock().push(format!("notify {}", title));
+            Ok(())
+        }
+        async fn play_sound(&self, sound: &str) -> Result<()> {
+            self.0.lock().push(format!("sound {}", sound));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn controller_routes_dnd_through_the_backend() {
+        let backend = Arc::new(RecordingBackend::default());
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let config = FocusModeConfig {
+            minimize_windows: false,
+            ..Default::default()
+        };
+
+        let controller = FocusModeController::enter_with_backend(config, backend.clone(), tx)
+            .await
+            .unwrap();
+        assert_eq!(*backend.0.lock(), ["dnd on"]);
+
+        // Ended early, so there's no completion notification or sound
+        controller.exit().await.unwrap();
+        assert_eq!(*backend.0.lock(), ["dnd on", "dnd off"]);
+    }
+
+    /// Let the paused clock run `seconds`, one timer tick at a time
+    async fn run_for(seconds: u64) {
+        for _ in 0..seconds {
+            tokio::time::advance(Duration::from_secs(1)).await;
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn manual_pause_holds_the_countdown() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let config = FocusModeConfig {
+            minimize_windows: false,
+            ..Default::default()
+        };
+        let mut controller = FocusModeController::enter_with_backend(
+            config,
+            Arc::new(RecordingBackend::default()),
+            tx,
+        )
+        .await
+        .unwrap();
+
+        run_for(2).await;
+        assert!(controller.toggle_pause());
+        tokio::task::yield_now().await;
+        let at_pause = controller.elapsed.load(Ordering::Relaxed);
+        assert!(at_pause >= 2, "counted {} seconds before pausing", at_pause);
+        run_for(10).await;
+        assert_eq!(controller.elapsed.load(Ordering::Relaxed), at_pause);
+
+        assert!(!controller.toggle_pause());
+        tokio::task::yield_now().await;
+        run_for(3).await;
+        assert_eq!(controller.elapsed.load(Ordering::Relaxed), at_pause + 3);
+
+        let mut pauses = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if let Event::FocusPauseChanged { paused } = event {
+                pauses.push(paused);
+            }
+        }
+        assert_eq!(pauses, [true, false]);
+        controller.exit().await.unwrap();
+    }
+
     #[test]
     fn test_format_remaining() {
         let mut session = FocusSession::new(25);
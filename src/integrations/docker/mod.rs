@@ -2,16 +2,21 @@
 
 #![allow(dead_code)]
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use bollard::container::{ListContainersOptions, Stats, StatsOptions};
 use bollard::exec::{CreateExecOptions, StartExecResults};
 use bollard::Docker;
 use futures::StreamExt;
 use std::path::Path;
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command;
+use tokio::sync::mpsc;
+
+use crate::actions::OutputLine;
 
 /// Container information for display
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct ContainerInfo {
     pub id: String,
     pub name: String,
@@ -21,6 +26,26 @@ pub struct ContainerInfo {
     pub ports: Vec<PortMapping>,
     pub stats: Option<ContainerStats>,
     pub created: chrono::DateTime<chrono::Utc>,
+    /// Compose service this container belongs to (from the compose labels)
+    pub compose_service: Option<String>,
+}
+
+#[cfg(test)]
+impl ContainerInfo {
+    /// Test container named `name` with no ports, stats or compose service
+    pub fn fixture(name: &str, status: ContainerStatus) -> Self {
+        Self {
+            id: name.to_string(),
+            name: name.to_string(),
+            image: "postgres:16".to_string(),
+            status,
+            state: String::new(),
+            ports: vec![],
+            stats: None,
+            created: chrono::Utc::now(),
+            compose_service: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -48,6 +73,12 @@ impl std::fmt::Display for ContainerStatus {
     }
 }
 
+impl serde::Serialize for ContainerStatus {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
 impl From<&str> for ContainerStatus {
     fn from(s: &str) -> Self {
         match s.to_lowercase().as_str() {
@@ -62,14 +93,14 @@ impl From<&str> for ContainerStatus {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct PortMapping {
     pub private_port: u16,
     pub public_port: Option<u16>,
     pub protocol: String,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, serde::Serialize)]
 pub struct ContainerStats {
     pub cpu_percent: f64,
     pub memory_usage_mb: f64,
@@ -77,6 +108,8 @@ pub struct ContainerStats {
     pub memory_percent: f64,
     pub network_rx_bytes: u64,
     pub network_tx_bytes: u64,
+    /// CPUs the container may use (`--cpus`), if limited
+    pub cpu_limit: Option<f64>,
 }
 
 /// Docker client wrapper
@@ -142,6 +175,11 @@ impl DockerClient {
             let created = chrono::DateTime::from_timestamp(container.created.unwrap_or(0), 0)
                 .unwrap_or_else(chrono::Utc::now);
 
+            let compose_service = container
+                .labels
+                .as_ref()
+                .and_then(|labels| labels.get("com.docker.compose.service").cloned());
+
             result.push(ContainerInfo {
                 id,
                 name,
@@ -151,6 +189,7 @@ impl DockerClient {
                 ports,
                 stats: None,
                 created,
+                compose_service,
             });
         }
 
@@ -203,10 +242,14 @@ impl DockerClient {
 
         // Memory calculation
         let memory_usage = stats.memory_stats.usage.unwrap_or(0) as f64;
-        let memory_limit = stats.memory_stats.limit.unwrap_or(1) as f64;
+        let memory_limit = stats.memory_stats.limit.unwrap_or(0) as f64;
         let memory_usage_mb = memory_usage / 1024.0 / 1024.0;
         let memory_limit_mb = memory_limit / 1024.0 / 1024.0;
-        let memory_percent = (memory_usage / memory_limit) * 100.0;
+        let memory_percent = if memory_limit > 0.0 {
+            (memory_usage / memory_limit) * 100.0
+        } else {
+            0.0
+        };
 
         // Network stats
         let (network_rx, network_tx) = stats
@@ -226,9 +269,35 @@ impl DockerClient {
             memory_percent,
             network_rx_bytes: network_rx,
             network_tx_bytes: network_tx,
+            cpu_limit: None,
         }
     }
 
+    /// CPU limit from the container's host config, in CPUs
+    ///
+    /// Covers both `--cpus` (NanoCpus) and the older `--cpu-quota`/`--cpu-period` pair.
+    pub async fn get_cpu_limit(&self, container_id: &str) -> Result<Option<f64>> {
+        let info = self
+            .docker
+            .inspect_container(
+                container_id,
+                None::<bollard::container::InspectContainerOptions>,
+            )
+            .await?;
+        let Some(host) = info.host_config else {
+            return Ok(None);
+        };
+
+        let limit = match (host.nano_cpus, host.cpu_quota, host.cpu_period) {
+            (Some(nano), _, _) if nano > 0 => Some(nano as f64 / 1e9),
+            (_, Some(quota), Some(period)) if quota > 0 && period > 0 => {
+                Some(quota as f64 / period as f64)
+            }
+            _ => None,
+        };
+        Ok(limit)
+    }
+
     /// Start a container
     pub async fn start_container(&self, container_id: &str) -> Result<()> {
         self.docker
@@ -298,68 +367,185 @@ impl DockerClient {
 
         Ok(logs)
     }
-}
 
-/// Run docker-compose up
-pub async fn compose_up(dir: &Path) -> Result<()> {
-    let compose_file = find_compose_file(dir);
+    /// Stream a container's logs as they are written, starting with the last `tail` lines
+    pub fn follow_logs(
+        &self,
+        container_id: &str,
+        tail: usize,
+    ) -> impl futures::Stream<Item = String> + Send + 'static {
+        use bollard::container::LogsOptions;
 
-    let mut cmd = Command::new("docker");
-    cmd.current_dir(dir);
-    cmd.arg("compose");
+        let options = LogsOptions::<String> {
+            follow: true,
+            stdout: true,
+            stderr: true,
+            tail: tail.to_string(),
+            ..Default::default()
+        };
 
-    if let Some(file) = &compose_file {
-        cmd.arg("-f").arg(file);
+        self.docker
+            .logs(container_id, Some(options))
+            .take_while(|log| futures::future::ready(log.is_ok()))
+            .filter_map(|log| futures::future::ready(log.ok().map(|log| log.to_string())))
     }
+}
 
-    cmd.arg("up").arg("-d");
-
-    let output = cmd.output().await?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("docker compose up failed: {}", stderr);
+/// Run `docker compose up -d`
+///
+/// With `output`, progress lines are streamed to it as the command runs (as the
+/// TUI does for actions); without one they're printed to stdout.
+pub async fn compose_up(dir: &Path, output: Option<mpsc::Sender<OutputLine>>) -> Result<()> {
+    run_compose(dir, &["up", "-d"], output.clone()).await?;
+    if output.is_none() {
+        println!("Docker containers started successfully");
     }
+    Ok(())
+}
 
-    println!("Docker containers started successfully");
+/// Run `docker compose down`, streaming like [`compose_up`]
+pub async fn compose_down(dir: &Path, output: Option<mpsc::Sender<OutputLine>>) -> Result<()> {
+    run_compose(dir, &["down"], output.clone()).await?;
+    if output.is_none() {
+        println!("Docker containers stopped successfully");
+    }
     Ok(())
 }
 
-/// Run docker-compose down
-pub async fn compose_down(dir: &Path) -> Result<()> {
-    let compose_file = find_compose_file(dir);
+/// Lines of stderr kept for the error when a compose command fails
+const COMPOSE_ERROR_LINES: usize = 5;
 
+async fn run_compose(
+    dir: &Path,
+    args: &[&str],
+    output: Option<mpsc::Sender<OutputLine>>,
+) -> Result<()> {
     let mut cmd = Command::new("docker");
-    cmd.current_dir(dir);
-    cmd.arg("compose");
-
-    if let Some(file) = &compose_file {
+    cmd.current_dir(dir).arg("compose");
+    if let Some(file) = find_compose_file(dir) {
         cmd.arg("-f").arg(file);
     }
+    let mut child = cmd
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .context("Failed to run docker compose")?;
+
+    let stdout = child.stdout.take().map(|out| BufReader::new(out).lines());
+    let stderr = child.stderr.take().map(|err| BufReader::new(err).lines());
+    let forward = |line: String, stderr: bool| {
+        let output = output.clone();
+        async move {
+            match output {
+                Some(tx) if stderr => {
+                    let _ = tx.send(OutputLine::Stderr(line)).await;
+                }
+                Some(tx) => {
+                    let _ = tx.send(OutputLine::Stdout(line)).await;
+                }
+                None if stderr => eprintln!("{}", line),
+                None => println!("{}", line),
+            }
+        }
+    };
 
-    cmd.arg("down");
+    let read_stdout = async {
+        if let Some(mut lines) = stdout {
+            while let Ok(Some(line)) = lines.next_line().await {
+                forward(line, false).await;
+            }
+        }
+    };
+    // Compose reports its progress on stderr; keep the tail for the error message
+    let read_stderr = async {
+        let mut tail = std::collections::VecDeque::new();
+        if let Some(mut lines) = stderr {
+            while let Ok(Some(line)) = lines.next_line().await {
+                if tail.len() == COMPOSE_ERROR_LINES {
+                    tail.pop_front();
+                }
+                tail.push_back(line.clone());
+                forward(line, true).await;
+            }
+        }
+        tail
+    };
+    let ((), tail) = tokio::join!(read_stdout, read_stderr);
 
-    let output = cmd.output().await?;
+    let status = child.wait().await?;
+    if let Some(tx) = &output {
+        let _ = tx.send(OutputLine::Exit(status.code().unwrap_or(-1))).await;
+    }
+    if !status.success() {
+        let tail: Vec<String> = tail.into();
+        anyhow::bail!("docker compose {} failed: {}", args[0], tail.join("\n"));
+    }
+    Ok(())
+}
+
+/// Run docker compose up with a replica count for a single service
+pub async fn compose_scale(dir: &Path, service: &str, count: u32) -> Result<()> {
+    let compose_file = find_compose_file(dir);
+
+    let output = Command::new("docker")
+        .current_dir(dir)
+        .args(compose_scale_args(compose_file.as_deref(), service, count))
+        .output()
+        .await?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("docker compose down failed: {}", stderr);
+        anyhow::bail!("docker compose scale failed: {}", stderr);
     }
 
-    println!("Docker containers stopped successfully");
     Ok(())
 }
 
-/// Print Docker status for CLI
-pub async fn print_status(_dir: &Path) -> Result<()> {
+fn compose_scale_args(compose_file: Option<&str>, service: &str, count: u32) -> Vec<String> {
+    let mut args = vec!["compose".to_string()];
+
+    if let Some(file) = compose_file {
+        args.push("-f".to_string());
+        args.push(file.to_string());
+    }
+
+    args.extend([
+        "up".to_string(),
+        "-d".to_string(),
+        "--scale".to_string(),
+        format!("{}={}", service, count),
+    ]);
+    args
+}
+
+/// Apply a +/- step to a service's replica count, never going below zero
+pub fn step_scale(current: u32, delta: i32) -> u32 {
+    current.saturating_add_signed(delta)
+}
+
+/// Print Docker status for CLI, as a table or (with `json`) as
+/// `{"available": bool, "containers": [...]}`
+pub async fn print_status(_dir: &Path, json: bool) -> Result<()> {
     let client = DockerClient::new()?;
+    let available = client.is_available().await;
+    let containers = if available {
+        client.list_containers(true).await?
+    } else {
+        Vec::new()
+    };
 
-    if !client.is_available().await {
-        println!("Docker is not running or not accessible");
+    if json {
+        let status = serde_json::json!({ "available": available, "containers": containers });
+        println!("{}", serde_json::to_string_pretty(&status)?);
         return Ok(());
     }
 
-    let containers = client.list_containers(true).await?;
+    if !available {
+        println!("Docker is not running or not accessible");
+        return Ok(());
+    }
 
     if containers.is_empty() {
         println!("No containers found");
@@ -382,8 +568,8 @@ pub async fn print_status(_dir: &Path) -> Result<()> {
 
         println!(
             "{:<20} {:<25} {:<12} {}",
-            truncate(&container.name, 18),
-            truncate(&container.image, 23),
+            crate::ui::util::truncate(&container.name, 18),
+            crate::ui::util::truncate(&container.image, 23),
             container.status.to_string(),
             ports
         );
@@ -409,17 +595,6 @@ fn find_compose_file(dir: &Path) -> Option<String> {
     None
 }
 
-fn truncate(s: &str, max_len: usize) -> String {
-    let char_count = s.chars().count();
-    if char_count <= max_len {
-        s.to_string()
-    } else if max_len > 3 {
-        format!("{}...", s.chars().take(max_len - 3).collect::<String>())
-    } else {
-        s.chars().take(max_len).collect()
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -434,4 +609,31 @@ mod tests {
             ContainerStatus::Unknown
         );
     }
+
+    #[test]
+    fn builds_compose_scale_args() {
+        assert_eq!(
+            compose_scale_args(Some("compose.yml"), "web", 3),
+            vec![
+                "compose",
+                "-f",
+                "compose.yml",
+                "up",
+                "-d",
+                "--scale",
+                "web=3"
+            ]
+        );
+        assert_eq!(
+            compose_scale_args(None, "worker", 0),
+            vec!["compose", "up", "-d", "--scale", "worker=0"]
+        );
+    }
+
+    #[test]
+    fn scale_step_clamps_at_zero() {
+        assert_eq!(step_scale(2, 1), 3);
+        assert_eq!(step_scale(1, -1), 0);
+        assert_eq!(step_scale(0, -1), 0);
+    }
 }
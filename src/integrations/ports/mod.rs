@@ -3,11 +3,13 @@
 #![allow(dead_code)]
 
 use anyhow::Result;
-use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::io::{IsTerminal, Write as _};
 use std::net::{SocketAddr, TcpListener};
+use std::time::Duration;
 use tokio::process::Command;
 
-use crate::detection::ProjectDetector;
+use crate::detection::{DiscoveredScript, ProjectDetector, ScriptCategory};
 
 /// Expected port from project configuration
 #[derive(Debug, Clone)]
@@ -28,12 +30,14 @@ impl From<crate::detection::ExpectedPort> for ExpectedPort {
 }
 
 /// Active port information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct ActivePort {
     pub port: u16,
     pub pid: u32,
     pub process_name: String,
     pub state: PortState,
+    /// The other end of a connection, e.g. `127.0.0.1:5432`; `None` for listeners
+    pub remote: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -45,6 +49,19 @@ pub enum PortState {
     Unknown,
 }
 
+impl PortState {
+    /// Parse the state lsof prints in parentheses after the address, e.g. `(LISTEN)`
+    pub fn from_lsof(state: &str) -> Self {
+        match state.trim_matches(|c| c == '(' || c == ')') {
+            "LISTEN" => Self::Listening,
+            "ESTABLISHED" => Self::Established,
+            "TIME_WAIT" => Self::TimeWait,
+            "CLOSE_WAIT" => Self::CloseWait,
+            _ => Self::Unknown,
+        }
+    }
+}
+
 impl std::fmt::Display for PortState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -57,8 +74,14 @@ impl std::fmt::Display for PortState {
     }
 }
 
+impl serde::Serialize for PortState {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
 /// Port conflict information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct PortConflict {
     pub port: u16,
     pub expected_service: String,
@@ -73,49 +96,75 @@ pub struct PortScanResult {
     pub conflicts: Vec<PortConflict>,
 }
 
-/// Scan for active ports on the system
+/// Scan for listening TCP ports on the system
 pub async fn scan_active_ports() -> Result<Vec<ActivePort>> {
     let output = Command::new("lsof")
         .args(["-iTCP", "-sTCP:LISTEN", "-n", "-P"])
         .output()
         .await?;
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let mut ports = Vec::new();
-    let mut seen_ports = HashMap::new();
+    Ok(parse_lsof(&String::from_utf8_lossy(&output.stdout))
+        .into_iter()
+        .filter(|p| p.state == PortState::Listening)
+        .collect())
+}
+
+/// Scan TCP connections in every state but LISTEN, to spot leaked ones
+pub async fn scan_connections() -> Result<Vec<ActivePort>> {
+    let output = Command::new("lsof")
+        .args(["-iTCP", "-n", "-P"])
+        .output()
+        .await?;
+
+    Ok(parse_lsof(&String::from_utf8_lossy(&output.stdout))
+        .into_iter()
+        .filter(|p| p.state != PortState::Listening)
+        .collect())
+}
 
+/// Parse `lsof -iTCP -n -P` output, one entry per listening port or connection
+///
+/// The NAME column is `*:3000` for a listener and `127.0.0.1:54321->127.0.0.1:5432`
+/// for a connection, followed by the state in parentheses.
+fn parse_lsof(stdout: &str) -> Vec<ActivePort> {
+    let mut ports: Vec<ActivePort> = Vec::new();
+
+    // Skip header
     for line in stdout.lines().skip(1) {
-        // Skip header
         let parts: Vec<&str> = line.split_whitespace().collect();
         if parts.len() < 9 {
             continue;
         }
 
-        let process_name = parts[0].to_string();
-        let pid: u32 = parts[1].parse().unwrap_or(0);
-
-        // Parse the name field (e.g., "*:3000" or "127.0.0.1:8080")
-        let name = parts[8];
-        if let Some(port_str) = name.rsplit(':').next() {
-            if let Ok(port) = port_str.parse::<u16>() {
-                // Deduplicate by port
-                if let std::collections::hash_map::Entry::Vacant(e) = seen_ports.entry(port) {
-                    e.insert(true);
-                    ports.push(ActivePort {
-                        port,
-                        pid,
-                        process_name,
-                        state: PortState::Listening,
-                    });
-                }
-            }
+        let (local, remote) = match parts[8].split_once("->") {
+            Some((local, remote)) => (local, Some(remote.to_string())),
+            None => (parts[8], None),
+        };
+        let Some(Ok(port)) = local.rsplit(':').next().map(str::parse::<u16>) else {
+            continue;
+        };
+        let state = parts
+            .get(9)
+            .map_or(PortState::Unknown, |s| PortState::from_lsof(s));
+
+        // One row per listening port (IPv4 and IPv6 show up separately), and per connection
+        let duplicate = ports
+            .iter()
+            .any(|p| p.port == port && p.state == state && p.remote == remote);
+        if !duplicate {
+            ports.push(ActivePort {
+                port,
+                pid: parts[1].parse().unwrap_or(0),
+                process_name: parts[0].to_string(),
+                state,
+                remote,
+            });
         }
     }
 
     // Sort by port number
     ports.sort_by_key(|p| p.port);
-
-    Ok(ports)
+    ports
 }
 
 /// Scan ports in a specific range
@@ -146,40 +195,131 @@ pub fn find_available_port(start: u16) -> Option<u16> {
     (start..65535).find(|&p| is_port_available(p))
 }
 
-/// Kill process on a specific port
-pub async fn kill_port(port: u16) -> Result<()> {
-    // First, find the PID using lsof
-    let output = Command::new("lsof")
-        .args(["-ti", &format!(":{}", port)])
-        .output()
-        .await?;
+/// Signal that ended up stopping a port's processes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopSignal {
+    Term,
+    Kill,
+}
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let pids: Vec<&str> = stdout.lines().collect();
+impl std::fmt::Display for StopSignal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Term => write!(f, "SIGTERM"),
+            Self::Kill => write!(f, "SIGKILL"),
+        }
+    }
+}
 
+/// What `kill_port` did
+#[derive(Debug, Clone)]
+pub struct PortKill {
+    pub port: u16,
+    /// Processes that were listening on the port
+    pub pids: Vec<u32>,
+    /// `None` when nothing was listening
+    pub signal: Option<StopSignal>,
+    /// Whether nothing is listening on the port any more
+    pub freed: bool,
+}
+
+/// Stop every process listening on `port`: SIGTERM first so dev servers can clean
+/// up, then SIGKILL if any of them is still alive after `grace`. `force` skips SIGTERM.
+///
+/// Both "stopped" and `freed` go by the listening PIDs rather than a bind probe,
+/// which misses servers on `::1` or a LAN address and privileged ports.
+pub async fn kill_port(port: u16, grace: Duration, force: bool) -> Result<PortKill> {
+    let pids = listening_pids(port).await?;
     if pids.is_empty() {
-        println!("No process found on port {}", port);
-        return Ok(());
+        return Ok(PortKill {
+            port,
+            pids,
+            signal: None,
+            freed: true,
+        });
+    }
+
+    let signal = signal_until_stopped(&pids, grace, force, || {
+        pids.iter().all(|&pid| !is_alive(pid))
+    })
+    .await?;
+
+    // SIGKILL can't be ignored, but the kernel takes a moment to reap the processes
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(1);
+    let mut freed = listening_pids(port).await?.is_empty();
+    while !freed && tokio::time::Instant::now() < deadline {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        freed = listening_pids(port).await?.is_empty();
     }
 
-    for pid_str in pids {
-        if let Ok(pid) = pid_str.trim().parse::<i32>() {
-            println!("Killing process {} on port {}", pid, port);
+    Ok(PortKill {
+        port,
+        pids,
+        signal: Some(signal),
+        freed,
+    })
+}
 
-            let kill_output = Command::new("kill")
-                .args(["-9", &pid.to_string()])
-                .output()
-                .await?;
+/// PIDs listening on `port`, without Orbit's own (IPv4 and IPv6 sockets, or
+/// worker processes sharing the socket, can make several)
+async fn listening_pids(port: u16) -> Result<Vec<u32>> {
+    let output = Command::new("lsof")
+        .args(["-t", &format!("-iTCP:{}", port), "-sTCP:LISTEN"])
+        .output()
+        .await?;
 
-            if !kill_output.status.success() {
-                let stderr = String::from_utf8_lossy(&kill_output.stderr);
-                eprintln!("Warning: Failed to kill process {}: {}", pid, stderr);
+    let own_pid = std::process::id();
+    let mut pids: Vec<u32> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.trim().parse().ok())
+        .filter(|&pid| pid != own_pid && pid != 0)
+        .collect();
+    pids.sort_unstable();
+    pids.dedup();
+    Ok(pids)
+}
+
+fn is_alive(pid: u32) -> bool {
+    nix::sys::signal::kill(nix::unistd::Pid::from_raw(pid as i32), None).is_ok()
+}
+
+/// SIGTERM `pids`, then SIGKILL them if `stopped` hasn't reported true within
+/// `grace`; `force` sends SIGKILL straight away. Already-exited PIDs are fine.
+async fn signal_until_stopped(
+    pids: &[u32],
+    grace: Duration,
+    force: bool,
+    mut stopped: impl FnMut() -> bool,
+) -> Result<StopSignal> {
+    use nix::sys::signal::{kill, Signal};
+    use nix::unistd::Pid;
+
+    let send = |signal: Signal| -> Result<()> {
+        for &pid in pids {
+            match kill(Pid::from_raw(pid as i32), signal) {
+                Ok(()) | Err(nix::errno::Errno::ESRCH) => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(())
+    };
+
+    if !force {
+        send(Signal::SIGTERM)?;
+        let deadline = tokio::time::Instant::now() + grace;
+        loop {
+            if stopped() {
+                return Ok(StopSignal::Term);
+            }
+            if tokio::time::Instant::now() >= deadline {
+                break;
             }
+            tokio::time::sleep(Duration::from_millis(100)).await;
         }
     }
 
-    println!("Port {} freed", port);
-    Ok(())
+    send(Signal::SIGKILL)?;
+    Ok(StopSignal::Kill)
 }
 
 /// Get process info for a port
@@ -203,6 +343,71 @@ pub async fn get_process_on_port(port: u16) -> Result<Option<(u32, String)>> {
     Ok(None)
 }
 
+/// The script that would bring up an expected port
+///
+/// A script declaring the port wins; otherwise one named after the service, and
+/// finally the project's only dev script (frameworks serve their default port from it).
+pub fn script_for_port<'a>(
+    expected: &ExpectedPort,
+    scripts: &'a [DiscoveredScript],
+) -> Option<&'a DiscoveredScript> {
+    if let Some(script) = scripts
+        .iter()
+        .filter(|s| s.ports.contains(&expected.port))
+        .min_by_key(|s| s.category != ScriptCategory::Dev)
+    {
+        return Some(script);
+    }
+    if let Some(script) = scripts.iter().find(|s| s.name == expected.service_name) {
+        return Some(script);
+    }
+    // Compose ports come up with their containers, not a project script
+    if expected.source == "docker-compose" {
+        return None;
+    }
+
+    let mut dev = scripts.iter().filter(|s| s.category == ScriptCategory::Dev);
+    match (dev.next(), dev.next()) {
+        (Some(script), None) => Some(script),
+        _ => None,
+    }
+}
+
+/// Free a conflicting port and restart the script that should own it
+#[derive(Debug, Clone)]
+pub struct ReclaimPlan {
+    pub port: u16,
+    pub pid: u32,
+    pub process: String,
+    pub script: DiscoveredScript,
+}
+
+/// What reclaiming `conflict` would do, or `None` if there's nothing safe to kill
+/// (Orbit itself, or an unknown PID) or no script to bring the port back up
+pub fn reclaim_plan(
+    conflict: &PortConflict,
+    expected: &[ExpectedPort],
+    scripts: &[DiscoveredScript],
+    own_pid: u32,
+) -> Option<ReclaimPlan> {
+    if conflict.actual_pid == 0 || conflict.actual_pid == own_pid {
+        return None;
+    }
+    let expected = expected.iter().find(|e| e.port == conflict.port)?;
+    let script = script_for_port(expected, scripts)?;
+    Some(ReclaimPlan {
+        port: conflict.port,
+        pid: conflict.actual_pid,
+        process: conflict.actual_process.clone(),
+        script: script.clone(),
+    })
+}
+
+/// SIGTERM `pid`, escalating to SIGKILL if it's still alive after `grace`
+pub async fn terminate_pid(pid: u32, grace: Duration) -> Result<StopSignal> {
+    signal_until_stopped(&[pid], grace, false, || !is_alive(pid)).await
+}
+
 /// Detect port conflicts between expected and active ports
 pub fn detect_conflicts(expected: &[ExpectedPort], active: &[ActivePort]) -> Vec<PortConflict> {
     let mut conflicts = Vec::new();
@@ -223,40 +428,124 @@ pub fn detect_conflicts(expected: &[ExpectedPort], active: &[ActivePort]) -> Vec
     conflicts
 }
 
-/// Print port status for CLI
-pub async fn print_port_status(detector: &ProjectDetector) -> Result<()> {
+/// Print port status for CLI, as a table or (with `json`) as JSON
+pub async fn print_port_status(detector: &ProjectDetector, json: bool) -> Result<()> {
     let context = detector.analyze().await?;
     let active_ports = scan_active_ports().await?;
 
-    println!("Expected Ports:");
-    println!("{:<8} {:<20} STATUS", "PORT", "SERVICE");
-    println!("{}", "-".repeat(50));
+    if json {
+        let status = port_status_json(&context.ports, &active_ports);
+        println!("{}", serde_json::to_string_pretty(&status)?);
+    } else {
+        print!("{}", format_port_status(&context.ports, &active_ports));
+    }
+    Ok(())
+}
+
+/// `{"expected": [...], "active": [...], "conflicts": [...]}`
+fn port_status_json(
+    expected_ports: &[crate::detection::ExpectedPort],
+    active_ports: &[ActivePort],
+) -> serde_json::Value {
+    let expected: Vec<ExpectedPort> = expected_ports.iter().cloned().map(Into::into).collect();
+    serde_json::json!({
+        "expected": expected_ports,
+        "active": active_ports,
+        "conflicts": detect_conflicts(&expected, active_ports),
+    })
+}
+
+/// Redraw the port status every `interval` until Ctrl-C
+///
+/// Falls back to printing once when stdout is not a terminal so output stays pipe-friendly.
+pub async fn watch_port_status(detector: &ProjectDetector, interval: Duration) -> Result<()> {
+    let context = detector.analyze().await?;
+
+    let term = std::env::var("TERM").ok();
+    if !should_watch(std::io::stdout().is_terminal(), term.as_deref()) {
+        let active_ports = scan_active_ports().await?;
+        print!("{}", format_port_status(&context.ports, &active_ports));
+        return Ok(());
+    }
+
+    loop {
+        let active_ports = scan_active_ports().await?;
+        // Clear screen and move the cursor home before redrawing
+        print!("\x1b[2J\x1b[H");
+        print!("{}", format_port_status(&context.ports, &active_ports));
+        println!(
+            "\nRefreshing every {}s - Ctrl-C to exit",
+            interval.as_secs_f32()
+        );
+        std::io::stdout().flush()?;
+
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => break,
+            _ = tokio::time::sleep(interval) => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Only keep redrawing when a terminal that understands clear-screen is attached
+fn should_watch(is_tty: bool, term: Option<&str>) -> bool {
+    is_tty && term != Some("dumb")
+}
 
-    for expected in &context.ports {
+fn format_port_status(
+    expected_ports: &[crate::detection::ExpectedPort],
+    active_ports: &[ActivePort],
+) -> String {
+    let mut out = String::new();
+
+    let _ = writeln!(out, "Expected Ports:");
+    let _ = writeln!(out, "{:<8} {:<20} STATUS", "PORT", "SERVICE");
+    let _ = writeln!(out, "{}", "-".repeat(50));
+
+    for expected in expected_ports {
         let status = if let Some(active) = active_ports.iter().find(|a| a.port == expected.port) {
             format!("IN USE by {} (PID: {})", active.process_name, active.pid)
         } else {
             "available".to_string()
         };
 
-        println!(
+        let _ = writeln!(
+            out,
             "{:<8} {:<20} {}",
             expected.port, expected.service_name, status
         );
     }
 
-    println!("\nActive Ports (listening):");
-    println!("{:<8} {:<15} {:<10} STATE", "PORT", "PROCESS", "PID");
-    println!("{}", "-".repeat(50));
+    let expected: Vec<ExpectedPort> = expected_ports.iter().cloned().map(Into::into).collect();
+    let conflicts = detect_conflicts(&expected, active_ports);
+    if !conflicts.is_empty() {
+        let _ = writeln!(out, "\nConflicts:");
+        for conflict in &conflicts {
+            let _ = writeln!(
+                out,
+                "{:<8} {} wants it, held by {} (PID: {})",
+                conflict.port,
+                conflict.expected_service,
+                conflict.actual_process,
+                conflict.actual_pid
+            );
+        }
+    }
+
+    let _ = writeln!(out, "\nActive Ports (listening):");
+    let _ = writeln!(out, "{:<8} {:<15} {:<10} STATE", "PORT", "PROCESS", "PID");
+    let _ = writeln!(out, "{}", "-".repeat(50));
 
-    for port in &active_ports {
-        println!(
+    for port in active_ports {
+        let _ = writeln!(
+            out,
             "{:<8} {:<15} {:<10} {}",
             port.port, port.process_name, port.pid, port.state
         );
     }
 
-    Ok(())
+    out
 }
 
 /// Common development ports to scan
@@ -291,6 +580,68 @@ mod tests {
         assert!(find_available_port(49152).is_some());
     }
 
+    #[tokio::test]
+    async fn sigterm_first_then_sigkill_for_processes_that_ignore_it() {
+        let grace = Duration::from_millis(300);
+        let spawn = |script: &str| {
+            std::process::Command::new("sh")
+                .args(["-c", script])
+                .spawn()
+                .unwrap()
+        };
+
+        let mut polite = spawn("exec sleep 30");
+        let pid = polite.id();
+        let signal = signal_until_stopped(&[pid], grace, false, || {
+            polite.try_wait().unwrap().is_some()
+        })
+        .await
+        .unwrap();
+        assert_eq!(signal, StopSignal::Term);
+
+        // An ignored signal disposition survives exec
+        let mut stubborn = spawn("trap '' TERM; exec sleep 30");
+        std::thread::sleep(Duration::from_millis(100));
+        let pid = stubborn.id();
+        let signal = signal_until_stopped(&[pid], grace, false, || {
+            stubborn.try_wait().unwrap().is_some()
+        })
+        .await
+        .unwrap();
+        assert_eq!(signal, StopSignal::Kill);
+        stubborn.wait().unwrap();
+
+        let mut forced = spawn("exec sleep 30");
+        let pid = forced.id();
+        let signal = signal_until_stopped(&[pid], grace, true, || false)
+            .await
+            .unwrap();
+        assert_eq!(signal, StopSignal::Kill);
+        forced.wait().unwrap();
+    }
+
+    #[test]
+    fn parses_listeners_and_connection_states_from_lsof() {
+        let output = concat!(
+            "COMMAND   PID USER   FD   TYPE DEVICE SIZE/OFF NODE NAME\n",
+            "node    41234 me   23u  IPv4 0x1      0t0  TCP *:3000 (LISTEN)\n",
+            "node    41234 me   24u  IPv6 0x2      0t0  TCP *:3000 (LISTEN)\n",
+            "node    41234 me   30u  IPv4 0x3      0t0  TCP 127.0.0.1:54321->127.0.0.1:5432 (ESTABLISHED)\n",
+            "psql    51000 me    3u  IPv4 0x4      0t0  TCP 127.0.0.1:54400->127.0.0.1:5432 (CLOSE_WAIT)\n",
+        );
+
+        let ports = parse_lsof(output);
+        assert_eq!(ports.len(), 3);
+        assert_eq!(ports[0].port, 3000);
+        assert_eq!(ports[0].state, PortState::Listening);
+        assert_eq!(ports[0].remote, None);
+        assert_eq!(ports[1].port, 54321);
+        assert_eq!(ports[1].state, PortState::Established);
+        assert_eq!(ports[1].remote.as_deref(), Some("127.0.0.1:5432"));
+        assert_eq!(ports[2].state, PortState::CloseWait);
+        assert_eq!(ports[2].process_name, "psql");
+    }
+
     #[test]
     fn test_detect_conflicts() {
         let expected = vec![ExpectedPort {
@@ -304,10 +655,109 @@ mod tests {
             pid: 1234,
             process_name: "node".to_string(),
             state: PortState::Listening,
+            remote: None,
         }];
 
         let conflicts = detect_conflicts(&expected, &active);
         assert_eq!(conflicts.len(), 1);
         assert_eq!(conflicts[0].port, 3000);
     }
+
+    #[test]
+    fn port_status_json_has_a_stable_schema() {
+        let expected = vec![crate::detection::ExpectedPort {
+            port: 5432,
+            source: "docker-compose.yml".to_string(),
+            service_name: "db".to_string(),
+        }];
+        let active = vec![ActivePort {
+            port: 5432,
+            pid: 81,
+            process_name: "postgres".to_string(),
+            state: PortState::Listening,
+            remote: None,
+        }];
+
+        let json = port_status_json(&expected, &active);
+        assert_eq!(json["active"][0]["port"], 5432);
+        assert_eq!(json["active"][0]["state"], "LISTEN");
+        assert_eq!(json["expected"][0]["service_name"], "db");
+        assert_eq!(json["conflicts"][0]["actual_process"], "postgres");
+    }
+
+    #[test]
+    fn correlates_expected_port_with_declaring_script() {
+        let script = |name: &str, category, ports: Vec<u16>| DiscoveredScript {
+            name: name.to_string(),
+            command: format!("npm run {}", name),
+            source: crate::detection::ScriptSource::PackageJson,
+            category,
+            description: None,
+            ports,
+            env_required: vec![],
+            working_dir: None,
+        };
+        let scripts = vec![
+            script("build", ScriptCategory::Build, vec![]),
+            script("dev", ScriptCategory::Dev, vec![]),
+            script("storybook", ScriptCategory::Dev, vec![6006]),
+        ];
+        let expected = |port: u16, source: &str| ExpectedPort {
+            port,
+            source: source.to_string(),
+            service_name: "web".to_string(),
+        };
+
+        let found = script_for_port(&expected(6006, "package.json"), &scripts);
+        assert_eq!(found.map(|s| s.name.as_str()), Some("storybook"));
+
+        // Two dev scripts and none declares 3000: too ambiguous to guess
+        assert!(script_for_port(&expected(3000, "package.json"), &scripts).is_none());
+        let found = script_for_port(&expected(3000, "package.json"), &scripts[..2]);
+        assert_eq!(found.map(|s| s.name.as_str()), Some("dev"));
+        assert!(script_for_port(&expected(5432, "docker-compose"), &scripts[..2]).is_none());
+    }
+
+    #[test]
+    fn reclaim_plan_kills_the_squatter_and_restarts_the_port_script() {
+        let scripts = vec![DiscoveredScript {
+            name: "dev".to_string(),
+            command: "npm run dev".to_string(),
+            source: crate::detection::ScriptSource::PackageJson,
+            category: ScriptCategory::Dev,
+            description: None,
+            ports: vec![3000],
+            env_required: vec![],
+            working_dir: None,
+        }];
+        let expected = vec![ExpectedPort {
+            port: 3000,
+            source: "package.json".to_string(),
+            service_name: "dev-server".to_string(),
+        }];
+        let conflict = |pid: u32| PortConflict {
+            port: 3000,
+            expected_service: "dev-server".to_string(),
+            actual_process: "node".to_string(),
+            actual_pid: pid,
+        };
+
+        let plan = reclaim_plan(&conflict(4242), &expected, &scripts, 1).unwrap();
+        assert_eq!((plan.port, plan.pid), (3000, 4242));
+        assert_eq!(plan.process, "node");
+        assert_eq!(plan.script.name, "dev");
+
+        // Never kill ourselves, and don't kill without something to restart
+        assert!(reclaim_plan(&conflict(1), &expected, &scripts, 1).is_none());
+        assert!(reclaim_plan(&conflict(0), &expected, &scripts, 1).is_none());
+        assert!(reclaim_plan(&conflict(4242), &expected, &[], 1).is_none());
+    }
+
+    #[test]
+    fn watch_only_loops_on_a_terminal() {
+        assert!(should_watch(true, Some("xterm-256color")));
+        assert!(should_watch(true, None));
+        assert!(!should_watch(false, Some("xterm-256color")));
+        assert!(!should_watch(true, Some("dumb")));
+    }
 }
@@ -35,8 +35,9 @@ impl SystemMonitor {
         Self { sys, disks }
     }
 
-    /// Refresh and return a metrics snapshot.
-    pub fn sample(&mut self) -> SystemMetrics {
+    /// Refresh and return a metrics snapshot, or `None` when sysinfo couldn't read
+    /// the system (no CPUs or memory reported) rather than a misleading 0%.
+    pub fn sample(&mut self) -> Option<SystemMetrics> {
         self.sys.refresh_cpu_usage();
         self.sys.refresh_memory();
         self.disks.refresh();
@@ -45,6 +46,9 @@ impl SystemMonitor {
         }
 
         let cpu_percent = self.sys.global_cpu_usage();
+        if !is_readable(self.sys.cpus().len(), self.sys.total_memory(), cpu_percent) {
+            return None;
+        }
         let memory_total_mb = self.sys.total_memory() / 1024 / 1024;
         let memory_used_mb = self.sys.used_memory() / 1024 / 1024;
 
@@ -67,23 +71,30 @@ impl SystemMonitor {
             0.0
         };
 
-        SystemMetrics {
+        Some(SystemMetrics {
             cpu_percent,
             memory_used_mb,
             memory_total_mb,
             disk_used_percent,
-        }
+        })
     }
 }
 
+/// Whether sysinfo actually read the system; it reports no CPUs and no memory
+/// where it can't (unsupported platforms, sandboxes without `/proc`)
+fn is_readable(cpus: usize, total_memory: u64, cpu_percent: f32) -> bool {
+    cpus > 0 && total_memory > 0 && cpu_percent.is_finite()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
     fn samples_metrics_without_nan() {
         let mut monitor = SystemMonitor::new();
-        let metrics = monitor.sample();
+        let metrics = monitor.sample().expect("sysinfo reads this platform");
 
         assert!(
             metrics.cpu_percent.is_finite(),
@@ -94,4 +105,12 @@ mod tests {
             "Disk percent should always be finite"
         );
     }
+
+    #[test]
+    fn unreadable_systems_yield_no_sample() {
+        assert!(is_readable(8, 16 << 30, 12.5));
+        assert!(!is_readable(0, 16 << 30, 0.0));
+        assert!(!is_readable(8, 0, 12.5));
+        assert!(!is_readable(8, 16 << 30, f32::NAN));
+    }
 }
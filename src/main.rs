@@ -13,9 +13,9 @@ mod integrations;
 mod secrets;
 mod ui;
 
-use anyhow::Result;
-use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use anyhow::{Context, Result};
+use clap::{CommandFactory, Parser, Subcommand};
+use std::path::{Path, PathBuf};
 use tracing::Level;
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
@@ -40,6 +40,36 @@ struct Cli {
     #[arg(short, long, value_name = "FILE")]
     config: Option<PathBuf>,
 
+    /// Serve a read-only JSON health endpoint (`--http=PORT` or `--http=ADDR`, localhost by default)
+    #[arg(
+        long,
+        value_name = "ADDR",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = crate::core::http::DEFAULT_HTTP_ADDR
+    )]
+    http: Option<String>,
+
+    /// Don't connect to Docker or detect Dockerfile/compose projects
+    #[arg(long)]
+    no_docker: bool,
+
+    /// Don't run git to read branch status and recent files
+    #[arg(long)]
+    no_git: bool,
+
+    /// Don't scan listening ports
+    #[arg(long)]
+    no_ports: bool,
+
+    /// Merge [profiles.<NAME>] from .orbit.toml over the base config (or set ORBIT_PROFILE)
+    #[arg(long, value_name = "NAME")]
+    profile: Option<String>,
+
+    /// Print `actions`, `env`, `ports` and `docker` output as JSON (other commands reject it)
+    #[arg(long, global = true)]
+    json: bool,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -59,6 +89,10 @@ enum Commands {
         /// Ambient sound URL or preset (lofi, rain, cafe)
         #[arg(short, long, default_value = "lofi")]
         sound: String,
+
+        /// Print focused time from past sessions instead of starting one
+        #[arg(long)]
+        stats: bool,
     },
 
     /// List detected scripts and actions
@@ -66,6 +100,11 @@ enum Commands {
         /// Show all available actions including system ones
         #[arg(short, long)]
         all: bool,
+
+        /// Print a project summary document, or the scripts as a Makefile,
+        /// justfile or shell functions, instead of the action table
+        #[arg(short, long, value_name = "FORMAT")]
+        export: Option<ExportFormat>,
     },
 
     /// Show environment variables status
@@ -83,9 +122,25 @@ enum Commands {
 
     /// Show port status for the project
     Ports {
-        /// Kill process on specified port
+        /// Stop the processes listening on a port: SIGTERM, then SIGKILL after --timeout
         #[arg(short, long)]
         kill: Option<u16>,
+
+        /// With --kill, send SIGKILL right away
+        #[arg(long, requires = "kill")]
+        force: bool,
+
+        /// With --kill, seconds to wait for the port to free up before SIGKILL
+        #[arg(long, default_value = "3", requires = "kill")]
+        timeout: u64,
+
+        /// Keep redrawing the port list until Ctrl-C
+        #[arg(short, long, conflicts_with = "json")]
+        watch: bool,
+
+        /// Seconds between refreshes in watch mode
+        #[arg(short, long, default_value = "2")]
+        interval: u64,
     },
 
     /// Show Docker container status
@@ -99,12 +154,50 @@ enum Commands {
         down: bool,
     },
 
+    /// Print a one-line project status for tmux or shell prompts
+    Status {
+        /// Color the segments with ANSI escape codes
+        #[arg(long)]
+        color: bool,
+    },
+
     /// Initialize Orbit configuration for this project
     Init {
         /// Force overwrite existing configuration
         #[arg(short, long)]
         force: bool,
     },
+
+    /// Check that the tools and config files Orbit relies on are usable
+    Doctor,
+
+    /// Print shell completions for Orbit to stdout
+    #[command(hide = true)]
+    Completions {
+        /// Target shell
+        shell: clap_complete::Shell,
+    },
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum ExportFormat {
+    Markdown,
+    Make,
+    #[value(alias = "just")]
+    Justfile,
+    Sh,
+}
+
+impl ExportFormat {
+    /// Task-runner format to export scripts as, or `None` for the Markdown summary
+    fn script_format(self) -> Option<actions::ScriptExportFormat> {
+        match self {
+            ExportFormat::Markdown => None,
+            ExportFormat::Make => Some(actions::ScriptExportFormat::Make),
+            ExportFormat::Justfile => Some(actions::ScriptExportFormat::Just),
+            ExportFormat::Sh => Some(actions::ScriptExportFormat::Sh),
+        }
+    }
 }
 
 #[derive(Subcommand)]
@@ -115,7 +208,7 @@ enum SecretsCommands {
     Set {
         /// Secret key name
         key: String,
-        /// Secret value (will prompt if not provided)
+        /// Secret value; prompts without echo if not provided, or reads stdin when piped
         value: Option<String>,
     },
     /// Remove a secret from the keychain
@@ -129,6 +222,79 @@ enum SecretsCommands {
         #[arg(short, long, default_value = "zsh")]
         shell: String,
     },
+    /// Move secrets from .env into the keychain
+    Import {
+        /// Remove imported keys from .env afterwards
+        #[arg(long)]
+        remove: bool,
+        /// Overwrite existing keychain entries without asking
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Write configured keychain secrets to .env (plain text)
+    Export {
+        /// Overwrite existing .env entries without asking
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+/// Canonical project directory, so symlinks, `.` and trailing slashes all name
+/// the same project (and find the same keychain secrets and cache paths)
+fn resolve_working_dir(path: Option<PathBuf>) -> Result<PathBuf> {
+    let path = match path {
+        Some(path) => path,
+        None => std::env::current_dir().context("Failed to get current directory")?,
+    };
+    std::fs::canonicalize(&path)
+        .with_context(|| format!("Project directory '{}' does not exist", path.display()))
+}
+
+/// Project detector honouring the configured type priority and opt-outs
+fn project_detector(working_dir: &Path, config: &config::Config) -> detection::ProjectDetector {
+    detection::ProjectDetector::new(working_dir)
+        .with_type_priority(config.general.type_priority.clone())
+        .with_git(config.general.enable_git)
+        .with_docker(config.general.enable_docker)
+}
+
+/// Generate completions for `shell` from the clap command definition
+fn write_completions(shell: clap_complete::Shell, out: &mut dyn std::io::Write) {
+    let mut cmd = Cli::command();
+    let name = cmd.get_name().to_string();
+    clap_complete::generate(shell, &mut cmd, name, out);
+}
+
+/// Reject `--json` wherever it would be silently ignored; clap's
+/// `conflicts_with` misses the global flag when it comes before the subcommand
+fn check_conflicts(cli: &Cli) -> Result<(), clap::Error> {
+    if !cli.json {
+        return Ok(());
+    }
+    let conflict = match &cli.command {
+        Some(Commands::Actions {
+            export: Some(_), ..
+        }) => "--export",
+        Some(Commands::Ports { kill: Some(_), .. }) => "--kill",
+        Some(Commands::Ports { watch: true, .. }) => "--watch",
+        Some(Commands::Docker { up: true, .. }) => "--up",
+        Some(Commands::Docker { down: true, .. }) => "--down",
+        Some(Commands::Actions { .. })
+        | Some(Commands::Env { .. })
+        | Some(Commands::Ports { .. })
+        | Some(Commands::Docker { .. }) => return Ok(()),
+        Some(Commands::Focus { .. }) => "focus",
+        Some(Commands::Secrets { .. }) => "secrets",
+        Some(Commands::Status { .. }) => "status",
+        Some(Commands::Init { .. }) => "init",
+        Some(Commands::Doctor) => "doctor",
+        Some(Commands::Completions { .. }) => "completions",
+        None => "the dashboard",
+    };
+    Err(Cli::command().error(
+        clap::error::ErrorKind::ArgumentConflict,
+        format!("the argument '--json' cannot be used with '{}'", conflict),
+    ))
 }
 
 fn setup_logging(verbosity: u8) -> Result<tracing_appender::non_blocking::WorkerGuard> {
@@ -142,10 +308,7 @@ fn setup_logging(verbosity: u8) -> Result<tracing_appender::non_blocking::Worker
     let filter = EnvFilter::from_default_env().add_directive(level.into());
 
     // Log to file in debug mode
-    let log_dir = dirs::cache_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join("orbit")
-        .join("logs");
+    let log_dir = config::log_dir();
 
     std::fs::create_dir_all(&log_dir)?;
 
@@ -163,13 +326,14 @@ fn setup_logging(verbosity: u8) -> Result<tracing_appender::non_blocking::Worker
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
+    if let Err(e) = check_conflicts(&cli) {
+        e.exit();
+    }
 
     // Keep the guard alive for the duration of the program
     let _logging_guard = setup_logging(cli.verbose)?;
 
-    let working_dir = cli
-        .path
-        .unwrap_or_else(|| std::env::current_dir().expect("Failed to get current directory"));
+    let working_dir = resolve_working_dir(cli.path)?;
 
     let config_path = cli.config.or_else(|| {
         let default_config = dirs::config_dir()?.join("orbit").join("config.toml");
@@ -180,10 +344,27 @@ async fn main() -> Result<()> {
         }
     });
 
-    let config = if let Some(path) = config_path {
-        config::Config::load(&path)?
-    } else {
+    let profile = config::resolve_profile(cli.profile);
+
+    // Doctor loads and reports on a broken config itself instead of failing here
+    let config = if matches!(cli.command, Some(Commands::Doctor)) {
         config::Config::default()
+    } else {
+        let mut config = if let Some(path) = &config_path {
+            config::Config::load(path)?
+        } else {
+            config::Config::default()
+        };
+        config.general.enable_docker &= !cli.no_docker;
+        config.general.enable_git &= !cli.no_git;
+        config.general.enable_ports &= !cli.no_ports;
+        // A mistyped profile would otherwise leave `.orbit.toml` unapplied without a word
+        if let Some(name) = &profile {
+            config::ProjectConfig::load(&working_dir, Some(name))
+                .with_context(|| format!("Can't apply profile '{}'", name))?;
+        }
+        config.general.profile = profile.clone();
+        config
     };
 
     match cli.command {
@@ -191,21 +372,42 @@ async fn main() -> Result<()> {
             duration,
             ambient,
             sound,
+            stats,
         }) => {
-            focus::enter_focus_mode(duration, ambient, &sound).await?;
+            if stats {
+                focus::print_focus_stats();
+            } else {
+                let project = working_dir
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                focus::enter_focus_mode(duration, ambient, &sound, &config.focus, &project).await?;
+            }
         }
-        Some(Commands::Actions { all }) => {
-            let detector = detection::ProjectDetector::new(&working_dir);
+        Some(Commands::Actions { all, export }) => {
+            let detector = project_detector(&working_dir, &config);
             let context = detector.analyze().await?;
-            actions::print_actions(&context.scripts, all);
+            match export.map(ExportFormat::script_format) {
+                Some(None) => print!("{}", detection::markdown::project_summary(&context)),
+                Some(Some(format)) => print!(
+                    "{}",
+                    actions::export_scripts(&context.scripts, format, &working_dir)
+                ),
+                None if cli.json => println!(
+                    "{}",
+                    serde_json::to_string_pretty(&actions::actions_json(&context.scripts, all))?
+                ),
+                None => actions::print_actions(&context.scripts, all),
+            }
         }
         Some(Commands::Env { show_values }) => {
-            let detector = detection::ProjectDetector::new(&working_dir);
-            secrets::print_env_status(&detector, show_values).await?;
+            let detector = project_detector(&working_dir, &config);
+            let matcher = secrets::SecretMatcher::new(&config.general.secret_patterns);
+            secrets::print_env_status(&detector, show_values, &matcher, cli.json).await?;
         }
         Some(Commands::Secrets { command }) => match command {
             SecretsCommands::List => {
-                secrets::list_secrets(&working_dir).await?;
+                secrets::list_secrets(&working_dir, config.general.profile.as_deref()).await?;
             }
             SecretsCommands::Set { key, value } => {
                 secrets::set_secret(&working_dir, &key, value).await?;
@@ -214,35 +416,218 @@ async fn main() -> Result<()> {
                 secrets::remove_secret(&working_dir, &key).await?;
             }
             SecretsCommands::Inject { shell } => {
-                secrets::inject_secrets(&working_dir, &shell).await?;
+                secrets::inject_secrets(&working_dir, config.general.profile.as_deref(), &shell)
+                    .await?;
+            }
+            SecretsCommands::Import { remove, yes } => {
+                let matcher = secrets::SecretMatcher::new(&config.general.secret_patterns);
+                secrets::import_secrets(
+                    &working_dir,
+                    config.general.profile.as_deref(),
+                    &matcher,
+                    remove,
+                    yes,
+                )
+                .await?;
+            }
+            SecretsCommands::Export { yes } => {
+                secrets::export_secrets(&working_dir, config.general.profile.as_deref(), yes)
+                    .await?;
             }
         },
-        Some(Commands::Ports { kill }) => {
+        Some(Commands::Ports {
+            kill,
+            force,
+            timeout,
+            watch,
+            interval,
+        }) => {
             if let Some(port) = kill {
-                integrations::ports::kill_port(port).await?;
+                let grace = std::time::Duration::from_secs(timeout);
+                let killed = integrations::ports::kill_port(port, grace, force).await?;
+                let pids: Vec<String> = killed.pids.iter().map(|p| p.to_string()).collect();
+                match killed.signal {
+                    None => println!("No process found on port {}", port),
+                    Some(signal) => println!(
+                        "Stopped PID {} on port {} with {}",
+                        pids.join(", "),
+                        port,
+                        signal
+                    ),
+                }
+                if !killed.freed {
+                    anyhow::bail!("Port {} is still in use", port);
+                }
+            } else if watch {
+                let detector = project_detector(&working_dir, &config);
+                let interval = std::time::Duration::from_secs(interval.max(1));
+                integrations::ports::watch_port_status(&detector, interval).await?;
             } else {
-                let detector = detection::ProjectDetector::new(&working_dir);
-                integrations::ports::print_port_status(&detector).await?;
+                let detector = project_detector(&working_dir, &config);
+                integrations::ports::print_port_status(&detector, cli.json).await?;
             }
         }
         Some(Commands::Docker { up, down }) => {
             if up {
-                integrations::docker::compose_up(&working_dir).await?;
+                integrations::docker::compose_up(&working_dir, None).await?;
             } else if down {
-                integrations::docker::compose_down(&working_dir).await?;
+                integrations::docker::compose_down(&working_dir, None).await?;
             } else {
-                integrations::docker::print_status(&working_dir).await?;
+                integrations::docker::print_status(&working_dir, cli.json).await?;
             }
         }
+        Some(Commands::Status { color }) => {
+            let detector = project_detector(&working_dir, &config);
+            let state = crate::core::status::gather(
+                &working_dir,
+                detector,
+                config.general.enable_docker,
+                config.general.enable_ports,
+            )
+            .await;
+            println!("{}", crate::core::status::status_line(&state, color));
+        }
         Some(Commands::Init { force }) => {
             config::init_project_config(&working_dir, force)?;
         }
+        // Exits 1 when a critical check fails
+        Some(Commands::Doctor) => {
+            let healthy = crate::core::doctor::run(
+                config_path.as_deref(),
+                &working_dir,
+                profile.as_deref(),
+                cli.no_git,
+                cli.no_docker,
+            )
+            .await;
+            std::process::exit(if healthy { 0 } else { 1 });
+        }
+        Some(Commands::Completions { shell }) => {
+            write_completions(shell, &mut std::io::stdout());
+        }
         None => {
             // Launch the main TUI
-            let mut app = App::new(working_dir, config).await?;
+            let http_addr = cli
+                .http
+                .as_deref()
+                .map(crate::core::http::parse_addr)
+                .transpose()?;
+            let mut app = App::new(working_dir, config)
+                .await?
+                .with_http_addr(http_addr)
+                .with_config_path(config_path);
             app.run().await?;
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zsh_completions_cover_subcommands() {
+        let mut out = Vec::new();
+        write_completions(clap_complete::Shell::Zsh, &mut out);
+        let script = String::from_utf8(out).unwrap();
+        assert!(!script.is_empty());
+        assert!(script.contains("focus"));
+    }
+
+    #[test]
+    fn equivalent_paths_share_a_keychain_account() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("foo")).unwrap();
+
+        let dotted = resolve_working_dir(Some(dir.path().join(".").join("foo"))).unwrap();
+        let slashed = resolve_working_dir(Some(PathBuf::from(format!(
+            "{}/foo/",
+            dir.path().display()
+        ))))
+        .unwrap();
+
+        assert_eq!(dotted, slashed);
+        assert_eq!(
+            secrets::keychain_account(&dotted, "API_KEY"),
+            secrets::keychain_account(&slashed, "API_KEY")
+        );
+        assert_eq!(secrets::keychain_account(&dotted, "API_KEY"), "foo:API_KEY");
+
+        let missing = resolve_working_dir(Some(dir.path().join("missing")));
+        assert!(missing.unwrap_err().to_string().contains("does not exist"));
+    }
+
+    #[test]
+    fn http_flag_leaves_the_next_argument_alone() {
+        let cli = Cli::try_parse_from(["orbit", "--http", "status"]).unwrap();
+        assert_eq!(
+            cli.http.as_deref(),
+            Some(crate::core::http::DEFAULT_HTTP_ADDR)
+        );
+        assert!(matches!(cli.command, Some(Commands::Status { .. })));
+
+        let cli = Cli::try_parse_from(["orbit", "--http=9000"]).unwrap();
+        assert_eq!(cli.http.as_deref(), Some("9000"));
+    }
+
+    #[test]
+    fn kill_options_require_kill() {
+        assert!(Cli::try_parse_from(["orbit", "ports", "--timeout", "5"]).is_err());
+        assert!(
+            Cli::try_parse_from(["orbit", "ports", "--kill", "3000", "--timeout", "5"]).is_ok()
+        );
+        assert!(Cli::try_parse_from(["orbit", "ports"]).is_ok());
+    }
+
+    #[test]
+    fn json_output_cannot_be_watched() {
+        assert!(Cli::try_parse_from(["orbit", "ports", "--watch", "--json"]).is_err());
+        let cli = Cli::try_parse_from(["orbit", "--json", "ports", "--watch"]).unwrap();
+        assert!(check_conflicts(&cli).is_err());
+        let cli = Cli::try_parse_from(["orbit", "ports", "--json"]).unwrap();
+        assert!(check_conflicts(&cli).is_ok());
+    }
+
+    #[test]
+    fn export_formats_are_validated() {
+        assert!(Cli::try_parse_from(["orbit", "actions", "--export", "yaml"]).is_err());
+        let cli = Cli::try_parse_from(["orbit", "actions", "--export", "just"]).unwrap();
+        let Some(Commands::Actions {
+            export: Some(format),
+            ..
+        }) = cli.command
+        else {
+            panic!("expected actions --export");
+        };
+        assert_eq!(
+            format.script_format(),
+            Some(actions::ScriptExportFormat::Just)
+        );
+        assert_eq!(ExportFormat::Markdown.script_format(), None);
+    }
+
+    #[test]
+    fn json_is_rejected_where_it_would_be_ignored() {
+        for args in [
+            &["orbit", "--json"][..],
+            &["orbit", "--json", "status"],
+            &["orbit", "doctor", "--json"],
+            &["orbit", "actions", "--export", "make", "--json"],
+            &["orbit", "--json", "docker", "--up"],
+        ] {
+            let cli = Cli::try_parse_from(args).unwrap();
+            assert!(check_conflicts(&cli).is_err(), "{:?}", args);
+        }
+        for args in [
+            &["orbit", "actions", "--json"][..],
+            &["orbit", "--json", "env"],
+            &["orbit", "docker", "--json"],
+            &["orbit", "status"],
+        ] {
+            let cli = Cli::try_parse_from(args).unwrap();
+            assert!(check_conflicts(&cli).is_ok(), "{:?}", args);
+        }
+    }
+}
@@ -3,19 +3,133 @@
 #![allow(dead_code)]
 
 use anyhow::Result;
+use regex::Regex;
+#[cfg(target_os = "macos")]
+use security_framework::item::{ItemClass, ItemSearchOptions, Limit};
 use security_framework::passwords::{
     delete_generic_password, get_generic_password, set_generic_password,
 };
 use std::collections::HashMap;
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Read, Write};
 use std::path::Path;
 
-use crate::detection::ProjectDetector;
+use crate::detection::{EnvVarSpec, ProjectDetector};
 
 const KEYCHAIN_SERVICE: &str = "orbit";
 
+/// errSecItemNotFound
+const ERR_SEC_ITEM_NOT_FOUND: i32 = -25300;
+/// errSecInteractionNotAllowed: the keychain is locked and can't prompt
+const ERR_SEC_INTERACTION_NOT_ALLOWED: i32 = -25308;
+/// errSecAuthFailed: wrong keychain password
+const ERR_SEC_AUTH_FAILED: i32 = -25293;
+/// errSecUserCanceled: the unlock prompt was dismissed
+const ERR_SEC_USER_CANCELED: i32 = -128;
+
+/// Keychain failures worth telling apart from a generic error
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeychainError {
+    NotFound,
+    /// Locked, or the unlock prompt was refused; the item may well exist
+    Locked,
+    Other(i32),
+}
+
+impl KeychainError {
+    pub fn from_code(code: i32) -> Self {
+        match code {
+            ERR_SEC_ITEM_NOT_FOUND => Self::NotFound,
+            ERR_SEC_INTERACTION_NOT_ALLOWED | ERR_SEC_AUTH_FAILED | ERR_SEC_USER_CANCELED => {
+                Self::Locked
+            }
+            other => Self::Other(other),
+        }
+    }
+}
+
+impl From<security_framework::base::Error> for KeychainError {
+    fn from(e: security_framework::base::Error) -> Self {
+        Self::from_code(e.code())
+    }
+}
+
+impl std::fmt::Display for KeychainError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "secret not found in Keychain"),
+            Self::Locked => write!(
+                f,
+                "Keychain is locked. Unlock it (e.g. `security unlock-keychain`) and try again"
+            ),
+            Self::Other(code) => write!(f, "Keychain error (OSStatus {})", code),
+        }
+    }
+}
+
+impl std::error::Error for KeychainError {}
+
+/// Built-in env var name patterns treated as secrets
+pub const DEFAULT_SECRET_PATTERNS: &[&str] = &[
+    "*_TOKEN",
+    "*_KEY",
+    "*_SECRET",
+    "*PASSWORD*",
+    "*_PASS",
+    "*_CREDENTIALS",
+    "DATABASE_URL",
+];
+
+/// Decides which env var names hold secrets
+///
+/// Patterns are case-insensitive globs where `*` matches anything; a `re:` prefix
+/// switches to a raw regular expression (e.g. `re:^STRIPE_`).
+#[derive(Debug, Clone)]
+pub struct SecretMatcher {
+    patterns: Vec<Regex>,
+}
+
+impl SecretMatcher {
+    pub fn new<S: AsRef<str>>(patterns: &[S]) -> Self {
+        let patterns = patterns
+            .iter()
+            .filter_map(|p| {
+                let pattern = p.as_ref();
+                let source = match pattern.strip_prefix("re:") {
+                    Some(re) => format!("(?i){}", re),
+                    None => format!(
+                        "(?i)^{}$",
+                        pattern
+                            .split('*')
+                            .map(regex::escape)
+                            .collect::<Vec<_>>()
+                            .join(".*")
+                    ),
+                };
+                match Regex::new(&source) {
+                    Ok(re) => Some(re),
+                    Err(e) => {
+                        tracing::warn!("Ignoring invalid secret pattern {:?}: {}", pattern, e);
+                        None
+                    }
+                }
+            })
+            .collect();
+        Self { patterns }
+    }
+
+    pub fn is_secret(&self, name: &str) -> bool {
+        self.patterns.iter().any(|re| re.is_match(name))
+    }
+}
+
+impl Default for SecretMatcher {
+    fn default() -> Self {
+        Self::new(DEFAULT_SECRET_PATTERNS)
+    }
+}
+
 /// Get the keychain account name for a project
-fn keychain_account(project_dir: &Path, key: &str) -> String {
+pub(crate) fn keychain_account(project_dir: &Path, key: &str) -> String {
     let project_name = project_dir
         .file_name()
         .and_then(|n| n.to_str())
@@ -27,15 +141,7 @@ fn keychain_account(project_dir: &Path, key: &str) -> String {
 pub async fn set_secret(project_dir: &Path, key: &str, value: Option<String>) -> Result<()> {
     let value = match value {
         Some(v) => v,
-        None => {
-            // Prompt for value
-            print!("Enter value for '{}': ", key);
-            io::stdout().flush()?;
-
-            let mut input = String::new();
-            io::stdin().read_line(&mut input)?;
-            input.trim().to_string()
-        }
+        None => read_secret_value(key)?,
     };
 
     let account = keychain_account(project_dir, key);
@@ -44,13 +150,44 @@ pub async fn set_secret(project_dir: &Path, key: &str, value: Option<String>) ->
     let _ = delete_generic_password(KEYCHAIN_SERVICE, &account);
 
     // Set new value
-    set_generic_password(KEYCHAIN_SERVICE, &account, value.as_bytes())?;
+    set_generic_password(KEYCHAIN_SERVICE, &account, value.as_bytes())
+        .map_err(KeychainError::from)?;
 
     println!("Secret '{}' stored in Keychain", key);
     Ok(())
 }
 
+/// Prompt for a secret without echoing it, or read all of stdin when it's piped
+fn read_secret_value(key: &str) -> Result<String> {
+    if !io::stdin().is_terminal() {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        return Ok(without_trailing_newline(input));
+    }
+
+    let value = rpassword::prompt_password(format!("Enter value for '{}': ", key))?;
+    let again = rpassword::prompt_password("Enter it again: ")?;
+    if value != again {
+        anyhow::bail!("Values didn't match; '{}' was not changed", key);
+    }
+    Ok(value)
+}
+
+/// Drop the one line ending `echo` adds, keeping newlines inside the value (e.g. a PEM key)
+fn without_trailing_newline(mut value: String) -> String {
+    if value.ends_with('\n') {
+        value.pop();
+        if value.ends_with('\r') {
+            value.pop();
+        }
+    }
+    value
+}
+
 /// Get a secret from the macOS Keychain
+///
+/// Only a missing item is `Ok(None)`; a locked keychain is an error so callers
+/// don't mistake it for an unset secret.
 pub fn get_secret(project_dir: &Path, key: &str) -> Result<Option<String>> {
     let account = keychain_account(project_dir, key);
 
@@ -59,7 +196,48 @@ pub fn get_secret(project_dir: &Path, key: &str) -> Result<Option<String>> {
             let value = String::from_utf8(password.to_vec())?;
             Ok(Some(value))
         }
-        Err(_) => Ok(None),
+        Err(e) => match KeychainError::from(e) {
+            KeychainError::NotFound => Ok(None),
+            err => Err(err.into()),
+        },
+    }
+}
+
+/// The shell command for `key`, with `{key}` substituted and quoted
+pub fn secret_command_line(template: &str, key: &str) -> String {
+    template.replace("{key}", &escape_shell_value(key))
+}
+
+/// Ask an external secret manager for `key`; a nonzero exit means not found
+pub fn run_secret_command(project_dir: &Path, template: &str, key: &str) -> Result<Option<String>> {
+    let output = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(secret_command_line(template, key))
+        .current_dir(project_dir)
+        .stdin(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .output()
+        .map_err(|e| anyhow::anyhow!("Failed to run secret command: {}", e))?;
+
+    if !output.status.success() {
+        return Ok(None);
+    }
+    let value = String::from_utf8(output.stdout)?;
+    Ok(Some(value.trim_end_matches(['\n', '\r']).to_string()))
+}
+
+/// Look up `key` in the Keychain, then in the configured external command
+pub fn resolve_secret(
+    project_dir: &Path,
+    key: &str,
+    config: &crate::config::ProjectSecretsConfig,
+) -> Result<Option<String>> {
+    if let Some(value) = get_secret(project_dir, key)? {
+        return Ok(Some(value));
+    }
+    match &config.command {
+        Some(template) => run_secret_command(project_dir, template, key),
+        None => Ok(None),
     }
 }
 
@@ -71,41 +249,160 @@ pub async fn remove_secret(project_dir: &Path, key: &str) -> Result<()> {
         Ok(_) => {
             println!("Secret '{}' removed from Keychain", key);
         }
-        Err(_) => {
-            println!("Secret '{}' not found in Keychain", key);
-        }
+        Err(e) => match KeychainError::from(e) {
+            KeychainError::NotFound => {
+                println!("Secret '{}' not found in Keychain", key);
+            }
+            err => return Err(err.into()),
+        },
     }
 
     Ok(())
 }
 
+/// Keys stored in the Keychain for the project at `project_dir`, sorted
+pub fn list_keychain_keys(project_dir: &Path) -> Result<Vec<String>> {
+    let accounts = keychain_accounts()?;
+    Ok(project_keys(&accounts, &keychain_account(project_dir, "")))
+}
+
+/// Accounts of every Orbit item in the Keychain
+///
+/// Only item attributes are read, never the secret data. Falls back to
+/// `security dump-keychain` when the item search API fails.
+#[cfg(target_os = "macos")]
+fn keychain_accounts() -> Result<Vec<String>> {
+    let search = ItemSearchOptions::new()
+        .class(ItemClass::generic_password())
+        .service(KEYCHAIN_SERVICE)
+        .load_attributes(true)
+        .limit(Limit::All)
+        .search();
+
+    let accounts = match search {
+        Ok(results) => results
+            .iter()
+            .filter_map(|result| result.simplify_dict())
+            .filter_map(|attributes| attributes.get("acct").cloned())
+            .collect(),
+        Err(e) => match KeychainError::from(e) {
+            KeychainError::NotFound => Vec::new(),
+            KeychainError::Locked => return Err(KeychainError::Locked.into()),
+            KeychainError::Other(code) => {
+                tracing::debug!("Keychain search failed ({}), trying dump-keychain", code);
+                let output = std::process::Command::new("security")
+                    .arg("dump-keychain")
+                    .stderr(std::process::Stdio::null())
+                    .output()
+                    .map_err(|e| anyhow::anyhow!("Failed to run security: {}", e))?;
+                if !output.status.success() {
+                    return Err(KeychainError::Other(code).into());
+                }
+                accounts_from_dump(&String::from_utf8_lossy(&output.stdout), KEYCHAIN_SERVICE)
+            }
+        },
+    };
+    Ok(accounts)
+}
+
+/// There's no Keychain to enumerate off macOS
+#[cfg(not(target_os = "macos"))]
+fn keychain_accounts() -> Result<Vec<String>> {
+    Ok(Vec::new())
+}
+
+/// Account names of the generic passwords for `service` in `security dump-keychain` output
+fn accounts_from_dump(dump: &str, service: &str) -> Vec<String> {
+    let quoted = |line: &str, attribute: &str| -> Option<String> {
+        let rest = line
+            .trim()
+            .strip_prefix(&format!("\"{}\"<blob>=", attribute))?;
+        let value = rest.strip_prefix('"')?.strip_suffix('"')?;
+        Some(value.to_string())
+    };
+
+    let mut accounts = Vec::new();
+    for item in dump.split("keychain: ").skip(1) {
+        if !item.contains("class: \"genp\"") {
+            continue;
+        }
+        let service_matches = item
+            .lines()
+            .any(|line| quoted(line, "svce").as_deref() == Some(service));
+        if !service_matches {
+            continue;
+        }
+        if let Some(account) = item.lines().find_map(|line| quoted(line, "acct")) {
+            accounts.push(account);
+        }
+    }
+    accounts
+}
+
+/// Key names from `accounts` that belong to the project with account `prefix`
+fn project_keys(accounts: &[String], prefix: &str) -> Vec<String> {
+    let mut keys: Vec<String> = accounts
+        .iter()
+        .filter_map(|account| account.strip_prefix(prefix))
+        .filter(|key| !key.is_empty())
+        .map(str::to_string)
+        .collect();
+    keys.sort();
+    keys.dedup();
+    keys
+}
+
 /// List all secrets for a project
-pub async fn list_secrets(project_dir: &Path) -> Result<()> {
+///
+/// Prints key names and where each one is stored, never the values.
+pub async fn list_secrets(project_dir: &Path, profile: Option<&str>) -> Result<()> {
     let project_name = project_dir
         .file_name()
         .and_then(|n| n.to_str())
         .unwrap_or("unknown");
 
-    // Note: security-framework doesn't have a direct way to list items
-    // We'd need to use the Security framework directly or shell out to `security`
-    // For now, we'll use the project config to know what secrets might exist
+    let stored = list_keychain_keys(project_dir)?;
+    let config = crate::config::ProjectConfig::load(project_dir, profile)
+        .ok()
+        .flatten()
+        .map(|c| c.secrets)
+        .unwrap_or_default();
 
     println!("Secrets for project '{}':", project_name);
     println!("(Use `orbit secrets set <key>` to add secrets)");
     println!();
 
-    // Try to load project config to see expected secrets
-    if let Ok(Some(config)) = crate::config::ProjectConfig::load(project_dir) {
-        if !config.secrets.keychain.is_empty() {
-            println!("Configured keychain secrets:");
-            for key in &config.secrets.keychain {
-                let status = if get_secret(project_dir, key)?.is_some() {
-                    "stored"
-                } else {
-                    "missing"
-                };
-                println!("  {} [{}]", key, status);
-            }
+    if !config.keychain.is_empty() {
+        println!("Configured keychain secrets:");
+        for key in &config.keychain {
+            let external = match &config.command {
+                Some(template) if !stored.contains(key) => {
+                    run_secret_command(project_dir, template, key)?.is_some()
+                }
+                _ => false,
+            };
+            let status = if stored.contains(key) {
+                "stored"
+            } else if external {
+                "external"
+            } else {
+                "missing"
+            };
+            println!("  {} [{}]", key, status);
+        }
+    }
+
+    let unlisted: Vec<&String> = stored
+        .iter()
+        .filter(|key| !config.keychain.contains(key))
+        .collect();
+    if !unlisted.is_empty() {
+        if !config.keychain.is_empty() {
+            println!();
+        }
+        println!("Stored but not in .orbit.toml:");
+        for key in unlisted {
+            println!("  {} [stored]", key);
         }
     }
 
@@ -113,13 +410,13 @@ pub async fn list_secrets(project_dir: &Path) -> Result<()> {
 }
 
 /// Inject secrets into shell environment
-pub async fn inject_secrets(project_dir: &Path, shell: &str) -> Result<()> {
+pub async fn inject_secrets(project_dir: &Path, profile: Option<&str>, shell: &str) -> Result<()> {
     let mut secrets = HashMap::new();
 
     // Load project config to get secret keys
-    if let Ok(Some(config)) = crate::config::ProjectConfig::load(project_dir) {
+    if let Ok(Some(config)) = crate::config::ProjectConfig::load(project_dir, profile) {
         for key in &config.secrets.keychain {
-            if let Some(value) = get_secret(project_dir, key)? {
+            if let Some(value) = resolve_secret(project_dir, key, &config.secrets)? {
                 secrets.insert(key.clone(), value);
             }
         }
@@ -150,58 +447,100 @@ fn escape_shell_value(value: &str) -> String {
     format!("'{}'", value.replace('\'', "'\\''"))
 }
 
-/// Print environment variable status
-pub async fn print_env_status(detector: &ProjectDetector, show_values: bool) -> Result<()> {
+/// A required variable's status as `orbit env` reports it, with the value redacted
+#[derive(Debug, serde::Serialize)]
+struct EnvVarStatus<'a> {
+    #[serde(flatten)]
+    spec: &'a EnvVarSpec,
+    set: bool,
+    /// `shell` or `.env`
+    set_in: Option<&'static str>,
+    value: Option<String>,
+}
+
+fn env_var_status<'a>(
+    spec: &'a EnvVarSpec,
+    set_in_dotenv: &[String],
+    show_values: bool,
+    secrets: &SecretMatcher,
+) -> EnvVarStatus<'a> {
+    let (set_in, value) = if let Ok(val) = std::env::var(&spec.name) {
+        let value = if !show_values {
+            "***".to_string()
+        } else if spec.is_secret || secrets.is_secret(&spec.name) {
+            mask_value(&val)
+        } else {
+            val
+        };
+        (Some("shell"), Some(value))
+    } else if set_in_dotenv.contains(&spec.name) {
+        let value = if show_values { "from .env" } else { "***" };
+        (Some(".env"), Some(value.to_string()))
+    } else {
+        (None, None)
+    };
+
+    EnvVarStatus {
+        spec,
+        set: set_in.is_some(),
+        set_in,
+        value,
+    }
+}
+
+/// Print environment variable status, as a table or (with `json`) as JSON
+pub async fn print_env_status(
+    detector: &ProjectDetector,
+    show_values: bool,
+    secrets: &SecretMatcher,
+    json: bool,
+) -> Result<()> {
     let context = detector.analyze().await?;
+    let env_vars = &context.env_vars;
+    let statuses: Vec<EnvVarStatus> = env_vars
+        .required
+        .iter()
+        .map(|spec| env_var_status(spec, &env_vars.set_in_dotenv, show_values, secrets))
+        .collect();
+
+    if json {
+        let status = serde_json::json!({
+            "variables": statuses,
+            "missing_required": env_vars.missing_required,
+            "undocumented": env_vars.undocumented,
+        });
+        println!("{}", serde_json::to_string_pretty(&status)?);
+        return Ok(());
+    }
 
     println!("Environment Variables Status:");
     println!("{:<25} {:<12} {:<15} VALUE", "VARIABLE", "STATUS", "SOURCE");
     println!("{}", "-".repeat(80));
 
-    // Check required variables
-    for spec in &context.env_vars.required {
-        let (status, source, value) = if let Ok(val) = std::env::var(&spec.name) {
-            (
-                "set",
-                "shell",
-                if show_values {
-                    if spec.is_secret {
-                        mask_value(&val)
-                    } else {
-                        val
-                    }
-                } else {
-                    "***".to_string()
-                },
-            )
-        } else if context.env_vars.set_in_dotenv.contains(&spec.name) {
-            (
-                "set",
-                ".env",
-                if show_values {
-                    "from .env".to_string()
-                } else {
-                    "***".to_string()
-                },
-            )
-        } else {
-            ("MISSING", "-", "-".to_string())
-        };
-
-        let status_colored = status.to_string();
-
+    for status in &statuses {
         println!(
             "{:<25} {:<12} {:<15} {}",
-            spec.name, status_colored, source, value
+            status.spec.name,
+            if status.set { "set" } else { "MISSING" },
+            status.set_in.unwrap_or("-"),
+            status.value.as_deref().unwrap_or("-")
         );
     }
 
     // Show missing count
-    if !context.env_vars.missing_required.is_empty() {
+    if !env_vars.missing_required.is_empty() {
         println!();
         println!(
             "Missing required variables: {}",
-            context.env_vars.missing_required.join(", ")
+            env_vars.missing_required.join(", ")
+        );
+    }
+
+    if !env_vars.undocumented.is_empty() {
+        println!();
+        println!(
+            "Undocumented (in .env but not .env.example): {}",
+            env_vars.undocumented.join(", ")
         );
     }
 
@@ -209,13 +548,229 @@ pub async fn print_env_status(detector: &ProjectDetector, show_values: bool) ->
 }
 
 fn mask_value(value: &str) -> String {
-    if value.len() <= 4 {
-        "*".repeat(value.len())
-    } else {
-        format!("{}...{}", &value[..2], &value[value.len() - 2..])
+    match crate::ui::util::char_ends(value, 2) {
+        Some((head, tail)) => format!("{}...{}", head, tail),
+        None => "*".repeat(value.chars().count()),
     }
 }
 
+/// `KEY=value` pairs from a `.env` file, in file order
+pub fn parse_dotenv(content: &str) -> Vec<(String, String)> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (key, value) = line.split_once('=')?;
+            let value = value.trim();
+            let value = match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+                Some(quoted) => unescape_dotenv(quoted),
+                None => value.trim_matches('"').trim_matches('\'').to_string(),
+            };
+            Some((key.trim().to_string(), value))
+        })
+        .collect()
+}
+
+/// Undo the escapes `dotenv_lines` writes inside double quotes
+fn unescape_dotenv(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some(c @ ('\\' | '"')) => unescaped.push(c),
+            Some(other) => {
+                unescaped.push('\\');
+                unescaped.push(other);
+            }
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+/// Keys from `.env` that `orbit secrets import` moves into the Keychain
+///
+/// With `secrets.keychain` configured only those keys move; otherwise anything that
+/// looks like a secret does. Keys listed in `secrets.allow_dotenv` always stay put.
+pub fn keys_to_import(
+    entries: &[(String, String)],
+    config: &crate::config::ProjectSecretsConfig,
+    matcher: &SecretMatcher,
+) -> Vec<String> {
+    entries
+        .iter()
+        .map(|(key, _)| key)
+        .filter(|key| !config.allow_dotenv.contains(key))
+        .filter(|key| {
+            if config.keychain.is_empty() {
+                matcher.is_secret(key)
+            } else {
+                config.keychain.contains(key)
+            }
+        })
+        .fold(Vec::new(), |mut keys, key| {
+            if !keys.contains(key) {
+                keys.push(key.clone());
+            }
+            keys
+        })
+}
+
+/// `.env` lines for `secrets`, quoting values that need it
+pub fn dotenv_lines(secrets: &[(String, String)]) -> Vec<String> {
+    secrets
+        .iter()
+        .map(|(key, value)| {
+            let plain = !value.is_empty()
+                && value
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "-_./:@+,%".contains(c));
+            if plain {
+                format!("{}={}", key, value)
+            } else {
+                let escaped = value
+                    .replace('\\', "\\\\")
+                    .replace('"', "\\\"")
+                    .replace('\n', "\\n");
+                format!("{}=\"{}\"", key, escaped)
+            }
+        })
+        .collect()
+}
+
+/// Drop the lines assigning any of `keys` from `.env` content
+fn without_dotenv_keys(content: &str, keys: &[String]) -> String {
+    let mut kept = String::new();
+    for line in content.lines() {
+        let assigned = parse_dotenv(line).into_iter().next().map(|(key, _)| key);
+        if assigned.is_some_and(|key| keys.contains(&key)) {
+            continue;
+        }
+        kept.push_str(line);
+        kept.push('\n');
+    }
+    kept
+}
+
+fn confirm(prompt: &str) -> Result<bool> {
+    print!("{} [y/N] ", prompt);
+    io::stdout().flush()?;
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(matches!(input.trim(), "y" | "Y" | "yes"))
+}
+
+/// Move secrets from `.env` into the Keychain, optionally removing them from the file
+pub async fn import_secrets(
+    project_dir: &Path,
+    profile: Option<&str>,
+    matcher: &SecretMatcher,
+    remove_from_dotenv: bool,
+    yes: bool,
+) -> Result<()> {
+    let dotenv_path = project_dir.join(".env");
+    let content = tokio::fs::read_to_string(&dotenv_path)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", dotenv_path.display(), e))?;
+    let entries = parse_dotenv(&content);
+    let config = crate::config::ProjectConfig::load(project_dir, profile)?
+        .map(|c| c.secrets)
+        .unwrap_or_default();
+
+    let mut imported = Vec::new();
+    for key in keys_to_import(&entries, &config, matcher) {
+        let Some((_, value)) = entries.iter().rev().find(|(k, _)| *k == key) else {
+            continue;
+        };
+        match get_secret(project_dir, &key)? {
+            Some(existing) if existing == *value => {}
+            Some(_) if !yes && !confirm(&format!("Overwrite '{}' in Keychain?", key))? => continue,
+            _ => set_secret(project_dir, &key, Some(value.clone())).await?,
+        }
+        imported.push(key);
+    }
+
+    if imported.is_empty() {
+        println!("No secrets to import from .env");
+        return Ok(());
+    }
+
+    // So `secrets inject`, `export` and `list` know about them from now on
+    let added: Vec<String> = imported
+        .iter()
+        .filter(|key| !config.keychain.contains(key))
+        .cloned()
+        .collect();
+    if !added.is_empty() {
+        crate::config::ProjectConfig::persist_keychain_keys(project_dir, &added)?;
+        println!(
+            "Added {} to secrets.keychain in .orbit.toml",
+            added.join(", ")
+        );
+    }
+
+    if remove_from_dotenv {
+        tokio::fs::write(&dotenv_path, without_dotenv_keys(&content, &imported)).await?;
+        println!("Removed {} from .env", imported.join(", "));
+    }
+    Ok(())
+}
+
+/// Write the configured Keychain secrets to `.env`
+pub async fn export_secrets(project_dir: &Path, profile: Option<&str>, yes: bool) -> Result<()> {
+    let config = crate::config::ProjectConfig::load(project_dir, profile)?
+        .map(|c| c.secrets)
+        .unwrap_or_default();
+    if config.keychain.is_empty() {
+        eprintln!("No secrets configured for this project (secrets.keychain in .orbit.toml)");
+        return Ok(());
+    }
+
+    let dotenv_path = project_dir.join(".env");
+    let content = tokio::fs::read_to_string(&dotenv_path)
+        .await
+        .unwrap_or_default();
+    let existing: Vec<String> = parse_dotenv(&content).into_iter().map(|(k, _)| k).collect();
+
+    let mut secrets = Vec::new();
+    for key in &config.keychain {
+        let Some(value) = resolve_secret(project_dir, key, &config)? else {
+            eprintln!("Skipping '{}': not in Keychain", key);
+            continue;
+        };
+        if existing.contains(key) && !yes && !confirm(&format!("Overwrite '{}' in .env?", key))? {
+            continue;
+        }
+        secrets.push((key.clone(), value));
+    }
+
+    if secrets.is_empty() {
+        println!("Nothing to export");
+        return Ok(());
+    }
+
+    let keys: Vec<String> = secrets.iter().map(|(k, _)| k.clone()).collect();
+    let mut updated = without_dotenv_keys(&content, &keys);
+    for line in dotenv_lines(&secrets) {
+        updated.push_str(&line);
+        updated.push('\n');
+    }
+    tokio::fs::write(&dotenv_path, updated).await?;
+
+    eprintln!(
+        "Warning: wrote {} secret(s) to .env in plain text; keep it out of version control",
+        secrets.len()
+    );
+    Ok(())
+}
+
 /// Environment variable source
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EnvSource {
@@ -226,35 +781,26 @@ pub enum EnvSource {
 }
 
 /// Get all environment variables for a project
-pub async fn get_project_env(project_dir: &Path) -> Result<HashMap<String, (String, EnvSource)>> {
+pub async fn get_project_env(
+    project_dir: &Path,
+    profile: Option<&str>,
+) -> Result<HashMap<String, (String, EnvSource)>> {
     let mut env = HashMap::new();
 
     // Load from .env file
     let dotenv_path = project_dir.join(".env");
     if dotenv_path.exists() {
         if let Ok(content) = tokio::fs::read_to_string(&dotenv_path).await {
-            for line in content.lines() {
-                let line = line.trim();
-                if line.is_empty() || line.starts_with('#') {
-                    continue;
-                }
-                if let Some((key, value)) = line.split_once('=') {
-                    let key = key.trim().to_string();
-                    let value = value
-                        .trim()
-                        .trim_matches('"')
-                        .trim_matches('\'')
-                        .to_string();
-                    env.insert(key, (value, EnvSource::DotEnv));
-                }
+            for (key, value) in parse_dotenv(&content) {
+                env.insert(key, (value, EnvSource::DotEnv));
             }
         }
     }
 
     // Load from keychain (configured secrets)
-    if let Ok(Some(config)) = crate::config::ProjectConfig::load(project_dir) {
+    if let Ok(Some(config)) = crate::config::ProjectConfig::load(project_dir, profile) {
         for key in &config.secrets.keychain {
-            if let Some(value) = get_secret(project_dir, key)? {
+            if let Some(value) = resolve_secret(project_dir, key, &config.secrets)? {
                 env.insert(key.clone(), (value, EnvSource::Keychain));
             }
         }
@@ -272,6 +818,86 @@ pub async fn get_project_env(project_dir: &Path) -> Result<HashMap<String, (Stri
 mod tests {
     use super::*;
 
+    #[test]
+    fn env_json_redacts_values_unless_shown() {
+        let spec = |name: &str| EnvVarSpec {
+            name: name.to_string(),
+            description: None,
+            source: ".env.example".to_string(),
+            example_value: None,
+            is_secret: false,
+        };
+        let token = spec("ORBIT_TEST_JSON_API_TOKEN");
+        let dotenv = spec("ORBIT_TEST_JSON_DATABASE_URL");
+        let missing = spec("ORBIT_TEST_JSON_MISSING");
+        std::env::set_var(&token.name, "sk_live_abcdef");
+        let set_in_dotenv = vec![dotenv.name.clone()];
+        let matcher = SecretMatcher::default();
+
+        let json = |spec, show| {
+            serde_json::to_value(env_var_status(spec, &set_in_dotenv, show, &matcher)).unwrap()
+        };
+        assert_eq!(json(&token, false)["value"], "***");
+        assert_eq!(json(&token, false)["set_in"], "shell");
+        // Names that look like secrets stay masked even when values are shown
+        assert_eq!(json(&token, true)["value"], "sk...ef");
+        assert_eq!(json(&dotenv, false)["value"], "***");
+        assert_eq!(json(&dotenv, false)["source"], ".env.example");
+        assert_eq!(json(&missing, true)["set"], false);
+        assert!(json(&missing, true)["value"].is_null());
+    }
+
+    #[test]
+    fn piped_values_lose_only_the_final_line_ending() {
+        let strip = |s: &str| without_trailing_newline(s.to_string());
+        assert_eq!(strip("sk_live_123"), "sk_live_123");
+        assert_eq!(strip("sk_live_123\n"), "sk_live_123");
+        assert_eq!(strip("sk_live_123\r\n"), "sk_live_123");
+        assert_eq!(
+            strip("-----BEGIN KEY-----\nabc\n\n"),
+            "-----BEGIN KEY-----\nabc\n"
+        );
+        assert_eq!(strip("  spaced  "), "  spaced  ");
+    }
+
+    #[test]
+    fn keychain_dump_lists_only_this_projects_orbit_keys() {
+        let dump = concat!(
+            "keychain: \"/Users/me/Library/Keychains/login.keychain-db\"\n",
+            "version: 512\n",
+            "class: \"genp\"\n",
+            "attributes:\n",
+            "    \"acct\"<blob>=\"api:STRIPE_KEY\"\n",
+            "    \"svce\"<blob>=\"orbit\"\n",
+            "keychain: \"/Users/me/Library/Keychains/login.keychain-db\"\n",
+            "class: \"genp\"\n",
+            "attributes:\n",
+            "    \"acct\"<blob>=\"api:GITHUB_TOKEN\"\n",
+            "    \"svce\"<blob>=\"gh:github.com\"\n",
+            "keychain: \"/Users/me/Library/Keychains/login.keychain-db\"\n",
+            "class: \"genp\"\n",
+            "attributes:\n",
+            "    \"acct\"<blob>=\"web:SESSION_SECRET\"\n",
+            "    \"svce\"<blob>=\"orbit\"\n",
+            "keychain: \"/Users/me/Library/Keychains/login.keychain-db\"\n",
+            "class: \"genp\"\n",
+            "attributes:\n",
+            "    \"acct\"<blob>=\"api:DATABASE_URL\"\n",
+            "    \"svce\"<blob>=\"orbit\"\n",
+        );
+
+        let accounts = accounts_from_dump(dump, KEYCHAIN_SERVICE);
+        assert_eq!(
+            accounts,
+            ["api:STRIPE_KEY", "web:SESSION_SECRET", "api:DATABASE_URL"]
+        );
+        let prefix = keychain_account(Path::new("/src/api"), "");
+        assert_eq!(
+            project_keys(&accounts, &prefix),
+            ["DATABASE_URL", "STRIPE_KEY"]
+        );
+    }
+
     #[test]
     fn test_mask_value() {
         assert_eq!(mask_value("ab"), "**");
@@ -279,9 +905,108 @@ mod tests {
         assert_eq!(mask_value("abcdefgh"), "ab...gh");
     }
 
+    #[test]
+    fn default_patterns_flag_secret_names() {
+        let matcher = SecretMatcher::default();
+        assert!(matcher.is_secret("GITHUB_TOKEN"));
+        assert!(matcher.is_secret("DB_PASSWORD"));
+        assert!(!matcher.is_secret("NODE_ENV"));
+
+        let custom = SecretMatcher::new(&["re:^STRIPE_", "SESSION"]);
+        assert!(custom.is_secret("stripe_live"));
+        assert!(custom.is_secret("SESSION"));
+        assert!(!custom.is_secret("SESSION_TTL"));
+    }
+
+    #[test]
+    fn import_picks_configured_keys_and_skips_allowed_dotenv() {
+        let entries = parse_dotenv(
+            "# local\nAPI_KEY=abc\nexport DB_PASSWORD='hunter2'\nNODE_ENV=dev\nSENTRY_TOKEN=x\n",
+        );
+        let matcher = SecretMatcher::default();
+
+        let by_pattern = crate::config::ProjectSecretsConfig {
+            keychain: vec![],
+            allow_dotenv: vec!["SENTRY_TOKEN".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            keys_to_import(&entries, &by_pattern, &matcher),
+            ["API_KEY", "DB_PASSWORD"]
+        );
+
+        let configured = crate::config::ProjectSecretsConfig {
+            keychain: vec!["API_KEY".to_string(), "NODE_ENV".to_string()],
+            allow_dotenv: vec!["NODE_ENV".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(keys_to_import(&entries, &configured, &matcher), ["API_KEY"]);
+    }
+
+    #[test]
+    fn export_writes_quoted_dotenv_lines() {
+        let secrets = vec![
+            ("API_KEY".to_string(), "sk-live_123".to_string()),
+            ("GREETING".to_string(), "hello \"world\"".to_string()),
+        ];
+        assert_eq!(
+            dotenv_lines(&secrets),
+            ["API_KEY=sk-live_123", "GREETING=\"hello \\\"world\\\"\""]
+        );
+
+        let tricky = vec![(
+            "CERT".to_string(),
+            "line one\nC:\\path \"quoted\" \\n".to_string(),
+        )];
+        let written = dotenv_lines(&tricky).join("\n");
+        assert_eq!(parse_dotenv(&written), tricky);
+
+        let content = "NODE_ENV=dev\nAPI_KEY=old\n";
+        assert_eq!(
+            without_dotenv_keys(content, &["API_KEY".to_string()]),
+            "NODE_ENV=dev\n"
+        );
+    }
+
+    #[test]
+    fn secret_command_substitutes_key_and_captures_stdout() {
+        let template = "op read op://vault/{key}/password";
+        assert_eq!(
+            secret_command_line(template, "API_KEY"),
+            "op read op://vault/'API_KEY'/password"
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let stub = "printf 'value-for-%s\\n' {key}";
+        assert_eq!(
+            run_secret_command(dir.path(), stub, "API_KEY").unwrap(),
+            Some("value-for-API_KEY".to_string())
+        );
+        assert_eq!(
+            run_secret_command(dir.path(), "echo leaked; exit 1", "API_KEY").unwrap(),
+            None
+        );
+    }
+
     #[test]
     fn test_escape_shell_value() {
         assert_eq!(escape_shell_value("simple"), "'simple'");
         assert_eq!(escape_shell_value("it's"), "'it'\\''s'");
     }
+
+    #[test]
+    fn locked_keychain_is_not_reported_as_missing() {
+        let locked = KeychainError::from_code(ERR_SEC_INTERACTION_NOT_ALLOWED);
+        assert_eq!(locked, KeychainError::Locked);
+        assert!(locked.to_string().contains("unlock"));
+        assert_eq!(
+            KeychainError::from_code(ERR_SEC_USER_CANCELED),
+            KeychainError::Locked
+        );
+        assert_eq!(
+            KeychainError::from_code(ERR_SEC_ITEM_NOT_FOUND),
+            KeychainError::NotFound
+        );
+        assert_eq!(KeychainError::from_code(-1), KeychainError::Other(-1));
+    }
 }
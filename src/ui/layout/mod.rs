@@ -2,7 +2,7 @@
 
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 
-use crate::core::state::{AppState, LayoutConfig, LayoutPreset};
+use crate::core::state::{AppState, FocusedPanel, LayoutConfig, LayoutPreset};
 
 /// Computed layout rects for all panels
 #[derive(Debug, Clone, Default)]
@@ -15,9 +15,27 @@ pub struct ComputedLayout {
     pub actions_panel: Option<Rect>,
     pub env_panel: Option<Rect>,
     pub output_panel: Option<Rect>,
+    /// Right half of the output area when `output_split` is on
+    pub pinned_logs_panel: Option<Rect>,
+    pub recent_files_panel: Option<Rect>,
     pub overlay_area: Option<Rect>,
 }
 
+impl ComputedLayout {
+    /// Where `panel` is drawn, or `None` when this layout hides it
+    pub fn panel(&self, panel: FocusedPanel) -> Option<Rect> {
+        match panel {
+            FocusedPanel::Actions => self.actions_panel,
+            FocusedPanel::Docker => self.docker_panel,
+            FocusedPanel::Ports => self.ports_panel,
+            FocusedPanel::RecentFiles => self.recent_files_panel,
+            FocusedPanel::Env => self.env_panel,
+            FocusedPanel::Output => self.output_panel,
+            FocusedPanel::PinnedLogs => self.pinned_logs_panel,
+        }
+    }
+}
+
 pub struct LayoutManager;
 
 impl LayoutManager {
@@ -25,13 +43,27 @@ impl LayoutManager {
     pub fn compute(area: Rect, state: &AppState) -> ComputedLayout {
         let config = &state.layout;
 
-        match config.preset {
+        let mut layout = match config.preset {
             LayoutPreset::Standard => Self::standard_layout(area, config),
             LayoutPreset::Compact => Self::compact_layout(area, config),
             LayoutPreset::Wide => Self::wide_layout(area, config),
             LayoutPreset::FocusMode => Self::focus_layout(area),
             LayoutPreset::TerminalFocus => Self::terminal_focus_layout(area),
+        };
+
+        // The embedded shell is sized to the whole output area, so it's never split
+        if config.output_split && config.preset != LayoutPreset::TerminalFocus {
+            if let Some(output) = layout.output_panel {
+                let halves = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                    .split(output);
+                layout.output_panel = Some(halves[0]);
+                layout.pinned_logs_panel = Some(halves[1]);
+            }
         }
+
+        layout
     }
 
     fn standard_layout(area: Rect, config: &LayoutConfig) -> ComputedLayout {
@@ -64,7 +96,7 @@ impl LayoutManager {
             sidebar_constraints.push(Constraint::Length(8));
         }
         sidebar_constraints.push(Constraint::Length(5)); // System metrics
-        sidebar_constraints.push(Constraint::Min(0)); // Spacer
+        sidebar_constraints.push(Constraint::Min(0)); // Recent files
 
         let sidebar_chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -102,6 +134,7 @@ impl LayoutManager {
             sidebar_idx += 1;
         }
         layout.system_panel = Some(sidebar_chunks[sidebar_idx]);
+        layout.recent_files_panel = Self::non_empty(sidebar_chunks[sidebar_idx + 1]);
 
         // Assign main panels
         layout.actions_panel = Some(main_chunks[0]);
@@ -170,7 +203,7 @@ impl LayoutManager {
             ])
             .split(body_chunks[0]);
 
-        // Right column: Env, System
+        // Right column: Env, System, Recent files
         let right_chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
@@ -195,7 +228,9 @@ impl LayoutManager {
             output_panel: Some(middle_chunks[1]),
             env_panel: Some(right_chunks[0]),
             system_panel: Some(right_chunks[1]),
+            recent_files_panel: Self::non_empty(right_chunks[2]),
             overlay_area: Some(Self::centered_rect(50, 60, area)),
+            ..Default::default()
         }
     }
 
@@ -238,6 +273,11 @@ impl LayoutManager {
         }
     }
 
+    /// A leftover area is only worth a panel if it can show a bordered row
+    fn non_empty(area: Rect) -> Option<Rect> {
+        (area.height >= 3).then_some(area)
+    }
+
     /// Create a centered rect with given percentage width/height
     fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
         let popup_layout = Layout::default()
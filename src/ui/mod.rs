@@ -2,5 +2,7 @@
 
 pub mod layout;
 pub mod renderer;
+pub mod snapshot;
 pub mod theme;
+pub mod util;
 pub mod widgets;
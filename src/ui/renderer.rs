@@ -42,6 +42,10 @@ impl Renderer {
             frame.render_widget(MetricsPanel::new(state, theme), system_area);
         }
 
+        if let Some(recent_area) = layout.recent_files_panel {
+            frame.render_widget(RecentFilesPanel::new(state, theme), recent_area);
+        }
+
         if let Some(actions_area) = layout.actions_panel {
             frame.render_widget(ActionsPanel::new(state, theme), actions_area);
         }
@@ -51,7 +55,18 @@ impl Renderer {
         }
 
         if let Some(output_area) = layout.output_panel {
-            frame.render_widget(OutputPanel::new(state, theme), output_area);
+            if state.mode == AppMode::Terminal {
+                frame.render_widget(TerminalPanel::new(state, theme), output_area);
+                if let Some(cursor) = TerminalPanel::cursor(state, output_area) {
+                    frame.set_cursor_position(cursor);
+                }
+            } else {
+                frame.render_widget(OutputPanel::new(state, theme), output_area);
+            }
+        }
+
+        if let Some(pinned_area) = layout.pinned_logs_panel {
+            frame.render_widget(OutputPanel::pinned_logs(state, theme), pinned_area);
         }
 
         // Render overlays based on mode
@@ -63,18 +78,37 @@ impl Renderer {
             }
             AppMode::Help => {
                 if let Some(overlay_area) = layout.overlay_area {
-                    frame.render_widget(HelpOverlay::new(theme), overlay_area);
+                    frame.render_widget(HelpOverlay::new(&state.keys, theme), overlay_area);
                 }
             }
             AppMode::FocusMode {
                 remaining_seconds,
                 ambient_playing,
+                paused,
             } => {
-                Self::render_focus_mode(frame, state, *remaining_seconds, *ambient_playing);
+                Self::render_focus_mode(
+                    frame,
+                    state,
+                    *remaining_seconds,
+                    *ambient_playing,
+                    *paused,
+                );
             }
-            AppMode::Confirm { message, .. } => {
+            AppMode::LogViewer => {
                 if let Some(overlay_area) = layout.overlay_area {
-                    Self::render_confirm_dialog(frame, state, message, overlay_area);
+                    frame.render_widget(ratatui::widgets::Clear, overlay_area);
+                    frame.render_widget(OutputPanel::log_viewer(state, theme), overlay_area);
+                }
+            }
+            AppMode::Notes => {
+                if let Some(overlay_area) = layout.overlay_area {
+                    frame.render_widget(ratatui::widgets::Clear, overlay_area);
+                    frame.render_widget(NotesOverlay::new(state, theme), overlay_area);
+                }
+            }
+            AppMode::Confirm { .. } => {
+                if let Some(overlay_area) = layout.overlay_area {
+                    Self::render_confirm_dialog(frame, state, overlay_area);
                 }
             }
             _ => {}
@@ -89,6 +123,7 @@ impl Renderer {
         state: &AppState,
         remaining_seconds: u32,
         ambient_playing: bool,
+        paused: bool,
     ) {
         use ratatui::layout::{Constraint, Direction, Layout};
         use ratatui::style::{Modifier, Style};
@@ -128,8 +163,13 @@ impl Renderer {
             format!("{:02}:{:02}", minutes, seconds)
         };
 
+        let title = if paused {
+            " 🎯 Focus Mode ⏸ "
+        } else {
+            " 🎯 Focus Mode "
+        };
         let block = Block::default()
-            .title(Span::styled(" 🎯 Focus Mode ", theme.styles.panel_title))
+            .title(Span::styled(title, theme.styles.panel_title))
             .borders(Borders::ALL)
             .border_style(theme.styles.panel_border_focused)
             .style(Style::default().bg(theme.colors.bg_secondary));
@@ -160,10 +200,12 @@ impl Renderer {
         );
 
         // Status line
-        let status = if ambient_playing {
-            "♪ Ambient playing • Press Esc to exit"
+        let status = if paused {
+            "⏸ Paused • space to resume • Esc to exit"
+        } else if ambient_playing {
+            "♪ Ambient playing • +/- volume • space to pause • Esc to exit"
         } else {
-            "Press Esc to exit focus mode"
+            "Space to pause • Esc to exit focus mode"
         };
 
         let status_line = Line::from(vec![Span::styled(
@@ -182,11 +224,22 @@ impl Renderer {
         );
     }
 
-    fn render_confirm_dialog(frame: &mut Frame, state: &AppState, message: &str, area: Rect) {
+    fn render_confirm_dialog(frame: &mut Frame, state: &AppState, area: Rect) {
         use ratatui::style::Style;
         use ratatui::text::{Line, Span};
         use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 
+        let AppMode::Confirm {
+            message,
+            destructive,
+            phrase,
+            input,
+            ..
+        } = &state.mode
+        else {
+            return;
+        };
+        let destructive = *destructive;
         let theme = &state.theme;
 
         // Make dialog smaller
@@ -194,35 +247,76 @@ impl Renderer {
 
         frame.render_widget(Clear, dialog_area);
 
+        // Data-destroying actions get a red dialog so they can't be confirmed on autopilot
+        let (title, border_style, message_color) = if destructive {
+            (
+                Span::styled(
+                    " ⚠ Data loss ",
+                    Style::default()
+                        .fg(theme.colors.error)
+                        .add_modifier(ratatui::style::Modifier::BOLD),
+                ),
+                Style::default().fg(theme.colors.error),
+                theme.colors.error,
+            )
+        } else {
+            (
+                Span::styled(" Confirm ", theme.styles.panel_title),
+                theme.styles.panel_border_focused,
+                theme.colors.fg_primary,
+            )
+        };
+
         let block = Block::default()
-            .title(Span::styled(" Confirm ", theme.styles.panel_title))
+            .title(title)
             .borders(Borders::ALL)
-            .border_style(theme.styles.panel_border_focused)
+            .border_style(border_style)
             .style(Style::default().bg(theme.colors.bg_secondary));
 
         let inner = block.inner(dialog_area);
         frame.render_widget(block, dialog_area);
 
         // Message
-        let msg_para = Paragraph::new(message)
-            .style(Style::default().fg(theme.colors.fg_primary))
+        let msg_para = Paragraph::new(message.as_str())
+            .style(Style::default().fg(message_color))
+            .wrap(ratatui::widgets::Wrap { trim: true })
             .alignment(ratatui::layout::Alignment::Center);
 
         frame.render_widget(
             msg_para,
             Rect {
                 y: inner.y + 1,
-                height: 2,
+                height: inner.height.saturating_sub(3).max(2),
                 ..inner
             },
         );
 
-        // Buttons
-        let buttons = Line::from(vec![
-            Span::styled("[Y]es", theme.styles.keybind_key),
-            Span::styled("  ", theme.styles.keybind),
-            Span::styled("[N]o", theme.styles.keybind_key),
-        ]);
+        // Buttons; type-to-confirm prompts show the typed text and only
+        // light up Enter once it matches
+        let buttons = if phrase.is_some() {
+            let enter_style = if state.mode.confirm_enabled() {
+                theme.styles.keybind_key
+            } else {
+                theme.styles.keybind
+            };
+            Line::from(vec![
+                Span::styled("> ", theme.styles.keybind),
+                Span::styled(
+                    format!("{}█", input),
+                    Style::default().fg(theme.colors.fg_primary),
+                ),
+                Span::styled("  ", theme.styles.keybind),
+                Span::styled("[Enter]", enter_style),
+                Span::styled(" ", theme.styles.keybind),
+                Span::styled("[Esc]", theme.styles.keybind_key),
+            ])
+        } else {
+            Line::from(vec![
+                Span::styled("[Y]es", theme.styles.keybind_key),
+                Span::styled("  ", theme.styles.keybind),
+                Span::styled("[N]o", theme.styles.keybind_key),
+            ])
+        };
 
         frame.render_widget(
             Paragraph::new(buttons).alignment(ratatui::layout::Alignment::Center),
@@ -259,7 +353,13 @@ impl Renderer {
                 crate::core::state::NotificationLevel::Error => "✗",
             };
 
-            let msg = format!(" {} {} ", icon, notification.message);
+            let badge = match notification.level {
+                crate::core::state::NotificationLevel::Success => theme.badge("OK "),
+                crate::core::state::NotificationLevel::Error => theme.badge("FAIL "),
+                _ => "",
+            };
+
+            let msg = format!(" {} {}{} ", icon, badge, notification.message);
             let width = (msg.len() as u16).min(40);
             let x = area.width.saturating_sub(width + 2);
 
@@ -0,0 +1,146 @@
+//! Off-screen dashboard rendering, exported as plain or ANSI-colored text
+
+use anyhow::Result;
+use ratatui::{
+    backend::TestBackend,
+    buffer::Buffer,
+    style::{Color, Modifier},
+    Terminal,
+};
+use std::fmt::Write;
+use unicode_width::UnicodeWidthStr;
+
+use crate::core::state::AppState;
+use crate::ui::renderer::Renderer;
+
+/// Render `state` exactly as the dashboard would draw it at `width`x`height`
+pub fn render_buffer(state: &AppState, width: u16, height: u16) -> Result<Buffer> {
+    let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+    terminal.draw(|frame| Renderer::render(frame, state))?;
+    Ok(terminal.backend().buffer().clone())
+}
+
+/// The buffer's characters, one line per row with trailing blanks trimmed
+pub fn to_plain_text(buf: &Buffer) -> String {
+    let mut out = String::new();
+    for row in rows(buf) {
+        let line: String = visible_cells(row).map(|cell| cell.symbol()).collect();
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+/// The buffer with SGR escape codes for colors and modifiers, for `cat` or `less -R`
+pub fn to_ansi(buf: &Buffer) -> String {
+    let mut out = String::new();
+    for row in rows(buf) {
+        let mut last = None;
+        for cell in visible_cells(row) {
+            let style = (cell.fg, cell.bg, cell.modifier);
+            if last != Some(style) {
+                let _ = write!(
+                    out,
+                    "\x1b[0{}{}{}m",
+                    modifier_codes(cell.modifier),
+                    color_code(cell.fg, false),
+                    color_code(cell.bg, true)
+                );
+                last = Some(style);
+            }
+            out.push_str(cell.symbol());
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+fn rows(buf: &Buffer) -> impl Iterator<Item = &[ratatui::buffer::Cell]> {
+    buf.content.chunks(buf.area.width.max(1) as usize)
+}
+
+/// Cells that start a glyph; the ones hidden under a wide character are dropped
+fn visible_cells(row: &[ratatui::buffer::Cell]) -> impl Iterator<Item = &ratatui::buffer::Cell> {
+    let mut skip = 0usize;
+    row.iter().filter(move |cell| {
+        let visible = skip == 0;
+        skip = skip.max(cell.symbol().width()).saturating_sub(1);
+        visible
+    })
+}
+
+fn modifier_codes(modifier: Modifier) -> String {
+    [
+        (Modifier::BOLD, ";1"),
+        (Modifier::DIM, ";2"),
+        (Modifier::ITALIC, ";3"),
+        (Modifier::UNDERLINED, ";4"),
+        (Modifier::REVERSED, ";7"),
+        (Modifier::CROSSED_OUT, ";9"),
+    ]
+    .iter()
+    .filter(|(m, _)| modifier.contains(*m))
+    .map(|(_, code)| *code)
+    .collect()
+}
+
+fn color_code(color: Color, background: bool) -> String {
+    let offset = if background { 10 } else { 0 };
+    let base = match color {
+        Color::Reset => return String::new(),
+        Color::Rgb(r, g, b) => return format!(";{};2;{};{};{}", 38 + offset, r, g, b),
+        Color::Indexed(i) => return format!(";{};5;{}", 38 + offset, i),
+        Color::Black => 30,
+        Color::Red => 31,
+        Color::Green => 32,
+        Color::Yellow => 33,
+        Color::Blue => 34,
+        Color::Magenta => 35,
+        Color::Cyan => 36,
+        Color::Gray => 37,
+        Color::DarkGray => 90,
+        Color::LightRed => 91,
+        Color::LightGreen => 92,
+        Color::LightYellow => 93,
+        Color::LightBlue => 94,
+        Color::LightMagenta => 95,
+        Color::LightCyan => 96,
+        Color::White => 97,
+    };
+    format!(";{}", base + offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::{EnvVarAnalysis, ProjectContext};
+    use crate::ui::theme::Theme;
+
+    #[test]
+    fn snapshot_contains_project_name() {
+        let mut state = AppState::new("/tmp/snapshot-demo".into(), Theme::from_name("nord"));
+        state.project = Some(ProjectContext {
+            root: "/tmp/snapshot-demo".into(),
+            name: "snapshot-demo".to_string(),
+            types: vec![],
+            scripts: vec![],
+            env_vars: EnvVarAnalysis::default(),
+            ports: vec![],
+            docker_services: vec![],
+            git_info: None,
+            advisories: vec![],
+            warnings: vec![],
+            member_manifests: vec![],
+        });
+
+        let buf = render_buffer(&state, 120, 40).unwrap();
+
+        let text = to_plain_text(&buf);
+        assert!(text.contains("snapshot-demo"));
+        assert_eq!(text.lines().count(), 40);
+
+        let ansi = to_ansi(&buf);
+        assert!(ansi.contains("snapshot-demo"));
+        assert!(ansi.contains("\x1b[0;"));
+    }
+}
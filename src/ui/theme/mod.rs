@@ -2,7 +2,14 @@
 
 #![allow(dead_code)]
 
+use anyhow::{bail, Context, Result};
 use ratatui::style::{Color, Modifier, Style};
+use std::path::{Path, PathBuf};
+
+use crate::config::Truncation;
+
+/// Built-in themes in the order `T` cycles through them
+pub const THEME_NAMES: &[&str] = &["tokyo-night", "catppuccin", "dracula", "nord", "gruvbox"];
 
 /// Complete theme definition
 #[derive(Debug, Clone)]
@@ -10,6 +17,10 @@ pub struct Theme {
     pub name: String,
     pub colors: ThemeColors,
     pub styles: ThemeStyles,
+    /// Status is also conveyed with text badges, not just color
+    pub colorblind: bool,
+    /// How container, port and action names are shortened
+    pub name_truncation: Truncation,
 }
 
 #[derive(Debug, Clone)]
@@ -63,6 +74,93 @@ pub struct ThemeStyles {
 }
 
 impl Theme {
+    /// The built-in theme after `current` in [`THEME_NAMES`], wrapping around
+    pub fn next_name(current: &str) -> &'static str {
+        let current = Self::from_name(current).name;
+        let index = THEME_NAMES
+            .iter()
+            .position(|name| Self::from_name(name).name == current)
+            .unwrap_or(0);
+        THEME_NAMES[(index + 1) % THEME_NAMES.len()]
+    }
+
+    /// A built-in theme by name, or a custom one from a TOML file
+    ///
+    /// `spec` is either a path to the file (`~` expands to the home directory) or
+    /// the name of a file in [`themes_dir`], e.g. `solarized` for `solarized.toml`.
+    pub fn load(spec: &str) -> Result<Self> {
+        let lower = spec.to_lowercase();
+        if THEME_NAMES.contains(&lower.as_str()) || lower == "catppuccin-mocha" {
+            return Ok(Self::from_name(spec));
+        }
+
+        if spec.ends_with(".toml") || spec.contains(std::path::MAIN_SEPARATOR) {
+            return Self::from_file(&expand_home(spec));
+        }
+        match themes_dir().map(|dir| dir.join(format!("{}.toml", spec))) {
+            Some(path) if path.exists() => Self::from_file(&path),
+            _ => bail!(
+                "Unknown theme `{}`; use one of {} or a theme file",
+                spec,
+                THEME_NAMES.join(", ")
+            ),
+        }
+    }
+
+    /// Load a custom theme: a `#rrggbb` string for every [`ThemeColors`] field,
+    /// plus an optional `name` (the file stem otherwise)
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read theme {}", path.display()))?;
+        Self::from_toml(&content, path).with_context(|| format!("Invalid theme {}", path.display()))
+    }
+
+    fn from_toml(content: &str, path: &Path) -> Result<Self> {
+        let table: toml::Table = content.parse()?;
+        let color = |key: &str| -> Result<Color> {
+            let value = table
+                .get(key)
+                .with_context(|| format!("missing `{}`", key))?;
+            let hex = value
+                .as_str()
+                .with_context(|| format!("`{}` must be a \"#rrggbb\" string", key))?;
+            parse_hex_color(hex)
+                .with_context(|| format!("`{}` = \"{}\" is not a #rrggbb color", key, hex))
+        };
+
+        let colors = ThemeColors {
+            bg_primary: color("bg_primary")?,
+            bg_secondary: color("bg_secondary")?,
+            bg_tertiary: color("bg_tertiary")?,
+            bg_highlight: color("bg_highlight")?,
+            fg_primary: color("fg_primary")?,
+            fg_secondary: color("fg_secondary")?,
+            fg_muted: color("fg_muted")?,
+            accent_primary: color("accent_primary")?,
+            accent_secondary: color("accent_secondary")?,
+            success: color("success")?,
+            warning: color("warning")?,
+            error: color("error")?,
+            info: color("info")?,
+            border: color("border")?,
+            border_focused: color("border_focused")?,
+            selection: color("selection")?,
+            cursor: color("cursor")?,
+        };
+
+        let name = match table.get("name") {
+            Some(name) => name
+                .as_str()
+                .context("`name` must be a string")?
+                .to_string(),
+            None => path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_else(|| "custom".to_string()),
+        };
+        Ok(Self::from_colors(&name, colors))
+    }
+
     pub fn from_name(name: &str) -> Self {
         match name.to_lowercase().as_str() {
             "catppuccin" | "catppuccin-mocha" => Self::catppuccin_mocha(),
@@ -73,6 +171,43 @@ impl Theme {
         }
     }
 
+    /// Swap red/green semantic colors for a blue/orange scheme that stays
+    /// distinguishable with red-green color blindness (Okabe-Ito palette)
+    pub fn with_colorblind(mut self, enabled: bool) -> Self {
+        if !enabled {
+            return self;
+        }
+
+        let mut colors = self.colors;
+        colors.success = Color::Rgb(86, 180, 233);
+        colors.warning = Color::Rgb(240, 228, 66);
+        colors.error = Color::Rgb(230, 159, 0);
+
+        let name = format!("{} (colorblind)", self.name);
+        self = Self::from_colors(&name, colors);
+        self.colorblind = true;
+        self
+    }
+
+    pub fn with_name_truncation(mut self, mode: Truncation) -> Self {
+        self.name_truncation = mode;
+        self
+    }
+
+    /// Shorten a container, port or action name per `name_truncation`
+    pub fn truncate_name(&self, name: &str, max_len: usize) -> String {
+        crate::ui::util::truncate_with(name, max_len, self.name_truncation)
+    }
+
+    /// Text badge shown next to a status icon in colorblind mode
+    pub fn badge(&self, text: &'static str) -> &'static str {
+        if self.colorblind {
+            text
+        } else {
+            ""
+        }
+    }
+
     /// Tokyo Night theme (default)
     pub fn tokyo_night() -> Self {
         let colors = ThemeColors {
@@ -254,6 +389,8 @@ impl Theme {
             name: name.to_string(),
             colors,
             styles,
+            colorblind: false,
+            name_truncation: Truncation::End,
         }
     }
 }
@@ -263,3 +400,163 @@ impl Default for Theme {
         Self::tokyo_night()
     }
 }
+
+/// Where custom themes are looked up by name, e.g. `~/.config/orbit/themes`
+pub fn themes_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|p| p.join("orbit").join("themes"))
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Parse a `#rrggbb` (or `rrggbb`) hex string into an RGB color
+pub fn parse_hex_color(hex: &str) -> Option<Color> {
+    let hex = hex.trim().trim_start_matches('#');
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+
+    let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
+    let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
+    let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
+    Some(Color::Rgb(r, g, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Hue in degrees of an RGB color
+    fn hue(color: Color) -> f32 {
+        let Color::Rgb(r, g, b) = color else {
+            panic!("expected an RGB color, got {:?}", color);
+        };
+        let (r, g, b) = (r as f32, g as f32, b as f32);
+        let max = r.max(g).max(b);
+        let delta = max - r.min(g).min(b);
+        if delta == 0.0 {
+            return 0.0;
+        }
+        let h = if max == r {
+            60.0 * ((g - b) / delta)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        h.rem_euclid(360.0)
+    }
+
+    fn is_red_or_green(color: Color) -> bool {
+        let h = hue(color);
+        !(25.0..=330.0).contains(&h) || (75.0..=160.0).contains(&h)
+    }
+
+    #[test]
+    fn theme_names_cycle_through_every_built_in() {
+        let mut name = "tokyo-night";
+        let mut seen = vec![];
+        for _ in THEME_NAMES {
+            name = Theme::next_name(name);
+            seen.push(name);
+        }
+        assert_eq!(
+            seen,
+            ["catppuccin", "dracula", "nord", "gruvbox", "tokyo-night"]
+        );
+        // Aliases and unknown names cycle from the theme they resolve to
+        assert_eq!(Theme::next_name("catppuccin-mocha"), "dracula");
+        assert_eq!(Theme::next_name("solarized"), "catppuccin");
+    }
+
+    const SAMPLE_THEME: &str = r##"
+        name = "Solarized Dark"
+        bg_primary = "#002b36"
+        bg_secondary = "#073642"
+        bg_tertiary = "#0a4250"
+        bg_highlight = "#114d5c"
+        fg_primary = "#eee8d5"
+        fg_secondary = "#93a1a1"
+        fg_muted = "#586e75"
+        accent_primary = "#268bd2"
+        accent_secondary = "#6c71c4"
+        success = "#859900"
+        warning = "#b58900"
+        error = "#dc322f"
+        info = "#2aa198"
+        border = "#073642"
+        border_focused = "#268bd2"
+        selection = "#073642"
+        cursor = "#93a1a1"
+    "##;
+
+    #[test]
+    fn custom_theme_loads_from_a_toml_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("solarized.toml");
+        std::fs::write(&path, SAMPLE_THEME).unwrap();
+
+        let theme = Theme::load(path.to_str().unwrap()).unwrap();
+        assert_eq!(theme.name, "Solarized Dark");
+        assert_eq!(theme.colors.bg_primary, Color::Rgb(0x00, 0x2b, 0x36));
+        assert_eq!(theme.colors.cursor, Color::Rgb(0x93, 0xa1, 0xa1));
+        // Styles are derived like the built-ins'
+        assert_eq!(
+            theme.styles.panel_title.fg,
+            Some(theme.colors.accent_primary)
+        );
+        assert_eq!(
+            theme.styles.status_stopped.fg,
+            Some(Color::Rgb(0xdc, 0x32, 0x2f))
+        );
+
+        // Without a name the file stem is used
+        let unnamed = SAMPLE_THEME.replace("name = \"Solarized Dark\"", "");
+        std::fs::write(&path, unnamed).unwrap();
+        assert_eq!(Theme::from_file(&path).unwrap().name, "solarized");
+
+        assert_eq!(Theme::load("Nord").unwrap().name, "Nord");
+    }
+
+    #[test]
+    fn malformed_theme_files_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("broken.toml");
+
+        std::fs::write(&path, SAMPLE_THEME.replace("#dc322f", "#dc32")).unwrap();
+        let err = format!("{:#}", Theme::from_file(&path).unwrap_err());
+        assert!(
+            err.contains("`error` = \"#dc32\" is not a #rrggbb color"),
+            "{}",
+            err
+        );
+
+        std::fs::write(&path, SAMPLE_THEME.replace("cursor = \"#93a1a1\"", "")).unwrap();
+        let err = format!("{:#}", Theme::from_file(&path).unwrap_err());
+        assert!(err.contains("missing `cursor`"), "{}", err);
+
+        assert!(Theme::load("no-such-theme").is_err());
+        assert_eq!(parse_hex_color("#1A1B26"), Some(Color::Rgb(26, 27, 38)));
+        assert_eq!(parse_hex_color("#1a1b2g"), None);
+        assert_eq!(parse_hex_color("#ñ1b26"), None);
+    }
+
+    #[test]
+    fn colorblind_mode_avoids_red_green_status_colors() {
+        for name in THEME_NAMES {
+            let theme = Theme::from_name(name).with_colorblind(true);
+            assert!(theme.colorblind);
+            assert_ne!(theme.colors.success, theme.colors.error);
+            assert!(!is_red_or_green(theme.colors.success), "{}", name);
+            assert!(!is_red_or_green(theme.colors.error), "{}", name);
+            assert_eq!(theme.styles.status_running.fg, Some(theme.colors.success));
+            assert_eq!(theme.badge("OK"), "OK");
+        }
+
+        assert_eq!(Theme::default().badge("OK"), "");
+    }
+}
@@ -0,0 +1,130 @@
+//! Text helpers shared by the widgets
+
+use crate::config::Truncation;
+
+/// Shorten `s` to at most `max_len` characters using `mode`
+pub fn truncate_with(s: &str, max_len: usize, mode: Truncation) -> String {
+    match mode {
+        Truncation::End => truncate(s, max_len),
+        Truncation::Middle => truncate_middle(s, max_len),
+    }
+}
+
+/// Keep the start of `s`, ending in `…` when it's longer than `max_len` characters
+pub fn truncate(s: &str, max_len: usize) -> String {
+    let char_count = s.chars().count();
+    if char_count <= max_len {
+        s.to_string()
+    } else if max_len > 1 {
+        format!("{}…", s.chars().take(max_len - 1).collect::<String>())
+    } else {
+        String::new()
+    }
+}
+
+/// Keep the end of `s`, e.g. the file name of a long path
+pub fn truncate_start(s: &str, max_len: usize) -> String {
+    let char_count = s.chars().count();
+    if char_count <= max_len {
+        s.to_string()
+    } else if max_len > 1 {
+        let tail: String = s.chars().skip(char_count - (max_len - 1)).collect();
+        format!("…{}", tail)
+    } else {
+        String::new()
+    }
+}
+
+/// The first and last `n` characters of `s`, or `None` if they would overlap
+///
+/// Slices on char boundaries, so it's safe for values with multibyte characters.
+pub fn char_ends(s: &str, n: usize) -> Option<(&str, &str)> {
+    let char_count = s.chars().count();
+    if char_count <= 2 * n {
+        return None;
+    }
+    let boundary = |index: usize| s.char_indices().nth(index).map_or(s.len(), |(i, _)| i);
+    Some((&s[..boundary(n)], &s[boundary(char_count - n)..]))
+}
+
+/// Keep both ends of `s` with a `…` in between, favoring the end
+pub fn truncate_middle(s: &str, max_len: usize) -> String {
+    let char_count = s.chars().count();
+    if char_count <= max_len {
+        return s.to_string();
+    }
+    if max_len < 3 {
+        return truncate(s, max_len);
+    }
+
+    let keep = max_len - 1;
+    let head = (keep + 1) / 3;
+    let tail = keep - head;
+    let start: String = s.chars().take(head).collect();
+    let end: String = s.chars().skip(char_count - tail).collect();
+    format!("{}…{}", start, end)
+}
+
+/// The last `width` values as block characters, oldest first, scaled so `max` is a full block
+pub fn sparkline<I>(values: I, max: f64, width: usize) -> String
+where
+    I: IntoIterator<Item = f64>,
+    I::IntoIter: ExactSizeIterator,
+{
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let values = values.into_iter();
+    let skip = values.len().saturating_sub(width);
+    values
+        .skip(skip)
+        .map(|v| {
+            let level = if max > 0.0 {
+                (v / max).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            BARS[(level * 7.0).round() as usize]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tail_and_middle_truncation_of_long_names() {
+        let primary = "docker-compose-postgres-primary";
+        let replica = "docker-compose-postgres-replica";
+
+        assert_eq!(truncate(replica, 15), "docker-compose…");
+        assert_eq!(truncate(primary, 15), truncate(replica, 15));
+
+        assert_eq!(truncate_middle(replica, 12), "dock…replica");
+        assert_eq!(truncate_middle(primary, 12), "dock…primary");
+        assert_eq!(truncate_middle(replica, 12).chars().count(), 12);
+
+        // Short enough names are left alone either way
+        assert_eq!(truncate_with("db", 12, Truncation::Middle), "db");
+        assert_eq!(truncate_with("db", 12, Truncation::End), "db");
+    }
+
+    #[test]
+    fn truncation_respects_multibyte_boundaries() {
+        // Byte 2 falls inside the 4-byte whale; `&s[..2]` would panic here
+        let name = "🐳-café-ñandú";
+        assert_eq!(truncate(name, 3), "🐳-…");
+        assert_eq!(truncate_middle(name, 6), "🐳-…ndú");
+        assert_eq!(truncate_start(name, 4), "…ndú");
+        assert_eq!(char_ends("🐳é-secret-ñú", 2), Some(("🐳é", "ñú")));
+        assert_eq!(char_ends("ñañá", 2), None);
+    }
+
+    #[test]
+    fn sparkline_scales_to_max_and_keeps_the_newest() {
+        let history = [0.0, 50.0, 100.0, 150.0, 12.0];
+        assert_eq!(sparkline(history, 100.0, 10), "▁▅██▂");
+        assert_eq!(sparkline(history, 100.0, 2), "█▂");
+        assert_eq!(sparkline([256.0, 512.0], 512.0, 4), "▅█");
+        assert_eq!(sparkline([3.0], 0.0, 4), "▁");
+    }
+}
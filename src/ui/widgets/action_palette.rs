@@ -11,6 +11,7 @@ use ratatui::{
 use crate::actions::ActionCategory;
 use crate::core::state::AppState;
 use crate::ui::theme::Theme;
+use crate::ui::util::truncate;
 
 pub struct ActionPalette<'a> {
     state: &'a AppState,
@@ -131,35 +132,55 @@ impl<'a> Widget for ActionPalette<'a> {
             };
 
             let indicator = if is_selected { "▸" } else { " " };
-            let icon = Self::category_icon(&action.category);
+            let icon = action
+                .icon
+                .as_deref()
+                .unwrap_or_else(|| Self::category_icon(&action.category));
+            let icon_color = action.color.unwrap_or(self.theme.colors.accent_secondary);
 
             // Highlight matching parts in name
-            let name = &action.name;
+            let name = action.display_name(&actions.actions);
             let desc = action.description.as_deref().unwrap_or(&action.command);
 
             let max_name_len = 24;
             let max_desc_len = results_area.width.saturating_sub(max_name_len as u16 + 8) as usize;
 
-            let name_display = truncate(name, max_name_len);
+            let name_display = self.theme.truncate_name(&name, max_name_len);
             let desc_display = truncate(desc, max_desc_len);
 
-            let line = Line::from(vec![
+            let mut spans = vec![
                 Span::styled(indicator, style),
-                Span::styled(
-                    format!(" {} ", icon),
-                    Style::default().fg(self.theme.colors.accent_secondary),
-                ),
-                Span::styled(
+                Span::styled(format!(" {} ", icon), Style::default().fg(icon_color)),
+            ];
+            let matched = crate::actions::fuzzy_indices(&name_display, &actions.filter);
+            if matched.is_empty() {
+                spans.push(Span::styled(
                     format!("{:<width$} ", name_display, width = max_name_len),
                     style.add_modifier(Modifier::BOLD),
-                ),
-                Span::styled(
-                    desc_display,
-                    style
-                        .fg(self.theme.colors.fg_muted)
-                        .remove_modifier(Modifier::BOLD),
-                ),
-            ]);
+                ));
+            } else {
+                // Bold just the characters the filter matched
+                let highlight = style
+                    .fg(self.theme.colors.accent_primary)
+                    .add_modifier(Modifier::BOLD);
+                for (i, c) in name_display.chars().enumerate() {
+                    let char_style = if matched.contains(&i) {
+                        highlight
+                    } else {
+                        style
+                    };
+                    spans.push(Span::styled(c.to_string(), char_style));
+                }
+                let pad = max_name_len.saturating_sub(name_display.chars().count()) + 1;
+                spans.push(Span::styled(" ".repeat(pad), style));
+            }
+            spans.push(Span::styled(
+                desc_display,
+                style
+                    .fg(self.theme.colors.fg_muted)
+                    .remove_modifier(Modifier::BOLD),
+            ));
+            let line = Line::from(spans);
 
             buf.set_line(
                 results_area.x,
@@ -182,14 +203,3 @@ impl<'a> Widget for ActionPalette<'a> {
         buf.set_span(hint_x, hint_y, &hint_span, hint_len);
     }
 }
-
-fn truncate(s: &str, max_len: usize) -> String {
-    let char_count = s.chars().count();
-    if char_count <= max_len {
-        s.to_string()
-    } else if max_len > 1 {
-        format!("{}…", s.chars().take(max_len - 1).collect::<String>())
-    } else {
-        String::new()
-    }
-}
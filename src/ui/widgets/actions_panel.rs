@@ -8,9 +8,10 @@ use ratatui::{
     widgets::{Block, Borders, Widget},
 };
 
-use crate::actions::ActionCategory;
+use crate::actions::{Action, ActionCategory};
 use crate::core::state::{AppState, FocusedPanel};
 use crate::ui::theme::Theme;
+use crate::ui::util::truncate;
 
 pub struct ActionsPanel<'a> {
     state: &'a AppState,
@@ -37,6 +38,77 @@ impl<'a> ActionsPanel<'a> {
     }
 }
 
+impl<'a> ActionsPanel<'a> {
+    fn action_line(
+        &self,
+        action: &Action,
+        is_selected: bool,
+        is_marked: bool,
+        width: u16,
+    ) -> Line<'static> {
+        let base_style = if is_selected {
+            self.theme.styles.list_item_selected
+        } else {
+            self.theme.styles.list_item
+        };
+
+        let indicator = if is_marked {
+            "●"
+        } else if is_selected {
+            "▸"
+        } else {
+            " "
+        };
+        let icon = action
+            .icon
+            .as_deref()
+            .unwrap_or_else(|| Self::category_icon(&action.category));
+        let icon_color = action.color.unwrap_or(self.theme.colors.accent_secondary);
+        let name = self
+            .theme
+            .truncate_name(&action.display_name(&self.state.panels.actions.actions), 20);
+        let muted = Style::default().fg(self.theme.colors.fg_muted);
+
+        // Workspace package actions show where they run, ahead of the description
+        let package_dir = action.working_dir.as_ref().map(|dir| {
+            let relative = dir.strip_prefix(&self.state.working_dir).unwrap_or(dir);
+            format!("{}/ ", relative.display())
+        });
+        let desc_width = (width.saturating_sub(28) as usize)
+            .saturating_sub(package_dir.as_ref().map_or(0, |d| d.chars().count()));
+        let desc = action
+            .description
+            .as_deref()
+            .or(Some(&action.command))
+            .map(|s| truncate(s, desc_width))
+            .unwrap_or_default();
+
+        let mut spans = vec![
+            Span::styled(indicator, base_style),
+            Span::styled(format!(" {} ", icon), Style::default().fg(icon_color)),
+        ];
+        match name
+            .split_once(':')
+            .filter(|_| action.working_dir.is_some())
+        {
+            Some((package, script)) => {
+                let width = 20usize.saturating_sub(package.chars().count() + 1);
+                spans.push(Span::styled(format!("{}:", package), muted));
+                spans.push(Span::styled(format!("{:<width$} ", script), base_style));
+            }
+            None => spans.push(Span::styled(format!("{:<20} ", name), base_style)),
+        }
+        if let Some(dir) = package_dir {
+            spans.push(Span::styled(
+                dir,
+                Style::default().fg(self.theme.colors.accent_secondary),
+            ));
+        }
+        spans.push(Span::styled(desc, muted));
+        Line::from(spans)
+    }
+}
+
 impl<'a> Widget for ActionsPanel<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let focused = self.state.focus_panel == FocusedPanel::Actions;
@@ -46,11 +118,14 @@ impl<'a> Widget for ActionsPanel<'a> {
             self.theme.styles.panel_border
         };
 
+        let marked = self.state.panels.actions.marked.len();
+        let title = if marked > 0 {
+            format!(" QUICK ACTIONS ({} marked) ", marked)
+        } else {
+            " QUICK ACTIONS ".to_string()
+        };
         let block = Block::default()
-            .title(Span::styled(
-                " QUICK ACTIONS ",
-                self.theme.styles.panel_title,
-            ))
+            .title(Span::styled(title, self.theme.styles.panel_title))
             .borders(Borders::ALL)
             .border_style(border_style)
             .style(Style::default().bg(self.theme.colors.bg_primary));
@@ -79,44 +154,82 @@ impl<'a> Widget for ActionsPanel<'a> {
 
             let action = &actions.actions[action_idx];
             let is_selected = display_idx == selected;
-
-            let base_style = if is_selected {
-                self.theme.styles.list_item_selected
-            } else {
-                self.theme.styles.list_item
-            };
-
-            let indicator = if is_selected { "▸" } else { " " };
-            let icon = Self::category_icon(&action.category);
-            let name = truncate(&action.name, 20);
-            let desc = action
-                .description
-                .as_deref()
-                .or(Some(&action.command))
-                .map(|s| truncate(s, inner.width.saturating_sub(28) as usize))
-                .unwrap_or_default();
-
-            let line = Line::from(vec![
-                Span::styled(indicator, base_style),
-                Span::styled(
-                    format!(" {} ", icon),
-                    Style::default().fg(self.theme.colors.accent_secondary),
-                ),
-                Span::styled(format!("{:<20} ", name), base_style),
-                Span::styled(desc, Style::default().fg(self.theme.colors.fg_muted)),
-            ]);
+            let is_marked = actions.marked.contains(&action_idx);
+            let line = self.action_line(action, is_selected, is_marked, inner.width);
 
             buf.set_line(inner.x, inner.y + display_idx as u16, &line, inner.width);
         }
     }
 }
 
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
-    } else if max_len > 1 {
-        format!("{}…", &s[..max_len - 1])
-    } else {
-        String::new()
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CustomAction;
+    use std::path::PathBuf;
+
+    #[test]
+    fn custom_icon_overrides_category_icon() {
+        let state = AppState::new(PathBuf::from("."), Theme::default());
+        let theme = Theme::default();
+        let panel = ActionsPanel::new(&state, &theme);
+
+        let action = Action::from_custom(&CustomAction {
+            name: "Deploy to prod".to_string(),
+            command: "./deploy.sh".to_string(),
+            category: Some("deploy".to_string()),
+            description: None,
+            keybinding: None,
+            confirm: true,
+            confirm_phrase: false,
+            icon: Some("🚀".to_string()),
+            color: Some("#ff0000".to_string()),
+            shell: None,
+        });
+
+        let line = panel.action_line(&action, false, false, 60);
+        assert_eq!(line.spans[1].content, " 🚀 ");
+        assert_eq!(
+            line.spans[1].style.fg,
+            Some(ratatui::style::Color::Rgb(255, 0, 0))
+        );
+
+        let plain = Action {
+            icon: None,
+            color: None,
+            shell: None,
+            ..action
+        };
+        let line = panel.action_line(&plain, false, true, 60);
+        assert_eq!(line.spans[0].content, "●");
+        assert_eq!(line.spans[1].content, " ↑ ");
+    }
+
+    #[test]
+    fn workspace_actions_show_their_package() {
+        let state = AppState::new(PathBuf::from("/repo"), Theme::default());
+        let theme = Theme::default();
+        let panel = ActionsPanel::new(&state, &theme);
+
+        let action = Action::from_script(
+            crate::detection::DiscoveredScript {
+                name: "dev".to_string(),
+                command: "pnpm run dev".to_string(),
+                source: crate::detection::ScriptSource::PackageJson,
+                category: crate::detection::ScriptCategory::Dev,
+                description: None,
+                ports: vec![],
+                env_required: vec![],
+                working_dir: None,
+            }
+            .in_package("web", std::path::Path::new("/repo/apps/web")),
+        );
+
+        let line = panel.action_line(&action, false, false, 60);
+        let text: Vec<&str> = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(
+            text[2..],
+            ["web:", "dev              ", "apps/web/ ", "pnpm run dev"]
+        );
     }
 }
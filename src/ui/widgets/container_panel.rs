@@ -3,14 +3,71 @@
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
-    style::Style,
+    style::{Color, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Widget},
 };
 
+use std::collections::VecDeque;
+
 use crate::core::state::{AppState, FocusedPanel};
-use crate::integrations::docker::ContainerStatus;
+use crate::integrations::docker::{ContainerStats, ContainerStatus};
 use crate::ui::theme::Theme;
+use crate::ui::util::sparkline;
+
+/// Width of the memory bar, shown once the panel has room for it
+const MEM_BAR_WIDTH: usize = 5;
+
+/// Width of the CPU and memory sparklines, shown on wide panels only
+const SPARKLINE_WIDTH: usize = 8;
+
+/// How close a container is to its memory limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryPressure {
+    Normal,
+    /// 75% or more of the limit
+    High,
+    /// 90% or more; the OOM killer is close
+    Critical,
+}
+
+impl MemoryPressure {
+    pub fn from_ratio(ratio: f64) -> Self {
+        if ratio >= 0.9 {
+            Self::Critical
+        } else if ratio >= 0.75 {
+            Self::High
+        } else {
+            Self::Normal
+        }
+    }
+
+    fn color(self, theme: &Theme) -> Color {
+        match self {
+            Self::Normal => theme.colors.accent_secondary,
+            Self::High => theme.colors.warning,
+            Self::Critical => theme.colors.error,
+        }
+    }
+}
+
+/// `512M` below a gigabyte, `1.5G` above
+fn format_mb(mb: f64) -> String {
+    if mb >= 1024.0 {
+        format!("{:.1}G", mb / 1024.0)
+    } else {
+        format!("{:.0}M", mb)
+    }
+}
+
+/// `2` for whole CPUs, `0.5` otherwise
+fn format_cpus(cpus: f64) -> String {
+    if cpus.fract() == 0.0 {
+        format!("{:.0}", cpus)
+    } else {
+        format!("{:.1}", cpus)
+    }
+}
 
 pub struct ContainerPanel<'a> {
     state: &'a AppState,
@@ -26,7 +83,7 @@ impl<'a> ContainerPanel<'a> {
         &self,
         container: &crate::integrations::docker::ContainerInfo,
         selected: bool,
-        _width: u16,
+        width: u16,
     ) -> Line<'a> {
         let status_icon = match container.status {
             ContainerStatus::Running => "●",
@@ -45,14 +102,24 @@ impl<'a> ContainerPanel<'a> {
             _ => self.theme.styles.list_item,
         };
 
-        let name = truncate(&container.name, 12);
-        let (cpu_percent, memory_mb) = container
-            .stats
-            .as_ref()
-            .map(|s| (s.cpu_percent, s.memory_usage_mb as u64))
-            .unwrap_or((0.0, 0));
-        let cpu = format!("{:>3.0}%", cpu_percent);
-        let mem = format!("{:>4}M", memory_mb);
+        let status = if self.theme.colorblind {
+            let badge = match container.status {
+                ContainerStatus::Running => "UP",
+                ContainerStatus::Exited => "DOWN",
+                ContainerStatus::Dead => "FAIL",
+                _ => "",
+            };
+            format!(" {} {:<4} ", status_icon, badge)
+        } else {
+            format!(" {} ", status_icon)
+        };
+
+        let name = self.theme.truncate_name(&container.name, 12);
+        let stats = container.stats.clone().unwrap_or_default();
+        let cpu = match stats.cpu_limit {
+            Some(cpus) => format!("{:>3.0}%/{}", stats.cpu_percent, format_cpus(cpus)),
+            None => format!("{:>3.0}%", stats.cpu_percent),
+        };
 
         let base_style = if selected {
             self.theme.styles.list_item_selected
@@ -62,14 +129,67 @@ impl<'a> ContainerPanel<'a> {
 
         let indicator = if selected { "▸" } else { " " };
 
-        Line::from(vec![
+        let mut spans = vec![
             Span::styled(indicator, base_style),
-            Span::styled(format!(" {} ", status_icon), status_style),
+            Span::styled(status, status_style),
             Span::styled(format!("{:<12} ", name), base_style),
             Span::styled(cpu, base_style.fg(self.theme.colors.accent_primary)),
             Span::raw(" "),
-            Span::styled(mem, base_style.fg(self.theme.colors.accent_secondary)),
-        ])
+        ];
+        let docker = &self.state.panels.docker;
+        let show_trends = width as usize >= 60;
+        if show_trends {
+            // A full block is 100% of the CPUs the container may use
+            let cpu_max = stats.cpu_limit.unwrap_or(1.0) * 100.0;
+            spans.extend(self.trend_span(docker.cpu_history.get(&container.id), cpu_max));
+        }
+        spans.extend(self.memory_spans(&stats, base_style, width));
+        if show_trends {
+            let memory = docker.memory_history.get(&container.id);
+            let memory_max = if stats.memory_limit_mb > 0.0 {
+                stats.memory_limit_mb
+            } else {
+                memory.map_or(0.0, |h| h.iter().copied().fold(0.0, f64::max))
+            };
+            spans.push(Span::raw(" "));
+            spans.extend(self.trend_span(memory, memory_max));
+        }
+        Line::from(spans)
+    }
+
+    /// Sparkline of a container's recent samples, followed by a space
+    fn trend_span(&self, history: Option<&VecDeque<f64>>, max: f64) -> Option<Span<'a>> {
+        let history = history.filter(|h| h.len() > 1)?;
+        let line = sparkline(history.iter().copied(), max, SPARKLINE_WIDTH);
+        Some(Span::styled(
+            format!("{:<width$} ", line, width = SPARKLINE_WIDTH),
+            self.theme.styles.sparkline,
+        ))
+    }
+
+    /// `███░░ 384M/512M`, colored by how close usage is to the limit
+    fn memory_spans(&self, stats: &ContainerStats, base_style: Style, width: u16) -> Vec<Span<'a>> {
+        if stats.memory_limit_mb <= 0.0 {
+            return vec![Span::styled(
+                format!("{:>4.0}M", stats.memory_usage_mb),
+                base_style.fg(self.theme.colors.accent_secondary),
+            )];
+        }
+
+        let ratio = stats.memory_usage_mb / stats.memory_limit_mb;
+        let style = base_style.fg(MemoryPressure::from_ratio(ratio).color(self.theme));
+        let label = format!(
+            "{}/{}",
+            format_mb(stats.memory_usage_mb),
+            format_mb(stats.memory_limit_mb)
+        );
+
+        if (width as usize) < 40 {
+            return vec![Span::styled(label, style)];
+        }
+        let filled = ((ratio.clamp(0.0, 1.0) * MEM_BAR_WIDTH as f64).round()) as usize;
+        let bar = "█".repeat(filled) + &"░".repeat(MEM_BAR_WIDTH - filled);
+        vec![Span::styled(format!("{} {}", bar, label), style)]
     }
 }
 
@@ -117,10 +237,23 @@ impl<'a> Widget for ContainerPanel<'a> {
     }
 }
 
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
-    } else {
-        format!("{}…", &s[..max_len - 1])
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_pressure_thresholds() {
+        assert_eq!(MemoryPressure::from_ratio(0.0), MemoryPressure::Normal);
+        assert_eq!(MemoryPressure::from_ratio(0.74), MemoryPressure::Normal);
+        assert_eq!(MemoryPressure::from_ratio(0.75), MemoryPressure::High);
+        assert_eq!(MemoryPressure::from_ratio(0.89), MemoryPressure::High);
+        assert_eq!(MemoryPressure::from_ratio(0.9), MemoryPressure::Critical);
+        // Usage can briefly exceed the limit before the OOM killer runs
+        assert_eq!(MemoryPressure::from_ratio(1.2), MemoryPressure::Critical);
+
+        assert_eq!(format_mb(384.0), "384M");
+        assert_eq!(format_mb(1536.0), "1.5G");
+        assert_eq!(format_cpus(2.0), "2");
+        assert_eq!(format_cpus(0.5), "0.5");
     }
 }
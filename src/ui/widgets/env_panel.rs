@@ -10,6 +10,7 @@ use ratatui::{
 
 use crate::core::state::{AppState, EnvSource, FocusedPanel};
 use crate::ui::theme::Theme;
+use crate::ui::util::{char_ends, truncate};
 
 pub struct EnvPanel<'a> {
     state: &'a AppState,
@@ -22,15 +23,12 @@ impl<'a> EnvPanel<'a> {
     }
 
     fn redact_value(value: &str) -> String {
-        if value.len() <= 4 {
-            "●".repeat(value.len())
-        } else {
-            format!(
-                "{}{}{}",
-                &value[..2],
-                "●".repeat((value.len() - 4).min(12)),
-                &value[value.len() - 2..]
-            )
+        let char_count = value.chars().count();
+        match char_ends(value, 2) {
+            Some((head, tail)) => {
+                format!("{}{}{}", head, "●".repeat((char_count - 4).min(12)), tail)
+            }
+            None => "●".repeat(char_count),
         }
     }
 }
@@ -128,13 +126,3 @@ impl<'a> Widget for EnvPanel<'a> {
         }
     }
 }
-
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
-    } else if max_len > 1 {
-        format!("{}…", &s[..max_len - 1])
-    } else {
-        String::new()
-    }
-}
@@ -7,7 +7,7 @@ use ratatui::{
     widgets::Widget,
 };
 
-use crate::core::state::{AppMode, AppState};
+use crate::core::state::{AppMode, AppState, FocusedPanel};
 use crate::ui::theme::Theme;
 
 pub struct Footer<'a> {
@@ -25,27 +25,15 @@ impl<'a> Widget for Footer<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         buf.set_style(area, self.theme.styles.footer);
 
-        let bindings = match &self.state.mode {
-            AppMode::Dashboard => vec![
-                ("Space", "Actions"),
-                ("e", "Env"),
-                ("d", "Docker"),
-                ("p", "Ports"),
-                ("f", "Focus"),
-                ("r", "Refresh"),
-                ("?", "Help"),
-                ("q", "Quit"),
-            ],
-            AppMode::CommandPalette => {
-                vec![("↑/↓", "Navigate"), ("Enter", "Execute"), ("Esc", "Close")]
-            }
-            AppMode::Help => vec![("Esc", "Close"), ("q", "Close")],
-            AppMode::FocusMode { .. } => vec![("Esc", "Exit Focus")],
-            AppMode::Confirm { .. } => vec![("y", "Confirm"), ("n", "Cancel"), ("Esc", "Cancel")],
-            _ => vec![],
-        };
+        let bindings = hints(self.state);
 
         let mut spans = Vec::new();
+        if let Some(steps) = &self.state.macro_recording {
+            spans.push(Span::styled(
+                format!("● REC {} ", steps.len()),
+                ratatui::style::Style::default().fg(self.theme.colors.error),
+            ));
+        }
         for (i, (key, action)) in bindings.iter().enumerate() {
             if i > 0 {
                 spans.push(Span::styled("  ", self.theme.styles.keybind));
@@ -62,5 +50,170 @@ impl<'a> Widget for Footer<'a> {
 
         let line = Line::from(spans);
         buf.set_line(area.x + 1, area.y, &line, area.width.saturating_sub(2));
+
+        // The running action's progress sits at the right edge, over any hints it hides
+        if let (AppMode::Dashboard, Some(progress)) =
+            (&self.state.mode, &self.state.panels.output.progress)
+        {
+            let label = progress_label(progress);
+            let width = label.chars().count() as u16;
+            if width + 2 < area.width {
+                let x = area.right() - width - 1;
+                buf.set_string(x - 1, area.y, " ", self.theme.styles.footer);
+                buf.set_string(x, area.y, label, self.theme.styles.keybind_key);
+            }
+        }
+    }
+}
+
+/// `serde ██████░░░░ 12/34`
+fn progress_label(progress: &crate::actions::progress::Progress) -> String {
+    const BAR_WIDTH: usize = 10;
+    let filled = (progress.ratio() * BAR_WIDTH as f64).round() as usize;
+    let bar = "█".repeat(filled) + &"░".repeat(BAR_WIDTH - filled);
+    let label = crate::ui::util::truncate(&progress.label, 20);
+    format!("{} {} {}/{}", label, bar, progress.current, progress.total)
+        .trim_start()
+        .to_string()
+}
+
+/// Key hints for the current mode and focused panel, using the configured bindings
+pub fn hints(state: &AppState) -> Vec<(String, &'static str)> {
+    let keys = &state.keys;
+    let scroll = format!("{}/{}", keys.navigate_down, keys.navigate_up);
+    let fixed = |hints: &[(&str, &'static str)]| -> Vec<(String, &'static str)> {
+        hints
+            .iter()
+            .map(|(key, action)| (key.to_string(), *action))
+            .collect()
+    };
+    match &state.mode {
+        AppMode::Dashboard => {
+            let mut bindings = match state.focus_panel {
+                FocusedPanel::Actions => vec![
+                    (keys.select.to_string(), "Run"),
+                    ("Space".to_string(), "Mark"),
+                    (scroll.clone(), "Select"),
+                    ("/".to_string(), "Actions"),
+                ],
+                FocusedPanel::Docker => fixed(&[
+                    ("R", "Restart"),
+                    ("l", "Logs"),
+                    ("+/-", "Scale"),
+                    ("P", "Pin logs"),
+                    ("u/D", "Up/Down"),
+                ]),
+                FocusedPanel::Ports => fixed(&[
+                    ("Enter", "Start"),
+                    ("x", "Kill"),
+                    ("R", "Reclaim"),
+                    ("c", "Connections"),
+                ]),
+                FocusedPanel::RecentFiles => {
+                    vec![("Enter".to_string(), "Open"), (scroll.clone(), "Select")]
+                }
+                FocusedPanel::Env => fixed(&[("v", "Reveal")]),
+                FocusedPanel::Output if state.panels.output.search_query.is_some() => {
+                    fixed(&[("n/N", "Next/Prev"), ("Esc", "End search")])
+                }
+                FocusedPanel::Output | FocusedPanel::PinnedLogs => {
+                    let mut bindings = vec![
+                        ("c".to_string(), "Clear"),
+                        ("o".to_string(), "Pager"),
+                        (scroll.clone(), "Scroll"),
+                        ("|".to_string(), "Split"),
+                    ];
+                    if state.focus_panel == FocusedPanel::Output {
+                        bindings.insert(0, ("/".to_string(), "Search"));
+                    }
+                    bindings
+                }
+            };
+            // Space marks in the Actions panel, so a palette bound to it isn't offered there
+            if state.focus_panel != FocusedPanel::Actions || !keys.palette.is_space() {
+                bindings.push((keys.palette.to_string(), "Actions"));
+            }
+            bindings.extend([
+                ("Tab".to_string(), "Panel"),
+                (keys.focus.to_string(), "Focus"),
+                (keys.terminal.to_string(), "Terminal"),
+                ("N".to_string(), "Notes"),
+                (keys.refresh.to_string(), "Refresh"),
+                (keys.help.to_string(), "Help"),
+                (keys.quit.to_string(), "Quit"),
+            ]);
+            bindings
+        }
+        AppMode::CommandPalette => vec![
+            ("↑/↓".to_string(), "Navigate"),
+            (keys.select.to_string(), "Execute"),
+            (keys.back.to_string(), "Close"),
+        ],
+        AppMode::Help => vec![
+            (keys.back.to_string(), "Close"),
+            (keys.quit.to_string(), "Close"),
+        ],
+        AppMode::LogViewer => fixed(&[("j/k", "Scroll"), ("r", "Reload"), ("Esc", "Close")]),
+        AppMode::Notes => fixed(&[
+            ("Ctrl+S", "Save"),
+            ("Ctrl+E", "$EDITOR"),
+            ("Esc", "Save & Close"),
+        ]),
+        AppMode::FocusMode {
+            ambient_playing: true,
+            ..
+        } => fixed(&[("+/-", "Volume"), ("Esc", "Exit Focus")]),
+        AppMode::FocusMode { .. } => fixed(&[("Esc", "Exit Focus")]),
+        AppMode::Confirm {
+            phrase: Some(_), ..
+        } => fixed(&[("Enter", "Confirm"), ("Esc", "Cancel")]),
+        AppMode::Confirm { .. } => fixed(&[("y", "Confirm"), ("n", "Cancel"), ("Esc", "Cancel")]),
+        AppMode::Terminal => fixed(&[("Ctrl+]", "Dashboard")]),
+        _ => vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn state_with_focus(panel: FocusedPanel) -> AppState {
+        let mut state = AppState::new(PathBuf::from("."), Theme::default());
+        state.focus_panel = panel;
+        state
+    }
+
+    #[test]
+    fn hints_follow_focused_panel() {
+        let has = |hints: &[(String, &str)], key: &str, action: &str| {
+            hints.iter().any(|(k, a)| k == key && *a == action)
+        };
+        let docker = hints(&state_with_focus(FocusedPanel::Docker));
+        assert!(has(&docker, "l", "Logs"));
+        assert!(has(&docker, "R", "Restart"));
+
+        let ports = hints(&state_with_focus(FocusedPanel::Ports));
+        assert!(has(&ports, "x", "Kill"));
+        assert!(!has(&ports, "l", "Logs"));
+    }
+
+    #[test]
+    fn hints_show_remapped_keys() {
+        let mut state = state_with_focus(FocusedPanel::Actions);
+        let has = |state: &AppState, key: &str, action: &str| {
+            hints(state).iter().any(|(k, a)| k == key && *a == action)
+        };
+        assert!(!has(&state, "Space", "Actions"));
+        assert!(has(&state, "q", "Quit"));
+
+        state.keys =
+            crate::core::events::KeyBindings::from_config(&crate::config::KeybindingsConfig {
+                palette: "ctrl+p".to_string(),
+                quit: "x".to_string(),
+                ..Default::default()
+            });
+        assert!(has(&state, "Ctrl+P", "Actions"));
+        assert!(has(&state, "x", "Quit"));
     }
 }
@@ -8,6 +8,7 @@ use ratatui::{
     widgets::Widget,
 };
 
+use crate::config::HeaderSegment;
 use crate::core::state::AppState;
 use crate::ui::theme::Theme;
 
@@ -20,157 +21,152 @@ impl<'a> Header<'a> {
     pub fn new(state: &'a AppState, theme: &'a Theme) -> Self {
         Self { state, theme }
     }
-}
 
-impl<'a> Widget for Header<'a> {
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        // Background
-        buf.set_style(area, self.theme.styles.header);
+    /// Text and color of `segment`, or `None` when there's nothing to show
+    fn segment(&self, segment: HeaderSegment) -> Option<(String, ratatui::style::Color)> {
+        let project = self.state.project.as_ref();
+        let colors = &self.theme.colors;
+        let (text, color) = match segment {
+            HeaderSegment::Project => (
+                project.map_or_else(|| "No project".to_string(), |p| p.name.clone()),
+                colors.fg_primary,
+            ),
+            HeaderSegment::Git => (
+                project
+                    .and_then(|p| p.git_info.as_ref())
+                    .map(git_summary)
+                    .unwrap_or_default(),
+                colors.accent_secondary,
+            ),
+            HeaderSegment::Types => (
+                project
+                    .map(|p| {
+                        p.types
+                            .iter()
+                            .filter(|t| t.primary)
+                            .map(|t| format_project_kind(&t.kind))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    })
+                    .unwrap_or_default(),
+                colors.info,
+            ),
+            HeaderSegment::Docker => (
+                docker_counts(&self.state.panels.docker.containers)
+                    .map(|(running, total)| format!("🐳 {}/{}", running, total))
+                    .unwrap_or_default(),
+                colors.success,
+            ),
+            HeaderSegment::Ports => {
+                let ports = &self.state.panels.ports;
+                let active = ports.active_ports.len();
+                let text = if !ports.conflicts.is_empty() {
+                    format!("⚡ {} ⚠", active)
+                } else if active > 0 {
+                    format!("⚡ {} ✓", active)
+                } else {
+                    String::new()
+                };
+                (text, colors.info)
+            }
+            HeaderSegment::Time => (
+                chrono::Local::now().format("%H:%M").to_string(),
+                colors.fg_muted,
+            ),
+        };
+        (!text.is_empty()).then_some((text, color))
+    }
 
-        let project_name = self
-            .state
-            .project
-            .as_ref()
-            .map(|p| p.name.as_str())
-            .unwrap_or("No project");
+    /// The configured segments in order, dropping from the right until they fit `width`
+    ///
+    /// The time, wherever it's listed, is drawn against the right edge.
+    pub fn line(&self, width: u16) -> Line<'static> {
+        let brand = Span::styled(
+            " 🛰️ ORBIT ",
+            Style::default()
+                .fg(self.theme.colors.accent_primary)
+                .add_modifier(ratatui::style::Modifier::BOLD),
+        );
+        let separator = Style::default().fg(self.theme.colors.fg_muted);
 
-        // Git info
-        let git_info = self
+        let segments: Vec<(HeaderSegment, String, ratatui::style::Color)> = self
             .state
-            .project
-            .as_ref()
-            .and_then(|p| p.git_info.as_ref())
-            .map(|g| {
-                let mut parts = vec![g.branch.clone()];
-                if g.ahead > 0 {
-                    parts.push(format!("↑{}", g.ahead));
-                }
-                if g.behind > 0 {
-                    parts.push(format!("↓{}", g.behind));
-                }
-                if g.dirty {
-                    parts.push("*".to_string());
-                }
-                parts.join(" ")
+            .layout
+            .header_segments
+            .iter()
+            .filter_map(|&segment| {
+                let (text, color) = self.segment(segment)?;
+                Some((segment, text, color))
             })
-            .unwrap_or_default();
+            .collect();
 
-        // Project types
-        let types: Vec<String> = self
-            .state
-            .project
-            .as_ref()
-            .map(|p| {
-                p.types
-                    .iter()
-                    .filter(|t| t.primary)
-                    .map(|t| format_project_kind(&t.kind))
-                    .collect()
-            })
-            .unwrap_or_default();
-        let types_str = types.join(", ");
-
-        // Docker status
-        let docker_status = {
-            let containers = &self.state.panels.docker.containers;
-            let running = containers
+        let mut keep = segments.len();
+        let fits = |count: usize| {
+            let used: usize = segments[..count]
                 .iter()
-                .filter(|c| c.status == crate::integrations::docker::ContainerStatus::Running)
-                .count();
-            let total = containers.len();
-            if total > 0 {
-                format!("🐳 {}/{}", running, total)
-            } else {
-                String::new()
-            }
-        };
-
-        // Port status
-        let port_status = {
-            let conflicts = self.state.panels.ports.conflicts.len();
-            let active = self.state.panels.ports.active_ports.len();
-            if conflicts > 0 {
-                format!("⚡ {} ⚠", active)
-            } else if active > 0 {
-                format!("⚡ {} ✓", active)
-            } else {
-                String::new()
-            }
+                .map(|(_, text, _)| Line::from(text.as_str()).width() + 3)
+                .sum();
+            brand.width() + used.saturating_sub(1) <= width as usize
         };
-
-        // Time
-        let time = chrono::Local::now().format("%H:%M").to_string();
-
-        // Build header line
-        let mut spans = vec![
-            Span::styled(
-                " 🛰️ ORBIT ",
-                Style::default()
-                    .fg(self.theme.colors.accent_primary)
-                    .add_modifier(ratatui::style::Modifier::BOLD),
-            ),
-            Span::styled("│ ", Style::default().fg(self.theme.colors.fg_muted)),
-            Span::styled(
-                project_name,
-                Style::default().fg(self.theme.colors.fg_primary),
-            ),
-        ];
-
-        if !git_info.is_empty() {
-            spans.push(Span::styled(
-                " │ ",
-                Style::default().fg(self.theme.colors.fg_muted),
-            ));
-            spans.push(Span::styled(
-                git_info,
-                Style::default().fg(self.theme.colors.accent_secondary),
-            ));
+        while keep > 0 && !fits(keep) {
+            keep -= 1;
         }
 
-        if !types_str.is_empty() {
-            spans.push(Span::styled(
-                " │ ",
-                Style::default().fg(self.theme.colors.fg_muted),
-            ));
-            spans.push(Span::styled(
-                types_str,
-                Style::default().fg(self.theme.colors.info),
-            ));
-        }
-
-        if !docker_status.is_empty() {
-            spans.push(Span::styled(
-                " │ ",
-                Style::default().fg(self.theme.colors.fg_muted),
-            ));
-            spans.push(Span::styled(
-                docker_status,
-                Style::default().fg(self.theme.colors.success),
-            ));
+        let mut spans = vec![brand];
+        let mut time = None;
+        for (segment, text, color) in segments.into_iter().take(keep) {
+            if segment == HeaderSegment::Time {
+                time = Some(Span::styled(text, Style::default().fg(color)));
+                continue;
+            }
+            let sep = if spans.len() == 1 { "│ " } else { " │ " };
+            spans.push(Span::styled(sep, separator));
+            spans.push(Span::styled(text, Style::default().fg(color)));
         }
-
-        if !port_status.is_empty() {
-            spans.push(Span::styled(
-                " │ ",
-                Style::default().fg(self.theme.colors.fg_muted),
-            ));
-            spans.push(Span::styled(
-                port_status,
-                Style::default().fg(self.theme.colors.info),
-            ));
+        if let Some(time) = time {
+            let used: usize = spans.iter().map(Span::width).sum::<usize>() + time.width() + 1;
+            spans.push(Span::raw(" ".repeat((width as usize).saturating_sub(used))));
+            spans.push(time);
+            spans.push(Span::raw(" "));
         }
+        Line::from(spans)
+    }
+}
 
-        // Calculate right side position for time
-        let left_line = Line::from(spans);
-        let _left_width = left_line.width();
+impl<'a> Widget for Header<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        // Background
+        buf.set_style(area, self.theme.styles.header);
 
-        buf.set_line(area.x, area.y, &left_line, area.width);
+        let line = self.line(area.width);
+        buf.set_line(area.x, area.y, &line, area.width);
+    }
+}
 
-        // Render time on the right
-        let time_span = Span::styled(&time, Style::default().fg(self.theme.colors.fg_muted));
-        let time_x = area.x + area.width.saturating_sub(time.len() as u16 + 1);
-        buf.set_span(time_x, area.y, &time_span, time.len() as u16);
+/// Branch with ahead/behind counts and a `*` when the tree is dirty
+pub fn git_summary(git: &crate::detection::GitInfo) -> String {
+    let mut parts = vec![git.branch.clone()];
+    if git.ahead > 0 {
+        parts.push(format!("↑{}", git.ahead));
     }
+    if git.behind > 0 {
+        parts.push(format!("↓{}", git.behind));
+    }
+    if git.dirty {
+        parts.push("*".to_string());
+    }
+    parts.join(" ")
+}
+
+/// Running and total container counts, or `None` when there are no containers
+pub fn docker_counts(
+    containers: &[crate::integrations::docker::ContainerInfo],
+) -> Option<(usize, usize)> {
+    let running = containers
+        .iter()
+        .filter(|c| c.status == crate::integrations::docker::ContainerStatus::Running)
+        .count();
+    (!containers.is_empty()).then_some((running, containers.len()))
 }
 
 fn format_project_kind(kind: &crate::detection::ProjectKind) -> String {
@@ -192,8 +188,70 @@ fn format_project_kind(kind: &crate::detection::ProjectKind) -> String {
             }
         }
         ProjectKind::Go { .. } => "Go".to_string(),
+        ProjectKind::Ruby { framework } => {
+            if let Some(fw) = framework {
+                format!("{:?}", fw)
+            } else {
+                "Ruby".to_string()
+            }
+        }
+        ProjectKind::Php { framework } => {
+            if let Some(fw) = framework {
+                format!("{:?}", fw)
+            } else {
+                "PHP".to_string()
+            }
+        }
+        ProjectKind::Jvm { build_tool } => build_tool.to_string(),
+        ProjectKind::OCaml => "OCaml".to_string(),
+        ProjectKind::Nim => "Nim".to_string(),
+        ProjectKind::Bazel { buck2: false } => "Bazel".to_string(),
+        ProjectKind::Bazel { buck2: true } => "Buck2".to_string(),
         ProjectKind::Docker { .. } => "Docker".to_string(),
         ProjectKind::Git => "Git".to_string(),
         ProjectKind::Generic => "Generic".to_string(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::ports::{ActivePort, PortState};
+
+    fn text(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn configured_segments_render_in_order_and_drop_from_the_right() {
+        let mut state = AppState::new("/tmp/api".into(), Theme::default());
+        state.panels.ports.active_ports = vec![ActivePort {
+            port: 3000,
+            pid: 42,
+            process_name: "node".to_string(),
+            state: PortState::Listening,
+            remote: None,
+        }];
+        state.layout.header_segments = vec![HeaderSegment::Ports, HeaderSegment::Project];
+        let theme = Theme::default();
+        let header = Header::new(&state, &theme);
+
+        assert_eq!(text(&header.line(80)), " 🛰️ ORBIT │ ⚡ 1 ✓ │ No project");
+        assert_eq!(text(&header.line(20)), " 🛰️ ORBIT │ ⚡ 1 ✓");
+        assert_eq!(text(&header.line(8)), " 🛰️ ORBIT ");
+
+        // Segments with nothing to show (no git repo here) are skipped
+        state.layout.header_segments = vec![HeaderSegment::Git, HeaderSegment::Project];
+        let header = Header::new(&state, &theme);
+        assert_eq!(text(&header.line(80)), " 🛰️ ORBIT │ No project");
+
+        // The time is pushed to the right edge, even when listed first
+        state.layout.header_segments = vec![HeaderSegment::Time, HeaderSegment::Project];
+        let header = Header::new(&state, &theme);
+        let line = header.line(40);
+        let rendered = text(&line);
+        assert_eq!(line.width(), 40);
+        assert!(rendered.starts_with(" 🛰️ ORBIT │ No project  "));
+        assert_eq!(rendered.chars().rev().nth(3), Some(':'));
+    }
+}
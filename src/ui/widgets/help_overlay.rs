@@ -8,15 +8,17 @@ use ratatui::{
     widgets::{Block, Borders, Clear, Widget},
 };
 
+use crate::core::events::KeyBindings;
 use crate::ui::theme::Theme;
 
 pub struct HelpOverlay<'a> {
+    keys: &'a KeyBindings,
     theme: &'a Theme,
 }
 
 impl<'a> HelpOverlay<'a> {
-    pub fn new(theme: &'a Theme) -> Self {
-        Self { theme }
+    pub fn new(keys: &'a KeyBindings, theme: &'a Theme) -> Self {
+        Self { keys, theme }
     }
 }
 
@@ -36,38 +38,90 @@ impl<'a> Widget for HelpOverlay<'a> {
         let inner = block.inner(area);
         block.render(area, buf);
 
+        let keys = self.keys;
         let keybindings = [
             (
                 "General",
                 vec![
-                    ("q", "Quit Orbit"),
-                    ("?", "Toggle help"),
-                    ("r", "Refresh project detection"),
-                    ("Tab", "Cycle panel focus"),
+                    (keys.quit.to_string(), "Quit Orbit"),
+                    (keys.help.to_string(), "Toggle help"),
+                    (keys.refresh.to_string(), "Refresh project detection"),
+                    ("S".to_string(), "Save dashboard snapshot"),
+                    ("Tab".to_string(), "Cycle panel focus"),
                 ],
             ),
             (
                 "Navigation",
                 vec![
-                    ("↑/k", "Move up"),
-                    ("↓/j", "Move down"),
-                    ("Enter", "Execute selected action"),
-                    ("Space", "Open command palette"),
+                    (format!("↑/{}", keys.navigate_up), "Move up"),
+                    (format!("↓/{}", keys.navigate_down), "Move down"),
+                    (keys.select.to_string(), "Execute selected action"),
+                    (
+                        format!("{} or /", keys.palette),
+                        "Open command palette (not / in Output, Space in Actions)",
+                    ),
+                    ("X".to_string(), "Dismiss notifications"),
+                    ("m".to_string(), "Start/stop recording a macro"),
+                    ("@".to_string(), "Replay the last macro"),
                 ],
             ),
             (
                 "Panels",
                 vec![
-                    ("d", "Toggle Docker panel"),
-                    ("p", "Toggle Ports panel"),
-                    ("e", "Toggle Environment panel"),
+                    (keys.toggle_docker.to_string(), "Toggle Docker panel"),
+                    (keys.toggle_ports.to_string(), "Toggle Ports panel"),
+                    (keys.toggle_env.to_string(), "Toggle Environment panel"),
+                    ("z".to_string(), "Cycle layout (standard/compact/wide)"),
+                    ("T".to_string(), "Cycle color theme"),
+                    ("|".to_string(), "Split output with pinned container logs"),
+                ],
+            ),
+            (
+                "Focused Panel",
+                vec![
+                    ("Space".to_string(), "Mark action for a batch run (Actions)"),
+                    ("Enter".to_string(), "Run marked actions in order (Actions)"),
+                    ("R".to_string(), "Restart container (Docker)"),
+                    ("l".to_string(), "Show container logs (Docker)"),
+                    ("P".to_string(), "Pin container logs beside output (Docker)"),
+                    ("+/-".to_string(), "Scale compose service (Docker)"),
+                    (
+                        "u/D".to_string(),
+                        "Compose up/down, streamed to output (Docker)",
+                    ),
+                    ("x".to_string(), "Kill process on port (Ports)"),
+                    (
+                        "R".to_string(),
+                        "Reclaim port and restart its script (Ports)",
+                    ),
+                    (
+                        "c".to_string(),
+                        "Show TCP connections, not just listeners (Ports)",
+                    ),
+                    ("Enter".to_string(), "Open file in $EDITOR (Recent Files)"),
+                    ("v".to_string(), "Reveal values (Env)"),
+                    ("c".to_string(), "Clear output (Output)"),
+                    ("o".to_string(), "Open output in $PAGER (Output)"),
+                    ("E".to_string(), "Show only errors and warnings (Output)"),
+                    (
+                        "/".to_string(),
+                        "Search output; n/N next/previous match (Output)",
+                    ),
                 ],
             ),
             (
                 "Modes",
                 vec![
-                    ("f", "Enter focus mode"),
-                    ("Esc", "Exit current mode / Close overlay"),
+                    (keys.focus.to_string(), "Enter focus mode"),
+                    (
+                        keys.terminal.to_string(),
+                        "Shell in the output area (Ctrl+] back)",
+                    ),
+                    (
+                        "N".to_string(),
+                        "Project notes (Ctrl+S save, Ctrl+E $EDITOR)",
+                    ),
+                    (keys.back.to_string(), "Exit current mode / Close overlay"),
                 ],
             ),
         ];
@@ -8,8 +8,11 @@ use ratatui::{
     widgets::{Block, Borders, Widget},
 };
 
+use std::collections::VecDeque;
+
 use crate::core::state::AppState;
 use crate::ui::theme::Theme;
+use crate::ui::util::sparkline;
 
 pub struct MetricsPanel<'a> {
     state: &'a AppState,
@@ -24,8 +27,14 @@ impl<'a> MetricsPanel<'a> {
 
 impl<'a> Widget for MetricsPanel<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
+        let metrics = &self.state.panels.metrics;
+        let title = if metrics.unavailable && metrics.has_sample() {
+            " SYSTEM (stale) "
+        } else {
+            " SYSTEM "
+        };
         let block = Block::default()
-            .title(Span::styled(" SYSTEM ", self.theme.styles.panel_title))
+            .title(Span::styled(title, self.theme.styles.panel_title))
             .borders(Borders::ALL)
             .border_style(self.theme.styles.panel_border)
             .style(Style::default().bg(self.theme.colors.bg_primary));
@@ -33,102 +42,88 @@ impl<'a> Widget for MetricsPanel<'a> {
         let inner = block.inner(area);
         block.render(area, buf);
 
-        let metrics = &self.state.panels.metrics;
-
-        // CPU line with sparkline
-        if inner.height >= 1 {
-            let cpu_data: Vec<u64> = metrics
-                .cpu_history
-                .iter()
-                .map(|&v| (v * 100.0) as u64)
-                .collect();
-
-            let label = format!("CPU {:>3.0}% ", metrics.cpu_percent);
-            let label_span =
-                Span::styled(&label, Style::default().fg(self.theme.colors.fg_secondary));
-            buf.set_span(inner.x, inner.y, &label_span, label.len() as u16);
-
-            if !cpu_data.is_empty() {
-                let sparkline_width = inner.width.saturating_sub(label.len() as u16 + 1);
-                if sparkline_width > 0 {
-                    let sparkline_area = Rect {
-                        x: inner.x + label.len() as u16,
-                        y: inner.y,
-                        width: sparkline_width,
-                        height: 1,
-                    };
-
-                    // Draw simple bar representation
-                    let bar_width = sparkline_width as usize;
-                    let recent: Vec<u64> = cpu_data
-                        .iter()
-                        .rev()
-                        .take(bar_width)
-                        .rev()
-                        .cloned()
-                        .collect();
-
-                    let bar_chars = "▁▂▃▄▅▆▇█";
-                    let bar: String = recent
-                        .iter()
-                        .map(|&v| {
-                            let idx = ((v as usize * 7) / 100).min(7);
-                            bar_chars.chars().nth(idx).unwrap_or('▁')
-                        })
-                        .collect();
-
-                    let bar_span = Span::styled(bar, self.theme.styles.sparkline);
-                    buf.set_span(
-                        sparkline_area.x,
-                        sparkline_area.y,
-                        &bar_span,
-                        sparkline_area.width,
-                    );
-                }
-            }
+        if metrics.unavailable && !metrics.has_sample() {
+            let span = Span::styled(
+                "metrics unavailable",
+                Style::default().fg(self.theme.colors.fg_muted),
+            );
+            buf.set_span(inner.x, inner.y, &span, inner.width);
+            return;
         }
 
-        // Memory line
-        if inner.height >= 2 {
-            let mem_percent = if metrics.memory_total_mb > 0 {
-                (metrics.memory_used_mb as f32 / metrics.memory_total_mb as f32) * 100.0
-            } else {
-                0.0
+        let memory_suffix = format!(
+            " {}G/{}G",
+            metrics.memory_used_mb / 1024,
+            metrics.memory_total_mb / 1024
+        );
+        let rows = [
+            ("CPU", metrics.cpu_percent, &metrics.cpu_history, None),
+            (
+                "MEM",
+                metrics.memory_percent(),
+                &metrics.memory_history,
+                Some(memory_suffix),
+            ),
+            (
+                "DSK",
+                metrics.disk_used_percent,
+                &metrics.disk_history,
+                None,
+            ),
+        ];
+        for (row, (name, percent, history, suffix)) in
+            rows.into_iter().take(inner.height as usize).enumerate()
+        {
+            let row_area = Rect {
+                y: inner.y + row as u16,
+                height: 1,
+                ..inner
             };
-
-            let mem_bar_width = 12;
-            // Clamp to prevent overflow if mem_percent > 100
-            let filled = ((mem_percent.min(100.0) / 100.0) * mem_bar_width as f32) as usize;
-            let bar: String = "█".repeat(filled) + &"░".repeat(mem_bar_width - filled);
-
-            let mem_label = format!(
-                "MEM {} {:>4}G/{:.0}G",
-                bar,
-                metrics.memory_used_mb / 1024,
-                metrics.memory_total_mb / 1024
-            );
-
-            let mem_span = Span::styled(
-                mem_label,
-                Style::default().fg(self.theme.colors.fg_secondary),
-            );
-            buf.set_span(inner.x, inner.y + 1, &mem_span, inner.width);
+            self.render_history_row(row_area, buf, name, percent, history, suffix);
         }
+    }
+}
 
-        // Disk line (if we have space)
-        if inner.height >= 3 {
-            let disk_percent = metrics.disk_used_percent;
-            let disk_bar_width = 12;
-            // Clamp to prevent overflow if disk_percent > 100
-            let filled = ((disk_percent.min(100.0) / 100.0) * disk_bar_width as f32) as usize;
-            let bar: String = "█".repeat(filled) + &"░".repeat(disk_bar_width - filled);
-
-            let disk_label = format!("DSK {} {:>3.0}%", bar, disk_percent);
-            let disk_span = Span::styled(
-                disk_label,
-                Style::default().fg(self.theme.colors.fg_secondary),
+impl<'a> MetricsPanel<'a> {
+    /// `MEM  48% ▃▃▄▄▅▅ 7G/16G`: label, current value, sparkline over the rest of the row
+    fn render_history_row(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        name: &str,
+        percent: f32,
+        history: &VecDeque<f32>,
+        suffix: Option<String>,
+    ) {
+        let text_style = Style::default().fg(self.theme.colors.fg_secondary);
+        let label = format!("{} {:>3.0}% ", name, percent);
+        let label_width = label.chars().count() as u16;
+        buf.set_span(area.x, area.y, &Span::styled(label, text_style), area.width);
+
+        let suffix = suffix.unwrap_or_default();
+        let suffix_width = suffix.chars().count() as u16;
+        let sparkline_width = area.width.saturating_sub(label_width + suffix_width + 1);
+        if sparkline_width > 0 && !history.is_empty() {
+            let bar = sparkline(
+                history.iter().map(|&v| v as f64),
+                100.0,
+                sparkline_width as usize,
             );
-            buf.set_span(inner.x, inner.y + 2, &disk_span, inner.width);
+            let bar_width = bar.chars().count() as u16;
+            buf.set_span(
+                area.x + label_width,
+                area.y,
+                &Span::styled(bar, self.theme.styles.sparkline),
+                sparkline_width,
+            );
+            if suffix_width > 0 {
+                buf.set_span(
+                    area.x + label_width + bar_width,
+                    area.y,
+                    &Span::styled(suffix, text_style),
+                    suffix_width,
+                );
+            }
         }
     }
 }
@@ -8,8 +8,11 @@ pub mod footer;
 pub mod header;
 pub mod help_overlay;
 pub mod metrics_panel;
+pub mod notes_overlay;
 pub mod output_panel;
 pub mod ports_panel;
+pub mod recent_files_panel;
+pub mod terminal_panel;
 
 pub use action_palette::ActionPalette;
 pub use actions_panel::ActionsPanel;
@@ -19,5 +22,8 @@ pub use footer::Footer;
 pub use header::Header;
 pub use help_overlay::HelpOverlay;
 pub use metrics_panel::MetricsPanel;
+pub use notes_overlay::NotesOverlay;
 pub use output_panel::OutputPanel;
 pub use ports_panel::PortsPanel;
+pub use recent_files_panel::RecentFilesPanel;
+pub use terminal_panel::TerminalPanel;
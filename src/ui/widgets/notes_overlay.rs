@@ -0,0 +1,92 @@
+//! Per-project notes scratchpad overlay
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::Span,
+    widgets::{Block, Borders, Widget},
+};
+
+use crate::core::state::AppState;
+use crate::ui::theme::Theme;
+
+pub struct NotesOverlay<'a> {
+    state: &'a AppState,
+    theme: &'a Theme,
+}
+
+impl<'a> NotesOverlay<'a> {
+    pub fn new(state: &'a AppState, theme: &'a Theme) -> Self {
+        Self { state, theme }
+    }
+}
+
+impl<'a> Widget for NotesOverlay<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let notes = &self.state.panels.notes;
+        let title = if notes.dirty { " NOTES * " } else { " NOTES " };
+
+        let block = Block::default()
+            .title(Span::styled(title, self.theme.styles.panel_title))
+            .borders(Borders::ALL)
+            .border_style(self.theme.styles.panel_border_focused)
+            .style(Style::default().bg(self.theme.colors.bg_secondary));
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if inner.height == 0 || inner.width < 2 {
+            return;
+        }
+
+        // Keep the cursor row in view
+        let visible = inner.height as usize;
+        let start = notes.cursor_row.saturating_sub(visible.saturating_sub(1));
+        let width = inner.width.saturating_sub(1) as usize;
+        let text_style = Style::default().fg(self.theme.colors.fg_primary);
+
+        for (i, line) in notes.lines.iter().skip(start).take(visible).enumerate() {
+            let y = inner.y + i as u16;
+            let row = start + i;
+
+            // Scroll long lines horizontally so the cursor stays visible
+            let offset = if row == notes.cursor_row {
+                notes.cursor_col.saturating_sub(width.saturating_sub(1))
+            } else {
+                0
+            };
+            let shown: String = line.chars().skip(offset).take(width).collect();
+            buf.set_stringn(inner.x + 1, y, &shown, width, text_style);
+
+            if row == notes.cursor_row {
+                let x = inner.x + 1 + (notes.cursor_col - offset) as u16;
+                if let Some(cell) = buf.cell_mut((x, y)) {
+                    if cell.symbol().is_empty() {
+                        cell.set_symbol(" ");
+                    }
+                    cell.set_style(
+                        Style::default()
+                            .fg(self.theme.colors.bg_secondary)
+                            .bg(self.theme.colors.cursor)
+                            .add_modifier(Modifier::BOLD),
+                    );
+                }
+            }
+        }
+
+        if let Some(path) = &notes.path {
+            let label = format!(" {} ", path.display());
+            let label_width = label.chars().count() as u16;
+            if label_width + 2 < area.width {
+                let x = area.x + area.width - label_width - 1;
+                buf.set_span(
+                    x,
+                    area.y + area.height - 1,
+                    &Span::styled(label, Style::default().fg(self.theme.colors.fg_muted)),
+                    label_width,
+                );
+            }
+        }
+    }
+}
@@ -8,34 +8,85 @@ use ratatui::{
     widgets::{Block, Borders, Widget},
 };
 
-use crate::core::state::{AppState, FocusedPanel, OutputStream};
+use crate::core::state::{AppState, FocusedPanel, OutputPanelState, OutputStream};
 use crate::ui::theme::Theme;
+use crate::ui::util::truncate;
 
 pub struct OutputPanel<'a> {
-    state: &'a AppState,
+    output: &'a OutputPanelState,
+    title: String,
+    empty_message: &'static str,
+    focused: bool,
     theme: &'a Theme,
 }
 
 impl<'a> OutputPanel<'a> {
     pub fn new(state: &'a AppState, theme: &'a Theme) -> Self {
-        Self { state, theme }
+        Self {
+            output: &state.panels.output,
+            title: "OUTPUT".to_string(),
+            empty_message: "Output will appear here...",
+            focused: state.focus_panel == FocusedPanel::Output,
+            theme,
+        }
+    }
+
+    /// Orbit's own log, shown as an overlay
+    pub fn log_viewer(state: &'a AppState, theme: &'a Theme) -> Self {
+        Self {
+            output: &state.panels.logs,
+            title: "ORBIT LOG".to_string(),
+            empty_message: "No log entries for today",
+            focused: true,
+            theme,
+        }
+    }
+
+    /// Followed logs of the pinned container, beside the output in split mode
+    pub fn pinned_logs(state: &'a AppState, theme: &'a Theme) -> Self {
+        let title = match &state.panels.pinned_container {
+            Some(name) => format!("LOGS: {}", name),
+            None => "LOGS".to_string(),
+        };
+        Self {
+            output: &state.panels.pinned_logs,
+            title,
+            empty_message: "Press P on a container to pin its logs",
+            focused: state.focus_panel == FocusedPanel::PinnedLogs,
+            theme,
+        }
     }
 }
 
 impl<'a> Widget for OutputPanel<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let focused = self.state.focus_panel == FocusedPanel::Output;
-        let border_style = if focused {
+        let border_style = if self.focused {
             self.theme.styles.panel_border_focused
         } else {
             self.theme.styles.panel_border
         };
 
-        let title = if self.state.panels.output.auto_scroll {
-            " OUTPUT "
-        } else {
-            " OUTPUT (scroll) "
-        };
+        let output = self.output;
+        let lines = output.visible_lines();
+
+        let mut title = format!(" {} ", self.title);
+        if output.errors_only {
+            let hidden = output.lines.len() - lines.len();
+            title.push_str(&format!("(errors only, {} hidden) ", hidden));
+        }
+        let query = output.search_query.as_deref().filter(|q| !q.is_empty());
+        if let Some(query) = query {
+            let count = output.match_indices.len();
+            match output.current_match {
+                Some(current) => {
+                    title.push_str(&format!("/{} ({}/{}) ", query, current + 1, count))
+                }
+                None if count > 0 => title.push_str(&format!("/{} ({} matches) ", query, count)),
+                None => title.push_str(&format!("/{} (no matches) ", query)),
+            }
+        } else if !output.auto_scroll {
+            title.push_str("(scroll) ");
+        }
 
         let block = Block::default()
             .title(Span::styled(title, self.theme.styles.panel_title))
@@ -43,22 +94,35 @@ impl<'a> Widget for OutputPanel<'a> {
             .border_style(border_style)
             .style(Style::default().bg(self.theme.colors.bg_primary));
 
-        let inner = block.inner(area);
+        let mut inner = block.inner(area);
         block.render(area, buf);
 
-        let output = &self.state.panels.output;
+        // The search input takes the bottom row while it's open
+        if output.search_input && inner.height > 1 {
+            inner.height -= 1;
+            let input = Line::from(vec![
+                Span::styled("/", Style::default().fg(self.theme.colors.accent_primary)),
+                Span::styled(
+                    format!("{}█", output.search_query.as_deref().unwrap_or_default()),
+                    Style::default().fg(self.theme.colors.fg_primary),
+                ),
+            ]);
+            buf.set_line(inner.x, inner.y + inner.height, &input, inner.width);
+        }
 
-        if output.lines.is_empty() {
-            let span = Span::styled(
-                "Output will appear here...",
-                Style::default().fg(self.theme.colors.fg_muted),
-            );
+        if lines.is_empty() {
+            let message = if output.errors_only && !output.lines.is_empty() {
+                "No errors or warnings (E to show everything)"
+            } else {
+                self.empty_message
+            };
+            let span = Span::styled(message, Style::default().fg(self.theme.colors.fg_muted));
             buf.set_span(inner.x + 1, inner.y, &span, inner.width.saturating_sub(2));
             return;
         }
 
         let visible_lines = inner.height as usize;
-        let total_lines = output.lines.len();
+        let total_lines = lines.len();
 
         let start = if output.auto_scroll {
             total_lines.saturating_sub(visible_lines)
@@ -68,13 +132,11 @@ impl<'a> Widget for OutputPanel<'a> {
                 .min(total_lines.saturating_sub(visible_lines))
         };
 
-        for (i, line) in output
-            .lines
-            .iter()
-            .skip(start)
-            .take(visible_lines)
-            .enumerate()
-        {
+        let current_line = output
+            .current_match
+            .and_then(|current| output.match_indices.get(current).copied());
+
+        for (i, line) in lines.iter().skip(start).take(visible_lines).enumerate() {
             let style = match line.stream {
                 OutputStream::Stdout => Style::default().fg(self.theme.colors.fg_primary),
                 OutputStream::Stderr => Style::default().fg(self.theme.colors.error),
@@ -83,6 +145,7 @@ impl<'a> Widget for OutputPanel<'a> {
 
             let prefix = match line.stream {
                 OutputStream::Stdout => "│ ",
+                OutputStream::Stderr if self.theme.colorblind => "E ",
                 OutputStream::Stderr => "! ",
                 OutputStream::System => "● ",
             };
@@ -97,10 +160,26 @@ impl<'a> Widget for OutputPanel<'a> {
             let max_content_width = inner.width.saturating_sub(3) as usize;
             let content = truncate(&line.content, max_content_width);
 
-            let display_line = Line::from(vec![
-                Span::styled(prefix, prefix_style),
-                Span::styled(content, style),
-            ]);
+            let mut spans = vec![Span::styled(prefix, prefix_style)];
+            match query {
+                Some(query) => {
+                    let highlight = if current_line == Some(start + i) {
+                        self.theme.colors.accent_primary
+                    } else {
+                        self.theme.colors.warning
+                    };
+                    spans.extend(highlight_matches(
+                        content,
+                        query,
+                        style,
+                        Style::default()
+                            .fg(self.theme.colors.bg_primary)
+                            .bg(highlight),
+                    ));
+                }
+                None => spans.push(Span::styled(content, style)),
+            }
+            let display_line = Line::from(spans);
 
             buf.set_line(inner.x, inner.y + i as u16, &display_line, inner.width);
         }
@@ -117,13 +196,24 @@ impl<'a> Widget for OutputPanel<'a> {
     }
 }
 
-fn truncate(s: &str, max_len: usize) -> String {
-    let char_count = s.chars().count();
-    if char_count <= max_len {
-        s.to_string()
-    } else if max_len > 1 {
-        format!("{}…", s.chars().take(max_len - 1).collect::<String>())
-    } else {
-        String::new()
+/// Split `content` into spans with every case-insensitive occurrence of `query` highlighted
+fn highlight_matches(
+    content: String,
+    query: &str,
+    style: Style,
+    highlight: Style,
+) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut last = 0;
+    for range in OutputPanelState::match_ranges(&content, query) {
+        if range.start > last {
+            spans.push(Span::styled(content[last..range.start].to_string(), style));
+        }
+        spans.push(Span::styled(content[range.clone()].to_string(), highlight));
+        last = range.end;
+    }
+    if last < content.len() || spans.is_empty() {
+        spans.push(Span::styled(content[last..].to_string(), style));
     }
+    spans
 }
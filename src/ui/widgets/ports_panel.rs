@@ -8,7 +8,9 @@ use ratatui::{
     widgets::{Block, Borders, Widget},
 };
 
-use crate::core::state::{AppState, FocusedPanel};
+use crate::core::state::{AppState, FocusedPanel, PortPanelState};
+use crate::integrations::docker::ContainerInfo;
+use crate::integrations::ports::{script_for_port, ExpectedPort, PortState};
 use crate::ui::theme::Theme;
 
 pub struct PortsPanel<'a> {
@@ -43,62 +45,53 @@ impl<'a> Widget for PortsPanel<'a> {
         let ports = &self.state.panels.ports;
         let selected = ports.selected_index;
 
-        // Combine expected and active ports
-        let mut display_items: Vec<PortDisplayItem> = Vec::new();
-
-        // Add expected ports with their status
-        for expected in &ports.expected_ports {
-            let active = ports.active_ports.iter().find(|a| a.port == expected.port);
-            let conflict = ports.conflicts.iter().find(|c| c.port == expected.port);
-
-            display_items.push(PortDisplayItem {
-                port: expected.port,
-                service: expected.service_name.clone(),
-                status: if conflict.is_some() {
-                    PortStatus::Conflict
-                } else if active.is_some() {
-                    PortStatus::Active
-                } else {
-                    PortStatus::Expected
-                },
-                process: active.map(|a| a.process_name.clone()),
-            });
-        }
-
-        // Add any active ports not in expected
-        for active in &ports.active_ports {
-            if !ports.expected_ports.iter().any(|e| e.port == active.port) {
-                display_items.push(PortDisplayItem {
-                    port: active.port,
-                    service: active.process_name.clone(),
-                    status: PortStatus::Active,
-                    process: Some(active.process_name.clone()),
-                });
-            }
-        }
-
-        // Sort by port number
-        display_items.sort_by_key(|i| i.port);
+        let display_items = display_items(ports, &self.state.panels.docker.containers);
+        let scripts = self
+            .state
+            .project
+            .as_ref()
+            .map(|p| p.scripts.as_slice())
+            .unwrap_or_default();
 
+        let mut row = 0usize;
         if display_items.is_empty() {
             let span = Span::styled(
                 "No ports detected",
                 Style::default().fg(self.theme.colors.fg_muted),
             );
             buf.set_span(inner.x + 1, inner.y, &span, inner.width.saturating_sub(2));
-            return;
+            row += 1;
         }
 
+        let mut current_group = None;
         for (i, item) in display_items.iter().enumerate() {
-            if i >= inner.height as usize {
+            if current_group != Some(item.group) {
+                if row >= inner.height as usize {
+                    break;
+                }
+                current_group = Some(item.group);
+                let header = Span::styled(
+                    item.group.label(),
+                    Style::default().fg(self.theme.colors.fg_muted),
+                );
+                buf.set_span(inner.x + 1, inner.y + row as u16, &header, inner.width);
+                row += 1;
+            }
+
+            if row >= inner.height as usize {
                 break;
             }
 
             let is_selected = i == selected;
-            let (icon, icon_style) = match item.status {
-                PortStatus::Active => ("✓", self.theme.styles.status_running),
-                PortStatus::Expected => ("○", self.theme.styles.list_item),
-                PortStatus::Conflict => ("✗", self.theme.styles.status_warning),
+            let (icon, badge, icon_style) = match item.status {
+                PortStatus::Active => ("✓", "OK", self.theme.styles.status_running),
+                PortStatus::Expected => ("○", "", self.theme.styles.list_item),
+                PortStatus::Conflict => ("✗", "FAIL", self.theme.styles.status_warning),
+            };
+            let status = if self.theme.colorblind {
+                format!("{} {:<4} ", icon, badge)
+            } else {
+                format!("{} ", icon)
             };
 
             let base_style = if is_selected {
@@ -108,41 +101,233 @@ impl<'a> Widget for PortsPanel<'a> {
             };
 
             let indicator = if is_selected { "▸" } else { " " };
-            let service = truncate(&item.service, 12);
+            let service = self.theme.truncate_name(&item.service, 12);
 
-            let line = Line::from(vec![
+            let mut spans = vec![
                 Span::styled(indicator, base_style),
                 Span::styled(
                     format!(" :{:<5} ", item.port),
                     Style::default().fg(self.theme.colors.accent_primary),
                 ),
-                Span::styled(format!("{} ", icon), icon_style),
+                Span::styled(status, icon_style),
                 Span::styled(service, base_style),
-            ]);
+            ];
+
+            // Nudge when an expected port's server hasn't been started yet
+            if matches!(item.status, PortStatus::Expected) {
+                let script = ports
+                    .expected_ports
+                    .iter()
+                    .find(|e| e.port == item.port)
+                    .and_then(|e| script_for_port(e, scripts));
+                let hint = match script {
+                    Some(script) if is_selected => format!(" not running · ↵ {}", script.name),
+                    Some(script) => format!(" {} not running", script.name),
+                    None => " not running".to_string(),
+                };
+                spans.push(Span::styled(
+                    hint,
+                    Style::default().fg(self.theme.colors.fg_muted),
+                ));
+            }
+
+            let line = Line::from(spans);
 
-            buf.set_line(inner.x, inner.y + i as u16, &line, inner.width);
+            buf.set_line(inner.x, inner.y + row as u16, &line, inner.width);
+            row += 1;
+        }
+
+        if ports.show_connections {
+            self.render_connections(ports, inner, row, buf);
         }
     }
 }
 
-struct PortDisplayItem {
-    port: u16,
-    service: String,
-    status: PortStatus,
-    #[allow(dead_code)]
-    process: Option<String>,
+impl<'a> PortsPanel<'a> {
+    /// `:54321 → 127.0.0.1:5432 ESTABLISHED node` rows below the listeners
+    fn render_connections(
+        &self,
+        ports: &PortPanelState,
+        inner: Rect,
+        mut row: usize,
+        buf: &mut Buffer,
+    ) {
+        let muted = Style::default().fg(self.theme.colors.fg_muted);
+        if row >= inner.height as usize {
+            return;
+        }
+        let header = format!("Connections ({})", ports.connections.len());
+        buf.set_span(
+            inner.x + 1,
+            inner.y + row as u16,
+            &Span::styled(header, muted),
+            inner.width,
+        );
+        row += 1;
+
+        for connection in &ports.connections {
+            if row >= inner.height as usize {
+                break;
+            }
+            let state_style = match connection.state {
+                PortState::Established => self.theme.styles.status_running,
+                _ => self.theme.styles.status_warning,
+            };
+            let line = Line::from(vec![
+                Span::styled(
+                    format!("  :{:<5} ", connection.port),
+                    Style::default().fg(self.theme.colors.accent_primary),
+                ),
+                Span::styled(
+                    format!("→ {} ", connection.remote.as_deref().unwrap_or("?")),
+                    self.theme.styles.list_item,
+                ),
+                Span::styled(format!("{} ", connection.state), state_style),
+                Span::styled(
+                    self.theme.truncate_name(&connection.process_name, 12),
+                    muted,
+                ),
+            ]);
+            buf.set_line(inner.x, inner.y + row as u16, &line, inner.width);
+            row += 1;
+        }
+    }
+}
+
+/// A row in the Port Scout, combining expected and active ports
+pub struct PortDisplayItem {
+    pub port: u16,
+    pub service: String,
+    pub status: PortStatus,
+    pub process: Option<String>,
+    pub pid: Option<u32>,
+    pub group: PortGroup,
 }
 
-enum PortStatus {
+pub enum PortStatus {
     Active,
     Expected,
     Conflict,
 }
 
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
+/// Who a port belongs to; also the display order of the groups
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PortGroup {
+    Project,
+    Docker,
+    Other,
+}
+
+impl PortGroup {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PortGroup::Project => "Project",
+            PortGroup::Docker => "Docker",
+            PortGroup::Other => "Other",
+        }
+    }
+}
+
+/// Group a port by owner: a published container port wins, then the project's expected ports
+pub fn classify_port(
+    port: u16,
+    expected: &[ExpectedPort],
+    containers: &[ContainerInfo],
+) -> PortGroup {
+    if container_for_port(port, containers).is_some() {
+        PortGroup::Docker
+    } else if expected.iter().any(|e| e.port == port) {
+        PortGroup::Project
     } else {
-        format!("{}…", &s[..max_len - 1])
+        PortGroup::Other
+    }
+}
+
+fn container_for_port(port: u16, containers: &[ContainerInfo]) -> Option<&ContainerInfo> {
+    containers
+        .iter()
+        .find(|c| c.ports.iter().any(|m| m.public_port == Some(port)))
+}
+
+/// Rows shown in the Port Scout, in display order
+pub fn display_items(ports: &PortPanelState, containers: &[ContainerInfo]) -> Vec<PortDisplayItem> {
+    let mut display_items: Vec<PortDisplayItem> = Vec::new();
+
+    // Add expected ports with their status
+    for expected in &ports.expected_ports {
+        let active = ports.active_ports.iter().find(|a| a.port == expected.port);
+        let conflict = ports.conflicts.iter().find(|c| c.port == expected.port);
+
+        display_items.push(PortDisplayItem {
+            port: expected.port,
+            service: expected.service_name.clone(),
+            status: if conflict.is_some() {
+                PortStatus::Conflict
+            } else if active.is_some() {
+                PortStatus::Active
+            } else {
+                PortStatus::Expected
+            },
+            process: active.map(|a| a.process_name.clone()),
+            pid: active.map(|a| a.pid),
+            group: classify_port(expected.port, &ports.expected_ports, containers),
+        });
+    }
+
+    // Add any active ports not in expected
+    for active in &ports.active_ports {
+        if !ports.expected_ports.iter().any(|e| e.port == active.port) {
+            let service = container_for_port(active.port, containers)
+                .map(|c| c.name.clone())
+                .unwrap_or_else(|| active.process_name.clone());
+            display_items.push(PortDisplayItem {
+                port: active.port,
+                service,
+                status: PortStatus::Active,
+                process: Some(active.process_name.clone()),
+                pid: Some(active.pid),
+                group: classify_port(active.port, &ports.expected_ports, containers),
+            });
+        }
+    }
+
+    // Group by owner, then sort by port number
+    display_items.sort_by_key(|i| (i.group, i.port));
+    display_items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::docker::{ContainerStatus, PortMapping};
+
+    #[test]
+    fn classifies_ports_by_owner() {
+        let expected = vec![ExpectedPort {
+            port: 3000,
+            source: "package.json".to_string(),
+            service_name: "web".to_string(),
+        }];
+        let containers = vec![ContainerInfo {
+            ports: vec![PortMapping {
+                private_port: 5432,
+                public_port: Some(5432),
+                protocol: "tcp".to_string(),
+            }],
+            ..ContainerInfo::fixture("db", ContainerStatus::Running)
+        }];
+
+        assert_eq!(
+            classify_port(5432, &expected, &containers),
+            PortGroup::Docker
+        );
+        assert_eq!(
+            classify_port(3000, &expected, &containers),
+            PortGroup::Project
+        );
+        assert_eq!(
+            classify_port(9999, &expected, &containers),
+            PortGroup::Other
+        );
     }
 }
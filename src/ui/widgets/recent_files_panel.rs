@@ -0,0 +1,108 @@
+//! Recently changed files panel widget
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Widget},
+};
+
+use crate::core::state::{AppState, FocusedPanel};
+use crate::ui::theme::Theme;
+use crate::ui::util::truncate_start;
+
+pub struct RecentFilesPanel<'a> {
+    state: &'a AppState,
+    theme: &'a Theme,
+}
+
+impl<'a> RecentFilesPanel<'a> {
+    pub fn new(state: &'a AppState, theme: &'a Theme) -> Self {
+        Self { state, theme }
+    }
+}
+
+impl<'a> Widget for RecentFilesPanel<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let focused = self.state.focus_panel == FocusedPanel::RecentFiles;
+        let border_style = if focused {
+            self.theme.styles.panel_border_focused
+        } else {
+            self.theme.styles.panel_border
+        };
+
+        let block = Block::default()
+            .title(Span::styled(
+                " RECENT FILES ",
+                self.theme.styles.panel_title,
+            ))
+            .borders(Borders::ALL)
+            .border_style(border_style)
+            .style(Style::default().bg(self.theme.colors.bg_primary));
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let recent = &self.state.panels.recent_files;
+
+        if recent.files.is_empty() {
+            let span = Span::styled(
+                "No recent changes",
+                Style::default().fg(self.theme.colors.fg_muted),
+            );
+            buf.set_span(inner.x + 1, inner.y, &span, inner.width.saturating_sub(2));
+            return;
+        }
+
+        let now = chrono::Utc::now();
+        for (i, file) in recent.files.iter().enumerate() {
+            if i >= inner.height as usize {
+                break;
+            }
+
+            let is_selected = i == recent.selected_index;
+            let base_style = if is_selected {
+                self.theme.styles.list_item_selected
+            } else {
+                self.theme.styles.list_item
+            };
+
+            let indicator = if is_selected { "▸" } else { " " };
+            let age = file
+                .modified
+                .map(|m| relative_time(now - m))
+                .unwrap_or_default();
+            let path_width = inner.width.saturating_sub(age.len() as u16 + 3) as usize;
+
+            let line = Line::from(vec![
+                Span::styled(indicator, base_style),
+                Span::styled(
+                    format!(
+                        " {:<width$}",
+                        truncate_start(&file.path, path_width),
+                        width = path_width
+                    ),
+                    base_style,
+                ),
+                Span::styled(
+                    format!(" {}", age),
+                    Style::default().fg(self.theme.colors.fg_muted),
+                ),
+            ]);
+
+            buf.set_line(inner.x, inner.y + i as u16, &line, inner.width);
+        }
+    }
+}
+
+/// Compact "5m"/"3h"/"2d" age
+fn relative_time(age: chrono::Duration) -> String {
+    let secs = age.num_seconds().max(0);
+    match secs {
+        0..=59 => "now".to_string(),
+        60..=3599 => format!("{}m", secs / 60),
+        3600..=86_399 => format!("{}h", secs / 3600),
+        _ => format!("{}d", secs / 86_400),
+    }
+}
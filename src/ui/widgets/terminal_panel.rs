@@ -0,0 +1,164 @@
+//! Embedded shell panel shown in Terminal mode
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Position, Rect},
+    style::{Color, Modifier, Style},
+    text::Span,
+    widgets::{Block, Borders, Widget},
+};
+
+use crate::core::state::AppState;
+use crate::ui::theme::Theme;
+
+pub struct TerminalPanel<'a> {
+    state: &'a AppState,
+    theme: &'a Theme,
+}
+
+impl<'a> TerminalPanel<'a> {
+    pub fn new(state: &'a AppState, theme: &'a Theme) -> Self {
+        Self { state, theme }
+    }
+
+    fn block(&self) -> Block<'a> {
+        Block::default()
+            .title(Span::styled(
+                " TERMINAL · Ctrl+] back to dashboard ",
+                self.theme.styles.panel_title,
+            ))
+            .borders(Borders::ALL)
+            .border_style(self.theme.styles.panel_border_focused)
+            .style(Style::default().bg(self.theme.colors.bg_primary))
+    }
+
+    /// Where the shell's cursor is on screen, unless it hid it or it's out of view
+    pub fn cursor(state: &AppState, area: Rect) -> Option<Position> {
+        let screen = state.panels.terminal.screen.as_ref()?;
+        if screen.hide_cursor() {
+            return None;
+        }
+        let inner = Block::default().borders(Borders::ALL).inner(area);
+        let (row, col) = screen.cursor_position();
+        (row < inner.height && col < inner.width)
+            .then(|| Position::new(inner.x + col, inner.y + row))
+    }
+}
+
+/// Rows and columns the shell gets in a full-screen `width` x `height` terminal:
+/// everything but the header, the footer and the panel border
+pub fn grid_size((width, height): (u16, u16)) -> (u16, u16) {
+    (
+        height.saturating_sub(4).max(1),
+        width.saturating_sub(2).max(1),
+    )
+}
+
+fn color(color: vt100::Color) -> Option<Color> {
+    match color {
+        vt100::Color::Default => None,
+        vt100::Color::Idx(i) => Some(Color::Indexed(i)),
+        vt100::Color::Rgb(r, g, b) => Some(Color::Rgb(r, g, b)),
+    }
+}
+
+fn cell_style(cell: &vt100::Cell) -> Style {
+    let mut style = Style::default();
+    if let Some(fg) = color(cell.fgcolor()) {
+        style = style.fg(fg);
+    }
+    if let Some(bg) = color(cell.bgcolor()) {
+        style = style.bg(bg);
+    }
+    for (on, modifier) in [
+        (cell.bold(), Modifier::BOLD),
+        (cell.italic(), Modifier::ITALIC),
+        (cell.underline(), Modifier::UNDERLINED),
+        (cell.inverse(), Modifier::REVERSED),
+    ] {
+        if on {
+            style = style.add_modifier(modifier);
+        }
+    }
+    style
+}
+
+impl<'a> Widget for TerminalPanel<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = self.block();
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let Some(screen) = &self.state.panels.terminal.screen else {
+            let span = Span::styled(
+                "Starting shell…",
+                Style::default().fg(self.theme.colors.fg_muted),
+            );
+            buf.set_span(inner.x, inner.y, &span, inner.width);
+            return;
+        };
+
+        let (rows, cols) = screen.size();
+        for row in 0..rows.min(inner.height) {
+            for col in 0..cols.min(inner.width) {
+                let Some(cell) = screen.cell(row, col) else {
+                    continue;
+                };
+                // The left half of a wide character already covers this column
+                if cell.is_wide_continuation() {
+                    continue;
+                }
+                if let Some(target) = buf.cell_mut((inner.x + col, inner.y + row)) {
+                    let contents = cell.contents();
+                    target.set_symbol(if contents.is_empty() { " " } else { &contents });
+                    target.set_style(cell_style(cell));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn renders_the_shell_screen_with_colors() {
+        let mut parser = vt100::Parser::new(3, 20, 0);
+        parser.process(b"$ ls\r\n\x1b[31msrc\x1b[0m Cargo.toml\r\n$ ");
+
+        let mut state = AppState::new(PathBuf::from("."), Theme::default());
+        state.panels.terminal.screen = Some(parser.screen().clone());
+
+        let area = Rect::new(0, 0, 24, 5);
+        let mut buf = Buffer::empty(area);
+        TerminalPanel::new(&state, &state.theme).render(area, &mut buf);
+
+        let row = |y: u16| {
+            (1..23)
+                .map(|x| buf[(x, y)].symbol().to_string())
+                .collect::<String>()
+        };
+        assert_eq!(row(1).trim_end(), "$ ls");
+        assert_eq!(row(2).trim_end(), "src Cargo.toml");
+        assert_eq!(buf[(1, 2)].fg, Color::Indexed(1));
+        assert_eq!(
+            TerminalPanel::cursor(&state, area),
+            Some(Position::new(3, 3))
+        );
+        assert_eq!(grid_size((24, 5)), (1, 22));
+    }
+
+    #[test]
+    fn grid_fills_the_output_area_even_with_pinned_logs() {
+        let mut state = AppState::new(PathBuf::from("."), Theme::default());
+        state.layout.output_split = true;
+        state.enter_terminal();
+
+        let layout = crate::ui::layout::LayoutManager::compute(Rect::new(0, 0, 100, 30), &state);
+        let output = layout.output_panel.unwrap();
+        assert!(layout.pinned_logs_panel.is_none());
+        assert_eq!(grid_size((100, 30)), (output.height - 2, output.width - 2));
+    }
+}